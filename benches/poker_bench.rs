@@ -4,7 +4,7 @@
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use poker_eval_rs::deck::{StdDeck, StdDeckCardMask};
-use poker_eval_rs::enumdefs::{EnumResult, Game, SampleType, ENUM_MAXPLAYERS};
+use poker_eval_rs::enumdefs::{EnumResult, Game, SampleType, ENUM_MAXPLAYERS, ENUM_NHANDTYPES};
 use poker_eval_rs::enumerate::evaluation::enum_exhaustive;
 use poker_eval_rs::evaluators::Eval;
 
@@ -75,7 +75,7 @@ fn bench_equity(c: &mut Criterion) {
     group.bench_function("aa_vs_kk_preflop", |b| {
         b.iter(|| {
             let mut result = create_empty_result(2);
-            let pockets = vec![aa.clone(), kk.clone()];
+            let pockets = vec![aa, kk];
             let _ = enum_exhaustive(
                 Game::Holdem,
                 &pockets,
@@ -95,11 +95,11 @@ fn bench_equity(c: &mut Criterion) {
     group.bench_function("aa_vs_kk_flop", |b| {
         b.iter(|| {
             let mut result = create_empty_result(2);
-            let pockets = vec![aa.clone(), kk.clone()];
+            let pockets = vec![aa, kk];
             let _ = enum_exhaustive(
                 Game::Holdem,
                 &pockets,
-                board3.clone(),
+                board3,
                 StdDeckCardMask::new(),
                 2,
                 3,
@@ -164,7 +164,12 @@ fn create_empty_result(nplayers: usize) -> EnumResult {
             ns
         },
         ev: [0.0; ENUM_MAXPLAYERS],
+        nhandtype: [[0; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS],
+        nwinhandtype: [[0; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS],
+        nlosehandtype: [[[0; ENUM_NHANDTYPES]; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS],
         ordering: None,
+        seed: None,
+        outright: false,
     }
 }
 
@@ -299,7 +299,7 @@ fn main() {
     }
 
     // Sort rows by density (densest first)
-    rows.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
+    rows.sort_by_key(|b| std::cmp::Reverse(b.1.len()));
 
     let mut row_offsets = vec![0u32; n_rows];
     // Start with a reasonable initial size for the compact lookup
@@ -0,0 +1,182 @@
+#ifndef POKER_EVAL_RS_H
+#define POKER_EVAL_RS_H
+
+/* Generated by cbindgen from src/capi.rs; do not edit by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+// Success.
+#define PE_OK 0
+
+// A required pointer argument was null.
+#define PE_ERR_NULL_POINTER -1
+
+// A string argument could not be parsed, or a pocket has the wrong number of
+// cards for the game.
+#define PE_ERR_INVALID_INPUT -2
+
+// Cards are off the deck, repeated, or the wrong number for an evaluator.
+#define PE_ERR_INVALID_CARDS -3
+
+// More than `PE_MAX_PLAYERS` players.
+#define PE_ERR_TOO_MANY_PLAYERS -4
+
+// The game name is unknown or the game cannot be enumerated this way.
+#define PE_ERR_UNSUPPORTED_GAME -5
+
+// The board has a number of cards the game does not allow.
+#define PE_ERR_UNSUPPORTED_BOARD -6
+
+// Anything else, including a panic inside the library.
+#define PE_ERR_INTERNAL -7
+
+// Most players an enumeration can hold.
+#define PE_MAX_PLAYERS 12
+
+// The low value of a hand without a qualifying low; it loses to every low.
+#define PE_LOW_NOTHING 135069696
+
+// Win/tie/loss counters of an enumeration, laid out for C.
+//
+// Only the first `nplayers` entries of each array are meaningful. `ev[i]` is the
+// player's summed pot share, so `ev[i] / nsamples` is their equity.
+typedef struct PeEnumResult {
+  // 0 for an exhaustive enumeration, 1 for Monte Carlo sampling.
+  int sample_type;
+  uint32_t nsamples;
+  uint32_t nplayers;
+  uint32_t nwinhi[PE_MAX_PLAYERS];
+  uint32_t ntiehi[PE_MAX_PLAYERS];
+  uint32_t nlosehi[PE_MAX_PLAYERS];
+  uint32_t nwinlo[PE_MAX_PLAYERS];
+  uint32_t ntielo[PE_MAX_PLAYERS];
+  uint32_t nloselo[PE_MAX_PLAYERS];
+  uint32_t nscoop[PE_MAX_PLAYERS];
+  double ev[PE_MAX_PLAYERS];
+} PeEnumResult;
+
+// Result of [`pe_range_equity`]. `equity` is between 0 and 1.
+typedef struct PeRangeEquity {
+  double equity;
+  uint64_t wins;
+  uint64_t ties;
+  uint64_t losses;
+  uint64_t samples;
+} PeRangeEquity;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Describes a status code. The string is static and must not be freed.
+const char *pe_strerror(int code);
+
+// Parses a card string such as `"As Kd 7h"` into a mask and its number of cards.
+// Repeated cards are rejected with `PE_ERR_INVALID_CARDS`.
+//
+// # Safety
+//
+// `cards` must be a NUL-terminated string; `mask_out` and `count_out` must be
+// valid for writes. `count_out` may be null.
+int pe_string_to_mask(const char *cards, uint64_t *mask_out, int *count_out);
+
+// Number of cards in a mask.
+int pe_mask_num_cards(uint64_t mask);
+
+// Writes the description of a high hand value (e.g. `"StFlush ( A)"`) into
+// `buf`, truncated to `len` bytes including the NUL. Returns the full length of the
+// description, like `snprintf`. The value must come from [`pe_eval_n_detailed`]
+// for the card ranks to be shown.
+//
+// # Safety
+//
+// `buf` must be valid for `len` bytes of writes, or null with `len == 0`.
+int pe_hand_val_to_string(uint32_t val, char *buf, size_t len);
+
+// Evaluates the best high hand in `mask`, as `StdDeck_StdRules_EVAL_N` does.
+// Higher values are better. Returns 0 unless `mask` holds 5 to 7 cards of the deck.
+uint32_t pe_eval_n(uint64_t mask);
+
+// Like [`pe_eval_n`], but with the ranks of every significant card filled in, for
+// display with [`pe_hand_val_to_string`]. Values compare like those of `pe_eval_n`
+// only within the same hand type. Returns 0 where `pe_eval_n` does.
+uint32_t pe_eval_n_detailed(uint64_t mask);
+
+// Evaluates an A-5 low of 5 to 7 cards. Lower values are better. Returns
+// `PE_LOW_NOTHING` for any other mask.
+uint32_t pe_eval_lowball(uint64_t mask);
+
+// Evaluates an 8-or-better A-5 low of 5 to 7 cards. Returns `PE_LOW_NOTHING`
+// without a qualifying low, or for any other mask.
+uint32_t pe_eval_lowball8(uint64_t mask);
+
+// Evaluates a 2-7 low of 5 to 7 cards. Lower values are better. Returns
+// `UINT32_MAX` for any other mask.
+uint32_t pe_eval_lowball27(uint64_t mask);
+
+// Evaluates an Omaha high hand, using exactly two hole cards and three board cards.
+// `hi_out` is 0 when no five-card hand can be made yet.
+//
+// # Safety
+//
+// `hi_out` must be valid for writes.
+int pe_eval_omaha_hi(uint64_t hole, uint64_t board, uint32_t *hi_out);
+
+// Evaluates an Omaha Hi/Lo hand. `hi_out` is 0 when no five-card hand can be made
+// yet and `lo_out` is `PE_LOW_NOTHING` without a qualifying low.
+//
+// # Safety
+//
+// `hi_out` and `lo_out` must be valid for writes.
+int pe_eval_omaha_hi_lo8(uint64_t hole, uint64_t board, uint32_t *hi_out, uint32_t *lo_out);
+
+// Enumerates every completion of the board for `game` (e.g. `"holdem"`,
+// `"omaha8"`) and writes the counters to `out`, like the C library's
+// `enumExhaustive`.
+//
+// # Safety
+//
+// `game` must be a NUL-terminated string, `pockets` must point to `npockets` masks
+// and `out` must be valid for writes.
+int pe_enum_exhaustive(const char *game,
+                       const uint64_t *pockets,
+                       size_t npockets,
+                       uint64_t board,
+                       uint64_t dead,
+                       struct PeEnumResult *out);
+
+// Samples `niter` random completions of the deal for `game` and writes the
+// counters to `out`, like the C library's `enumSample`. `seed` may be null to
+// sample from entropy; with a seed the result is reproducible.
+//
+// # Safety
+//
+// As for [`pe_enum_exhaustive`]; `seed` must be null or point to a seed.
+int pe_enum_sample(const char *game,
+                   const uint64_t *pockets,
+                   size_t npockets,
+                   uint64_t board,
+                   uint64_t dead,
+                   size_t niter,
+                   const uint64_t *seed,
+                   struct PeEnumResult *out);
+
+// Calculates the Hold'em equity of one range against another (e.g. `"QQ+,AKs"`)
+// over `iterations` Monte Carlo deals.
+//
+// # Safety
+//
+// `range1` and `range2` must be NUL-terminated strings and `out` must be valid for
+// writes.
+int pe_range_equity(const char *range1,
+                    const char *range2,
+                    uint64_t board,
+                    size_t iterations,
+                    struct PeRangeEquity *out);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* POKER_EVAL_RS_H */
@@ -1,14 +1,21 @@
 //! Board texture analysis and outs calculation.
 //!
 //! Provides tools to analyze the board state (flush draws, straight draws, paired board)
-//! and calculate outs for specific hands.
+//! and calculate outs for specific hands, either in isolation or against an opponent
+//! hand or weighted range.
 
-use crate::deck::{StdDeckCardMask, STD_DECK_N_CARDS, STD_DECK_RANK_COUNT};
+use crate::deck::{StdDeck, StdDeckCardMask, STD_DECK_N_CARDS, STD_DECK_RANK_COUNT};
+use crate::enumdefs::Game;
+use crate::errors::PokerError;
 use crate::evaluators::Eval;
+use crate::game_rules::GameRules;
+use crate::handval::HandVal;
+use crate::range::HandRange;
 use crate::rules::HandType;
+use serde::Serialize;
 
 /// Represents the texture of a board (flop, turn, or river).
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct BoardTexture {
     /// True if all cards are of different suits.
     pub is_rainbow: bool,
@@ -191,6 +198,20 @@ impl BoardTexture {
     }
 }
 
+/// Names under which the bindings key [`OutsResult::outs_by_type`], indexed by
+/// `HandType as usize`.
+pub const OUTS_TYPE_NAMES: [&str; 9] = [
+    "HighCard",
+    "Pair",
+    "TwoPair",
+    "Trips",
+    "Straight",
+    "Flush",
+    "FullHouse",
+    "Quads",
+    "StraightFlush",
+];
+
 /// Result of an outs calculation, grouping improving cards by the hand type they achieve.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OutsResult {
@@ -230,6 +251,15 @@ impl OutsResult {
             0
         }
     }
+
+    /// The non-empty out lists, keyed by their [`OUTS_TYPE_NAMES`] name.
+    pub fn named(&self) -> impl Iterator<Item = (&'static str, &[StdDeckCardMask])> {
+        OUTS_TYPE_NAMES
+            .iter()
+            .zip(&self.outs_by_type)
+            .filter(|(_, cards)| !cards.is_empty())
+            .map(|(&name, cards)| (name, cards.as_slice()))
+    }
 }
 
 /// Calculates outs for a given pocket and board.
@@ -253,6 +283,31 @@ impl OutsResult {
 /// assert_eq!(outs.count(HandType::Flush), 9);
 /// ```
 pub fn calculate_outs(pocket: &StdDeckCardMask, board: &StdDeckCardMask) -> OutsResult {
+    calculate_outs_with_dead(pocket, board, &StdDeckCardMask::new())
+}
+
+/// Like [`calculate_outs`], but never counts a card from `dead` as an out.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::board::calculate_outs_with_dead;
+/// use poker_eval_rs::deck::StdDeck;
+/// use poker_eval_rs::rules::HandType;
+///
+/// // Two of the nine remaining spades were folded.
+/// let (pocket, _) = StdDeck::string_to_mask("2s3s").unwrap();
+/// let (board, _) = StdDeck::string_to_mask("AsKs9d").unwrap();
+/// let (dead, _) = StdDeck::string_to_mask("QsJs").unwrap();
+///
+/// let outs = calculate_outs_with_dead(&pocket, &board, &dead);
+/// assert_eq!(outs.count(HandType::Flush), 7);
+/// ```
+pub fn calculate_outs_with_dead(
+    pocket: &StdDeckCardMask,
+    board: &StdDeckCardMask,
+    dead: &StdDeckCardMask,
+) -> OutsResult {
     let mut results = OutsResult::new();
 
     // Evaluate current hand
@@ -272,11 +327,11 @@ pub fn calculate_outs(pocket: &StdDeckCardMask, board: &StdDeckCardMask) -> Outs
     let current_type = current_val.hand_type();
 
     // Iterate all remaining cards
-    let dead = current_hand; // pocket | board
+    let used = current_hand | *dead;
 
-    // We iterate 0..52. If not in dead, check it.
+    // We iterate 0..52. If not already used, check it.
     for i in 0..STD_DECK_N_CARDS {
-        if !dead.card_is_set(i) {
+        if !used.card_is_set(i) {
             let card_mask = StdDeckCardMask::from_card_index(i);
             let next_hand = current_hand | card_mask;
             let next_val = Eval::eval_n(&next_hand, n_cards + 1);
@@ -291,6 +346,403 @@ pub fn calculate_outs(pocket: &StdDeckCardMask, board: &StdDeckCardMask) -> Outs
     results
 }
 
+/// Result of the next card for hero against an opponent hand or range.
+///
+/// `win`, `tie` and `lose` are fractions of the opponent's (weighted) holdings that
+/// remain live once `card` is dealt, so they always sum to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CardOutcome {
+    /// The unseen card being dealt next.
+    pub card: StdDeckCardMask,
+    /// Weighted share of opponent holdings hero beats after this card.
+    pub win: f64,
+    /// Weighted share of opponent holdings hero ties after this card.
+    pub tie: f64,
+    /// Weighted share of opponent holdings that beat hero after this card.
+    pub lose: f64,
+    /// True if this card raises hero's `HandType` (a naive out for `calculate_outs`).
+    pub improves: bool,
+}
+
+/// Outs computed against an opponent hand or weighted range.
+///
+/// Unlike [`OutsResult`], which only looks at hero's own `HandType`, every unseen card
+/// is scored by how hero's hand compares to the opponent's after that card.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutsVsResult {
+    /// Weighted share of the opponent range hero beats on the current board.
+    pub current_win: f64,
+    /// Weighted share of the opponent range hero ties on the current board.
+    pub current_tie: f64,
+    /// One entry per unseen card, in card index order.
+    pub outcomes: Vec<CardOutcome>,
+}
+
+impl OutsVsResult {
+    /// Cards after which hero is ahead of every live opponent holding (ties allowed).
+    pub fn clean_outs(&self) -> Vec<StdDeckCardMask> {
+        self.outcomes
+            .iter()
+            .filter(|o| o.win > 0.0 && o.lose == 0.0)
+            .map(|o| o.card)
+            .collect()
+    }
+
+    /// Cards after which hero only ties every live opponent holding.
+    pub fn tie_outs(&self) -> Vec<StdDeckCardMask> {
+        self.outcomes
+            .iter()
+            .filter(|o| o.win == 0.0 && o.lose == 0.0 && o.tie > 0.0)
+            .map(|o| o.card)
+            .collect()
+    }
+
+    /// Cards that improve hero's `HandType` but still leave part of the opponent
+    /// range ahead (e.g. pairing up while completing the opponent's flush).
+    pub fn tainted_outs(&self) -> Vec<StdDeckCardMask> {
+        self.outcomes
+            .iter()
+            .filter(|o| o.improves && o.lose > 0.0)
+            .map(|o| o.card)
+            .collect()
+    }
+
+    /// Out count discounted by the share of the range each card actually wins
+    /// against, counting ties as half.
+    pub fn discounted_outs(&self) -> f64 {
+        self.outcomes.iter().map(|o| o.win + o.tie / 2.0).sum()
+    }
+}
+
+/// Calculates outs for hero against a weighted opponent range.
+///
+/// The board must be a flop or a turn; each unseen card is dealt as the next card and
+/// hero's hand is compared to every opponent holding that does not conflict with
+/// hero's pocket, the board, `dead` or the dealt card. Opponent weights are
+/// renormalised per card, so card removal is accounted for.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::board::calculate_outs_vs_range;
+/// use poker_eval_rs::deck::{StdDeck, StdDeckCardMask};
+/// use poker_eval_rs::range::HandRange;
+/// use std::str::FromStr;
+///
+/// // Set-mining 77 against a made flush: no single card puts us ahead.
+/// let (pocket, _) = StdDeck::string_to_mask("7c7d").unwrap();
+/// let (board, _) = StdDeck::string_to_mask("Ah9h2h").unwrap();
+/// let villain = HandRange::from_str("KhQh").unwrap();
+///
+/// let outs = calculate_outs_vs_range(&pocket, &board, &StdDeckCardMask::new(), &villain).unwrap();
+/// assert!(outs.clean_outs().is_empty());
+/// ```
+pub fn calculate_outs_vs_range(
+    pocket: &StdDeckCardMask,
+    board: &StdDeckCardMask,
+    dead: &StdDeckCardMask,
+    opponent: &HandRange,
+) -> Result<OutsVsResult, PokerError> {
+    let nboard = board.num_cards();
+    if !(3..=4).contains(&nboard) {
+        return Err(PokerError::UnsupportedBoardConfiguration);
+    }
+    if (pocket.as_raw() & (board.as_raw() | dead.as_raw())) != 0
+        || (board.as_raw() & dead.as_raw()) != 0
+    {
+        return Err(PokerError::InvalidCardConfiguration(
+            "Pocket, board and dead cards must not overlap".to_string(),
+        ));
+    }
+
+    let used = *pocket | *board | *dead;
+    let villains: Vec<(StdDeckCardMask, f64)> = opponent
+        .hands()
+        .iter()
+        .filter(|(h, w)| *w > 0.0 && (h.as_raw() & used.as_raw()) == 0)
+        .copied()
+        .collect();
+    if villains.is_empty() {
+        return Err(PokerError::InvalidInput(
+            "Opponent range is empty once known cards are removed".to_string(),
+        ));
+    }
+
+    let hero_now = *pocket | *board;
+    let hero_val = Eval::eval_n(&hero_now, hero_now.num_cards());
+    let (current_win, current_tie, _) = compare_vs_range(hero_val, board, &villains);
+
+    let mut outcomes = Vec::new();
+    for i in 0..STD_DECK_N_CARDS {
+        if used.card_is_set(i) {
+            continue;
+        }
+        let card = StdDeckCardMask::from_card_index(i);
+        let next_board = *board | card;
+        let hero_next = hero_now | card;
+        let next_val = Eval::eval_n(&hero_next, hero_next.num_cards());
+
+        let live: Vec<(StdDeckCardMask, f64)> = villains
+            .iter()
+            .filter(|(h, _)| (h.as_raw() & card.as_raw()) == 0)
+            .copied()
+            .collect();
+        if live.is_empty() {
+            continue;
+        }
+
+        let (win, tie, lose) = compare_vs_range(next_val, &next_board, &live);
+        outcomes.push(CardOutcome {
+            card,
+            win,
+            tie,
+            lose,
+            improves: next_val.hand_type() > hero_val.hand_type(),
+        });
+    }
+
+    Ok(OutsVsResult {
+        current_win,
+        current_tie,
+        outcomes,
+    })
+}
+
+/// Calculates outs for hero against a single known opponent hand.
+///
+/// Convenience wrapper around [`calculate_outs_vs_range`]; every outcome is then a
+/// plain win, tie or loss.
+pub fn calculate_outs_vs_hand(
+    pocket: &StdDeckCardMask,
+    board: &StdDeckCardMask,
+    dead: &StdDeckCardMask,
+    opponent: &StdDeckCardMask,
+) -> Result<OutsVsResult, PokerError> {
+    if (opponent.as_raw() & (pocket.as_raw() | board.as_raw() | dead.as_raw())) != 0 {
+        return Err(PokerError::InvalidCardConfiguration(
+            "Opponent hand overlaps known cards".to_string(),
+        ));
+    }
+    let range = HandRange::from_hand_masks(vec![*opponent]);
+    calculate_outs_vs_range(pocket, board, dead, &range)
+}
+
+/// Returns weighted (win, tie, lose) fractions of hero against `villains` on `board`.
+fn compare_vs_range(
+    hero: HandVal,
+    board: &StdDeckCardMask,
+    villains: &[(StdDeckCardMask, f64)],
+) -> (f64, f64, f64) {
+    let mut win = 0.0;
+    let mut tie = 0.0;
+    let mut lose = 0.0;
+    for (hand, weight) in villains {
+        let villain = *hand | *board;
+        let val = Eval::eval_n(&villain, villain.num_cards());
+        if hero > val {
+            win += weight;
+        } else if hero < val {
+            lose += weight;
+        } else {
+            tie += weight;
+        }
+    }
+    let total = win + tie + lose;
+    (win / total, tie / total, lose / total)
+}
+
+/// Result of a game-aware outs calculation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameOutsResult {
+    /// The game the outs were computed for.
+    pub game: Game,
+    /// Cards improving the high hand, grouped by the `HandType` they make.
+    /// Always empty for games without a high pot.
+    pub hi: OutsResult,
+    /// Cards that make a qualifying low or improve the current one.
+    /// Always empty for games without a low pot.
+    pub lo: Vec<StdDeckCardMask>,
+    /// Low cards (A-8) that duplicate one of hero's low pocket ranks without improving
+    /// hero's made low, letting the board hand everyone the same low cards.
+    /// Only filled in for hi/lo board games when hero already holds a low.
+    pub counterfeits: Vec<StdDeckCardMask>,
+}
+
+/// Calculates outs for a given game, using that game's evaluators.
+///
+/// For board games (Hold'em, Omaha, Short Deck) the board must be a flop or a turn and
+/// the next card is dealt to the board. For stud and draw games the board must be empty
+/// and the next card is added to hero's own cards; until hero holds five cards, the
+/// low outs are the unpaired eights or lower. Hands are evaluated with the game's
+/// [`GameRules`] evaluators. Joker draw games are not supported.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::board::calculate_game_outs;
+/// use poker_eval_rs::deck::{StdDeck, StdDeckCardMask};
+/// use poker_eval_rs::enumdefs::Game;
+/// use poker_eval_rs::rules::HandType;
+///
+/// // A single spade in hand is not a flush draw in Omaha.
+/// let (pocket, _) = StdDeck::string_to_mask("AsKdQhJc").unwrap();
+/// let (board, _) = StdDeck::string_to_mask("9s5s2s").unwrap();
+/// let outs = calculate_game_outs(Game::Omaha, &pocket, &board, &StdDeckCardMask::new()).unwrap();
+/// assert_eq!(outs.hi.count(HandType::Flush), 0);
+/// ```
+pub fn calculate_game_outs(
+    game: Game,
+    pocket: &StdDeckCardMask,
+    board: &StdDeckCardMask,
+    dead: &StdDeckCardMask,
+) -> Result<GameOutsResult, PokerError> {
+    if matches!(game, Game::Draw5 | Game::Draw58 | Game::Draw5nsq) {
+        return Err(PokerError::UnsupportedGameType);
+    }
+    let params = game.game_params().ok_or(PokerError::UnsupportedGameType)?;
+
+    let npocket = pocket.num_cards() as i32;
+    let nboard = board.num_cards();
+    if params.maxboard > 0 {
+        if !(3..=4).contains(&nboard) {
+            return Err(PokerError::UnsupportedBoardConfiguration);
+        }
+        if npocket < params.minpocket || npocket > params.maxpocket {
+            return Err(PokerError::InvalidCardConfiguration(format!(
+                "{} needs {} pocket cards",
+                params.name, params.maxpocket
+            )));
+        }
+    } else {
+        if nboard != 0 {
+            return Err(PokerError::UnsupportedBoardConfiguration);
+        }
+        if npocket == 0 || npocket >= params.maxpocket {
+            return Err(PokerError::InvalidCardConfiguration(format!(
+                "{} needs between 1 and {} known cards",
+                params.name,
+                params.maxpocket - 1
+            )));
+        }
+    }
+    if (pocket.as_raw() & (board.as_raw() | dead.as_raw())) != 0
+        || (board.as_raw() & dead.as_raw()) != 0
+    {
+        return Err(PokerError::InvalidCardConfiguration(
+            "Pocket, board and dead cards must not overlap".to_string(),
+        ));
+    }
+
+    let has_hi = params.hashipot == 1;
+    let has_lo = params.haslopot == 1;
+    let shares_board = params.maxboard > 0;
+
+    let cur_hi = if has_hi {
+        game.eval_hi(pocket, board)?
+    } else {
+        None
+    };
+    let cur_lo = if has_lo {
+        game.eval_lo(pocket, board)?
+    } else {
+        None
+    };
+
+    // Pocket ranks that can take part in an 8-or-better low.
+    let low_pocket_ranks: Vec<usize> = (0..STD_DECK_N_CARDS)
+        .filter(|&i| pocket.card_is_set(i))
+        .map(|i| StdDeck::rank(i).as_usize())
+        .filter(|&r| is_low8_rank(r))
+        .collect();
+    // Below five cards there is no low to compare yet, so an unpaired eight or
+    // lower is the out. Deuce-to-seven plays the ace high.
+    let incomplete = !shares_board && pocket.num_cards() < 5;
+    let is_low_rank = |r: usize| {
+        if game == Game::Lowball27 {
+            r <= 6
+        } else {
+            is_low8_rank(r)
+        }
+    };
+
+    let mut result = GameOutsResult {
+        game,
+        hi: OutsResult::new(),
+        lo: Vec::new(),
+        counterfeits: Vec::new(),
+    };
+    let used = *pocket | *board | *dead;
+
+    for i in 0..STD_DECK_N_CARDS {
+        if used.card_is_set(i) {
+            continue;
+        }
+        // Short Deck plays without the 2s through 5s.
+        if game == Game::ShortDeck && StdDeck::rank(i).as_usize() < 4 {
+            continue;
+        }
+        let card = StdDeckCardMask::from_card_index(i);
+        let (next_pocket, next_board) = if shares_board {
+            (*pocket, *board | card)
+        } else {
+            (*pocket | card, *board)
+        };
+
+        if let Some(cur) = cur_hi {
+            if let Some(next) = game.eval_hi(&next_pocket, &next_board)? {
+                if next.hand_type() > cur.hand_type() {
+                    result.hi.add(display_hand_type(game, next), card);
+                }
+            }
+        }
+
+        if has_lo && incomplete {
+            let rank = StdDeck::rank(i).as_usize();
+            let pairs = (0..STD_DECK_N_CARDS)
+                .any(|c| pocket.card_is_set(c) && StdDeck::rank(c).as_usize() == rank);
+            if is_low_rank(rank) && !pairs {
+                result.lo.push(card);
+            }
+        } else if has_lo {
+            let next_lo = game.eval_lo(&next_pocket, &next_board)?;
+            let improves = match (next_lo, cur_lo) {
+                (Some(next), Some(cur)) => next.value < cur.value,
+                (Some(_), None) => true,
+                _ => false,
+            };
+            if improves {
+                result.lo.push(card);
+            } else if shares_board
+                && params.hashipot == 1
+                && cur_lo.is_some()
+                && low_pocket_ranks.contains(&StdDeck::rank(i).as_usize())
+            {
+                result.counterfeits.push(card);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Returns true for ranks eligible for an 8-or-better low (A, 2-8).
+fn is_low8_rank(rank: usize) -> bool {
+    rank <= 6 || rank == 12
+}
+
+/// Maps an evaluator result back to the `HandType` a player would call it.
+///
+/// `ShortDeckEvaluator` swaps the Flush and Full House types so that raw values
+/// compare correctly; this undoes the swap for reporting.
+pub(crate) fn display_hand_type(game: Game, val: HandVal) -> HandType {
+    let hand_type = val.get_hand_type();
+    match (game, hand_type) {
+        (Game::ShortDeck, HandType::Flush) => HandType::FullHouse,
+        (Game::ShortDeck, HandType::FullHouse) => HandType::Flush,
+        _ => hand_type,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -445,4 +897,158 @@ mod tests {
         let straight_outs = outs.count(HandType::Straight);
         assert_eq!(straight_outs, 8);
     }
+
+    #[test]
+    fn test_outs_vs_hand_tainted_set() {
+        // 77 vs a made heart flush: the 7h gives us a set but makes villain a bigger flush.
+        let (pocket, _) = StdDeck::string_to_mask("7c7d").unwrap();
+        let (board, _) = StdDeck::string_to_mask("Ah9h2h").unwrap();
+        let (villain, _) = StdDeck::string_to_mask("KhQh").unwrap();
+
+        let outs =
+            calculate_outs_vs_hand(&pocket, &board, &StdDeckCardMask::new(), &villain).unwrap();
+        assert_eq!(outs.current_win, 0.0);
+        assert_eq!(outs.outcomes.len(), 52 - 7);
+
+        let tainted = outs.tainted_outs();
+        for set_card in ["7h", "7s"] {
+            let (card, _) = StdDeck::string_to_mask(set_card).unwrap();
+            assert!(tainted.contains(&card));
+        }
+        assert!(outs.clean_outs().is_empty());
+        assert_eq!(outs.discounted_outs(), 0.0);
+    }
+
+    #[test]
+    fn test_outs_vs_hand_clean_flush_draw() {
+        // Nut flush draw vs top pair: the nine remaining spades win outright.
+        let (pocket, _) = StdDeck::string_to_mask("AsJs").unwrap();
+        let (board, _) = StdDeck::string_to_mask("Ks7s2d").unwrap();
+        let (villain, _) = StdDeck::string_to_mask("KdQc").unwrap();
+
+        let outs =
+            calculate_outs_vs_hand(&pocket, &board, &StdDeckCardMask::new(), &villain).unwrap();
+        let clean = outs.clean_outs();
+        // 9 spades + 3 aces.
+        assert_eq!(clean.len(), 12);
+        assert_eq!(outs.discounted_outs(), 12.0);
+
+        // Pairing the jack improves us but still loses to top pair.
+        let tainted = outs.tainted_outs();
+        let (jack, _) = StdDeck::string_to_mask("Jh").unwrap();
+        assert!(tainted.contains(&jack));
+        assert!(tainted.iter().all(|c| !clean.contains(c)));
+    }
+
+    #[test]
+    fn test_outs_vs_range_weighted_discount() {
+        let (pocket, _) = StdDeck::string_to_mask("AsJs").unwrap();
+        let (board, _) = StdDeck::string_to_mask("Ks7s2d").unwrap();
+        // Against the set, pairing cards fill it up and the flush is no longer clean.
+        let range = "KdQc,7c7d:0.25".parse::<HandRange>().unwrap();
+
+        let outs =
+            calculate_outs_vs_range(&pocket, &board, &StdDeckCardMask::new(), &range).unwrap();
+        assert_eq!(outs.current_win, 0.0);
+        // Eight spades beat both hands. The 2s and the three aces beat only KdQc,
+        // 1.0 of the 1.25 weight.
+        assert!((outs.discounted_outs() - (8.0 + 4.0 * 0.8)).abs() < 1e-9);
+        let two = StdDeck::string_to_mask("2s").unwrap().0;
+        assert!(outs.tainted_outs().contains(&two));
+    }
+
+    #[test]
+    fn test_outs_vs_range_rejects_river() {
+        let (pocket, _) = StdDeck::string_to_mask("AsJs").unwrap();
+        let (board, _) = StdDeck::string_to_mask("Ks7s2d3c4h").unwrap();
+        let range = "QQ".parse::<HandRange>().unwrap();
+        assert_eq!(
+            calculate_outs_vs_range(&pocket, &board, &StdDeckCardMask::new(), &range),
+            Err(PokerError::UnsupportedBoardConfiguration)
+        );
+    }
+
+    #[test]
+    fn test_game_outs_omaha_needs_two_suited_hole_cards() {
+        let (pocket, _) = StdDeck::string_to_mask("AsKdQhJc").unwrap();
+        let (board, _) = StdDeck::string_to_mask("9s5s2s").unwrap();
+
+        let outs =
+            calculate_game_outs(Game::Omaha, &pocket, &board, &StdDeckCardMask::new()).unwrap();
+        assert_eq!(outs.hi.count(HandType::Flush), 0);
+        assert!(outs.lo.is_empty());
+    }
+
+    #[test]
+    fn test_game_outs_short_deck_flush_ranks() {
+        // Flush draw in Short Deck: the 2s-5s are not in the deck.
+        let (pocket, _) = StdDeck::string_to_mask("AsJs").unwrap();
+        let (board, _) = StdDeck::string_to_mask("Ks7s6d").unwrap();
+
+        let outs =
+            calculate_game_outs(Game::ShortDeck, &pocket, &board, &StdDeckCardMask::new()).unwrap();
+        // 6s 8s 9s Ts Qs.
+        assert_eq!(outs.hi.count(HandType::Flush), 5);
+        assert!(outs.hi.outs_by_type[HandType::Flush as usize]
+            .iter()
+            .all(|c| { StdDeck::rank(StdDeck::mask_to_index(c).unwrap()).as_usize() >= 4 }));
+    }
+
+    #[test]
+    fn test_game_outs_omaha8_low_and_counterfeit() {
+        // A2 with a two-card low board: any new low card completes the low.
+        let (pocket, _) = StdDeck::string_to_mask("Ah2hKsKd").unwrap();
+        let (board, _) = StdDeck::string_to_mask("7c5dQs").unwrap();
+
+        let outs =
+            calculate_game_outs(Game::Omaha8, &pocket, &board, &StdDeckCardMask::new()).unwrap();
+        // Low outs: 3, 4, 6, 8 (4 each) = 16.
+        assert_eq!(outs.lo.len(), 16);
+        // Without a made low there is nothing to counterfeit.
+        assert!(outs.counterfeits.is_empty());
+
+        // Holding 7-5-3-2-A, a 4 or a 6 improves the low while the remaining
+        // aces and deuces only duplicate our own low cards.
+        let (board, _) = StdDeck::string_to_mask("7c5d3s").unwrap();
+        let outs =
+            calculate_game_outs(Game::Omaha8, &pocket, &board, &StdDeckCardMask::new()).unwrap();
+        assert_eq!(outs.lo.len(), 8);
+        assert_eq!(outs.counterfeits.len(), 6);
+    }
+
+    #[test]
+    fn test_game_outs_razz_next_card() {
+        // Razz: four low cards, any unpaired low card makes a five-card low.
+        let (pocket, _) = StdDeck::string_to_mask("As2d3c4h").unwrap();
+        let outs = calculate_game_outs(
+            Game::Razz,
+            &pocket,
+            &StdDeckCardMask::new(),
+            &StdDeckCardMask::new(),
+        )
+        .unwrap();
+        assert!(outs.hi.outs_by_type.iter().all(|v| v.is_empty()));
+        // Fifth cards 5 through 8 keep the hand unpaired and eight-low or better.
+        assert_eq!(outs.lo.len(), 16);
+
+        // With three cards, any unpaired eight or lower is an out.
+        let (pocket, _) = StdDeck::string_to_mask("As2d9c").unwrap();
+        let outs = calculate_game_outs(
+            Game::Razz,
+            &pocket,
+            &StdDeckCardMask::new(),
+            &StdDeckCardMask::new(),
+        )
+        .unwrap();
+        // 3 through 8, four each.
+        assert_eq!(outs.lo.len(), 24);
+
+        let err = calculate_game_outs(
+            Game::Razz,
+            &pocket,
+            &StdDeck::string_to_mask("Kh").unwrap().0,
+            &StdDeckCardMask::new(),
+        );
+        assert_eq!(err, Err(PokerError::UnsupportedBoardConfiguration));
+    }
 }
@@ -0,0 +1,569 @@
+//! C ABI for embedding the evaluators in C and C++ applications.
+//!
+//! Enabled with the `capi` feature. Card sets cross the boundary as the raw 64-bit
+//! [`StdDeckCardMask`] value, hand values as the raw [`HandVal`] and [`LowHandVal`]
+//! integers, and games by their short names (`"holdem"`, `"omaha8"`, ...). Functions
+//! that can fail return one of the `PE_*` status codes, `PE_OK` on success, and write
+//! their results through out-pointers; [`pe_strerror`] describes a status code.
+//!
+//! The declarations are in `include/poker_eval_rs.h`, generated with
+//! `cbindgen --config cbindgen.toml --output include/poker_eval_rs.h`. `cargo cinstall
+//! --features capi` (from cargo-c) installs the library, the header and a
+//! `poker_eval_rs.pc` pkg-config file.
+
+use crate::deck::{StdDeck, StdDeckCardMask};
+use crate::enumdefs::{EnumResult, Game, SampleType, ENUM_MAXPLAYERS};
+use crate::enumerate::{enum_game_exhaustive, enum_game_sample};
+use crate::errors::PokerError;
+use crate::evaluators::range_equity::calculate_equity;
+use crate::evaluators::{
+    std_deck_lowball27_eval, std_deck_lowball8_eval, std_deck_lowball_eval, Eval, HandEvaluator,
+    OmahaHiEvaluator, OmahaHiLoEvaluator,
+};
+use crate::game_rules::{GameRef, GameRules};
+use crate::handval::HandVal;
+use crate::handval_low::{LowHandVal, LOW_HAND_VAL_NOTHING};
+use crate::query::check_deal;
+use crate::range::HandRange;
+use std::ffi::{c_char, c_int, CStr};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::str::FromStr;
+
+/// Success.
+pub const PE_OK: c_int = 0;
+/// A required pointer argument was null.
+pub const PE_ERR_NULL_POINTER: c_int = -1;
+/// A string argument could not be parsed, or a pocket has the wrong number of
+/// cards for the game.
+pub const PE_ERR_INVALID_INPUT: c_int = -2;
+/// Cards are off the deck, repeated, or the wrong number for an evaluator.
+pub const PE_ERR_INVALID_CARDS: c_int = -3;
+/// More than `PE_MAX_PLAYERS` players.
+pub const PE_ERR_TOO_MANY_PLAYERS: c_int = -4;
+/// The game name is unknown or the game cannot be enumerated this way.
+pub const PE_ERR_UNSUPPORTED_GAME: c_int = -5;
+/// The board has a number of cards the game does not allow.
+pub const PE_ERR_UNSUPPORTED_BOARD: c_int = -6;
+/// Anything else, including a panic inside the library.
+pub const PE_ERR_INTERNAL: c_int = -7;
+
+/// Most players an enumeration can hold.
+pub const PE_MAX_PLAYERS: usize = 12;
+const _: () = assert!(PE_MAX_PLAYERS == ENUM_MAXPLAYERS);
+
+/// The low value of a hand without a qualifying low; it loses to every low.
+pub const PE_LOW_NOTHING: u32 = 135069696;
+const _: () = assert!(PE_LOW_NOTHING == LOW_HAND_VAL_NOTHING);
+
+/// Win/tie/loss counters of an enumeration, laid out for C.
+///
+/// Only the first `nplayers` entries of each array are meaningful. `ev[i]` is the
+/// player's summed pot share, so `ev[i] / nsamples` is their equity.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PeEnumResult {
+    /// 0 for an exhaustive enumeration, 1 for Monte Carlo sampling.
+    pub sample_type: c_int,
+    pub nsamples: u32,
+    pub nplayers: u32,
+    pub nwinhi: [u32; PE_MAX_PLAYERS],
+    pub ntiehi: [u32; PE_MAX_PLAYERS],
+    pub nlosehi: [u32; PE_MAX_PLAYERS],
+    pub nwinlo: [u32; PE_MAX_PLAYERS],
+    pub ntielo: [u32; PE_MAX_PLAYERS],
+    pub nloselo: [u32; PE_MAX_PLAYERS],
+    pub nscoop: [u32; PE_MAX_PLAYERS],
+    pub ev: [f64; PE_MAX_PLAYERS],
+}
+
+impl From<&EnumResult> for PeEnumResult {
+    fn from(r: &EnumResult) -> Self {
+        PeEnumResult {
+            sample_type: match r.sample_type {
+                SampleType::Exhaustive => 0,
+                SampleType::Sample => 1,
+            },
+            nsamples: r.nsamples,
+            nplayers: r.nplayers,
+            nwinhi: r.nwinhi,
+            ntiehi: r.ntiehi,
+            nlosehi: r.nlosehi,
+            nwinlo: r.nwinlo,
+            ntielo: r.ntielo,
+            nloselo: r.nloselo,
+            nscoop: r.nscoop,
+            ev: r.ev,
+        }
+    }
+}
+
+/// Result of [`pe_range_equity`]. `equity` is between 0 and 1.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PeRangeEquity {
+    pub equity: f64,
+    pub wins: u64,
+    pub ties: u64,
+    pub losses: u64,
+    pub samples: u64,
+}
+
+/// Maps a `PokerError` to its status code.
+fn status(e: &PokerError) -> c_int {
+    match e {
+        PokerError::InvalidInput(_) => PE_ERR_INVALID_INPUT,
+        PokerError::InvalidCardConfiguration(_) => PE_ERR_INVALID_CARDS,
+        PokerError::TooManyPlayers => PE_ERR_TOO_MANY_PLAYERS,
+        PokerError::UnsupportedGameType => PE_ERR_UNSUPPORTED_GAME,
+        PokerError::UnsupportedBoardConfiguration => PE_ERR_UNSUPPORTED_BOARD,
+        _ => PE_ERR_INTERNAL,
+    }
+}
+
+/// Runs `f`, turning an error into its status code and a panic into `PE_ERR_INTERNAL`.
+fn guard<F: FnOnce() -> Result<(), c_int>>(f: F) -> c_int {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => PE_OK,
+        Ok(Err(code)) => code,
+        Err(_) => PE_ERR_INTERNAL,
+    }
+}
+
+/// Borrows a C string as UTF-8.
+///
+/// # Safety
+///
+/// `s` must be null or point to a NUL-terminated string that outlives the borrow.
+unsafe fn str_arg<'a>(s: *const c_char) -> Result<&'a str, c_int> {
+    if s.is_null() {
+        return Err(PE_ERR_NULL_POINTER);
+    }
+    CStr::from_ptr(s).to_str().map_err(|_| PE_ERR_INVALID_INPUT)
+}
+
+/// A mask argument, rejecting bits outside the 52-card deck.
+fn mask_arg(mask: u64) -> Result<StdDeckCardMask, c_int> {
+    if mask & !StdDeckCardMask::all_cards().as_raw() != 0 {
+        return Err(PE_ERR_INVALID_CARDS);
+    }
+    Ok(StdDeckCardMask::from_raw(mask))
+}
+
+/// A hand for the single-hand evaluators: 5 to 7 cards, all on the deck.
+fn hand_arg(mask: u64) -> Result<StdDeckCardMask, c_int> {
+    let hand = mask_arg(mask)?;
+    if !(5..=7).contains(&hand.num_cards()) {
+        return Err(PE_ERR_INVALID_CARDS);
+    }
+    Ok(hand)
+}
+
+fn game_arg(game: &str) -> Result<GameRef, c_int> {
+    GameRef::from_name(game).map_err(|_| PE_ERR_UNSUPPORTED_GAME)
+}
+
+/// Checks the cards of an enumeration against the rules of `game`, as an
+/// [`EquityQuery`](crate::query::EquityQuery) does, and returns the pockets.
+///
+/// # Safety
+///
+/// `pockets` must be null or point to `npockets` masks.
+unsafe fn deal_args(
+    game: &dyn GameRules,
+    pockets: *const u64,
+    npockets: usize,
+    board: u64,
+    dead: u64,
+) -> Result<(Vec<StdDeckCardMask>, StdDeckCardMask, StdDeckCardMask), c_int> {
+    if pockets.is_null() {
+        return Err(PE_ERR_NULL_POINTER);
+    }
+    if npockets > PE_MAX_PLAYERS {
+        return Err(PE_ERR_TOO_MANY_PLAYERS);
+    }
+    let raw = std::slice::from_raw_parts(pockets, npockets);
+    let named = raw
+        .iter()
+        .enumerate()
+        .map(|(i, &m)| Ok((format!("player {}", i + 1), mask_arg(m)?)))
+        .collect::<Result<Vec<_>, c_int>>()?;
+    let (board, dead) = (mask_arg(board)?, mask_arg(dead)?);
+    check_deal(game, &named, board, dead).map_err(|e| status(&e))?;
+    Ok((
+        named.into_iter().map(|(_, pocket)| pocket).collect(),
+        board,
+        dead,
+    ))
+}
+
+/// Describes a status code. The string is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn pe_strerror(code: c_int) -> *const c_char {
+    let msg: &'static [u8] = match code {
+        PE_OK => b"success\0",
+        PE_ERR_NULL_POINTER => b"null pointer argument\0",
+        PE_ERR_INVALID_INPUT => b"invalid input\0",
+        PE_ERR_INVALID_CARDS => b"invalid card configuration\0",
+        PE_ERR_TOO_MANY_PLAYERS => b"too many players\0",
+        PE_ERR_UNSUPPORTED_GAME => b"unsupported game type\0",
+        PE_ERR_UNSUPPORTED_BOARD => b"unsupported board configuration\0",
+        PE_ERR_INTERNAL => b"internal error\0",
+        _ => b"unknown error\0",
+    };
+    msg.as_ptr() as *const c_char
+}
+
+/// Parses a card string such as `"As Kd 7h"` into a mask and its number of cards.
+/// Repeated cards are rejected with `PE_ERR_INVALID_CARDS`.
+///
+/// # Safety
+///
+/// `cards` must be a NUL-terminated string; `mask_out` and `count_out` must be
+/// valid for writes. `count_out` may be null.
+#[no_mangle]
+pub unsafe extern "C" fn pe_string_to_mask(
+    cards: *const c_char,
+    mask_out: *mut u64,
+    count_out: *mut c_int,
+) -> c_int {
+    guard(|| {
+        let cards = str_arg(cards)?;
+        if mask_out.is_null() {
+            return Err(PE_ERR_NULL_POINTER);
+        }
+        let (mask, count) = StdDeck::string_to_mask(cards).map_err(|_| PE_ERR_INVALID_INPUT)?;
+        if mask.num_cards() != count {
+            return Err(PE_ERR_INVALID_CARDS);
+        }
+        *mask_out = mask.as_raw();
+        if !count_out.is_null() {
+            *count_out = count as c_int;
+        }
+        Ok(())
+    })
+}
+
+/// Number of cards in a mask.
+#[no_mangle]
+pub extern "C" fn pe_mask_num_cards(mask: u64) -> c_int {
+    StdDeckCardMask::from_raw(mask).num_cards() as c_int
+}
+
+/// Writes the description of a high hand value (e.g. `"StFlush ( A)"`) into
+/// `buf`, truncated to `len` bytes including the NUL. Returns the full length of the
+/// description, like `snprintf`. The value must come from [`pe_eval_n_detailed`]
+/// for the card ranks to be shown.
+///
+/// # Safety
+///
+/// `buf` must be valid for `len` bytes of writes, or null with `len == 0`.
+#[no_mangle]
+pub unsafe extern "C" fn pe_hand_val_to_string(val: u32, buf: *mut c_char, len: usize) -> c_int {
+    let text = HandVal { value: val }.std_rules_hand_val_to_string();
+    if !buf.is_null() && len > 0 {
+        let n = text.len().min(len - 1);
+        std::ptr::copy_nonoverlapping(text.as_ptr() as *const c_char, buf, n);
+        *buf.add(n) = 0;
+    }
+    text.len() as c_int
+}
+
+/// Evaluates the best high hand in `mask`, as `StdDeck_StdRules_EVAL_N` does.
+/// Higher values are better. Returns 0 unless `mask` holds 5 to 7 cards of the deck.
+#[no_mangle]
+pub extern "C" fn pe_eval_n(mask: u64) -> u32 {
+    hand_arg(mask).map_or(0, |m| Eval::eval_n(&m, m.num_cards()).value)
+}
+
+/// Like [`pe_eval_n`], but with the ranks of every significant card filled in, for
+/// display with [`pe_hand_val_to_string`]. Values compare like those of `pe_eval_n`
+/// only within the same hand type. Returns 0 where `pe_eval_n` does.
+#[no_mangle]
+pub extern "C" fn pe_eval_n_detailed(mask: u64) -> u32 {
+    hand_arg(mask).map_or(0, |m| Eval::eval_n_detailed(&m, m.num_cards()).value)
+}
+
+/// Evaluates an A-5 low of 5 to 7 cards. Lower values are better. Returns
+/// `PE_LOW_NOTHING` for any other mask.
+#[no_mangle]
+pub extern "C" fn pe_eval_lowball(mask: u64) -> u32 {
+    hand_arg(mask).map_or(PE_LOW_NOTHING, |m| {
+        std_deck_lowball_eval(&m, m.num_cards()).value
+    })
+}
+
+/// Evaluates an 8-or-better A-5 low of 5 to 7 cards. Returns `PE_LOW_NOTHING`
+/// without a qualifying low, or for any other mask.
+#[no_mangle]
+pub extern "C" fn pe_eval_lowball8(mask: u64) -> u32 {
+    hand_arg(mask)
+        .ok()
+        .and_then(|m| std_deck_lowball8_eval(&m, m.num_cards()))
+        .map_or(PE_LOW_NOTHING, |v: LowHandVal| v.value)
+}
+
+/// Evaluates a 2-7 low of 5 to 7 cards. Lower values are better. Returns
+/// `UINT32_MAX` for any other mask.
+#[no_mangle]
+pub extern "C" fn pe_eval_lowball27(mask: u64) -> u32 {
+    hand_arg(mask).map_or(u32::MAX, |m| {
+        std_deck_lowball27_eval(&m, m.num_cards()).value
+    })
+}
+
+/// Evaluates an Omaha high hand, using exactly two hole cards and three board cards.
+/// `hi_out` is 0 when no five-card hand can be made yet.
+///
+/// # Safety
+///
+/// `hi_out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn pe_eval_omaha_hi(hole: u64, board: u64, hi_out: *mut u32) -> c_int {
+    guard(|| {
+        if hi_out.is_null() {
+            return Err(PE_ERR_NULL_POINTER);
+        }
+        let hi = OmahaHiEvaluator::evaluate_hand(&mask_arg(hole)?, &mask_arg(board)?)
+            .map_err(|e| status(&e))?;
+        *hi_out = hi.map_or(0, |v| v.value);
+        Ok(())
+    })
+}
+
+/// Evaluates an Omaha Hi/Lo hand. `hi_out` is 0 when no five-card hand can be made
+/// yet and `lo_out` is `PE_LOW_NOTHING` without a qualifying low.
+///
+/// # Safety
+///
+/// `hi_out` and `lo_out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn pe_eval_omaha_hi_lo8(
+    hole: u64,
+    board: u64,
+    hi_out: *mut u32,
+    lo_out: *mut u32,
+) -> c_int {
+    guard(|| {
+        if hi_out.is_null() || lo_out.is_null() {
+            return Err(PE_ERR_NULL_POINTER);
+        }
+        let (hi, lo) = OmahaHiLoEvaluator::evaluate_hand(&mask_arg(hole)?, &mask_arg(board)?)
+            .map_err(|e| status(&e))?;
+        *hi_out = hi.map_or(0, |v| v.value);
+        *lo_out = lo.map_or(PE_LOW_NOTHING, |v| v.value);
+        Ok(())
+    })
+}
+
+/// Enumerates every completion of the board for `game` (e.g. `"holdem"`,
+/// `"omaha8"`) and writes the counters to `out`, like the C library's
+/// `enumExhaustive`.
+///
+/// # Safety
+///
+/// `game` must be a NUL-terminated string, `pockets` must point to `npockets` masks
+/// and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn pe_enum_exhaustive(
+    game: *const c_char,
+    pockets: *const u64,
+    npockets: usize,
+    board: u64,
+    dead: u64,
+    out: *mut PeEnumResult,
+) -> c_int {
+    guard(|| {
+        let game = game_arg(str_arg(game)?)?;
+        let (pockets, board, dead) = deal_args(&*game, pockets, npockets, board, dead)?;
+        if out.is_null() {
+            return Err(PE_ERR_NULL_POINTER);
+        }
+        let mut result = EnumResult::new(game.game().unwrap_or(Game::Holdem));
+        enum_game_exhaustive(&*game, &pockets, board, dead, false, &mut result)
+            .map_err(|e| status(&e))?;
+        *out = PeEnumResult::from(&result);
+        Ok(())
+    })
+}
+
+/// Samples `niter` random completions of the deal for `game` and writes the
+/// counters to `out`, like the C library's `enumSample`. `seed` may be null to
+/// sample from entropy; with a seed the result is reproducible.
+///
+/// # Safety
+///
+/// As for [`pe_enum_exhaustive`]; `seed` must be null or point to a seed.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn pe_enum_sample(
+    game: *const c_char,
+    pockets: *const u64,
+    npockets: usize,
+    board: u64,
+    dead: u64,
+    niter: usize,
+    seed: *const u64,
+    out: *mut PeEnumResult,
+) -> c_int {
+    guard(|| {
+        let game = game_arg(str_arg(game)?)?;
+        let (pockets, board, dead) = deal_args(&*game, pockets, npockets, board, dead)?;
+        if out.is_null() {
+            return Err(PE_ERR_NULL_POINTER);
+        }
+        let mut result = EnumResult::new(game.game().unwrap_or(Game::Holdem));
+        result.seed = seed.as_ref().copied();
+        enum_game_sample(&*game, &pockets, board, dead, niter, false, &mut result)
+            .map_err(|e| status(&e))?;
+        *out = PeEnumResult::from(&result);
+        Ok(())
+    })
+}
+
+/// Calculates the Hold'em equity of one range against another (e.g. `"QQ+,AKs"`)
+/// over `iterations` Monte Carlo deals.
+///
+/// # Safety
+///
+/// `range1` and `range2` must be NUL-terminated strings and `out` must be valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn pe_range_equity(
+    range1: *const c_char,
+    range2: *const c_char,
+    board: u64,
+    iterations: usize,
+    out: *mut PeRangeEquity,
+) -> c_int {
+    guard(|| {
+        let parse = |s: &str| HandRange::from_str(s).map_err(|_| PE_ERR_INVALID_INPUT);
+        let range1 = parse(str_arg(range1)?)?;
+        let range2 = parse(str_arg(range2)?)?;
+        let board = mask_arg(board)?;
+        if out.is_null() {
+            return Err(PE_ERR_NULL_POINTER);
+        }
+        if range1.is_empty() || range2.is_empty() {
+            return Err(status(&PokerError::InvalidInput(
+                "Ranges cannot be empty".to_string(),
+            )));
+        }
+        if board.num_cards() > 5 {
+            return Err(status(&PokerError::UnsupportedBoardConfiguration));
+        }
+        // What is left to fail is ranges the board or the other range use up.
+        let res = calculate_equity(&range1, &range2, &board, iterations)
+            .map_err(|e| status(&PokerError::InvalidCardConfiguration(e)))?;
+        *out = PeRangeEquity {
+            equity: res.equity,
+            wins: res.wins as u64,
+            ties: res.ties as u64,
+            losses: res.losses as u64,
+            samples: res.samples as u64,
+        };
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    #[test]
+    fn test_status_codes_and_enumeration() {
+        let mut mask = 0u64;
+        let mut count = 0;
+        let cards = CString::new("As Ks Qs Js Ts").unwrap();
+        unsafe {
+            assert_eq!(
+                pe_string_to_mask(cards.as_ptr(), &mut mask, &mut count),
+                PE_OK
+            );
+        }
+        assert_eq!(count, 5);
+        let (royal, _) = StdDeck::string_to_mask("AsKsQsJsTs").unwrap();
+        assert_eq!(pe_eval_n(mask), Eval::eval_n(&royal, 5).value);
+        assert_eq!(pe_eval_n(u64::MAX), 0);
+
+        let dup = CString::new("As As").unwrap();
+        unsafe {
+            assert_eq!(
+                pe_string_to_mask(dup.as_ptr(), &mut mask, std::ptr::null_mut()),
+                PE_ERR_INVALID_CARDS
+            );
+        }
+
+        let game = CString::new("holdem").unwrap();
+        let pockets = [
+            StdDeck::string_to_mask("AsAd").unwrap().0.as_raw(),
+            StdDeck::string_to_mask("KsKd").unwrap().0.as_raw(),
+        ];
+        let board = StdDeck::string_to_mask("2c7d9h").unwrap().0.as_raw();
+        let mut out = std::mem::MaybeUninit::<PeEnumResult>::uninit();
+        let out = unsafe {
+            assert_eq!(
+                pe_enum_exhaustive(
+                    game.as_ptr(),
+                    pockets.as_ptr(),
+                    2,
+                    board,
+                    0,
+                    out.as_mut_ptr()
+                ),
+                PE_OK
+            );
+            out.assume_init()
+        };
+        assert_eq!(out.nsamples, 990);
+        assert_eq!(out.nplayers, 2);
+        assert!(out.nwinhi[0] > out.nwinhi[1]);
+
+        let overlapping = [pockets[0], pockets[0]];
+        let mut bad = out;
+        unsafe {
+            assert_eq!(
+                pe_enum_exhaustive(game.as_ptr(), overlapping.as_ptr(), 2, board, 0, &mut bad),
+                PE_ERR_INVALID_CARDS
+            );
+            let chess = CString::new("chess").unwrap();
+            assert_eq!(
+                pe_enum_exhaustive(chess.as_ptr(), pockets.as_ptr(), 2, board, 0, &mut bad),
+                PE_ERR_UNSUPPORTED_GAME
+            );
+            let omaha = CString::new("omaha").unwrap();
+            assert_eq!(
+                pe_enum_exhaustive(omaha.as_ptr(), pockets.as_ptr(), 2, board, 0, &mut bad),
+                PE_ERR_INVALID_INPUT
+            );
+        }
+
+        let aces = CString::new("AsAd").unwrap();
+        let kings = CString::new("KK").unwrap();
+        let mut equity = std::mem::MaybeUninit::<PeRangeEquity>::uninit();
+        let full = StdDeck::string_to_mask("2c7d9hTh3s4s").unwrap().0.as_raw();
+        let ace_flop = StdDeck::string_to_mask("As7d9h").unwrap().0.as_raw();
+        unsafe {
+            assert_eq!(
+                pe_range_equity(
+                    aces.as_ptr(),
+                    kings.as_ptr(),
+                    full,
+                    100,
+                    equity.as_mut_ptr()
+                ),
+                PE_ERR_UNSUPPORTED_BOARD
+            );
+            assert_eq!(
+                pe_range_equity(
+                    aces.as_ptr(),
+                    kings.as_ptr(),
+                    ace_flop,
+                    100,
+                    equity.as_mut_ptr()
+                ),
+                PE_ERR_INVALID_CARDS
+            );
+        }
+    }
+}
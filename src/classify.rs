@@ -0,0 +1,656 @@
+//! Made-hand and draw classification.
+//!
+//! Labels a holding on a board with the categories players use when talking about
+//! hands: top pair/top kicker, overpair, set vs trips, nut flush draw, combo draw,
+//! open-ended and gutshot straight draws, backdoor draws and, for Omaha, wraps.
+//!
+//! Two-card holdings are classified with Hold'em rules. Holdings of four to six cards
+//! use Omaha rules, where exactly two hole cards and three board cards must be used.
+
+use crate::board::BoardTexture;
+use crate::deck::{StdDeckCardMask, STD_DECK_RANK_COUNT};
+use crate::errors::PokerError;
+use crate::evaluators::{Eval, OmahaHiEvaluator};
+use crate::handval::HandVal;
+use crate::range::HandRange;
+use crate::rules::HandType;
+use std::fmt;
+use std::str::FromStr;
+
+/// A made-hand or draw category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HandCategory {
+    /// Straight flush.
+    StraightFlush,
+    /// Four of a kind.
+    Quads,
+    /// Full house.
+    FullHouse,
+    /// Flush.
+    Flush,
+    /// Straight.
+    Straight,
+    /// Three of a kind using a pocket pair.
+    Set,
+    /// Three of a kind using a pair on the board.
+    Trips,
+    /// Two pair.
+    TwoPair,
+    /// Pocket pair above every board card.
+    Overpair,
+    /// Pair with the top board card and the best available kicker.
+    TopPairTopKicker,
+    /// Pair with the top board card.
+    TopPair,
+    /// Pair with the second board rank, or a pocket pair just below the top card.
+    SecondPair,
+    /// Pair below second pair.
+    WeakPair,
+    /// Pocket pair below every board card.
+    Underpair,
+    /// No pair of our own.
+    HighCard,
+    /// Flush draw to the best possible flush.
+    NutFlushDraw,
+    /// Four to a flush.
+    FlushDraw,
+    /// Flush draw together with a straight draw.
+    ComboDraw,
+    /// Straight draw with three or more out ranks (Omaha).
+    Wrap,
+    /// Straight draw with two out ranks (open-ended or double gutshot).
+    OpenEnded,
+    /// Straight draw with a single out rank.
+    Gutshot,
+    /// Three to a flush on the flop.
+    BackdoorFlushDraw,
+    /// Needs two more cards to make a straight, on the flop.
+    BackdoorStraightDraw,
+}
+
+/// All categories, from the strongest made hand to the weakest draw.
+pub const HAND_CATEGORIES: [HandCategory; 23] = [
+    HandCategory::StraightFlush,
+    HandCategory::Quads,
+    HandCategory::FullHouse,
+    HandCategory::Flush,
+    HandCategory::Straight,
+    HandCategory::Set,
+    HandCategory::Trips,
+    HandCategory::TwoPair,
+    HandCategory::Overpair,
+    HandCategory::TopPairTopKicker,
+    HandCategory::TopPair,
+    HandCategory::SecondPair,
+    HandCategory::WeakPair,
+    HandCategory::Underpair,
+    HandCategory::HighCard,
+    HandCategory::NutFlushDraw,
+    HandCategory::FlushDraw,
+    HandCategory::ComboDraw,
+    HandCategory::Wrap,
+    HandCategory::OpenEnded,
+    HandCategory::Gutshot,
+    HandCategory::BackdoorFlushDraw,
+    HandCategory::BackdoorStraightDraw,
+];
+
+impl HandCategory {
+    /// Returns the short lowercase name used for parsing (e.g. "tptk", "oesd").
+    pub fn name(&self) -> &'static str {
+        match self {
+            HandCategory::StraightFlush => "straightflush",
+            HandCategory::Quads => "quads",
+            HandCategory::FullHouse => "fullhouse",
+            HandCategory::Flush => "flush",
+            HandCategory::Straight => "straight",
+            HandCategory::Set => "set",
+            HandCategory::Trips => "trips",
+            HandCategory::TwoPair => "twopair",
+            HandCategory::Overpair => "overpair",
+            HandCategory::TopPairTopKicker => "tptk",
+            HandCategory::TopPair => "toppair",
+            HandCategory::SecondPair => "secondpair",
+            HandCategory::WeakPair => "weakpair",
+            HandCategory::Underpair => "underpair",
+            HandCategory::HighCard => "highcard",
+            HandCategory::NutFlushDraw => "nfd",
+            HandCategory::FlushDraw => "flushdraw",
+            HandCategory::ComboDraw => "combodraw",
+            HandCategory::Wrap => "wrap",
+            HandCategory::OpenEnded => "oesd",
+            HandCategory::Gutshot => "gutshot",
+            HandCategory::BackdoorFlushDraw => "bdfd",
+            HandCategory::BackdoorStraightDraw => "bdsd",
+        }
+    }
+
+    /// Returns true for draw categories.
+    pub fn is_draw(&self) -> bool {
+        *self >= HandCategory::NutFlushDraw
+    }
+}
+
+impl fmt::Display for HandCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            HandCategory::StraightFlush => "Straight flush",
+            HandCategory::Quads => "Quads",
+            HandCategory::FullHouse => "Full house",
+            HandCategory::Flush => "Flush",
+            HandCategory::Straight => "Straight",
+            HandCategory::Set => "Set",
+            HandCategory::Trips => "Trips",
+            HandCategory::TwoPair => "Two pair",
+            HandCategory::Overpair => "Overpair",
+            HandCategory::TopPairTopKicker => "Top pair, top kicker",
+            HandCategory::TopPair => "Top pair",
+            HandCategory::SecondPair => "Second pair",
+            HandCategory::WeakPair => "Weak pair",
+            HandCategory::Underpair => "Underpair",
+            HandCategory::HighCard => "High card",
+            HandCategory::NutFlushDraw => "Nut flush draw",
+            HandCategory::FlushDraw => "Flush draw",
+            HandCategory::ComboDraw => "Combo draw",
+            HandCategory::Wrap => "Wrap",
+            HandCategory::OpenEnded => "Open-ended straight draw",
+            HandCategory::Gutshot => "Gutshot",
+            HandCategory::BackdoorFlushDraw => "Backdoor flush draw",
+            HandCategory::BackdoorStraightDraw => "Backdoor straight draw",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+impl FromStr for HandCategory {
+    type Err = String;
+
+    /// Parses a category name, ignoring case, spaces, `_` and `-`.
+    /// Accepts the short names returned by [`HandCategory::name`] and a few common aliases.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key: String = s
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '_' | '-'))
+            .collect::<String>()
+            .to_lowercase();
+        let alias = match key.as_str() {
+            "sf" => Some(HandCategory::StraightFlush),
+            "boat" => Some(HandCategory::FullHouse),
+            "toppairtopkicker" => Some(HandCategory::TopPairTopKicker),
+            "nutflushdraw" => Some(HandCategory::NutFlushDraw),
+            "fd" => Some(HandCategory::FlushDraw),
+            "openended" => Some(HandCategory::OpenEnded),
+            "backdoorflushdraw" => Some(HandCategory::BackdoorFlushDraw),
+            "backdoorstraightdraw" => Some(HandCategory::BackdoorStraightDraw),
+            _ => None,
+        };
+        alias
+            .or_else(|| HAND_CATEGORIES.iter().copied().find(|c| c.name() == key))
+            .ok_or_else(|| format!("Unknown hand category: {}", s))
+    }
+}
+
+/// Classification of a holding on a board.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandClass {
+    /// The best made-hand category.
+    pub made: HandCategory,
+    /// The hand's best five-card value from [`Eval::eval_n_detailed`], so the kicker
+    /// fields are filled in (Omaha rules for 4+ hole cards).
+    pub value: HandVal,
+    /// Draw categories, strongest first. Empty on the river.
+    pub draws: Vec<HandCategory>,
+    /// Unseen cards that complete a flush.
+    pub flush_outs: usize,
+    /// Unseen cards that complete a straight.
+    pub straight_outs: usize,
+}
+
+impl HandClass {
+    /// Returns true if the hand belongs to `category`.
+    ///
+    /// Broader categories include their stronger variants: `TopPair` matches top pair
+    /// top kicker and `FlushDraw` matches nut flush draws.
+    pub fn matches(&self, category: HandCategory) -> bool {
+        match category {
+            HandCategory::TopPair => {
+                matches!(
+                    self.made,
+                    HandCategory::TopPair | HandCategory::TopPairTopKicker
+                )
+            }
+            c if c.is_draw() => self.draws.contains(&c),
+            c => self.made == c,
+        }
+    }
+
+    /// Returns true if the hand has any draw.
+    pub fn has_draw(&self) -> bool {
+        !self.draws.is_empty()
+    }
+}
+
+/// Classifies `pocket` on `board`.
+///
+/// The board must have 3 to 5 cards. Two hole cards are classified with Hold'em rules,
+/// four to six with Omaha rules.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::classify::{classify, HandCategory};
+/// use poker_eval_rs::deck::StdDeck;
+///
+/// let (pocket, _) = StdDeck::string_to_mask("AhKh").unwrap();
+/// let (board, _) = StdDeck::string_to_mask("Kd7h2h").unwrap();
+/// let class = classify(&pocket, &board).unwrap();
+/// assert_eq!(class.made, HandCategory::TopPairTopKicker);
+/// assert!(class.matches(HandCategory::NutFlushDraw));
+/// assert_eq!(class.flush_outs, 9);
+/// ```
+pub fn classify(
+    pocket: &StdDeckCardMask,
+    board: &StdDeckCardMask,
+) -> Result<HandClass, PokerError> {
+    let npocket = pocket.num_cards();
+    let nboard = board.num_cards();
+    if !(3..=5).contains(&nboard) {
+        return Err(PokerError::UnsupportedBoardConfiguration);
+    }
+    if npocket != 2 && !(4..=6).contains(&npocket) {
+        return Err(PokerError::InvalidCardConfiguration(
+            "Classification needs 2 (Hold'em) or 4-6 (Omaha) hole cards".to_string(),
+        ));
+    }
+    if (pocket.as_raw() & board.as_raw()) != 0 {
+        return Err(PokerError::InvalidCardConfiguration(
+            "Pocket and board cards must not overlap".to_string(),
+        ));
+    }
+
+    let hole = HoleCards::new(pocket, board);
+    let value = if hole.omaha {
+        OmahaHiEvaluator::evaluate_detailed(pocket, board)
+            .ok_or(PokerError::UnsupportedBoardConfiguration)?
+    } else {
+        let all = *pocket | *board;
+        Eval::eval_n_detailed(&all, all.num_cards())
+    };
+    let made = made_category(&hole, value);
+
+    let mut class = HandClass {
+        made,
+        value,
+        draws: Vec::new(),
+        flush_outs: 0,
+        straight_outs: 0,
+    };
+    if nboard == 5 {
+        return Ok(class);
+    }
+
+    let texture = BoardTexture::analyze(board);
+    let hand_type = value.get_hand_type().as_usize();
+    let mut flush_draw = None;
+    let mut straight_draw = None;
+
+    // A flush draw needs two board cards of the suit, so a rainbow board has none.
+    if hand_type < HandType::Flush.as_usize() && !texture.is_rainbow {
+        let mut nut = false;
+        for suit in 0..4 {
+            if hole.completes_flush(suit) {
+                let outs = STD_DECK_RANK_COUNT
+                    - (hole.pocket_suits[suit] | hole.board_suits[suit]).count_ones() as usize;
+                class.flush_outs += outs;
+                nut |= hole.holds_nut_card(suit);
+            }
+        }
+        if class.flush_outs > 0 {
+            flush_draw = Some(if nut {
+                HandCategory::NutFlushDraw
+            } else {
+                HandCategory::FlushDraw
+            });
+        }
+    }
+
+    if hand_type < HandType::Straight.as_usize() {
+        let mut out_ranks = 0;
+        for rank in 0..STD_DECK_RANK_COUNT {
+            if hole.makes_straight(hole.board_ranks | 1 << rank) {
+                out_ranks += 1;
+                let seen = (hole.pocket_count(rank) + hole.board_count(rank)) as usize;
+                class.straight_outs += 4 - seen;
+            }
+        }
+        straight_draw = match out_ranks {
+            0 => None,
+            1 => Some(HandCategory::Gutshot),
+            2 => Some(HandCategory::OpenEnded),
+            _ => Some(HandCategory::Wrap),
+        };
+    }
+
+    if flush_draw.is_some() && straight_draw.is_some() {
+        class.draws.push(HandCategory::ComboDraw);
+    }
+    if let Some(draw) = flush_draw {
+        class.draws.push(draw);
+        if draw == HandCategory::NutFlushDraw {
+            class.draws.push(HandCategory::FlushDraw);
+        }
+    }
+    if let Some(draw) = straight_draw {
+        class.draws.push(draw);
+    }
+
+    if nboard == 3 {
+        if flush_draw.is_none()
+            && hand_type < HandType::Flush.as_usize()
+            && (0..4).any(|suit| hole.backdoor_flush(suit))
+        {
+            class.draws.push(HandCategory::BackdoorFlushDraw);
+        }
+        if straight_draw.is_none()
+            && hand_type < HandType::Straight.as_usize()
+            && hole.backdoor_straight()
+        {
+            class.draws.push(HandCategory::BackdoorStraightDraw);
+        }
+    }
+
+    Ok(class)
+}
+
+/// Returns the hands of `range` that belong to `category` on `board`, keeping weights.
+///
+/// Hands that share a card with the board are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::classify::{filter_range, HandCategory};
+/// use poker_eval_rs::deck::StdDeck;
+/// use poker_eval_rs::range::HandRange;
+/// use std::str::FromStr;
+///
+/// let range = HandRange::from_str("QQ+,AK").unwrap();
+/// let (board, _) = StdDeck::string_to_mask("Jh7c2d").unwrap();
+/// let overpairs = filter_range(&range, &board, HandCategory::Overpair);
+/// assert_eq!(overpairs.len(), 18);
+/// ```
+pub fn filter_range(
+    range: &HandRange,
+    board: &StdDeckCardMask,
+    category: HandCategory,
+) -> HandRange {
+    range.filter(|hand| {
+        (hand.as_raw() & board.as_raw()) == 0
+            && classify(hand, board)
+                .map(|class| class.matches(category))
+                .unwrap_or(false)
+    })
+}
+
+/// Rank and suit bitmasks for a holding and a board.
+struct HoleCards {
+    omaha: bool,
+    pocket_ranks: u16,
+    board_ranks: u16,
+    /// Ranks per suit, indexed like card indices (h, d, c, s).
+    pocket_suits: [u16; 4],
+    board_suits: [u16; 4],
+}
+
+/// The ten straights as rank bitmasks, wheel first.
+const STRAIGHTS: [u16; 10] = [
+    0b1_0000_0000_1111,
+    0b0_0000_0001_1111,
+    0b0_0000_0011_1110,
+    0b0_0000_0111_1100,
+    0b0_0000_1111_1000,
+    0b0_0001_1111_0000,
+    0b0_0011_1110_0000,
+    0b0_0111_1100_0000,
+    0b0_1111_1000_0000,
+    0b1_1111_0000_0000,
+];
+
+impl HoleCards {
+    fn new(pocket: &StdDeckCardMask, board: &StdDeckCardMask) -> Self {
+        let suits = |m: &StdDeckCardMask| [m.hearts(), m.diamonds(), m.clubs(), m.spades()];
+        let pocket_suits = suits(pocket);
+        let board_suits = suits(board);
+        HoleCards {
+            omaha: pocket.num_cards() > 2,
+            pocket_ranks: pocket_suits.iter().fold(0, |acc, s| acc | s),
+            board_ranks: board_suits.iter().fold(0, |acc, s| acc | s),
+            pocket_suits,
+            board_suits,
+        }
+    }
+
+    fn pocket_count(&self, rank: usize) -> u32 {
+        self.pocket_suits
+            .iter()
+            .filter(|s| *s & (1 << rank) != 0)
+            .count() as u32
+    }
+
+    fn board_count(&self, rank: usize) -> u32 {
+        self.board_suits
+            .iter()
+            .filter(|s| *s & (1 << rank) != 0)
+            .count() as u32
+    }
+
+    /// True if one more card of `suit` on the board gives us a flush.
+    fn completes_flush(&self, suit: usize) -> bool {
+        let pocket = self.pocket_suits[suit].count_ones();
+        let board = self.board_suits[suit].count_ones();
+        if self.omaha {
+            pocket >= 2 && board == 2
+        } else {
+            pocket >= 1 && pocket + board == 4
+        }
+    }
+
+    /// True if two more cards of `suit` on the board give us a flush.
+    fn backdoor_flush(&self, suit: usize) -> bool {
+        let pocket = self.pocket_suits[suit].count_ones();
+        let board = self.board_suits[suit].count_ones();
+        if self.omaha {
+            pocket >= 2 && board == 1
+        } else {
+            pocket >= 1 && pocket + board == 3
+        }
+    }
+
+    /// True if we hold the highest card of `suit` that is not on the board.
+    fn holds_nut_card(&self, suit: usize) -> bool {
+        (0..STD_DECK_RANK_COUNT)
+            .rev()
+            .find(|&r| self.board_suits[suit] & (1 << r) == 0)
+            .is_some_and(|r| self.pocket_suits[suit] & (1 << r) != 0)
+    }
+
+    /// True if our hole cards make a straight with the given board ranks.
+    fn makes_straight(&self, board_ranks: u16) -> bool {
+        STRAIGHTS.iter().any(|&straight| {
+            let missing = straight & !board_ranks;
+            if self.omaha {
+                // Exactly two hole cards and three board cards.
+                missing.count_ones() <= 2
+                    && missing & !self.pocket_ranks == 0
+                    && (straight & self.pocket_ranks).count_ones() >= 2
+            } else {
+                missing != 0 && missing & !self.pocket_ranks == 0
+            }
+        })
+    }
+
+    /// True if two more board cards can give us a straight.
+    fn backdoor_straight(&self) -> bool {
+        (0..STD_DECK_RANK_COUNT).any(|r1| {
+            (r1 + 1..STD_DECK_RANK_COUNT)
+                .any(|r2| self.makes_straight(self.board_ranks | 1 << r1 | 1 << r2))
+        })
+    }
+}
+
+/// Picks the made-hand category from the hand value and the cards behind it.
+fn made_category(hole: &HoleCards, value: HandVal) -> HandCategory {
+    match value.get_hand_type() {
+        HandType::StFlush => HandCategory::StraightFlush,
+        HandType::Quads => HandCategory::Quads,
+        HandType::FullHouse => HandCategory::FullHouse,
+        HandType::Flush => HandCategory::Flush,
+        HandType::Straight => HandCategory::Straight,
+        HandType::Trips => {
+            if hole.pocket_count(value.top_card() as usize) >= 2 {
+                HandCategory::Set
+            } else {
+                HandCategory::Trips
+            }
+        }
+        HandType::TwoPair => HandCategory::TwoPair,
+        HandType::OnePair => pair_category(hole, value),
+        HandType::NoPair => HandCategory::HighCard,
+    }
+}
+
+fn pair_category(hole: &HoleCards, value: HandVal) -> HandCategory {
+    let pair = value.top_card() as usize;
+    if hole.board_count(pair) >= 2 {
+        // The pair is on the board.
+        return HandCategory::HighCard;
+    }
+    // Number of distinct board ranks above the pair.
+    let above = (hole.board_ranks >> (pair + 1)).count_ones();
+
+    if hole.board_count(pair) == 0 {
+        return if above == 0 {
+            HandCategory::Overpair
+        } else if hole.board_ranks & ((1 << pair) - 1) == 0 {
+            HandCategory::Underpair
+        } else if above == 1 {
+            HandCategory::SecondPair
+        } else {
+            HandCategory::WeakPair
+        };
+    }
+
+    match above {
+        0 => {
+            // Best kicker available: the highest rank that is neither paired nor on board.
+            let top_kicker = (0..STD_DECK_RANK_COUNT)
+                .rev()
+                .find(|&r| r != pair && hole.board_count(r) == 0);
+            if top_kicker == Some(value.second_card() as usize) {
+                HandCategory::TopPairTopKicker
+            } else {
+                HandCategory::TopPair
+            }
+        }
+        1 => HandCategory::SecondPair,
+        _ => HandCategory::WeakPair,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::StdDeck;
+
+    fn class_of(pocket: &str, board: &str) -> HandClass {
+        let (p, _) = StdDeck::string_to_mask(pocket).unwrap();
+        let (b, _) = StdDeck::string_to_mask(board).unwrap();
+        classify(&p, &b).unwrap()
+    }
+
+    #[test]
+    fn test_made_hand_pairs() {
+        assert_eq!(
+            class_of("AhKc", "Kd7s2h").made,
+            HandCategory::TopPairTopKicker
+        );
+        assert_eq!(class_of("KcQh", "Kd7s2h").made, HandCategory::TopPair);
+        assert!(class_of("KcQh", "Kd7s2h").matches(HandCategory::TopPair));
+        assert_eq!(class_of("AhAc", "Kd7s2h").made, HandCategory::Overpair);
+        assert_eq!(class_of("7h8h", "Kd7s2c").made, HandCategory::SecondPair);
+        assert_eq!(class_of("9h9c", "Kd7s2c").made, HandCategory::SecondPair);
+        assert_eq!(class_of("2h3h", "Kd7s2c").made, HandCategory::WeakPair);
+        assert_eq!(class_of("3h3c", "Kd7s4c").made, HandCategory::Underpair);
+        assert_eq!(class_of("AhQc", "Kd7s7c").made, HandCategory::HighCard);
+    }
+
+    #[test]
+    fn test_set_vs_trips() {
+        assert_eq!(class_of("7h7c", "Kd7s2h").made, HandCategory::Set);
+        assert_eq!(class_of("Ah7c", "7d7s2h").made, HandCategory::Trips);
+    }
+
+    #[test]
+    fn test_draws() {
+        // Nut flush draw + gutshot = combo draw.
+        let c = class_of("AhKh", "QhJh2c");
+        assert!(c.matches(HandCategory::NutFlushDraw));
+        assert!(c.matches(HandCategory::FlushDraw));
+        assert!(c.matches(HandCategory::ComboDraw));
+        assert_eq!(c.flush_outs, 9);
+
+        let c = class_of("9c8d", "Th7s2c");
+        assert_eq!(c.draws, vec![HandCategory::OpenEnded]);
+        assert_eq!(c.straight_outs, 8);
+
+        let c = class_of("9c8d", "Jh7s2c");
+        assert!(c.matches(HandCategory::Gutshot));
+        assert_eq!(c.straight_outs, 4);
+
+        let c = class_of("KhQh", "Js5h2c");
+        assert!(c.matches(HandCategory::BackdoorFlushDraw));
+        assert!(c.matches(HandCategory::BackdoorStraightDraw));
+
+        // No draws on the river.
+        assert!(!class_of("AhJh", "Qh9h2c3d4s").has_draw());
+    }
+
+    #[test]
+    fn test_omaha_wrap_and_flush_rule() {
+        // JT98 on 7-6-2: any 5, 8, 9 or T completes a straight.
+        let c = class_of("JcTd9h8s", "7c6d2h");
+        assert!(c.matches(HandCategory::Wrap));
+        assert_eq!(c.straight_outs, 13);
+
+        // A single heart in hand is no flush draw in Omaha.
+        let c = class_of("AhKcQdJs", "9h5h2c");
+        assert!(!c.matches(HandCategory::FlushDraw));
+    }
+
+    #[test]
+    fn test_category_parse() {
+        assert_eq!(
+            HandCategory::from_str("TPTK").unwrap(),
+            HandCategory::TopPairTopKicker
+        );
+        assert_eq!(
+            HandCategory::from_str("flush_draw").unwrap(),
+            HandCategory::FlushDraw
+        );
+        for c in HAND_CATEGORIES {
+            assert_eq!(HandCategory::from_str(c.name()).unwrap(), c);
+        }
+        assert!(HandCategory::from_str("monster").is_err());
+    }
+
+    #[test]
+    fn test_filter_range_by_category() {
+        let range = HandRange::from_str("AK,KQs,77").unwrap();
+        let (board, _) = StdDeck::string_to_mask("Kd7s2h").unwrap();
+        let tptk = filter_range(&range, &board, HandCategory::TopPairTopKicker);
+        // AK with one king gone from the deck: 4 aces x 3 kings.
+        assert_eq!(tptk.len(), 12);
+        let sets = filter_range(&range, &board, HandCategory::Set);
+        assert_eq!(sets.len(), 3);
+    }
+}
@@ -0,0 +1,547 @@
+//! Chip EV of calling, folding and jamming.
+//!
+//! A [`DecisionSpot`] describes hero facing bets from one or more opponents, each
+//! holding a [`HandRange`]. [`decision_ev`] compares the pot odds on offer with
+//! hero's actual equity and returns the chip EV of each option relative to folding.
+//!
+//! When players have different stacks the chips are split into a main pot and side
+//! pots, and hero's equity is computed for each pot separately. Heads-up equity
+//! comes from [`enum_exhaustive`] against a single hand or [`calculate_equity`]
+//! against a range; multiway spots are sampled.
+
+use crate::deck::{StdDeckCardMask, STD_DECK_N_CARDS};
+use crate::enumdefs::{EnumResult, Game, ENUM_MAXPLAYERS};
+use crate::enumerate::enum_exhaustive;
+use crate::errors::PokerError;
+use crate::evaluators::range_equity::calculate_equity;
+use crate::evaluators::Eval;
+use crate::range::HandRange;
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+
+/// Attempts to deal non-conflicting opponent hands before a sample is skipped.
+const MAX_DEAL_RETRIES: usize = 20;
+
+/// An opponent in a [`DecisionSpot`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Opponent {
+    /// The hands the opponent continues with.
+    pub range: HandRange,
+    /// Chips the opponent has put in on this street.
+    pub bet: f64,
+    /// Chips the opponent has left behind after `bet`.
+    pub stack: f64,
+}
+
+/// A call/fold/jam decision for hero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecisionSpot {
+    /// Hero's two hole cards.
+    pub hero: StdDeckCardMask,
+    /// Board cards, 0 to 5.
+    pub board: StdDeckCardMask,
+    /// Cards known to be out of play.
+    pub dead: StdDeckCardMask,
+    /// Chips already in the pot before this street's bets (blinds, antes, earlier
+    /// streets, folded players). Everyone still in can win them.
+    pub pot: f64,
+    /// Hero's chips, none of them in the pot yet.
+    pub hero_stack: f64,
+    /// Opponents still in the hand.
+    pub opponents: Vec<Opponent>,
+    /// Monte Carlo iterations for range and multiway equities.
+    pub iterations: usize,
+}
+
+/// One pot hero can win.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PotEquity {
+    /// Chips in the pot.
+    pub amount: f64,
+    /// Players who can win it, hero included.
+    pub players: usize,
+    /// Hero's expected share of the pot (0 to 1).
+    pub equity: f64,
+}
+
+/// Chip EV of one option, relative to folding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionEv {
+    /// Chips hero puts in.
+    pub risk: f64,
+    /// Pots hero plays for if called, main pot first.
+    pub pots: Vec<PotEquity>,
+    /// Expected chip gain compared with folding.
+    pub ev: f64,
+}
+
+/// Result of [`decision_ev`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecisionEv {
+    /// Chips hero needs to put in to call, capped by hero's stack.
+    pub to_call: f64,
+    /// Equity needed for calling to break even.
+    pub required_equity: f64,
+    /// Hero's equity in the pots at stake when calling: the chips hero expects to
+    /// win divided by the chips hero can win.
+    pub equity: f64,
+    /// Calling.
+    pub call: OptionEv,
+    /// Jamming, if a fold frequency was given.
+    pub jam: Option<OptionEv>,
+    /// True if every equity was enumerated rather than sampled.
+    pub exact: bool,
+}
+
+impl DecisionEv {
+    /// Folding never gains or loses chips from here.
+    pub fn fold_ev(&self) -> f64 {
+        0.0
+    }
+
+    /// True if calling is at least as good as folding.
+    pub fn should_call(&self) -> bool {
+        self.call.ev >= 0.0
+    }
+}
+
+/// Computes the pot odds and chip EV of calling, folding and, if `fold_frequency`
+/// is given, jamming.
+///
+/// Calling matches the largest bet, or puts hero all-in for less; uncalled chips
+/// go back to their owner. Jamming puts in hero's whole stack: with probability
+/// `fold_frequency` every opponent folds and hero wins the pot and bets, otherwise
+/// every opponent calls as far as their stack allows. Opponent ranges are used
+/// as given in both cases.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::decision::{decision_ev, DecisionSpot, Opponent};
+/// use poker_eval_rs::deck::{StdDeck, StdDeckCardMask};
+/// use poker_eval_rs::range::HandRange;
+/// use std::str::FromStr;
+///
+/// // 100 in the pot, villain bets 50 on the turn with a set; we hold the nut flush draw.
+/// let spot = DecisionSpot {
+///     hero: StdDeck::string_to_mask("AhKh").unwrap().0,
+///     board: StdDeck::string_to_mask("Qh7h2c3s").unwrap().0,
+///     dead: StdDeckCardMask::new(),
+///     pot: 100.0,
+///     hero_stack: 500.0,
+///     opponents: vec![Opponent {
+///         range: HandRange::from_str("QdQc").unwrap(),
+///         bet: 50.0,
+///         stack: 450.0,
+///     }],
+///     iterations: 10_000,
+/// };
+/// let res = decision_ev(&spot, None).unwrap();
+/// assert_eq!(res.required_equity, 0.25);
+/// assert!(res.exact);
+/// // Nine hearts out of 44 rivers are not enough.
+/// assert!(!res.should_call());
+/// ```
+pub fn decision_ev(
+    spot: &DecisionSpot,
+    fold_frequency: Option<f64>,
+) -> Result<DecisionEv, PokerError> {
+    validate(spot)?;
+    if let Some(f) = fold_frequency {
+        if !(0.0..=1.0).contains(&f) {
+            return Err(PokerError::InvalidInput(format!(
+                "Fold frequency must be between 0 and 1, got {}",
+                f
+            )));
+        }
+    }
+
+    let max_bet = spot.opponents.iter().map(|o| o.bet).fold(0.0, f64::max);
+    let to_call = max_bet.min(spot.hero_stack);
+    let bets: Vec<f64> = spot.opponents.iter().map(|o| o.bet).collect();
+    let (call, call_exact) = option_ev(spot, to_call, &bets)?;
+
+    let jam = match fold_frequency {
+        Some(f) => {
+            let risk = spot.hero_stack;
+            let called: Vec<f64> = spot
+                .opponents
+                .iter()
+                .map(|o| (o.bet + o.stack).min(risk.max(o.bet)))
+                .collect();
+            let (mut jam, exact) = option_ev(spot, risk, &called)?;
+            let steal = spot.pot + bets.iter().sum::<f64>();
+            jam.ev = f * steal + (1.0 - f) * jam.ev;
+            Some((jam, exact))
+        }
+        None => None,
+    };
+
+    let at_stake: f64 = call.pots.iter().map(|p| p.amount).sum();
+    let won: f64 = call.pots.iter().map(|p| p.amount * p.equity).sum();
+    Ok(DecisionEv {
+        to_call,
+        required_equity: if at_stake > 0.0 {
+            to_call / at_stake
+        } else {
+            0.0
+        },
+        equity: if at_stake > 0.0 { won / at_stake } else { 0.0 },
+        exact: call_exact && jam.as_ref().map_or(true, |(_, e)| *e),
+        jam: jam.map(|(j, _)| j),
+        call,
+    })
+}
+
+fn validate(spot: &DecisionSpot) -> Result<(), PokerError> {
+    if spot.opponents.is_empty() {
+        return Err(PokerError::InvalidInput(
+            "Need at least one opponent".to_string(),
+        ));
+    }
+    if spot.opponents.len() + 1 > ENUM_MAXPLAYERS {
+        return Err(PokerError::TooManyPlayers);
+    }
+    if spot.hero.num_cards() != 2 {
+        return Err(PokerError::InvalidCardConfiguration(
+            "Hero hand must have exactly 2 cards".to_string(),
+        ));
+    }
+    if spot.board.num_cards() > 5 {
+        return Err(PokerError::UnsupportedBoardConfiguration);
+    }
+    if (spot.hero.as_raw() & (spot.board.as_raw() | spot.dead.as_raw())) != 0
+        || (spot.board.as_raw() & spot.dead.as_raw()) != 0
+    {
+        return Err(PokerError::InvalidCardConfiguration(
+            "Hero, board and dead cards must not overlap".to_string(),
+        ));
+    }
+    let chips = std::iter::once(spot.pot)
+        .chain(std::iter::once(spot.hero_stack))
+        .chain(spot.opponents.iter().flat_map(|o| [o.bet, o.stack]));
+    for c in chips {
+        if !c.is_finite() || c < 0.0 {
+            return Err(PokerError::InvalidInput(format!(
+                "Chip amounts must be non-negative, got {}",
+                c
+            )));
+        }
+    }
+    if spot.iterations == 0 {
+        return Err(PokerError::InvalidInput(
+            "Iterations must be positive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// EV of hero putting in `risk` while opponents put in `contributions`.
+fn option_ev(
+    spot: &DecisionSpot,
+    risk: f64,
+    contributions: &[f64],
+) -> Result<(OptionEv, bool), PokerError> {
+    let layers = pot_layers(spot.pot, risk, contributions);
+    let known = spot.hero | spot.board | spot.dead;
+    let live: Vec<Vec<(StdDeckCardMask, f64)>> = spot
+        .opponents
+        .iter()
+        .map(|o| {
+            o.range
+                .hands()
+                .iter()
+                .filter(|(h, w)| *w > 0.0 && (h.as_raw() & known.as_raw()) == 0)
+                .copied()
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    if live.iter().any(|r| r.is_empty()) {
+        return Err(PokerError::InvalidInput(
+            "Opponent range is empty once known cards are removed".to_string(),
+        ));
+    }
+
+    let (shares, exact) = if live.len() == 1 {
+        let (equity, exact) = heads_up_equity(spot, &live[0])?;
+        let shares = layers
+            .iter()
+            .map(|l| match l.eligible.len() {
+                1 if l.eligible[0] == 0 => 1.0,
+                1 => 0.0,
+                _ => equity,
+            })
+            .collect();
+        (shares, exact)
+    } else {
+        (multiway_shares(spot, &live, &layers)?, false)
+    };
+
+    let pots: Vec<PotEquity> = layers
+        .iter()
+        .zip(shares)
+        .filter(|(l, _)| l.eligible.contains(&0))
+        .map(|(l, equity)| PotEquity {
+            amount: l.amount,
+            players: l.eligible.len(),
+            equity,
+        })
+        .collect();
+    let won: f64 = pots.iter().map(|p| p.amount * p.equity).sum();
+    Ok((
+        OptionEv {
+            risk,
+            ev: won - risk,
+            pots,
+        },
+        exact,
+    ))
+}
+
+/// A pot and the players who can win it (0 is hero, `i + 1` is opponent `i`).
+struct Layer {
+    amount: f64,
+    eligible: Vec<usize>,
+}
+
+/// Splits contributions into a main pot and side pots. `dead` money goes to the
+/// main pot; a layer only one player reached is that player's uncalled bet.
+fn pot_layers(dead: f64, hero: f64, opponents: &[f64]) -> Vec<Layer> {
+    let contributions: Vec<f64> = std::iter::once(hero)
+        .chain(opponents.iter().copied())
+        .collect();
+    let mut levels: Vec<f64> = contributions.iter().copied().filter(|&c| c > 0.0).collect();
+    levels.sort_by(f64::total_cmp);
+    levels.dedup();
+
+    let mut layers = Vec::new();
+    let mut floor = 0.0;
+    for level in levels {
+        let eligible: Vec<usize> = (0..contributions.len())
+            .filter(|&p| contributions[p] >= level)
+            .collect();
+        let amount = (level - floor) * eligible.len() as f64;
+        layers.push(Layer { amount, eligible });
+        floor = level;
+    }
+    match layers.first_mut() {
+        Some(main) => main.amount += dead,
+        None => layers.push(Layer {
+            amount: dead,
+            eligible: (0..contributions.len()).collect(),
+        }),
+    }
+    layers
+}
+
+/// Hero's heads-up equity, exact against a single hand.
+fn heads_up_equity(
+    spot: &DecisionSpot,
+    villain: &[(StdDeckCardMask, f64)],
+) -> Result<(f64, bool), PokerError> {
+    if let [(hand, _)] = villain {
+        let mut result = EnumResult::new(Game::Holdem);
+        let nboard = spot.board.num_cards();
+        enum_exhaustive(
+            Game::Holdem,
+            &[spot.hero, *hand],
+            spot.board,
+            spot.dead,
+            2,
+            nboard,
+            false,
+            &mut result,
+        )?;
+        return Ok((result.ev[0] / result.nsamples.max(1) as f64, true));
+    }
+    let hero = HandRange::from_hand_masks(vec![spot.hero]);
+    let villain = HandRange {
+        hands: villain.to_vec(),
+    };
+    let res = calculate_equity(&hero, &villain, &spot.board, spot.iterations)
+        .map_err(PokerError::InvalidInput)?;
+    Ok((res.equity, false))
+}
+
+/// Hero's expected share of each layer, sampled over opponent hands and runouts.
+fn multiway_shares(
+    spot: &DecisionSpot,
+    ranges: &[Vec<(StdDeckCardMask, f64)>],
+    layers: &[Layer],
+) -> Result<Vec<f64>, PokerError> {
+    let dists = ranges
+        .iter()
+        .map(|r| WeightedIndex::new(r.iter().map(|(_, w)| *w)))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| PokerError::InvalidInput(format!("Invalid range weights: {}", e)))?;
+    let known = spot.hero | spot.board | spot.dead;
+    let mut deck: Vec<usize> = (0..STD_DECK_N_CARDS)
+        .filter(|&i| !known.card_is_set(i))
+        .collect();
+    let missing = 5 - spot.board.num_cards();
+    let needed = missing + 2 * ranges.len();
+
+    let mut rng = thread_rng();
+    let mut shares = vec![0.0; layers.len()];
+    let mut samples = 0usize;
+    let mut values = vec![0u32; ranges.len() + 1];
+    let mut hands = vec![StdDeckCardMask::new(); ranges.len()];
+    for _ in 0..spot.iterations {
+        let mut used = known;
+        let mut dealt = true;
+        for (hand, (range, dist)) in hands.iter_mut().zip(ranges.iter().zip(&dists)) {
+            let pick = (0..MAX_DEAL_RETRIES)
+                .map(|_| range[dist.sample(&mut rng)].0)
+                .find(|h| (h.as_raw() & used.as_raw()) == 0);
+            match pick {
+                Some(h) => {
+                    *hand = h;
+                    used = used | h;
+                }
+                None => {
+                    dealt = false;
+                    break;
+                }
+            }
+        }
+        if !dealt {
+            continue;
+        }
+
+        let (cards, _) = deck.partial_shuffle(&mut rng, needed);
+        let mut board = spot.board;
+        for &c in cards
+            .iter()
+            .filter(|&&c| !used.card_is_set(c))
+            .take(missing)
+        {
+            board.set(c);
+        }
+        for (value, pocket) in values
+            .iter_mut()
+            .zip(std::iter::once(&spot.hero).chain(hands.iter()))
+        {
+            let full = *pocket | board;
+            *value = Eval::eval_n(&full, full.num_cards()).value;
+        }
+
+        for (share, layer) in shares.iter_mut().zip(layers) {
+            let best = layer.eligible.iter().map(|&p| values[p]).max().unwrap_or(0);
+            if layer.eligible.contains(&0) && values[0] == best {
+                let winners = layer
+                    .eligible
+                    .iter()
+                    .filter(|&&p| values[p] == best)
+                    .count();
+                *share += 1.0 / winners as f64;
+            }
+        }
+        samples += 1;
+    }
+
+    if samples == 0 {
+        return Err(PokerError::InvalidInput(
+            "Opponent ranges conflict with each other".to_string(),
+        ));
+    }
+    Ok(shares.into_iter().map(|s| s / samples as f64).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::StdDeck;
+    use std::str::FromStr;
+
+    fn mask(s: &str) -> StdDeckCardMask {
+        StdDeck::string_to_mask(s).unwrap().0
+    }
+
+    fn opponent(range: &str, bet: f64, stack: f64) -> Opponent {
+        Opponent {
+            range: HandRange::from_str(range).unwrap(),
+            bet,
+            stack,
+        }
+    }
+
+    #[test]
+    fn test_call_on_the_river() {
+        let spot = DecisionSpot {
+            hero: mask("AhKh"),
+            board: mask("Kd7c2s9h4d"),
+            dead: StdDeckCardMask::new(),
+            pot: 100.0,
+            hero_stack: 1000.0,
+            opponents: vec![opponent("QQ", 100.0, 900.0)],
+            iterations: 1000,
+        };
+        let res = decision_ev(&spot, Some(0.5)).unwrap();
+        assert!((res.required_equity - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(res.equity, 1.0);
+        assert_eq!(res.call.ev, 200.0);
+
+        // Jamming 1000: half the time we take 200, otherwise we win 1000 more.
+        let jam = res.jam.unwrap();
+        assert_eq!(jam.risk, 1000.0);
+        assert_eq!(jam.ev, 0.5 * 200.0 + 0.5 * 1100.0);
+    }
+
+    #[test]
+    fn test_short_stack_call_returns_excess() {
+        let spot = DecisionSpot {
+            hero: mask("2c2d"),
+            board: mask("AsKsQd"),
+            dead: StdDeckCardMask::new(),
+            pot: 20.0,
+            hero_stack: 40.0,
+            opponents: vec![opponent("AcAd", 200.0, 0.0)],
+            iterations: 1000,
+        };
+        let res = decision_ev(&spot, None).unwrap();
+        assert_eq!(res.to_call, 40.0);
+        // Only 40 of villain's 200 can be won.
+        assert_eq!(res.call.pots.len(), 1);
+        assert_eq!(res.call.pots[0].amount, 100.0);
+        assert!(res.exact);
+    }
+
+    #[test]
+    fn test_multiway_side_pot() {
+        // A short stack is all-in for 50, a deep stack bets 200; we hold the nuts.
+        let spot = DecisionSpot {
+            hero: mask("AsAd"),
+            board: mask("AhAc7d2s3h"),
+            dead: StdDeckCardMask::new(),
+            pot: 30.0,
+            hero_stack: 500.0,
+            opponents: vec![opponent("KK", 50.0, 0.0), opponent("QQ", 200.0, 300.0)],
+            iterations: 200,
+        };
+        let res = decision_ev(&spot, None).unwrap();
+        assert_eq!(res.call.pots.len(), 2);
+        assert_eq!(res.call.pots[0].amount, 30.0 + 150.0);
+        assert_eq!(res.call.pots[0].players, 3);
+        assert_eq!(res.call.pots[1].amount, 300.0);
+        assert_eq!(res.call.ev, 480.0 - 200.0);
+        assert!(!res.exact);
+    }
+
+    #[test]
+    fn test_decision_errors() {
+        let mut spot = DecisionSpot {
+            hero: mask("AsAd"),
+            board: StdDeckCardMask::new(),
+            dead: StdDeckCardMask::new(),
+            pot: 10.0,
+            hero_stack: 100.0,
+            opponents: vec![opponent("AA", 10.0, 90.0)],
+            iterations: 100,
+        };
+        assert!(decision_ev(&spot, Some(1.5)).is_err());
+        spot.opponents[0].range = HandRange::from_str("AsAh").unwrap();
+        assert!(decision_ev(&spot, None).is_err());
+        spot.opponents.clear();
+        assert!(decision_ev(&spot, None).is_err());
+    }
+}
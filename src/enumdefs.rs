@@ -3,6 +3,9 @@ use crate::enumord::EnumOrdering;
 // Constant defining the maximum number of players
 pub const ENUM_MAXPLAYERS: usize = 12;
 
+// Number of high hand types tracked in the hand-type distribution (see `HandType`)
+pub const ENUM_NHANDTYPES: usize = 9;
+
 use serde::{Deserialize, Serialize};
 
 // Enumeration of different poker variants
@@ -66,8 +69,28 @@ pub struct EnumResult {
     pub nshare: Box<[[[u32; ENUM_MAXPLAYERS + 1]; ENUM_MAXPLAYERS + 1]; ENUM_MAXPLAYERS]>,
     pub ev: [f64; ENUM_MAXPLAYERS], // Average equity of each player
 
+    // Final high hand type of each player, indexed by `HandType`
+    #[serde(default)]
+    pub nhandtype: [[u32; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS],
+    // Outright high wins of each player, by the winning hand type
+    #[serde(default)]
+    pub nwinhandtype: [[u32; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS],
+    // High losses of each player, by [own hand type][best hand type]
+    #[serde(default)]
+    pub nlosehandtype: [[[u32; ENUM_NHANDTYPES]; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS],
+
     // Safe nullable pointer to an enumeration ordering structure
     pub ordering: Option<Box<EnumOrdering>>,
+
+    // Seed for Monte Carlo sampling; `None` samples from entropy. Kept by `clear`.
+    #[serde(skip)]
+    pub seed: Option<u64>,
+
+    // Count each deal once per player as the C library does (a win only for the sole
+    // best hand, `ev` the player's share of the pot) rather than once per opponent.
+    // Kept by `clear`.
+    #[serde(default)]
+    pub outright: bool,
 }
 
 impl Default for EnumResult {
@@ -88,7 +111,12 @@ impl Default for EnumResult {
             nsharelo: [[0; ENUM_MAXPLAYERS + 1]; ENUM_MAXPLAYERS],
             nshare: Box::new([[[0; ENUM_MAXPLAYERS + 1]; ENUM_MAXPLAYERS + 1]; ENUM_MAXPLAYERS]),
             ev: [0.0; ENUM_MAXPLAYERS],
+            nhandtype: [[0; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS],
+            nwinhandtype: [[0; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS],
+            nlosehandtype: [[[0; ENUM_NHANDTYPES]; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS],
             ordering: None,
+            seed: None,
+            outright: false,
         }
     }
 }
@@ -1,13 +1,23 @@
 //! Top-level enumeration functions: exhaustive and sample-based evaluation.
 
-use crate::enumdefs::{EnumResult, Game, ENUM_MAXPLAYERS};
+use super::generic::{enum_rules_exhaustive, enum_rules_sample};
+use crate::combinations::for_each_combination;
+use crate::deck::STD_DECK_N_CARDS;
+use crate::enumdefs::{EnumResult, Game, SampleType, ENUM_MAXPLAYERS};
 use crate::enumord::EnumOrderingMode;
 use crate::errors::PokerError;
+use crate::game_rules::GameRules;
+use crate::pot::{distribute, Showdown};
 use crate::tables::t_cardmasks::StdDeckCardMask;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
 
 /// Runs a Monte Carlo sample evaluation for the given game and player hands.
 ///
-/// Draws random boards `niter` times and aggregates win/tie/loss statistics.
+/// Draws random boards `niter` times and aggregates win/tie/loss statistics. With
+/// `orderflag` the result also gets the histogram of the players' relative hand ranks
+/// (see [`EnumResult::print_ordering`]). `pockets` holds at least `npockets` hands and
+/// `board` must hold `nboard` cards. This is [`enum_game_sample`] for a built-in game.
 ///
 /// # Examples
 ///
@@ -38,96 +48,15 @@ pub fn enum_sample(
     orderflag: bool,
     result: &mut EnumResult,
 ) -> Result<(), PokerError> {
-    if npockets > ENUM_MAXPLAYERS {
-        return Err(PokerError::TooManyPlayers);
-    }
-    result.clear();
-
-    let mode = match game {
-        Game::Holdem
-        | Game::Omaha
-        | Game::Omaha5
-        | Game::Omaha6
-        | Game::Stud7
-        | Game::Draw5
-        | Game::ShortDeck => EnumOrderingMode::Hi,
-        Game::Razz | Game::Lowball | Game::Lowball27 => EnumOrderingMode::Lo,
-        Game::Holdem8
-        | Game::Omaha8
-        | Game::Omaha85
-        | Game::Stud78
-        | Game::Stud7nsq
-        | Game::Draw58
-        | Game::Draw5nsq => EnumOrderingMode::Hilo,
-        _ => return Err(PokerError::UnsupportedGameType),
-    };
-
-    if orderflag {
-        result.allocate_resources(npockets, mode)?;
-    }
-
-    match game {
-        Game::Holdem => {
-            result.simulate_holdem_game(pockets, board, dead, npockets, nboard, niter)?;
-        }
-        Game::Holdem8 => {
-            result.simulate_holdem8_game(pockets, board, dead, npockets, nboard, niter)?;
-        }
-        Game::Omaha => {
-            result.simulate_omaha_game(pockets, board, dead, npockets, nboard, niter)?;
-        }
-        Game::Omaha5 => {
-            result.simulate_omaha5_game(pockets, board, dead, npockets, nboard, niter)?;
-        }
-        Game::Omaha6 => {
-            result.simulate_omaha6_game(pockets, board, dead, npockets, nboard, niter)?;
-        }
-        Game::Omaha8 => {
-            result.simulate_omaha8_game(pockets, board, dead, npockets, nboard, niter)?;
-        }
-        Game::Omaha85 => {
-            // Omaha85 uses same logic as Omaha8 (5-card variant?)
-            // Check result.rs simulate_omaha8_game comment: "Works for Omaha8 and Omaha85 variants."
-            result.simulate_omaha8_game(pockets, board, dead, npockets, nboard, niter)?;
-        }
-        Game::ShortDeck => {
-            result.simulate_short_deck_game(pockets, board, dead, npockets, nboard, niter)?;
-        }
-        Game::Stud7 => {
-            result.simulate_stud_game(pockets, dead, npockets, niter)?;
-        }
-        Game::Stud78 => {
-            result.simulate_stud8_game(pockets, dead, npockets, niter)?;
-        }
-        Game::Stud7nsq => {
-            result.simulate_studnsq_game(pockets, dead, npockets, niter)?;
-        }
-        Game::Razz => {
-            result.simulate_razz_game(pockets, dead, npockets, niter)?;
-        }
-        Game::Draw5 => {
-            result.simulate_draw_game(pockets, dead, npockets, niter)?;
-        }
-        Game::Draw58 => {
-            result.simulate_draw8_game(pockets, dead, npockets, niter)?;
-        }
-        Game::Draw5nsq => {
-            result.simulate_drawnsq_game(pockets, dead, npockets, niter)?;
-        }
-        Game::Lowball => {
-            result.simulate_lowball_game(pockets, dead, npockets, niter)?;
-        }
-        Game::Lowball27 => {
-            result.simulate_lowball27_game(pockets, dead, npockets, niter)?;
-        }
-        _ => return Err(PokerError::UnsupportedGameType),
-    }
-    Ok(())
+    let pockets = player_pockets(pockets, npockets, board, nboard)?;
+    enum_game_sample(&game, pockets, board, dead, niter, orderflag, result)
 }
 
 /// Runs an exhaustive (all possible boards) evaluation for the given game and player hands.
 ///
 /// Enumerates every possible board runout and aggregates win/tie/loss/equity statistics.
+/// `orderflag` records the relative hand ranks as in [`enum_sample`]. This is
+/// [`enum_game_exhaustive`] for a built-in game.
 ///
 /// # Examples
 ///
@@ -158,50 +87,265 @@ pub fn enum_exhaustive(
     orderflag: bool,
     result: &mut EnumResult,
 ) -> Result<(), PokerError> {
-    result.clear();
+    let pockets = player_pockets(pockets, npockets, board, nboard)?;
+    enum_game_exhaustive(&game, pockets, board, dead, orderflag, result)
+}
+
+/// Samples `niter` random deals of any game, using the game's specialised sampler
+/// when [`GameRules::enum_sample_specialised`] provides one and
+/// [`enum_rules_sample`] otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::deck::{StdDeck, StdDeckCardMask};
+/// use poker_eval_rs::enumdefs::{EnumResult, Game};
+/// use poker_eval_rs::enumerate::enum_game_sample;
+/// use poker_eval_rs::game_rules::game_rules;
+///
+/// let pockets = [
+///     StdDeck::string_to_mask("As2sKdKc").unwrap().0,
+///     StdDeck::string_to_mask("QhQd3c4c").unwrap().0,
+/// ];
+/// let rules = game_rules("omaha8").unwrap();
+/// let mut result = EnumResult::new(Game::Omaha8);
+/// result.seed = Some(1);
+/// enum_game_sample(
+///     &*rules, &pockets, StdDeckCardMask::new(), StdDeckCardMask::new(), 500, false, &mut result,
+/// )
+/// .unwrap();
+/// assert_eq!(result.nsamples, 500);
+/// ```
+pub fn enum_game_sample(
+    rules: &dyn GameRules,
+    pockets: &[StdDeckCardMask],
+    board: StdDeckCardMask,
+    dead: StdDeckCardMask,
+    niter: usize,
+    orderflag: bool,
+    result: &mut EnumResult,
+) -> Result<(), PokerError> {
+    prepare(rules, pockets.len(), SampleType::Sample, orderflag, result)?;
+    match rules.enum_sample_specialised(pockets, board, dead, niter, result) {
+        Some(done) => done,
+        None => enum_rules_sample(rules, pockets, board, dead, niter, result),
+    }
+}
+
+/// Enumerates every deal of any game, using the game's specialised enumerator when
+/// [`GameRules::enum_exhaustive_specialised`] provides one and
+/// [`enum_rules_exhaustive`] otherwise.
+pub fn enum_game_exhaustive(
+    rules: &dyn GameRules,
+    pockets: &[StdDeckCardMask],
+    board: StdDeckCardMask,
+    dead: StdDeckCardMask,
+    orderflag: bool,
+    result: &mut EnumResult,
+) -> Result<(), PokerError> {
+    prepare(
+        rules,
+        pockets.len(),
+        SampleType::Exhaustive,
+        orderflag,
+        result,
+    )?;
+    match rules.enum_exhaustive_specialised(pockets, board, dead, result) {
+        Some(done) => done,
+        None => enum_rules_exhaustive(rules, pockets, board, dead, result),
+    }
+}
 
+/// The first `npockets` of `pockets`, checking that `board` holds `nboard` cards.
+fn player_pockets(
+    pockets: &[StdDeckCardMask],
+    npockets: usize,
+    board: StdDeckCardMask,
+    nboard: usize,
+) -> Result<&[StdDeckCardMask], PokerError> {
+    if board.num_cards() != nboard {
+        return Err(PokerError::UnsupportedBoardConfiguration);
+    }
+    pockets.get(..npockets).ok_or_else(|| {
+        PokerError::InvalidInput(format!(
+            "Got {} pockets for {} players",
+            pockets.len(),
+            npockets
+        ))
+    })
+}
+
+/// Clears `result` for a run of `rules`, allocating the ordering histogram when
+/// asked to.
+fn prepare(
+    rules: &dyn GameRules,
+    npockets: usize,
+    sample_type: SampleType,
+    orderflag: bool,
+    result: &mut EnumResult,
+) -> Result<(), PokerError> {
     if npockets > ENUM_MAXPLAYERS {
         return Err(PokerError::TooManyPlayers);
     }
-
+    let mode = rules.ordering_mode();
+    if mode == EnumOrderingMode::None {
+        return Err(PokerError::UnsupportedGameType);
+    }
+    let game = rules.game().unwrap_or(result.game);
+    result.clear();
+    result.game = game;
+    result.sample_type = sample_type;
+    result.nplayers = npockets as u32;
     if orderflag {
-        let mode = match game {
-            Game::Holdem
-            | Game::Omaha
-            | Game::Omaha5
-            | Game::Omaha6
-            | Game::Stud7
-            | Game::Draw5
-            | Game::ShortDeck => EnumOrderingMode::Hi,
-            Game::Razz | Game::Lowball | Game::Lowball27 => EnumOrderingMode::Lo,
-            Game::Holdem8
-            | Game::Omaha8
-            | Game::Omaha85
-            | Game::Stud78
-            | Game::Stud7nsq
-            | Game::Draw58
-            | Game::Draw5nsq => EnumOrderingMode::Hilo,
-            _ => return Err(PokerError::UnsupportedGameType),
-        };
-
         result.allocate_resources(npockets, mode)?;
     }
+    Ok(())
+}
+
+/// Computes each player's expected chips collected at showdown, for board games
+/// where every player is all-in or has called.
+///
+/// `contributions[i]` is what player `i` put in the pot; players are in seat order
+/// starting left of the button, which decides who gets odd chips. Every remaining
+/// board is enumerated when `niter` is `None`, otherwise `niter` random boards are
+/// dealt. Each board is settled with [`distribute`], so side pots, hi/lo splits and
+/// odd chips follow the rules of the [`pot`](crate::pot) module. The returned
+/// values are expected payouts, so they sum to the pot; subtract
+/// `contributions[i]` for player `i`'s net chip EV.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::deck::{StdDeck, StdDeckCardMask};
+/// use poker_eval_rs::enumdefs::Game;
+/// use poker_eval_rs::enumerate::enum_chip_ev;
+///
+/// // AA all-in for 100 against two 300 stacks on a flop.
+/// let pockets: Vec<StdDeckCardMask> = ["AhAd", "KhKd", "QhQd"]
+///     .iter()
+///     .map(|h| StdDeck::string_to_mask(h).unwrap().0)
+///     .collect();
+/// let (board, _) = StdDeck::string_to_mask("2c7s9d").unwrap();
+/// let payouts = enum_chip_ev(
+///     Game::Holdem, &pockets, board, StdDeckCardMask::new(), &[100, 300, 300], None,
+/// ).unwrap();
+/// assert!((payouts.iter().sum::<f64>() - 700.0).abs() < 1e-9);
+/// // KhKd wins most of the side pot, so it profits even when AA holds.
+/// assert!(payouts[0] > 240.0 && payouts[1] > 300.0);
+/// ```
+pub fn enum_chip_ev(
+    game: Game,
+    pockets: &[StdDeckCardMask],
+    board: StdDeckCardMask,
+    dead: StdDeckCardMask,
+    contributions: &[u64],
+    niter: Option<usize>,
+) -> Result<Vec<f64>, PokerError> {
+    let mode = game.ordering_mode();
+    if mode == EnumOrderingMode::None || game.board_cards() == 0 {
+        return Err(PokerError::UnsupportedGameType);
+    }
+    let npockets = pockets.len();
+    if npockets > ENUM_MAXPLAYERS {
+        return Err(PokerError::TooManyPlayers);
+    }
+    if npockets < 2 || contributions.len() != npockets {
+        return Err(PokerError::InvalidInput(
+            "Need one contribution for each of at least 2 players".to_string(),
+        ));
+    }
+    let nboard = board.num_cards();
+    let missing = game
+        .board_cards()
+        .checked_sub(nboard)
+        .ok_or(PokerError::UnsupportedBoardConfiguration)?;
+    let mut used = board | dead;
+    if (board.as_raw() & dead.as_raw()) != 0 {
+        return Err(PokerError::InvalidCardConfiguration(
+            "Board and dead cards must not overlap".to_string(),
+        ));
+    }
+    for pocket in pockets {
+        if (pocket.as_raw() & used.as_raw()) != 0 {
+            return Err(PokerError::InvalidCardConfiguration(
+                "Pockets, board and dead cards must not overlap".to_string(),
+            ));
+        }
+        used = used | *pocket;
+    }
+
+    let in_play = game.deck();
+    let mut deck: Vec<usize> = (0..STD_DECK_N_CARDS)
+        .filter(|&i| in_play.card_is_set(i) && !used.card_is_set(i))
+        .collect();
+    let has_hi = mode != EnumOrderingMode::Lo;
+    let has_lo = mode != EnumOrderingMode::Hi;
 
-    match game {
-        Game::Holdem => {
-            result.exhaustive_holdem_evaluation(pockets, board, dead, npockets, nboard)?;
+    let mut totals = vec![0.0; npockets];
+    let mut ndeals = 0u64;
+    let mut settle = |full: StdDeckCardMask| -> Result<(), PokerError> {
+        let mut hands = Vec::with_capacity(npockets);
+        for pocket in pockets {
+            let hi = if has_hi {
+                game.eval_hi(pocket, &full)?
+            } else {
+                None
+            };
+            let lo = if has_lo {
+                game.eval_lo(pocket, &full)?
+            } else {
+                None
+            };
+            hands.push(Some(Showdown { hi, lo }));
         }
-        Game::Holdem8 => {
-            result.exhaustive_holdem8_evaluation(pockets, board, dead, npockets, nboard)?;
+        for (total, chips) in totals.iter_mut().zip(distribute(contributions, &hands)?) {
+            *total += chips as f64;
         }
-        Game::Omaha => {
-            result.exhaustive_omaha_evaluation(pockets, board, dead, npockets, nboard)?;
+        ndeals += 1;
+        Ok(())
+    };
+
+    match niter {
+        None => {
+            let mut error = None;
+            for_each_combination(deck.len(), missing, |idx| {
+                if error.is_some() {
+                    return;
+                }
+                let mut full = board;
+                for &i in idx {
+                    full.set(deck[i]);
+                }
+                if let Err(e) = settle(full) {
+                    error = Some(e);
+                }
+            });
+            if let Some(e) = error {
+                return Err(e);
+            }
         }
-        Game::ShortDeck => {
-            result.exhaustive_short_deck_evaluation(pockets, board, dead, npockets, nboard)?;
+        Some(niter) => {
+            if deck.len() < missing {
+                return Err(PokerError::InvalidCardConfiguration(
+                    "Not enough cards left to deal".to_string(),
+                ));
+            }
+            let mut rng = thread_rng();
+            for _ in 0..niter {
+                let (dealt, _) = deck.partial_shuffle(&mut rng, missing);
+                let mut full = board;
+                for &c in dealt.iter() {
+                    full.set(c);
+                }
+                settle(full)?;
+            }
         }
-        _ => return Err(PokerError::UnsupportedGameType),
     }
 
-    Ok(())
+    if ndeals == 0 {
+        return Err(PokerError::InvalidInput(
+            "No boards to enumerate".to_string(),
+        ));
+    }
+    Ok(totals.into_iter().map(|t| t / ndeals as f64).collect())
 }
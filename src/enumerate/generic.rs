@@ -0,0 +1,412 @@
+//! Enumeration driven by [`GameRules`] instead of a built-in game.
+//!
+//! The engine deals whatever is missing (board cards, and pocket cards up to
+//! [`GameRules::showdown_pocket_cards`]) from the rules' deck, evaluates each player
+//! with the rules' evaluators and records the results in an [`EnumResult`]. It is
+//! slower than the specialised paths behind [`enum_exhaustive`](super::enum_exhaustive)
+//! and [`enum_sample`](super::enum_sample), but works for any game.
+
+use super::montecarlo::sample_rng;
+use crate::combinations::for_each_combination;
+use crate::deck::STD_DECK_N_CARDS;
+use crate::enumdefs::{EnumResult, SampleType, ENUM_MAXPLAYERS};
+use crate::enumord::EnumOrderingMode;
+use crate::errors::PokerError;
+use crate::game_rules::GameRules;
+use crate::handval::HandVal;
+use crate::handval_low::{LowHandVal, LOW_HAND_VAL_NOTHING};
+use crate::tables::t_cardmasks::StdDeckCardMask;
+use rand::seq::SliceRandom;
+
+/// The cards of one enumeration: what is known, and what each player and the board
+/// still need.
+struct Deal<'a> {
+    rules: &'a dyn GameRules,
+    pockets: &'a [StdDeckCardMask],
+    board: StdDeckCardMask,
+    /// Cards still to be dealt: the board first, then each player in order.
+    missing: Vec<usize>,
+    /// Cards that can still be dealt.
+    live: Vec<usize>,
+}
+
+impl<'a> Deal<'a> {
+    fn new(
+        rules: &'a dyn GameRules,
+        pockets: &'a [StdDeckCardMask],
+        board: StdDeckCardMask,
+        dead: StdDeckCardMask,
+    ) -> Result<Self, PokerError> {
+        let npockets = pockets.len();
+        if npockets > ENUM_MAXPLAYERS {
+            return Err(PokerError::TooManyPlayers);
+        }
+        if npockets == 0 {
+            return Err(PokerError::InvalidInput(
+                "Need at least one player".to_string(),
+            ));
+        }
+        let board_cards = rules.board_cards();
+        let nboard = board.num_cards();
+        if nboard > board_cards {
+            return Err(PokerError::UnsupportedBoardConfiguration);
+        }
+
+        let deck = rules.deck();
+        let (min_pocket, max_pocket) = rules.pocket_cards();
+        let showdown = rules.showdown_pocket_cards();
+        let mut missing = vec![board_cards - nboard];
+        let mut used = board | dead;
+        if (board.as_raw() & dead.as_raw()) != 0 {
+            return Err(PokerError::InvalidCardConfiguration(
+                "Board and dead cards must not overlap".to_string(),
+            ));
+        }
+        for (i, pocket) in pockets.iter().enumerate() {
+            let n = pocket.num_cards();
+            if n < min_pocket || n > max_pocket || n > showdown {
+                return Err(PokerError::InvalidInput(format!(
+                    "Player {} has {} cards; {} takes {} to {}",
+                    i + 1,
+                    n,
+                    rules.description(),
+                    min_pocket,
+                    max_pocket.min(showdown)
+                )));
+            }
+            if (pocket.as_raw() & used.as_raw()) != 0 {
+                return Err(PokerError::InvalidCardConfiguration(
+                    "Pockets, board and dead cards must not overlap".to_string(),
+                ));
+            }
+            used = used | *pocket;
+            missing.push(showdown - n);
+        }
+        if (used.as_raw() & !deck.as_raw()) != 0 {
+            return Err(PokerError::InvalidCardConfiguration(format!(
+                "Cards not in the {} deck",
+                rules.description()
+            )));
+        }
+
+        let live: Vec<usize> = (0..STD_DECK_N_CARDS)
+            .filter(|&c| deck.card_is_set(c) && !used.card_is_set(c))
+            .collect();
+        if missing.iter().sum::<usize>() > live.len() {
+            return Err(PokerError::InvalidCardConfiguration(
+                "Not enough cards left to deal".to_string(),
+            ));
+        }
+        Ok(Self {
+            rules,
+            pockets,
+            board,
+            missing,
+            live,
+        })
+    }
+
+    /// Splits `cards` into the board and pocket slots, in order, and scores the deal.
+    fn score(
+        &self,
+        cards: &[usize],
+        scratch: &mut Scratch,
+        result: &mut EnumResult,
+    ) -> Result<(), PokerError> {
+        let mut next = cards.iter();
+        let mut take = |n: usize, mut mask: StdDeckCardMask| {
+            for &c in next.by_ref().take(n) {
+                mask.set(c);
+            }
+            mask
+        };
+        let board = take(self.missing[0], self.board);
+        for (i, pocket) in self.pockets.iter().enumerate() {
+            scratch.pockets[i] = take(self.missing[i + 1], *pocket);
+        }
+
+        let npockets = self.pockets.len();
+        let mode = self.rules.ordering_mode();
+        for i in 0..npockets {
+            let pocket = &scratch.pockets[i];
+            if matches!(mode, EnumOrderingMode::Hi | EnumOrderingMode::Hilo) {
+                scratch.hival[i] = self
+                    .rules
+                    .eval_hi(pocket, &board)?
+                    .unwrap_or(HandVal { value: 0 });
+            }
+            if matches!(mode, EnumOrderingMode::Lo | EnumOrderingMode::Hilo) {
+                scratch.loval[i] = self.rules.eval_lo(pocket, &board)?.unwrap_or(LowHandVal {
+                    value: LOW_HAND_VAL_NOTHING,
+                });
+            }
+        }
+
+        match mode {
+            EnumOrderingMode::Hi => result.update_statistics_batched(&scratch.hival, npockets),
+            EnumOrderingMode::Lo => result.update_statistics_batched_lo(&scratch.loval, npockets),
+            EnumOrderingMode::Hilo => {
+                result.update_statistics_batched_hilo(&scratch.hival, &scratch.loval, npockets)
+            }
+            EnumOrderingMode::None => return Err(PokerError::UnsupportedGameType),
+        }
+        result.nsamples += 1;
+        Ok(())
+    }
+}
+
+/// Per-deal buffers reused across deals.
+struct Scratch {
+    pockets: Vec<StdDeckCardMask>,
+    hival: Vec<HandVal>,
+    loval: Vec<LowHandVal>,
+}
+
+impl Scratch {
+    fn new(npockets: usize) -> Self {
+        Self {
+            pockets: vec![StdDeckCardMask::new(); npockets],
+            hival: vec![HandVal { value: 0 }; npockets],
+            loval: vec![
+                LowHandVal {
+                    value: LOW_HAND_VAL_NOTHING
+                };
+                npockets
+            ],
+        }
+    }
+}
+
+/// Resets `result` for a run of `rules`, keeping its seed and, emptied, an ordering
+/// histogram allocated for these players.
+fn reset(rules: &dyn GameRules, npockets: usize, sample_type: SampleType, result: &mut EnumResult) {
+    let game = rules.game().unwrap_or(result.game);
+    let ordering = result
+        .ordering
+        .take()
+        .filter(|o| o.nplayers == npockets && o.mode == rules.ordering_mode());
+    result.clear();
+    result.ordering = ordering.map(|mut o| {
+        o.hist.fill(0);
+        o
+    });
+    result.game = game;
+    result.sample_type = sample_type;
+    result.nplayers = npockets as u32;
+}
+
+/// Enumerates every way to complete the board and the pockets under `rules`.
+///
+/// Each slot (the board, then each player) is dealt every combination of the cards
+/// left after the slots before it. `result.game` is set when the rules describe a
+/// built-in game and left alone otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::deck::{StdDeck, StdDeckCardMask};
+/// use poker_eval_rs::enumdefs::{EnumResult, Game};
+/// use poker_eval_rs::enumerate::enum_rules_exhaustive;
+///
+/// let pockets = [
+///     StdDeck::string_to_mask("As2sKdKc").unwrap().0,
+///     StdDeck::string_to_mask("QhQd3c4c").unwrap().0,
+/// ];
+/// let (board, _) = StdDeck::string_to_mask("5h7d8s9c").unwrap();
+/// let mut result = EnumResult::new(Game::Omaha8);
+/// enum_rules_exhaustive(&Game::Omaha8, &pockets, board, StdDeckCardMask::new(), &mut result)
+///     .unwrap();
+/// assert_eq!(result.nsamples, 40);
+/// ```
+pub fn enum_rules_exhaustive(
+    rules: &dyn GameRules,
+    pockets: &[StdDeckCardMask],
+    board: StdDeckCardMask,
+    dead: StdDeckCardMask,
+    result: &mut EnumResult,
+) -> Result<(), PokerError> {
+    let deal = Deal::new(rules, pockets, board, dead)?;
+    reset(rules, pockets.len(), SampleType::Exhaustive, result);
+    let mut scratch = Scratch::new(pockets.len());
+    let mut dealt = Vec::with_capacity(deal.missing.iter().sum());
+    deal_slots(&deal, 0, &deal.live, &mut dealt, &mut scratch, result)
+}
+
+/// Deals slot `slot` every combination of `live`, recursing into the later slots.
+fn deal_slots(
+    deal: &Deal,
+    slot: usize,
+    live: &[usize],
+    dealt: &mut Vec<usize>,
+    scratch: &mut Scratch,
+    result: &mut EnumResult,
+) -> Result<(), PokerError> {
+    let Some(&k) = deal.missing.get(slot) else {
+        return deal.score(dealt, scratch, result);
+    };
+    let mut error = None;
+    for_each_combination(live.len(), k, |idx| {
+        if error.is_some() {
+            return;
+        }
+        let start = dealt.len();
+        dealt.extend(idx.iter().map(|&i| live[i]));
+        let rest: Vec<usize> = if k == 0 {
+            live.to_vec()
+        } else {
+            live.iter()
+                .copied()
+                .filter(|c| !dealt[start..].contains(c))
+                .collect()
+        };
+        if let Err(e) = deal_slots(deal, slot + 1, &rest, dealt, scratch, result) {
+            error = Some(e);
+        }
+        dealt.truncate(start);
+    });
+    error.map_or(Ok(()), Err)
+}
+
+/// Samples `niter` random completions of the board and the pockets under `rules`.
+///
+/// Uses `result.seed` when set, so seeded runs are reproducible. `result.game` is
+/// set as in [`enum_rules_exhaustive`].
+pub fn enum_rules_sample(
+    rules: &dyn GameRules,
+    pockets: &[StdDeckCardMask],
+    board: StdDeckCardMask,
+    dead: StdDeckCardMask,
+    niter: usize,
+    result: &mut EnumResult,
+) -> Result<(), PokerError> {
+    let mut deal = Deal::new(rules, pockets, board, dead)?;
+    reset(rules, pockets.len(), SampleType::Sample, result);
+    let mut scratch = Scratch::new(pockets.len());
+    let mut rng = sample_rng(result.seed);
+    let ndeal: usize = deal.missing.iter().sum();
+    let mut live = std::mem::take(&mut deal.live);
+    for _ in 0..niter {
+        let (cards, _) = live.partial_shuffle(&mut rng, ndeal);
+        deal.score(cards, &mut scratch, result)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::StdDeck;
+    use crate::enumdefs::Game;
+    use crate::enumerate::enum_exhaustive;
+
+    fn masks(hands: &[&str]) -> Vec<StdDeckCardMask> {
+        hands
+            .iter()
+            .map(|h| StdDeck::string_to_mask(h).unwrap().0)
+            .collect()
+    }
+
+    #[test]
+    fn test_generic_matches_specialised_holdem() {
+        let pockets = masks(&["AhKh", "7c7d", "QsJs"]);
+        let (board, _) = StdDeck::string_to_mask("Th9h2c").unwrap();
+        let dead = StdDeckCardMask::new();
+
+        let mut fast = EnumResult::new(Game::Holdem);
+        enum_exhaustive(Game::Holdem, &pockets, board, dead, 3, 3, false, &mut fast).unwrap();
+        let mut generic = EnumResult::new(Game::Holdem);
+        enum_rules_exhaustive(&Game::Holdem, &pockets, board, dead, &mut generic).unwrap();
+
+        assert_eq!(generic.nsamples, fast.nsamples);
+        assert_eq!(generic.nwinhi, fast.nwinhi);
+        assert_eq!(generic.ntiehi, fast.ntiehi);
+        assert_eq!(generic.nhandtype, fast.nhandtype);
+    }
+
+    #[test]
+    fn test_generic_deals_pockets_for_stud() {
+        // One card short each: 40 * 39 deals.
+        let pockets = masks(&["AsAdKc2h3h4d", "QsQdJc9h8h7d"]);
+        let mut result = EnumResult::new(Game::Stud7);
+        enum_rules_exhaustive(
+            &Game::Stud7,
+            &pockets,
+            StdDeckCardMask::new(),
+            StdDeckCardMask::new(),
+            &mut result,
+        )
+        .unwrap();
+        assert_eq!(result.nsamples, 40 * 39);
+        assert!(result.nwinhi[0] > result.nwinhi[1]);
+
+        let mut seeded = EnumResult::new(Game::Razz);
+        seeded.seed = Some(3);
+        let razz = masks(&["As2s3s", "KsKdQh"]);
+        let run = |r: &mut EnumResult| {
+            enum_rules_sample(
+                &Game::Razz,
+                &razz,
+                StdDeckCardMask::new(),
+                StdDeckCardMask::new(),
+                2000,
+                r,
+            )
+            .unwrap();
+            (r.nwinlo, r.nloselo)
+        };
+        let first = run(&mut seeded);
+        assert_eq!(first, run(&mut seeded));
+        assert!(first.0[0] > first.0[1]);
+    }
+
+    #[test]
+    fn test_ordering_histogram() {
+        let pockets = masks(&["AsAd", "KhKd", "7c7d"]);
+        let (board, _) = StdDeck::string_to_mask("2c3c4c").unwrap();
+        let dead = StdDeckCardMask::new();
+        let mut result = EnumResult::new(Game::Holdem);
+        enum_exhaustive(Game::Holdem, &pockets, board, dead, 3, 3, true, &mut result).unwrap();
+
+        let ordering = result.ordering.as_ref().unwrap();
+        assert_eq!(ordering.hist.iter().sum::<u32>(), result.nsamples);
+        // Boards making a straight on the board tie everyone; aces usually hold up.
+        let all_tied = crate::enumord::enum_ordering_encode(3, &[0, 0, 0]) as usize;
+        let aces_first = crate::enumord::enum_ordering_encode(3, &[0, 1, 2]) as usize;
+        assert!(ordering.hist[all_tied] > 0);
+        assert!(ordering.hist[aces_first] > ordering.hist[all_tied]);
+
+        // Hi/lo histograms rank a low that does not qualify as NQ.
+        let omaha = masks(&["As2s3d4d", "KsKd5h6h"]);
+        let (board, _) = StdDeck::string_to_mask("7c8cQh").unwrap();
+        let mut result = EnumResult::new(Game::Omaha8);
+        enum_exhaustive(Game::Omaha8, &omaha, board, dead, 2, 3, true, &mut result).unwrap();
+        let ordering = result.ordering.as_ref().unwrap();
+        assert_eq!(ordering.hist.iter().sum::<u32>(), result.nsamples);
+        let no_low = crate::enumord::enum_ordering_encode_hilo(2, &[1, 0], &[0, 2]) as usize;
+        assert!(ordering.hist[no_low] > 0);
+    }
+
+    #[test]
+    fn test_generic_rejects_bad_deals() {
+        let dead = StdDeckCardMask::new();
+        let mut result = EnumResult::new(Game::ShortDeck);
+        let low = masks(&["AsKs", "5d5c"]);
+        assert!(enum_rules_exhaustive(
+            &Game::ShortDeck,
+            &low,
+            StdDeckCardMask::new(),
+            dead,
+            &mut result
+        )
+        .is_err());
+        let omaha = masks(&["AsKs", "QdQc"]);
+        assert!(enum_rules_exhaustive(
+            &Game::Omaha,
+            &omaha,
+            StdDeckCardMask::new(),
+            dead,
+            &mut result
+        )
+        .is_err());
+    }
+}
@@ -6,18 +6,22 @@ mod card_enum_dead;
 
 pub mod evaluation;
 pub mod game_params;
+pub mod generic;
 pub mod inner_loops;
 mod montecarlo;
 pub mod result;
 
 pub use crate::errors::PokerError;
 pub use evaluation::*;
+pub use generic::*;
 pub use inner_loops::*;
 
 use crate::enumdefs::{Game, GameParams};
 use crate::tables::t_cardmasks::StdDeckCardMask;
 use crate::tables::t_jokercardmasks::JokerDeckCardMask;
+use std::fmt;
 use std::ops::BitOr;
+use std::str::FromStr;
 
 /// Trait for abstracting over card mask types (standard deck and joker deck).
 ///
@@ -85,6 +89,52 @@ impl BitOr for JokerDeckCardMask {
 }
 
 impl Game {
+    /// Every playable game variant, in declaration order.
+    pub const ALL: [Game; 17] = [
+        Game::Holdem,
+        Game::Holdem8,
+        Game::Omaha,
+        Game::Omaha5,
+        Game::Omaha6,
+        Game::Omaha8,
+        Game::Omaha85,
+        Game::Stud7,
+        Game::Stud78,
+        Game::Stud7nsq,
+        Game::Razz,
+        Game::Draw5,
+        Game::Draw58,
+        Game::Draw5nsq,
+        Game::Lowball,
+        Game::Lowball27,
+        Game::ShortDeck,
+    ];
+
+    /// The short lowercase name used on the command line and in bindings
+    /// (e.g. `"holdem"`, `"omaha8"`). [`FromStr`] accepts these names.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Game::Holdem => "holdem",
+            Game::Holdem8 => "holdem8",
+            Game::Omaha => "omaha",
+            Game::Omaha5 => "omaha5",
+            Game::Omaha6 => "omaha6",
+            Game::Omaha8 => "omaha8",
+            Game::Omaha85 => "omaha85",
+            Game::Stud7 => "stud7",
+            Game::Stud78 => "stud78",
+            Game::Stud7nsq => "stud7nsq",
+            Game::Razz => "razz",
+            Game::Draw5 => "draw5",
+            Game::Draw58 => "draw58",
+            Game::Draw5nsq => "draw5nsq",
+            Game::Lowball => "lowball",
+            Game::Lowball27 => "lowball27",
+            Game::ShortDeck => "shortdeck",
+            Game::NumGames => "numgames",
+        }
+    }
+
     /// Returns the `GameParams` for this game variant, or `None` if unsupported.
     pub fn game_params(self) -> Option<GameParams> {
         match self {
@@ -232,11 +282,40 @@ impl Game {
                 hashipot: 0,
                 name: "5-card Draw 2-7 Lowball",
             }),
+            Game::ShortDeck => Some(GameParams {
+                game: Game::ShortDeck,
+                minpocket: 2,
+                maxpocket: 2,
+                maxboard: 5,
+                haslopot: 0,
+                hashipot: 1,
+                name: "Short Deck Holdem Hi",
+            }),
             _ => None,
         }
     }
 }
 
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Game {
+    type Err = PokerError;
+
+    /// Parses a game name such as `"holdem"` or `"Omaha8"`, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = s.trim().to_lowercase();
+        Game::ALL
+            .iter()
+            .copied()
+            .find(|g| g.as_str() == key)
+            .ok_or_else(|| PokerError::InvalidInput(format!("Unsupported game variant: {}", s)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::card_enum::*;
@@ -341,4 +420,17 @@ mod tests {
         assert_eq!(STD_DECK_N_CARDS, 52);
         assert_eq!(STD_DECK_CARD_MASKS_TABLE.len(), 52);
     }
+
+    #[test]
+    fn test_game_names_round_trip() {
+        for game in Game::ALL {
+            assert_eq!(game.to_string().parse::<Game>(), Ok(game));
+            assert!(game.game_params().is_some());
+        }
+        assert_eq!("  OMAHA8 ".parse::<Game>(), Ok(Game::Omaha8));
+        assert!(matches!(
+            "texas".parse::<Game>(),
+            Err(PokerError::InvalidInput(_))
+        ));
+    }
 }
@@ -5,25 +5,57 @@
 
 use super::CardMask;
 use crate::tables::t_cardmasks::StdDeckCardMask;
+use rand::rngs::SmallRng;
 use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// Number of seeded iterations sampled per parallel chunk.
+///
+/// Seeded runs split the work into chunks that do not depend on the thread count,
+/// so the same seed gives the same result on any machine.
+#[cfg(feature = "parallel")]
+const SEEDED_CHUNK_SIZE: usize = 4096;
+
+/// The random number generator for one sampling run: seeded when `seed` is set,
+/// from entropy otherwise.
+pub(crate) fn sample_rng(seed: Option<u64>) -> SmallRng {
+    match seed {
+        Some(seed) => SmallRng::seed_from_u64(seed),
+        None => SmallRng::from_entropy(),
+    }
+}
+
+/// Number of iterations each parallel chunk samples out of `niter`.
+#[cfg(feature = "parallel")]
+pub(crate) fn sample_chunk_size(niter: usize, seed: Option<u64>) -> usize {
+    match seed {
+        Some(_) => SEEDED_CHUNK_SIZE,
+        None => (niter / (rayon::current_num_threads() * 4)).max(128),
+    }
+}
+
+/// The seed for parallel chunk `chunk`, derived from the run's seed.
+#[cfg(feature = "parallel")]
+pub(crate) fn chunk_seed(seed: Option<u64>, chunk: usize) -> Option<u64> {
+    seed.map(|s| s.wrapping_add((chunk as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+}
 
 /// Draws `num_cards` random cards from a deck `num_iter` times, excluding dead cards.
 ///
 /// Uses Fisher-Yates partial shuffle: builds a live deck (excluding dead cards),
-/// then shuffles only the first `num_cards` positions per iteration.
+/// then picks `num_cards` of them with a partial shuffle per iteration.
 /// This is O(num_cards) per iteration instead of O(num_cards * deck_size) with rejection sampling.
 pub(crate) fn deck_montecarlo_n_cards_d<F>(
     deck: &[StdDeckCardMask],
     dead_cards: StdDeckCardMask,
     num_cards: usize,
     num_iter: usize,
+    seed: Option<u64>,
     mut action: F,
 ) where
     F: FnMut(&[StdDeckCardMask]),
 {
-    use rand::rngs::SmallRng;
-    use rand::SeedableRng;
-    let mut rng = SmallRng::from_entropy();
+    let mut rng = sample_rng(seed);
 
     // Build live deck excluding dead cards (one-time allocation)
     let mut live_deck: Vec<StdDeckCardMask> = deck
@@ -38,9 +70,9 @@ pub(crate) fn deck_montecarlo_n_cards_d<F>(
     }
 
     for _ in 0..num_iter {
-        // Fisher-Yates partial shuffle: only shuffle first num_cards positions
-        live_deck.partial_shuffle(&mut rng, num_cards);
-        action(&live_deck[..num_cards]);
+        // Fisher-Yates partial shuffle: only the num_cards picked cards are shuffled
+        let (picked, _) = live_deck.partial_shuffle(&mut rng, num_cards);
+        action(picked);
     }
 }
 
@@ -50,16 +82,14 @@ pub(crate) fn deck_montecarlo_n_cards_joker<F>(
     dead_cards: crate::tables::t_jokercardmasks::JokerDeckCardMask,
     num_cards: usize,
     num_iter: usize,
+    seed: Option<u64>,
     mut action: F,
 ) where
     F: FnMut(&[crate::tables::t_jokercardmasks::JokerDeckCardMask]),
 {
     use crate::tables::t_jokercardmasks::JokerDeckCardMask;
-    use rand::rngs::SmallRng;
-    use rand::seq::SliceRandom;
-    use rand::SeedableRng;
 
-    let mut rng = SmallRng::from_entropy();
+    let mut rng = sample_rng(seed);
 
     // Build live deck
     // We can't use .overlaps() if it's not defined on JokerDeckCardMask.
@@ -81,8 +111,8 @@ pub(crate) fn deck_montecarlo_n_cards_joker<F>(
     }
 
     for _ in 0..num_iter {
-        live_deck.partial_shuffle(&mut rng, num_cards);
-        action(&live_deck[..num_cards]);
+        let (picked, _) = live_deck.partial_shuffle(&mut rng, num_cards);
+        action(picked);
     }
 }
 
@@ -101,8 +131,6 @@ where
     T: CardMask,
     F: FnMut(Vec<Vec<&T>>),
 {
-    use rand::rngs::SmallRng;
-    use rand::SeedableRng;
     let mut rng = SmallRng::from_entropy();
     let max_cards: usize = set_sizes.iter().sum();
     let mut used_cards = Vec::with_capacity(dead_cards.len() + max_cards);
@@ -132,3 +160,40 @@ where
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::STD_DECK_N_CARDS;
+
+    #[test]
+    fn test_draws_are_fresh_and_uniform() {
+        let deck: Vec<StdDeckCardMask> = (0..STD_DECK_N_CARDS)
+            .map(StdDeckCardMask::from_card_index)
+            .collect();
+        let mut counts = [0u32; STD_DECK_N_CARDS];
+        let mut repeats = 0;
+        let mut last = StdDeckCardMask::new();
+        deck_montecarlo_n_cards_d(&deck, StdDeckCardMask::new(), 2, 26_000, Some(1), |cards| {
+            let drawn = cards[0] | cards[1];
+            assert_eq!(drawn.num_cards(), 2);
+            for (c, count) in counts.iter_mut().enumerate() {
+                if drawn.card_is_set(c) {
+                    *count += 1;
+                }
+            }
+            if drawn == last {
+                repeats += 1;
+            }
+            last = drawn;
+        });
+        // Each draw is a new pair: 1,000 of each card, and the pair just drawn again
+        // about once in 1,326 draws.
+        assert!(repeats < 60, "{} repeated draws", repeats);
+        assert!(
+            counts.iter().all(|&n| (850..1150).contains(&n)),
+            "{:?}",
+            counts
+        );
+    }
+}
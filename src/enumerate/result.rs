@@ -3,21 +3,25 @@
 use super::card_enum_dead::*;
 use super::inner_loops::inner_loop_holdem;
 use super::CardMask;
-use crate::deck::StdDeck;
 use crate::deck::STD_DECK_N_CARDS;
-use crate::enumdefs::{EnumResult, Game, SampleType, ENUM_MAXPLAYERS};
+use crate::deck::{Rank, StdDeck};
+use crate::enumdefs::{EnumResult, Game, SampleType, ENUM_MAXPLAYERS, ENUM_NHANDTYPES};
+#[cfg(feature = "parallel")]
+use crate::enumerate::montecarlo::{chunk_seed, sample_chunk_size};
 use crate::enumerate::montecarlo::{deck_montecarlo_n_cards_d, deck_montecarlo_n_cards_joker};
 use crate::enumord::EnumOrdering;
 use crate::enumord::EnumOrderingMode;
 use crate::enumord::{
     enum_ordering_decode_hilo_k_hi, enum_ordering_decode_hilo_k_lo, enum_ordering_decode_k,
-    enum_ordering_nentries, enum_ordering_nentries_hilo,
+    enum_ordering_increment, enum_ordering_increment_hilo, enum_ordering_nentries,
+    enum_ordering_nentries_hilo, enum_ordering_rank,
 };
 use crate::enumord::{ENUM_ORDERING_MAXPLAYERS, ENUM_ORDERING_MAXPLAYERS_HILO};
 use crate::errors::PokerError;
 use crate::evaluators::{std_deck_lowball8_eval, std_deck_omaha_hi_eval, Eval, HandEvaluator};
 use crate::handval::HandVal;
 use crate::handval_low::{LowHandVal, LOW_HAND_VAL_NOTHING};
+use crate::rules::HandType;
 use crate::tables::t_cardmasks::StdDeckCardMask;
 use crate::tables::t_cardmasks::STD_DECK_CARD_MASKS_TABLE;
 #[cfg(feature = "parallel")]
@@ -42,7 +46,12 @@ impl EnumResult {
             nsharelo: [[0; ENUM_MAXPLAYERS + 1]; ENUM_MAXPLAYERS],
             nshare: Box::new([[[0; ENUM_MAXPLAYERS + 1]; ENUM_MAXPLAYERS + 1]; ENUM_MAXPLAYERS]),
             ev: [0.0; ENUM_MAXPLAYERS],
+            nhandtype: [[0; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS],
+            nwinhandtype: [[0; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS],
+            nlosehandtype: [[[0; ENUM_NHANDTYPES]; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS],
             ordering: None,
+            seed: None,
+            outright: false,
         };
         res.clear();
         res.game = game;
@@ -70,9 +79,24 @@ impl EnumResult {
         *self.nshare = [[[0; ENUM_MAXPLAYERS + 1]; ENUM_MAXPLAYERS + 1]; ENUM_MAXPLAYERS];
 
         self.ev = [0.0; ENUM_MAXPLAYERS];
+        self.nhandtype = [[0; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS];
+        self.nwinhandtype = [[0; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS];
+        self.nlosehandtype = [[[0; ENUM_NHANDTYPES]; ENUM_NHANDTYPES]; ENUM_MAXPLAYERS];
         self.ordering = None;
     }
 
+    /// An empty result for one worker of this run, with an ordering histogram of the
+    /// same shape when this run records one, ready to be [`merge`](Self::merge)d back.
+    fn worker_result(&self) -> EnumResult {
+        let mut res = EnumResult::new(self.game);
+        res.outright = self.outright;
+        res.ordering = self
+            .ordering
+            .as_ref()
+            .map(|o| Box::new(EnumOrdering::new(o.mode, o.nplayers)));
+        res
+    }
+
     /// Merges another `EnumResult` into this one.
     pub fn merge(&mut self, other: &Self) {
         self.nsamples += other.nsamples;
@@ -86,6 +110,14 @@ impl EnumResult {
             self.nscoop[i] += other.nscoop[i];
             self.ev[i] += other.ev[i];
 
+            for t in 0..ENUM_NHANDTYPES {
+                self.nhandtype[i][t] += other.nhandtype[i][t];
+                self.nwinhandtype[i][t] += other.nwinhandtype[i][t];
+                for u in 0..ENUM_NHANDTYPES {
+                    self.nlosehandtype[i][t][u] += other.nlosehandtype[i][t][u];
+                }
+            }
+
             for j in 0..ENUM_MAXPLAYERS + 1 {
                 self.nsharehi[i][j] += other.nsharehi[i][j];
                 self.nsharelo[i][j] += other.nsharelo[i][j];
@@ -101,6 +133,29 @@ impl EnumResult {
         }
     }
 
+    /// Fraction of samples in which `player` ended with `hand_type` as their high hand.
+    pub fn hand_type_probability(&self, player: usize, hand_type: HandType) -> f64 {
+        self.sample_fraction(self.nhandtype[player][hand_type.as_usize()])
+    }
+
+    /// Fraction of samples `player` won outright with `hand_type`.
+    pub fn win_with_probability(&self, player: usize, hand_type: HandType) -> f64 {
+        self.sample_fraction(self.nwinhandtype[player][hand_type.as_usize()])
+    }
+
+    /// Fraction of samples `player` lost holding `with` when the best hand was `to`.
+    pub fn lose_with_probability(&self, player: usize, with: HandType, to: HandType) -> f64 {
+        self.sample_fraction(self.nlosehandtype[player][with.as_usize()][to.as_usize()])
+    }
+
+    fn sample_fraction(&self, count: u32) -> f64 {
+        if self.nsamples == 0 {
+            0.0
+        } else {
+            count as f64 / self.nsamples as f64
+        }
+    }
+
     /// Allocates ordering resources for hand ranking histograms.
     pub fn allocate_resources(
         &mut self,
@@ -158,14 +213,10 @@ impl EnumResult {
             exclusion_mask = exclusion_mask | *pocket;
         }
 
-        // Short Deck: Ranks 6..A (indices 4..12).
-        // Standard deck cards: 0..51.
-        // Rank = card_index / 4.
-        // We want Rank >= 4.
+        // Short Deck: Ranks 6..A.
         let deck = (0..STD_DECK_N_CARDS)
             .filter_map(|i| {
-                let rank = i / 4;
-                if rank < 4 {
+                if StdDeck::rank(i) < Rank::SIX {
                     return None; // Skip 2, 3, 4, 5
                 }
                 let card_mask = StdDeckCardMask::get_mask(i);
@@ -182,8 +233,7 @@ impl EnumResult {
 
         #[cfg(feature = "parallel")]
         {
-            let n_threads = rayon::current_num_threads();
-            let chunk_size = (niter / (n_threads * 4)).max(128);
+            let chunk_size = sample_chunk_size(niter, self.seed);
             let num_chunks = (niter + chunk_size - 1) / chunk_size;
 
             let total_res = (0..num_chunks)
@@ -195,7 +245,7 @@ impl EnumResult {
                         chunk_size
                     };
 
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
 
                     let mut hival = vec![HandVal::new(0, 0, 0, 0, 0, 0); npockets];
@@ -207,6 +257,7 @@ impl EnumResult {
                         no_dead_cards,
                         num_cards_to_draw,
                         iter_in_this_chunk,
+                        chunk_seed(self.seed, chunk_idx),
                         |combo| {
                             let mut complete_board = board;
                             for &card in combo {
@@ -228,7 +279,7 @@ impl EnumResult {
                     local_res
                 })
                 .reduce(
-                    || EnumResult::new(self.game),
+                    || self.worker_result(),
                     |mut a, b| {
                         a.merge(&b);
                         a
@@ -239,29 +290,36 @@ impl EnumResult {
 
         #[cfg(not(feature = "parallel"))]
         {
-            let mut local_res = EnumResult::new(self.game);
+            let mut local_res = self.worker_result();
             local_res.nplayers = npockets as u32;
             let mut hival = vec![HandVal::new(0, 0, 0, 0, 0, 0); npockets];
             let mut loval = vec![LowHandVal::new(0, 0, 0, 0, 0, 0); npockets];
 
             let empty_mask = StdDeckCardMask::new();
-            deck_montecarlo_n_cards_d(&deck, no_dead_cards, num_cards_to_draw, niter, |combo| {
-                let mut complete_board = board;
-                for &card in combo {
-                    complete_board = complete_board | card;
-                }
+            deck_montecarlo_n_cards_d(
+                &deck,
+                no_dead_cards,
+                num_cards_to_draw,
+                niter,
+                self.seed,
+                |combo| {
+                    let mut complete_board = board;
+                    for &card in combo {
+                        complete_board = complete_board | card;
+                    }
 
-                if let Ok(()) = inner_loop_short_deck(
-                    pockets,
-                    &complete_board,
-                    &empty_mask,
-                    &mut hival,
-                    &mut loval,
-                ) {
-                    local_res.update_statistics_batched(&hival, npockets);
-                    local_res.nsamples += 1;
-                }
-            });
+                    if let Ok(()) = inner_loop_short_deck(
+                        pockets,
+                        &complete_board,
+                        &empty_mask,
+                        &mut hival,
+                        &mut loval,
+                    ) {
+                        local_res.update_statistics_batched(&hival, npockets);
+                        local_res.nsamples += 1;
+                    }
+                },
+            );
             self.merge(&local_res);
         }
 
@@ -302,8 +360,7 @@ impl EnumResult {
 
         #[cfg(feature = "parallel")]
         {
-            let n_threads = rayon::current_num_threads();
-            let chunk_size = (niter / (n_threads * 4)).max(128);
+            let chunk_size = sample_chunk_size(niter, self.seed);
             let num_chunks = (niter + chunk_size - 1) / chunk_size;
 
             let total_res = (0..num_chunks)
@@ -315,7 +372,7 @@ impl EnumResult {
                         chunk_size
                     };
 
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
 
                     let mut hival = [HandVal { value: 0 }; ENUM_MAXPLAYERS];
@@ -328,6 +385,7 @@ impl EnumResult {
                         no_dead_cards,
                         num_cards_to_draw,
                         iter_in_this_chunk,
+                        chunk_seed(self.seed, chunk_idx),
                         |combo| {
                             // Pre-compute board mask using raw u64 OR (faster than per-card OR)
                             let mut raw = board_raw;
@@ -351,7 +409,7 @@ impl EnumResult {
                     local_res
                 })
                 .reduce(
-                    || EnumResult::new(self.game),
+                    || self.worker_result(),
                     |mut a, b| {
                         a.merge(&b);
                         a
@@ -362,29 +420,36 @@ impl EnumResult {
 
         #[cfg(not(feature = "parallel"))]
         {
-            let mut local_res = EnumResult::new(self.game);
+            let mut local_res = self.worker_result();
             local_res.nplayers = npockets as u32;
             let mut hival = vec![HandVal { value: 0 }; npockets];
             let mut loval = vec![LowHandVal { value: 0 }; npockets];
 
             let empty_mask = StdDeckCardMask::new();
-            deck_montecarlo_n_cards_d(&deck, no_dead_cards, num_cards_to_draw, niter, |combo| {
-                let mut complete_board = board;
-                for &card in combo {
-                    complete_board = complete_board | card;
-                }
+            deck_montecarlo_n_cards_d(
+                &deck,
+                no_dead_cards,
+                num_cards_to_draw,
+                niter,
+                self.seed,
+                |combo| {
+                    let mut complete_board = board;
+                    for &card in combo {
+                        complete_board = complete_board | card;
+                    }
 
-                if let Ok(()) = inner_loop_holdem(
-                    pockets,
-                    &complete_board,
-                    &empty_mask,
-                    &mut hival,
-                    &mut loval,
-                ) {
-                    local_res.update_statistics_batched(&hival, npockets);
-                    local_res.nsamples += 1;
-                }
-            });
+                    if let Ok(()) = inner_loop_holdem(
+                        pockets,
+                        &complete_board,
+                        &empty_mask,
+                        &mut hival,
+                        &mut loval,
+                    ) {
+                        local_res.update_statistics_batched(&hival, npockets);
+                        local_res.nsamples += 1;
+                    }
+                },
+            );
             self.merge(&local_res);
         }
 
@@ -425,8 +490,7 @@ impl EnumResult {
 
         #[cfg(feature = "parallel")]
         {
-            let n_threads = rayon::current_num_threads();
-            let chunk_size = (niter / (n_threads * 4)).max(128);
+            let chunk_size = sample_chunk_size(niter, self.seed);
             let num_chunks = (niter + chunk_size - 1) / chunk_size;
 
             let total_res = (0..num_chunks)
@@ -438,7 +502,7 @@ impl EnumResult {
                         chunk_size
                     };
 
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
 
                     let mut hival = [HandVal { value: 0 }; ENUM_MAXPLAYERS];
@@ -449,6 +513,7 @@ impl EnumResult {
                         no_dead_cards,
                         num_cards_to_draw,
                         iter_in_this_chunk,
+                        chunk_seed(self.seed, chunk_idx),
                         |combo| {
                             let mut raw = board_raw;
                             for &card in combo {
@@ -473,7 +538,7 @@ impl EnumResult {
                     local_res
                 })
                 .reduce(
-                    || EnumResult::new(self.game),
+                    || self.worker_result(),
                     |mut a, b| {
                         a.merge(&b);
                         a
@@ -484,31 +549,38 @@ impl EnumResult {
 
         #[cfg(not(feature = "parallel"))]
         {
-            let mut local_res = EnumResult::new(self.game);
+            let mut local_res = self.worker_result();
             local_res.nplayers = npockets as u32;
             let mut hival = [HandVal { value: 0 }; ENUM_MAXPLAYERS];
 
             let board_raw = board.as_raw();
-            deck_montecarlo_n_cards_d(&deck, no_dead_cards, num_cards_to_draw, niter, |combo| {
-                let mut raw = board_raw;
-                for &card in combo {
-                    raw |= card.as_raw();
-                }
-                let complete_board = StdDeckCardMask::from_raw(raw);
-
-                // Evaluate manually to avoid allocation
-                for i in 0..npockets {
-                    let mut val: Option<HandVal> = None;
-                    let _ = crate::evaluators::omaha::std_deck_omaha_hi_eval(
-                        pockets[i],
-                        complete_board,
-                        &mut val,
-                    );
-                    hival[i] = val.unwrap_or(HandVal { value: 0 });
-                }
-                local_res.update_statistics_batched(&hival[..npockets], npockets);
-                local_res.nsamples += 1;
-            });
+            deck_montecarlo_n_cards_d(
+                &deck,
+                no_dead_cards,
+                num_cards_to_draw,
+                niter,
+                self.seed,
+                |combo| {
+                    let mut raw = board_raw;
+                    for &card in combo {
+                        raw |= card.as_raw();
+                    }
+                    let complete_board = StdDeckCardMask::from_raw(raw);
+
+                    // Evaluate manually to avoid allocation
+                    for i in 0..npockets {
+                        let mut val: Option<HandVal> = None;
+                        let _ = crate::evaluators::omaha::std_deck_omaha_hi_eval(
+                            pockets[i],
+                            complete_board,
+                            &mut val,
+                        );
+                        hival[i] = val.unwrap_or(HandVal { value: 0 });
+                    }
+                    local_res.update_statistics_batched(&hival[..npockets], npockets);
+                    local_res.nsamples += 1;
+                },
+            );
             self.merge(&local_res);
         }
 
@@ -549,8 +621,7 @@ impl EnumResult {
 
         #[cfg(feature = "parallel")]
         {
-            let n_threads = rayon::current_num_threads();
-            let chunk_size = (niter / (n_threads * 4)).max(128);
+            let chunk_size = sample_chunk_size(niter, self.seed);
             let num_chunks = (niter + chunk_size - 1) / chunk_size;
 
             let total_res = (0..num_chunks)
@@ -562,7 +633,7 @@ impl EnumResult {
                         chunk_size
                     };
 
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
 
                     let mut hival = vec![HandVal { value: 0 }; npockets];
@@ -573,6 +644,7 @@ impl EnumResult {
                         no_dead_cards,
                         num_cards_to_draw,
                         iter_in_this_chunk,
+                        chunk_seed(self.seed, chunk_idx),
                         |combo| {
                             let mut complete_board = board;
                             for &card in combo {
@@ -582,8 +654,9 @@ impl EnumResult {
                             for i in 0..npockets {
                                 let hand = pockets[i] | complete_board;
                                 hival[i] = Eval::eval_n(&hand, 7);
-                                loval[i] = std_deck_lowball8_eval(&hand, 7)
-                                    .unwrap_or(LowHandVal { value: 0 });
+                                loval[i] = std_deck_lowball8_eval(&hand, 7).unwrap_or(LowHandVal {
+                                    value: LOW_HAND_VAL_NOTHING,
+                                });
                             }
                             local_res.update_statistics_batched_hilo(&hival, &loval, npockets);
                             local_res.nsamples += 1;
@@ -592,7 +665,7 @@ impl EnumResult {
                     local_res
                 })
                 .reduce(
-                    || EnumResult::new(self.game),
+                    || self.worker_result(),
                     |mut a, b| {
                         a.merge(&b);
                         a
@@ -603,26 +676,35 @@ impl EnumResult {
 
         #[cfg(not(feature = "parallel"))]
         {
-            let mut local_res = EnumResult::new(self.game);
+            let mut local_res = self.worker_result();
             local_res.nplayers = npockets as u32;
 
             let mut hival = vec![HandVal { value: 0 }; npockets];
             let mut loval = vec![LowHandVal { value: 0 }; npockets];
 
-            deck_montecarlo_n_cards_d(&deck, no_dead_cards, num_cards_to_draw, niter, |combo| {
-                let mut complete_board = board;
-                for &card in combo {
-                    complete_board = complete_board | card;
-                }
+            deck_montecarlo_n_cards_d(
+                &deck,
+                no_dead_cards,
+                num_cards_to_draw,
+                niter,
+                self.seed,
+                |combo| {
+                    let mut complete_board = board;
+                    for &card in combo {
+                        complete_board = complete_board | card;
+                    }
 
-                for i in 0..npockets {
-                    let hand = pockets[i] | complete_board;
-                    hival[i] = Eval::eval_n(&hand, 7);
-                    loval[i] = std_deck_lowball8_eval(&hand, 7).unwrap_or(LowHandVal { value: 0 });
-                }
-                local_res.update_statistics_batched_hilo(&hival, &loval, npockets);
-                local_res.nsamples += 1;
-            });
+                    for i in 0..npockets {
+                        let hand = pockets[i] | complete_board;
+                        hival[i] = Eval::eval_n(&hand, 7);
+                        loval[i] = std_deck_lowball8_eval(&hand, 7).unwrap_or(LowHandVal {
+                            value: LOW_HAND_VAL_NOTHING,
+                        });
+                    }
+                    local_res.update_statistics_batched_hilo(&hival, &loval, npockets);
+                    local_res.nsamples += 1;
+                },
+            );
             self.merge(&local_res);
         }
 
@@ -696,8 +778,7 @@ impl EnumResult {
 
         #[cfg(feature = "parallel")]
         {
-            let n_threads = rayon::current_num_threads();
-            let chunk_size = (niter / (n_threads * 4)).max(128);
+            let chunk_size = sample_chunk_size(niter, self.seed);
             let num_chunks = (niter + chunk_size - 1) / chunk_size;
 
             let total_res = (0..num_chunks)
@@ -709,7 +790,7 @@ impl EnumResult {
                         chunk_size
                     };
 
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
 
                     let mut hival = vec![HandVal { value: 0 }; npockets];
@@ -720,6 +801,7 @@ impl EnumResult {
                         no_dead_cards,
                         num_cards_to_draw,
                         iter_in_this_chunk,
+                        chunk_seed(self.seed, chunk_idx),
                         |combo| {
                             let mut complete_board = board;
                             for &card in combo {
@@ -736,7 +818,9 @@ impl EnumResult {
                                     &mut lo,
                                 );
                                 hival[i] = hi.unwrap_or(HandVal { value: 0 });
-                                loval[i] = lo.unwrap_or(LowHandVal { value: 0 });
+                                loval[i] = lo.unwrap_or(LowHandVal {
+                                    value: LOW_HAND_VAL_NOTHING,
+                                });
                             }
                             local_res.update_statistics_batched_hilo(&hival, &loval, npockets);
                             local_res.nsamples += 1;
@@ -745,7 +829,7 @@ impl EnumResult {
                     local_res
                 })
                 .reduce(
-                    || EnumResult::new(self.game),
+                    || self.worker_result(),
                     |mut a, b| {
                         a.merge(&b);
                         a
@@ -756,29 +840,42 @@ impl EnumResult {
 
         #[cfg(not(feature = "parallel"))]
         {
-            let mut local_res = EnumResult::new(self.game);
+            let mut local_res = self.worker_result();
             local_res.nplayers = npockets as u32;
 
             let mut hival = vec![HandVal { value: 0 }; npockets];
             let mut loval = vec![LowHandVal { value: 0 }; npockets];
 
-            deck_montecarlo_n_cards_d(&deck, no_dead_cards, num_cards_to_draw, niter, |combo| {
-                let mut complete_board = board;
-                for &card in combo {
-                    complete_board = complete_board | card;
-                }
+            deck_montecarlo_n_cards_d(
+                &deck,
+                no_dead_cards,
+                num_cards_to_draw,
+                niter,
+                self.seed,
+                |combo| {
+                    let mut complete_board = board;
+                    for &card in combo {
+                        complete_board = complete_board | card;
+                    }
 
-                for i in 0..npockets {
-                    let mut hi: Option<HandVal> = None;
-                    let mut lo: Option<LowHandVal> = None;
-                    let _ =
-                        std_deck_omaha_hi_low8_eval(pockets[i], complete_board, &mut hi, &mut lo);
-                    hival[i] = hi.unwrap_or(HandVal { value: 0 });
-                    loval[i] = lo.unwrap_or(LowHandVal { value: 0 });
-                }
-                local_res.update_statistics_batched_hilo(&hival, &loval, npockets);
-                local_res.nsamples += 1;
-            });
+                    for i in 0..npockets {
+                        let mut hi: Option<HandVal> = None;
+                        let mut lo: Option<LowHandVal> = None;
+                        let _ = std_deck_omaha_hi_low8_eval(
+                            pockets[i],
+                            complete_board,
+                            &mut hi,
+                            &mut lo,
+                        );
+                        hival[i] = hi.unwrap_or(HandVal { value: 0 });
+                        loval[i] = lo.unwrap_or(LowHandVal {
+                            value: LOW_HAND_VAL_NOTHING,
+                        });
+                    }
+                    local_res.update_statistics_batched_hilo(&hival, &loval, npockets);
+                    local_res.nsamples += 1;
+                },
+            );
             self.merge(&local_res);
         }
 
@@ -847,7 +944,7 @@ impl EnumResult {
 
         let no_dead_cards = StdDeckCardMask::new();
 
-        let run_batch = |chunk_size: usize, local_res: &mut EnumResult| {
+        let run_batch = |chunk_size: usize, seed: Option<u64>, local_res: &mut EnumResult| {
             let mut hival = vec![HandVal::new(0, 0, 0, 0, 0, 0); npockets];
             let mut loval = vec![LowHandVal::new(0, 0, 0, 0, 0, 0); npockets];
             let mut player_hands = vec![StdDeckCardMask::new(); npockets];
@@ -857,6 +954,7 @@ impl EnumResult {
                 no_dead_cards,
                 num_cards_to_draw,
                 chunk_size,
+                seed,
                 |combo| {
                     let mut card_idx = 0;
                     for i in 0..npockets {
@@ -894,8 +992,7 @@ impl EnumResult {
 
         #[cfg(feature = "parallel")]
         {
-            let n_threads = rayon::current_num_threads();
-            let chunk_size = (niter / (n_threads * 4)).max(128);
+            let chunk_size = sample_chunk_size(niter, self.seed);
             let num_chunks = (niter + chunk_size - 1) / chunk_size;
 
             let total_res = (0..num_chunks)
@@ -907,13 +1004,17 @@ impl EnumResult {
                         chunk_size
                     };
 
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
-                    run_batch(iter_in_this_chunk, &mut local_res);
+                    run_batch(
+                        iter_in_this_chunk,
+                        chunk_seed(self.seed, chunk_idx),
+                        &mut local_res,
+                    );
                     local_res
                 })
                 .reduce(
-                    || EnumResult::new(self.game),
+                    || self.worker_result(),
                     |mut a, b| {
                         a.merge(&b);
                         a
@@ -924,9 +1025,9 @@ impl EnumResult {
 
         #[cfg(not(feature = "parallel"))]
         {
-            let mut local_res = EnumResult::new(self.game);
+            let mut local_res = self.worker_result();
             local_res.nplayers = npockets as u32;
-            run_batch(niter, &mut local_res);
+            run_batch(niter, self.seed, &mut local_res);
             self.merge(&local_res);
         }
 
@@ -960,15 +1061,17 @@ impl EnumResult {
         // Implementing inline if missing or use fallback
         // inner_loop_7stud8 is standard stud hi/lo 8-or-better.
         // If not exported, we can use a closure here similar to inner loop logic.
-        let eval_wrapper =
-            |hands: &[StdDeckCardMask], hival: &mut [HandVal], loval: &mut [LowHandVal]| {
-                for (i, hand) in hands.iter().enumerate() {
-                    hival[i] = Eval::eval_n(hand, hand.num_cards());
-                    loval[i] = std_deck_lowball8_eval(hand, hand.num_cards())
-                        .unwrap_or(LowHandVal { value: 0 });
-                }
-                Ok(())
-            };
+        let eval_wrapper = |hands: &[StdDeckCardMask],
+                            hival: &mut [HandVal],
+                            loval: &mut [LowHandVal]| {
+            for (i, hand) in hands.iter().enumerate() {
+                hival[i] = Eval::eval_n(hand, hand.num_cards());
+                loval[i] = std_deck_lowball8_eval(hand, hand.num_cards()).unwrap_or(LowHandVal {
+                    value: LOW_HAND_VAL_NOTHING,
+                });
+            }
+            Ok(())
+        };
         self.simulate_independent_game(pockets, dead, npockets, 7, niter, eval_wrapper)
     }
 
@@ -1099,7 +1202,7 @@ impl EnumResult {
 
         let no_dead_cards = JokerDeckCardMask { cards_n: 0 };
 
-        let run_batch = |chunk_size: usize, local_res: &mut EnumResult| {
+        let run_batch = |chunk_size: usize, seed: Option<u64>, local_res: &mut EnumResult| {
             let mut hival = vec![HandVal::new(0, 0, 0, 0, 0, 0); npockets];
             let mut loval = vec![LowHandVal::new(0, 0, 0, 0, 0, 0); npockets];
             let mut player_hands = vec![JokerDeckCardMask { cards_n: 0 }; npockets];
@@ -1109,6 +1212,7 @@ impl EnumResult {
                 no_dead_cards,
                 total_draw_needed,
                 chunk_size,
+                seed,
                 |combo| {
                     let mut card_idx = 0;
                     for i in 0..npockets {
@@ -1149,8 +1253,7 @@ impl EnumResult {
 
         #[cfg(feature = "parallel")]
         {
-            let n_threads = rayon::current_num_threads();
-            let chunk_size = (niter / (n_threads * 4)).max(128);
+            let chunk_size = sample_chunk_size(niter, self.seed);
             let num_chunks = (niter + chunk_size - 1) / chunk_size;
 
             let total_res = (0..num_chunks)
@@ -1162,13 +1265,17 @@ impl EnumResult {
                         chunk_size
                     };
 
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
-                    run_batch(iter_in_this_chunk, &mut local_res);
+                    run_batch(
+                        iter_in_this_chunk,
+                        chunk_seed(self.seed, chunk_idx),
+                        &mut local_res,
+                    );
                     local_res
                 })
                 .reduce(
-                    || EnumResult::new(self.game),
+                    || self.worker_result(),
                     |mut a, b| {
                         a.merge(&b);
                         a
@@ -1179,9 +1286,9 @@ impl EnumResult {
 
         #[cfg(not(feature = "parallel"))]
         {
-            let mut local_res = EnumResult::new(self.game);
+            let mut local_res = self.worker_result();
             local_res.nplayers = npockets as u32;
-            run_batch(niter, &mut local_res);
+            run_batch(niter, self.seed, &mut local_res);
             self.merge(&local_res);
         }
 
@@ -1296,7 +1403,7 @@ impl EnumResult {
                         .par_iter()
                         .enumerate()
                         .map(|(i1, c1)| {
-                            let mut local_res = EnumResult::new(self.game);
+                            let mut local_res = self.worker_result();
                             local_res.nplayers = npockets as u32;
                             enumerate_4_cards_d(&deck[..i1], no_dead, |c2, c3, c4, c5| {
                                 let new_board = board | *c1 | *c2 | *c3 | *c4 | *c5;
@@ -1309,7 +1416,7 @@ impl EnumResult {
                             local_res
                         })
                         .reduce(
-                            || EnumResult::new(self.game),
+                            || self.worker_result(),
                             |mut a, b| {
                                 a.merge(&b);
                                 a
@@ -1319,7 +1426,7 @@ impl EnumResult {
                 }
                 #[cfg(not(feature = "parallel"))]
                 {
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
                     // For sequential, we can just iterate. We might skip reducing per iteration to avoid overhead,
                     // or just accumulate in one result.
@@ -1345,7 +1452,7 @@ impl EnumResult {
                         .par_iter()
                         .enumerate()
                         .map(|(i1, c1)| {
-                            let mut local_res = EnumResult::new(self.game);
+                            let mut local_res = self.worker_result();
                             local_res.nplayers = npockets as u32;
                             enumerate_1_cards_d(&deck[..i1], no_dead, |c2| {
                                 let new_board = board | *c1 | *c2;
@@ -1358,7 +1465,7 @@ impl EnumResult {
                             local_res
                         })
                         .reduce(
-                            || EnumResult::new(self.game),
+                            || self.worker_result(),
                             |mut a, b| {
                                 a.merge(&b);
                                 a
@@ -1368,7 +1475,7 @@ impl EnumResult {
                 }
                 #[cfg(not(feature = "parallel"))]
                 {
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
                     for (i1, c1) in deck.iter().enumerate() {
                         enumerate_1_cards_d(&deck[..i1], no_dead, |c2| {
@@ -1389,7 +1496,7 @@ impl EnumResult {
                     let total_res = deck
                         .par_iter()
                         .map(|c| {
-                            let mut local_res = EnumResult::new(self.game);
+                            let mut local_res = self.worker_result();
                             local_res.nplayers = npockets as u32;
                             let new_board = board | *c;
                             if let Ok(()) =
@@ -1400,7 +1507,7 @@ impl EnumResult {
                             local_res
                         })
                         .reduce(
-                            || EnumResult::new(self.game),
+                            || self.worker_result(),
                             |mut a, b| {
                                 a.merge(&b);
                                 a
@@ -1410,7 +1517,7 @@ impl EnumResult {
                 }
                 #[cfg(not(feature = "parallel"))]
                 {
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
                     for c in deck.iter() {
                         let new_board = board | *c;
@@ -1470,7 +1577,7 @@ impl EnumResult {
                         .par_iter()
                         .enumerate()
                         .map(|(i1, c1)| {
-                            let mut local_res = EnumResult::new(self.game);
+                            let mut local_res = self.worker_result();
                             local_res.nplayers = npockets as u32;
                             enumerate_4_cards_d(&deck[..i1], no_dead, |c2, c3, c4, c5| {
                                 let new_board = board | *c1 | *c2 | *c3 | *c4 | *c5;
@@ -1483,7 +1590,7 @@ impl EnumResult {
                             local_res
                         })
                         .reduce(
-                            || EnumResult::new(self.game),
+                            || self.worker_result(),
                             |mut a, b| {
                                 a.merge(&b);
                                 a
@@ -1493,7 +1600,7 @@ impl EnumResult {
                 }
                 #[cfg(not(feature = "parallel"))]
                 {
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
                     for (i1, c1) in deck.iter().enumerate() {
                         enumerate_4_cards_d(&deck[..i1], no_dead, |c2, c3, c4, c5| {
@@ -1515,7 +1622,7 @@ impl EnumResult {
                         .par_iter()
                         .enumerate()
                         .map(|(i1, c1)| {
-                            let mut local_res = EnumResult::new(self.game);
+                            let mut local_res = self.worker_result();
                             local_res.nplayers = npockets as u32;
                             enumerate_1_cards_d(&deck[..i1], no_dead, |c2| {
                                 let new_board = board | *c1 | *c2;
@@ -1528,7 +1635,7 @@ impl EnumResult {
                             local_res
                         })
                         .reduce(
-                            || EnumResult::new(self.game),
+                            || self.worker_result(),
                             |mut a, b| {
                                 a.merge(&b);
                                 a
@@ -1538,7 +1645,7 @@ impl EnumResult {
                 }
                 #[cfg(not(feature = "parallel"))]
                 {
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
                     for (i1, c1) in deck.iter().enumerate() {
                         enumerate_1_cards_d(&deck[..i1], no_dead, |c2| {
@@ -1559,7 +1666,7 @@ impl EnumResult {
                     let total_res = deck
                         .par_iter()
                         .map(|c| {
-                            let mut local_res = EnumResult::new(self.game);
+                            let mut local_res = self.worker_result();
                             local_res.nplayers = npockets as u32;
                             let new_board = board | *c;
                             if let Ok(()) =
@@ -1570,7 +1677,7 @@ impl EnumResult {
                             local_res
                         })
                         .reduce(
-                            || EnumResult::new(self.game),
+                            || self.worker_result(),
                             |mut a, b| {
                                 a.merge(&b);
                                 a
@@ -1580,7 +1687,7 @@ impl EnumResult {
                 }
                 #[cfg(not(feature = "parallel"))]
                 {
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
                     for c in deck.iter() {
                         let new_board = board | *c;
@@ -1629,9 +1736,15 @@ impl EnumResult {
             .collect();
 
         // Compare cached results
-        for i in 0..npockets {
-            self.update_statistics_cached(i, &hand_values, npockets);
+        if self.outright {
+            self.record_outright(&hand_values, &[]);
+        } else {
+            for i in 0..npockets {
+                self.update_statistics_cached(i, &hand_values, npockets);
+            }
         }
+        self.record_hand_types(&hand_values);
+        self.record_ordering(&hand_values, &[]);
 
         Ok(())
     }
@@ -1659,10 +1772,26 @@ impl EnumResult {
             })
             .collect();
 
+        let hand_values: Vec<HandVal> = evals.iter().map(|(hi, _)| *hi).collect();
+        let low_values: Vec<LowHandVal> = evals
+            .iter()
+            .map(|(_, lo)| {
+                lo.unwrap_or(LowHandVal {
+                    value: LOW_HAND_VAL_NOTHING,
+                })
+            })
+            .collect();
+
         // Compare cached results
-        for i in 0..npockets {
-            self.update_statistics_hilo_cached(i, &evals, npockets);
+        if self.outright {
+            self.record_outright(&hand_values, &low_values);
+        } else {
+            for i in 0..npockets {
+                self.update_statistics_hilo_cached(i, &evals, npockets);
+            }
         }
+        self.record_hand_types(&hand_values);
+        self.record_ordering(&hand_values, &low_values);
         Ok(())
     }
 
@@ -1692,9 +1821,15 @@ impl EnumResult {
             .collect();
 
         // Compare cached results
-        for i in 0..npockets {
-            self.update_statistics_cached(i, &hand_values, npockets);
+        if self.outright {
+            self.record_outright(&hand_values, &[]);
+        } else {
+            for i in 0..npockets {
+                self.update_statistics_cached(i, &hand_values, npockets);
+            }
         }
+        self.record_hand_types(&hand_values);
+        self.record_ordering(&hand_values, &[]);
         Ok(())
     }
 
@@ -1724,8 +1859,8 @@ impl EnumResult {
         for &card in STD_DECK_CARD_MASKS_TABLE.iter() {
             let idx = StdDeck::mask_to_index(&card);
             if let Some(i) = idx {
-                // Filter for Short Deck: Rank >= 4 (2,3,4,5 are 0,1,2,3)
-                if (i / 4) >= 4 && !exclusion_mask.card_is_set(i) {
+                // Filter for Short Deck: 6 and up
+                if StdDeck::rank(i) >= Rank::SIX && !exclusion_mask.card_is_set(i) {
                     deck_buf[deck_len] = card;
                     deck_len += 1;
                 }
@@ -1742,7 +1877,7 @@ impl EnumResult {
                         .par_iter()
                         .enumerate()
                         .map(|(i1, c1)| {
-                            let mut local_res = EnumResult::new(self.game);
+                            let mut local_res = self.worker_result();
                             local_res.nplayers = npockets as u32;
                             enumerate_4_cards_d(&deck[..i1], no_dead, |c2, c3, c4, c5| {
                                 let new_board = board | *c1 | *c2 | *c3 | *c4 | *c5;
@@ -1755,7 +1890,7 @@ impl EnumResult {
                             local_res
                         })
                         .reduce(
-                            || EnumResult::new(self.game),
+                            || self.worker_result(),
                             |mut a, b| {
                                 a.merge(&b);
                                 a
@@ -1765,7 +1900,7 @@ impl EnumResult {
                 }
                 #[cfg(not(feature = "parallel"))]
                 {
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
                     for (i1, c1) in deck.iter().enumerate() {
                         enumerate_4_cards_d(&deck[..i1], no_dead, |c2, c3, c4, c5| {
@@ -1787,7 +1922,7 @@ impl EnumResult {
                         .par_iter()
                         .enumerate()
                         .map(|(i1, c1)| {
-                            let mut local_res = EnumResult::new(self.game);
+                            let mut local_res = self.worker_result();
                             local_res.nplayers = npockets as u32;
                             enumerate_1_cards_d(&deck[..i1], no_dead, |c2| {
                                 let new_board = board | *c1 | *c2;
@@ -1800,7 +1935,7 @@ impl EnumResult {
                             local_res
                         })
                         .reduce(
-                            || EnumResult::new(self.game),
+                            || self.worker_result(),
                             |mut a, b| {
                                 a.merge(&b);
                                 a
@@ -1810,7 +1945,7 @@ impl EnumResult {
                 }
                 #[cfg(not(feature = "parallel"))]
                 {
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
                     for (i1, c1) in deck.iter().enumerate() {
                         enumerate_1_cards_d(&deck[..i1], no_dead, |c2| {
@@ -1831,7 +1966,7 @@ impl EnumResult {
                     let total_res = deck
                         .par_iter()
                         .map(|c| {
-                            let mut local_res = EnumResult::new(self.game);
+                            let mut local_res = self.worker_result();
                             local_res.nplayers = npockets as u32;
                             let new_board = board | *c;
                             if let Ok(()) =
@@ -1842,7 +1977,7 @@ impl EnumResult {
                             local_res
                         })
                         .reduce(
-                            || EnumResult::new(self.game),
+                            || self.worker_result(),
                             |mut a, b| {
                                 a.merge(&b);
                                 a
@@ -1852,7 +1987,7 @@ impl EnumResult {
                 }
                 #[cfg(not(feature = "parallel"))]
                 {
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
                     for c in deck.iter() {
                         let new_board = board | *c;
@@ -1911,7 +2046,7 @@ impl EnumResult {
                         .par_iter()
                         .enumerate()
                         .map(|(i1, c1)| {
-                            let mut local_res = EnumResult::new(self.game);
+                            let mut local_res = self.worker_result();
                             local_res.nplayers = npockets as u32;
                             enumerate_4_cards_d(&deck[..i1], no_dead, |c2, c3, c4, c5| {
                                 let new_board = board | *c1 | *c2 | *c3 | *c4 | *c5;
@@ -1924,7 +2059,7 @@ impl EnumResult {
                             local_res
                         })
                         .reduce(
-                            || EnumResult::new(self.game),
+                            || self.worker_result(),
                             |mut a, b| {
                                 a.merge(&b);
                                 a
@@ -1934,7 +2069,7 @@ impl EnumResult {
                 }
                 #[cfg(not(feature = "parallel"))]
                 {
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
                     for (i1, c1) in deck.iter().enumerate() {
                         enumerate_4_cards_d(&deck[..i1], no_dead, |c2, c3, c4, c5| {
@@ -1956,7 +2091,7 @@ impl EnumResult {
                         .par_iter()
                         .enumerate()
                         .map(|(i1, c1)| {
-                            let mut local_res = EnumResult::new(self.game);
+                            let mut local_res = self.worker_result();
                             local_res.nplayers = npockets as u32;
                             enumerate_1_cards_d(&deck[..i1], no_dead, |c2| {
                                 let new_board = board | *c1 | *c2;
@@ -1969,7 +2104,7 @@ impl EnumResult {
                             local_res
                         })
                         .reduce(
-                            || EnumResult::new(self.game),
+                            || self.worker_result(),
                             |mut a, b| {
                                 a.merge(&b);
                                 a
@@ -1979,7 +2114,7 @@ impl EnumResult {
                 }
                 #[cfg(not(feature = "parallel"))]
                 {
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
                     for (i1, c1) in deck.iter().enumerate() {
                         enumerate_1_cards_d(&deck[..i1], no_dead, |c2| {
@@ -2000,7 +2135,7 @@ impl EnumResult {
                     let total_res = deck
                         .par_iter()
                         .map(|c| {
-                            let mut local_res = EnumResult::new(self.game);
+                            let mut local_res = self.worker_result();
                             local_res.nplayers = npockets as u32;
                             let new_board = board | *c;
                             if let Ok(()) =
@@ -2011,7 +2146,7 @@ impl EnumResult {
                             local_res
                         })
                         .reduce(
-                            || EnumResult::new(self.game),
+                            || self.worker_result(),
                             |mut a, b| {
                                 a.merge(&b);
                                 a
@@ -2021,7 +2156,7 @@ impl EnumResult {
                 }
                 #[cfg(not(feature = "parallel"))]
                 {
-                    let mut local_res = EnumResult::new(self.game);
+                    let mut local_res = self.worker_result();
                     local_res.nplayers = npockets as u32;
                     for c in deck.iter() {
                         let new_board = board | *c;
@@ -2129,6 +2264,12 @@ impl EnumResult {
     /// Updates statistics for all players at once using N*(N-1)/2 comparisons.
     #[inline]
     pub fn update_statistics_batched(&mut self, hand_values: &[HandVal], npockets: usize) {
+        if self.outright {
+            self.record_outright(&hand_values[..npockets], &[]);
+            self.record_hand_types(&hand_values[..npockets]);
+            self.record_ordering(&hand_values[..npockets], &[]);
+            return;
+        }
         use crate::enumdefs::ENUM_MAXPLAYERS;
         let mut wins = [0u32; ENUM_MAXPLAYERS];
         let mut ties = [0u32; ENUM_MAXPLAYERS];
@@ -2162,11 +2303,154 @@ impl EnumResult {
                 self.ev[i] += equity;
             }
         }
+        self.record_hand_types(&hand_values[..npockets]);
+        self.record_ordering(&hand_values[..npockets], &[]);
+    }
+
+    /// Counts one deal once per player, as the C library does: a win for the sole
+    /// best hand, a tie for a shared one and a loss otherwise, with `ev` gaining the
+    /// player's share of the pot. `hival` is empty in low-only games and `loval` in
+    /// high-only ones. A hand without a qualifying low plays no part in the low half,
+    /// and the high hands take the whole pot when no low qualifies.
+    fn record_outright(&mut self, hival: &[HandVal], loval: &[LowHandVal]) {
+        use crate::handval_low::LOW_HAND_VAL_NOTHING;
+
+        let best_hi = hival
+            .iter()
+            .copied()
+            .reduce(|a, b| if b > a { b } else { a });
+        let best_lo = loval
+            .iter()
+            .map(|l| l.value)
+            .filter(|&v| v != LOW_HAND_VAL_NOTHING)
+            .min();
+        let hi_share = hival.iter().filter(|&&v| Some(v) == best_hi).count();
+        let lo_share = loval.iter().filter(|l| Some(l.value) == best_lo).count();
+        let (hi_pot, lo_pot) = match (hi_share, lo_share) {
+            (0, 0) => (0.0, 0.0),
+            (h, 0) => (1.0 / h as f64, 0.0),
+            (0, l) => (0.0, 1.0 / l as f64),
+            (h, l) => (0.5 / h as f64, 0.5 / l as f64),
+        };
+
+        for i in 0..hival.len().max(loval.len()) {
+            let mut pot = 0.0;
+            if let Some(&hi) = hival.get(i) {
+                if Some(hi) == best_hi {
+                    pot += hi_pot;
+                    if hi_share == 1 {
+                        self.nwinhi[i] += 1;
+                    } else {
+                        self.ntiehi[i] += 1;
+                    }
+                } else {
+                    self.nlosehi[i] += 1;
+                }
+            }
+            if let Some(lo) = loval.get(i).filter(|l| l.value != LOW_HAND_VAL_NOTHING) {
+                if Some(lo.value) == best_lo {
+                    pot += lo_pot;
+                    if lo_share == 1 {
+                        self.nwinlo[i] += 1;
+                    } else {
+                        self.ntielo[i] += 1;
+                    }
+                } else {
+                    self.nloselo[i] += 1;
+                }
+            }
+            if pot >= 1.0 {
+                self.nscoop[i] += 1;
+            }
+            self.ev[i] += pot;
+        }
+    }
+
+    /// Counts the relative ranks of the players' hands in the ordering histogram, if
+    /// [`allocate_resources`](Self::allocate_resources) set one up. Rank 0 is the
+    /// best hand; a low that does not qualify is ranked `nplayers` ("NQ").
+    fn record_ordering(&mut self, hival: &[HandVal], loval: &[LowHandVal]) {
+        let Some(ordering) = self.ordering.as_deref_mut() else {
+            return;
+        };
+        let n = ordering.nplayers;
+        let hiranks = |ranks: &mut [i32]| {
+            let mut hands = hival[..n].to_vec();
+            enum_ordering_rank(&mut hands, HandVal { value: 0 }, n, ranks, true);
+        };
+        let loranks = |ranks: &mut [i32]| {
+            let mut hands: Vec<HandVal> = loval[..n]
+                .iter()
+                .map(|v| HandVal { value: v.value })
+                .collect();
+            let noqual = HandVal {
+                value: LOW_HAND_VAL_NOTHING,
+            };
+            enum_ordering_rank(&mut hands, noqual, n, ranks, false);
+        };
+        let mut ranks = [0i32; ENUM_ORDERING_MAXPLAYERS];
+        match ordering.mode {
+            EnumOrderingMode::Hi => {
+                hiranks(&mut ranks[..n]);
+                enum_ordering_increment(ordering, &ranks[..n]);
+            }
+            EnumOrderingMode::Lo => {
+                loranks(&mut ranks[..n]);
+                enum_ordering_increment(ordering, &ranks[..n]);
+            }
+            EnumOrderingMode::Hilo => {
+                let mut lo = [0i32; ENUM_ORDERING_MAXPLAYERS];
+                hiranks(&mut ranks[..n]);
+                loranks(&mut lo[..n]);
+                enum_ordering_increment_hilo(ordering, &ranks[..n], &lo[..n]);
+            }
+            EnumOrderingMode::None => {}
+        }
+    }
+
+    /// Records each player's final high hand type, with the type of the hand that
+    /// won or beat it, for the hand-type distribution.
+    ///
+    /// Short Deck values store flushes and full houses swapped; they are recorded
+    /// under their real `HandType`.
+    #[inline]
+    pub fn record_hand_types(&mut self, hand_values: &[HandVal]) {
+        let short_deck = self.game == Game::ShortDeck;
+        let type_of = |v: &HandVal| -> usize {
+            match (v.hand_type() as usize).min(ENUM_NHANDTYPES - 1) {
+                5 if short_deck => 6,
+                6 if short_deck => 5,
+                t => t,
+            }
+        };
+
+        let Some(best) = hand_values.iter().max_by_key(|v| v.value) else {
+            return;
+        };
+        let best_type = type_of(best);
+        let nbest = hand_values.iter().filter(|v| v.value == best.value).count();
+
+        for (i, v) in hand_values.iter().enumerate() {
+            let t = type_of(v);
+            self.nhandtype[i][t] += 1;
+            if v.value == best.value {
+                if nbest == 1 {
+                    self.nwinhandtype[i][t] += 1;
+                }
+            } else {
+                self.nlosehandtype[i][t][best_type] += 1;
+            }
+        }
     }
 
     /// Updates statistics for Lowball games (smaller is better).
     #[inline]
     pub fn update_statistics_batched_lo(&mut self, hand_values: &[LowHandVal], npockets: usize) {
+        if self.outright {
+            self.record_outright(&[], &hand_values[..npockets]);
+            self.record_ordering(&[], &hand_values[..npockets]);
+            return;
+        }
         use crate::enumdefs::ENUM_MAXPLAYERS;
         let mut wins = [0u32; ENUM_MAXPLAYERS];
         let mut ties = [0u32; ENUM_MAXPLAYERS];
@@ -2204,6 +2488,7 @@ impl EnumResult {
                 self.ev[i] += equity;
             }
         }
+        self.record_ordering(&[], &hand_values[..npockets]);
     }
 
     /// Updates statistics for Hi/Lo games.
@@ -2217,6 +2502,13 @@ impl EnumResult {
         use crate::enumdefs::ENUM_MAXPLAYERS;
         use crate::handval_low::LOW_HAND_VAL_NOTHING;
 
+        if self.outright {
+            self.record_outright(&hival[..npockets], &loval[..npockets]);
+            self.record_hand_types(&hival[..npockets]);
+            self.record_ordering(&hival[..npockets], &loval[..npockets]);
+            return;
+        }
+
         let mut hi_wins = [0u32; ENUM_MAXPLAYERS];
         let mut hi_ties = [0u32; ENUM_MAXPLAYERS];
         let mut hi_losses = [0u32; ENUM_MAXPLAYERS];
@@ -2382,6 +2674,8 @@ impl EnumResult {
                 self.ev[i] += hi_equity + lo_equity;
             }
         }
+        self.record_hand_types(&hival[..npockets]);
+        self.record_ordering(&hival[..npockets], &loval[..npockets]);
     }
 
     /// Updates statistics from pre-evaluated hand values (avoids redundant evaluation).
@@ -2483,7 +2777,9 @@ impl EnumResult {
             self.nloselo[player_index] += lo_losses;
         }
 
-        if lo_val.is_some() && hi_wins > 0 && lo_wins > 0 && hi_ties == 0 && lo_ties == 0 {
+        let won_hi = hi_wins > 0 && hi_ties == 0 && hi_losses == 0;
+        let won_lo = lo_wins > 0 && lo_ties == 0 && lo_losses == 0;
+        if lo_val.is_some() && won_hi && won_lo {
             self.nscoop[player_index] += 1;
         }
 
@@ -2494,8 +2790,8 @@ impl EnumResult {
     }
 
     /// Prints the ordering histogram of relative hand ranks.
-    pub fn print_ordering(&mut self, terse: bool) {
-        if let Some(ordering) = &mut self.ordering {
+    pub fn print_ordering(&self, terse: bool) {
+        if let Some(ordering) = &self.ordering {
             if !terse {
                 println!("Histogram of relative hand ranks:");
             }
@@ -2503,23 +2799,12 @@ impl EnumResult {
                 EnumOrderingMode::Hi | EnumOrderingMode::Lo => {
                     if !terse {
                         for k in 0..ordering.nplayers {
-                            print!(" {:2}", (b'A' + k as u8) as char);
+                            print!(" {:>2}", (b'A' + k as u8) as char);
                         }
-                        println!(" {:8}", "Freq");
+                        println!(" {:>8}", "Freq");
                     } else {
                         print!("ORD {} {}:", ordering.mode as u32, ordering.nplayers);
                     }
-                    let nplayers = self.nplayers as usize;
-                    for i in 0..nplayers {
-                        for j in 0..nplayers {
-                            if i != j {
-                                let index = i * nplayers + j;
-                                ordering.hist[index] += self.nwinhi[i];
-                                ordering.hist[index] += self.ntiehi[i];
-                                ordering.hist[index] += self.nlosehi[j];
-                            }
-                        }
-                    }
                     for i in 0..ordering.nentries {
                         if ordering.hist[i] > 0 {
                             for k in 0..ordering.nplayers {
@@ -2543,15 +2828,15 @@ impl EnumResult {
                     if !terse {
                         print!("HI:");
                         for k in 0..ordering.nplayers {
-                            print!(" {:2}", (b'A' + k as u8) as char);
+                            print!(" {:>2}", (b'A' + k as u8) as char);
                         }
                         print!("  LO:");
                         for k in 0..ordering.nplayers {
-                            print!(" {:2}", (b'A' + k as u8) as char);
+                            print!(" {:>2}", (b'A' + k as u8) as char);
                         }
-                        println!(" {:8}", "Freq");
+                        println!(" {:>8}", "Freq");
                     } else {
-                        print!("ORD HILO {}:", ordering.nplayers);
+                        print!("ORD {} {}:", ordering.mode as u32, ordering.nplayers);
                     }
 
                     for i in 0..ordering.nentries {
@@ -2607,14 +2892,14 @@ impl EnumResult {
 
     /// Prints detailed enumeration results including win/tie/loss counts and equity.
     pub fn enum_result_print(
-        &mut self,
+        &self,
         pockets: &[StdDeckCardMask],
         board: StdDeckCardMask,
     ) -> Result<(), PokerError> {
         let gp = self.game.game_params();
         if let Some(gp) = gp {
             let width = gp.maxpocket * 3 - 1;
-            println!(
+            print!(
                 "{}: {} {} {}{}",
                 gp.name,
                 self.nsamples,
@@ -2625,14 +2910,14 @@ impl EnumResult {
                 if gp.maxboard > 0 { "board" } else { "outcome" },
                 if self.nsamples == 1 { "" } else { "s" }
             );
-
             if board.num_cards() > 0 {
-                println!(" containing {}", board.to_string_representation());
+                print!(" containing {}", board.to_string_representation());
             }
+            println!();
 
             if gp.haslopot == 1 && gp.hashipot == 1 {
                 println!(
-                    "{:width$} {:>7} {:>7} {:>7} {:>7} {:>7} {:>7} {:>7} {:>5}",
+                    "{:>width$} {:>7}   {:>7} {:>7} {:>7}   {:>7} {:>7} {:>7}   {:>5}",
                     "cards",
                     "scoop",
                     "HIwin",
@@ -2646,7 +2931,7 @@ impl EnumResult {
                 );
                 for (i, pocket) in pockets.iter().enumerate().take(self.nplayers as usize) {
                     println!(
-                        "{:width$} {:7} {:7} {:7} {:7} {:7} {:7} {:7} {:5.3}",
+                        "{:>width$} {:7}   {:7} {:7} {:7}   {:7} {:7} {:7}   {:5.3}",
                         pocket.to_string_representation(),
                         self.nscoop[i],
                         self.nwinhi[i],
@@ -2661,7 +2946,7 @@ impl EnumResult {
                 }
             } else {
                 println!(
-                    "{:width$} {:>7} {:>6} {:>7} {:>6} {:>7} {:>6} {:>5}",
+                    "{:>width$} {:>7} {:>6}   {:>7} {:>6}   {:>7} {:>6}     {:>5}",
                     "cards",
                     "win",
                     "%win",
@@ -2685,7 +2970,7 @@ impl EnumResult {
                     let ev = self.ev[i] / self.nsamples as f64;
 
                     println!(
-                        "{:width$} {:7} {:6.2}% {:7} {:6.2}% {:7} {:6.2}% {:5.3}",
+                        "{:>width$} {:7} {:6.2}   {:7} {:6.2}   {:7} {:6.2}     {:5.3}",
                         pocket.to_string_representation(),
                         nwin,
                         win_percent,
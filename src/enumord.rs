@@ -46,17 +46,18 @@ pub fn enum_ordering_rank(
 
     // Sort hands by value, in ascending or descending order
     if reverse {
-        elems.sort_by(|a, b| b.1.value.cmp(&a.1.value)); // Descending order if reverse is true
+        elems.sort_by_key(|e| std::cmp::Reverse(e.1.value)); // Descending order if reverse is true
     } else {
-        elems.sort_by(|a, b| a.1.value.cmp(&b.1.value)); // Ascending order otherwise
+        elems.sort_by_key(|e| e.1.value); // Ascending order otherwise
     }
 
-    // Assign ranks based on sorting
+    // Assign ranks based on sorting; tied hands share the rank of the first of them
+    // and the next hand is ranked by its position, as in poker-eval.
     let mut currank = 0;
     let mut lastval = elems[0].1.value;
-    for &(index, ref handval) in &elems {
+    for (pos, &(index, ref handval)) in elems.iter().enumerate() {
         if handval.value != lastval {
-            currank += 1;
+            currank = pos as i32;
             lastval = handval.value;
         }
         if handval.value == noqual.value {
@@ -101,10 +102,9 @@ pub fn enum_ordering_decode_k(encoding: i32, nplayers: usize, k: usize) -> i32 {
 pub fn enum_ordering_decode_hilo_k_hi(encoding: i32, nplayers: usize, k: usize) -> i32 {
     let nbits_per_rank = ENUM_NBITS[nplayers] as usize;
     let total_bits = nbits_per_rank * nplayers * 2; // Multiplied by 2 for high and low ranks
-    let high_bits_offset = total_bits / 2; // Half the bits for high ranks
 
-    // Compute the shift for player k's high rank
-    let shift = high_bits_offset - (k + 1) * nbits_per_rank;
+    // High ranks fill the upper half, player 0 first
+    let shift = total_bits - (k + 1) * nbits_per_rank;
 
     // Extract player k's high rank
     (encoding >> shift) & ((1 << nbits_per_rank) - 1)
@@ -192,6 +192,15 @@ mod tests {
         enum_ordering_rank(&mut hands, noqual, nplayers, &mut ranks, false);
 
         assert_eq!(ranks, vec![1, 2, 0]); // Expected ranks after sorting
+
+        // Best first; two tied hands share rank 0 and the next is third, not second.
+        let mut hands = vec![
+            HandVal { value: 7 },
+            HandVal { value: 4 },
+            HandVal { value: 7 },
+        ];
+        enum_ordering_rank(&mut hands, noqual, nplayers, &mut ranks, true);
+        assert_eq!(ranks, vec![0, 2, 0]);
     }
 
     // Test for enum_ordering_encode
@@ -204,6 +213,19 @@ mod tests {
         assert_eq!(encoded, 24); // Expected encoded value
     }
 
+    #[test]
+    fn test_enum_ordering_decode_hilo() {
+        let encoded = enum_ordering_encode_hilo(3, &[2, 0, 1], &[0, 3, 1]);
+        let hi: Vec<i32> = (0..3)
+            .map(|k| enum_ordering_decode_hilo_k_hi(encoded, 3, k))
+            .collect();
+        let lo: Vec<i32> = (0..3)
+            .map(|k| enum_ordering_decode_hilo_k_lo(encoded, 3, k))
+            .collect();
+        assert_eq!(hi, vec![2, 0, 1]);
+        assert_eq!(lo, vec![0, 3, 1]);
+    }
+
     // Test for enum_ordering_decode_k
     #[test]
     fn test_enum_ordering_decode_k() {
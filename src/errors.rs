@@ -20,6 +20,8 @@ pub enum PokerError {
     InternalError(String),
     /// A generic error with a descriptive message.
     Other(String),
+    /// The caller stopped a long-running calculation before it finished.
+    Cancelled,
 }
 
 impl fmt::Display for PokerError {
@@ -37,6 +39,7 @@ impl fmt::Display for PokerError {
             PokerError::ExecutionError(msg) => write!(f, "Execution Error: {}", msg),
             PokerError::InternalError(msg) => write!(f, "Internal Error: {}", msg),
             PokerError::Other(msg) => write!(f, "Error: {}", msg),
+            PokerError::Cancelled => write!(f, "Cancelled"),
         }
     }
 }
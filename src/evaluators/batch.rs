@@ -0,0 +1,207 @@
+//! Batch evaluation of many hands at once.
+//!
+//! Hands are spread over threads with `rayon` when the `parallel` feature is enabled.
+//! [`eval_n_batch`] also uses [`Eval::eval_8_hands`] when it is compiled in (the
+//! `simd` and `large-table` features on x86_64) and the CPU supports AVX2.
+
+use super::Eval;
+use crate::errors::PokerError;
+use crate::handval::HandVal;
+use crate::tables::t_cardmasks::StdDeckCardMask;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Hands per rayon task; large enough that scheduling is cheap next to evaluation.
+#[cfg(feature = "parallel")]
+const BATCH_CHUNK_SIZE: usize = 1024;
+
+/// Evaluates each hand with `eval`, in order.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::deck::StdDeck;
+/// use poker_eval_rs::evaluators::batch::eval_batch;
+/// use poker_eval_rs::evaluators::std_deck_lowball_eval;
+///
+/// let hands = [StdDeck::string_to_mask("Ac2d3h4s5c").unwrap().0];
+/// let lows = eval_batch(&hands, |h| std_deck_lowball_eval(h, 5));
+/// assert_eq!(lows[0].to_string(), "Low NoPair ( 5 4 3 2 1)");
+/// ```
+pub fn eval_batch<T, F>(hands: &[StdDeckCardMask], eval: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&StdDeckCardMask) -> T + Sync,
+{
+    #[cfg(feature = "parallel")]
+    {
+        hands
+            .par_iter()
+            .with_min_len(BATCH_CHUNK_SIZE)
+            .map(&eval)
+            .collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        hands.iter().map(eval).collect()
+    }
+}
+
+/// Evaluates each pocket against the board at the same position, in order.
+///
+/// Panics if `pockets` and `boards` differ in length.
+pub fn eval_pairs_batch<T, F>(
+    pockets: &[StdDeckCardMask],
+    boards: &[StdDeckCardMask],
+    eval: F,
+) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&StdDeckCardMask, &StdDeckCardMask) -> T + Sync,
+{
+    assert_eq!(pockets.len(), boards.len(), "one board per pocket");
+    #[cfg(feature = "parallel")]
+    {
+        pockets
+            .par_iter()
+            .zip(boards.par_iter())
+            .with_min_len(BATCH_CHUNK_SIZE)
+            .map(|(p, b)| eval(p, b))
+            .collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        pockets
+            .iter()
+            .zip(boards)
+            .map(|(p, b)| eval(p, b))
+            .collect()
+    }
+}
+
+/// Evaluates high hands of 5 to 7 cards with [`Eval::eval_n`].
+///
+/// Returns [`PokerError::InvalidInput`] naming the first hand of another size,
+/// before evaluating anything.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::deck::StdDeck;
+/// use poker_eval_rs::evaluators::batch::eval_n_batch;
+/// use poker_eval_rs::evaluators::Eval;
+///
+/// let hands: Vec<_> = ["AsKsQsJsTs", "2c2d7h8s9c3d"]
+///     .iter()
+///     .map(|h| StdDeck::string_to_mask(h).unwrap().0)
+///     .collect();
+/// let vals = eval_n_batch(&hands).unwrap();
+/// assert_eq!(vals[1], Eval::eval_n(&hands[1], 6));
+/// assert!(vals[0] > vals[1]);
+/// ```
+pub fn eval_n_batch(hands: &[StdDeckCardMask]) -> Result<Vec<HandVal>, PokerError> {
+    if let Some((i, hand)) = hands
+        .iter()
+        .enumerate()
+        .find(|(_, h)| !(5..=7).contains(&h.num_cards()))
+    {
+        return Err(PokerError::InvalidInput(format!(
+            "Hand {} has {} cards; eval_n takes 5 to 7",
+            i,
+            hand.num_cards()
+        )));
+    }
+
+    #[cfg(all(
+        feature = "simd",
+        target_arch = "x86_64",
+        feature = "large-table",
+        not(feature = "compact-table")
+    ))]
+    if std::arch::is_x86_feature_detected!("avx2") {
+        return Ok(eval_n_batch_avx2(hands));
+    }
+    Ok(eval_batch(hands, |h| Eval::eval_n(h, h.num_cards())))
+}
+
+#[cfg(all(
+    feature = "simd",
+    target_arch = "x86_64",
+    feature = "large-table",
+    not(feature = "compact-table")
+))]
+fn eval_n_batch_avx2(hands: &[StdDeckCardMask]) -> Vec<HandVal> {
+    let eval_chunk = |chunk: &[StdDeckCardMask]| -> Vec<HandVal> {
+        match <&[StdDeckCardMask; 8]>::try_from(chunk) {
+            // SAFETY: the caller checked for AVX2.
+            Ok(eight) => unsafe { Eval::eval_8_hands(eight) }.to_vec(),
+            Err(_) => chunk
+                .iter()
+                .map(|h| Eval::eval_n(h, h.num_cards()))
+                .collect(),
+        }
+    };
+    #[cfg(feature = "parallel")]
+    {
+        hands
+            .par_chunks(8)
+            .with_min_len(BATCH_CHUNK_SIZE / 8)
+            .flat_map_iter(eval_chunk)
+            .collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        hands.chunks(8).flat_map(eval_chunk).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::StdDeck;
+    use crate::evaluators::{HandEvaluator, OmahaHiEvaluator};
+
+    #[test]
+    fn test_batch_matches_scalar() {
+        // Enough hands for several chunks, with a ragged tail.
+        let hands: Vec<StdDeckCardMask> = (0..5000u64)
+            .map(|i| {
+                let mut mask = StdDeckCardMask::new();
+                for k in 0..7 {
+                    mask.set(((i * 7 + k * 11 + i / 3) % 52) as usize);
+                }
+                mask
+            })
+            .collect();
+        assert!(hands.iter().all(|h| h.num_cards() == 7));
+        let vals = eval_n_batch(&hands).unwrap();
+        assert_eq!(vals.len(), hands.len());
+        for (h, v) in hands.iter().zip(&vals) {
+            assert_eq!(*v, Eval::eval_n(h, h.num_cards()));
+        }
+
+        let (board, _) = StdDeck::string_to_mask("2c7d9hTsJs").unwrap();
+        let pockets = [
+            StdDeck::string_to_mask("AsKsQdJd").unwrap().0,
+            StdDeck::string_to_mask("8c8d3h4h").unwrap().0,
+        ];
+        let his = eval_pairs_batch(&pockets, &[board, board], |p, b| {
+            OmahaHiEvaluator::evaluate_hand(p, b).unwrap()
+        });
+        assert!(his[0].unwrap() > his[1].unwrap());
+    }
+
+    #[test]
+    fn test_eval_n_batch_rejects_card_counts() {
+        let mask = |s: &str| StdDeck::string_to_mask(s).unwrap().0;
+        let hands = [mask("AsKsQsJsTs"), StdDeckCardMask::new(), mask("2c3c")];
+        assert_eq!(
+            eval_n_batch(&hands),
+            Err(PokerError::InvalidInput(
+                "Hand 1 has 0 cards; eval_n takes 5 to 7".to_string()
+            ))
+        );
+        assert!(eval_n_batch(&[mask("2c3c4c5c6c7c8c9c")]).is_err());
+    }
+}
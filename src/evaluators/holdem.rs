@@ -104,6 +104,65 @@ impl Eval {
         Self::get_non_flush_val(ss, sc, sd, sh)
     }
 
+    /// Evaluates a hand like [`Eval::eval_n`], but always returns a `HandVal` whose card
+    /// fields (`top_card` .. `fifth_card`) hold the ranks of the hand and its kickers.
+    ///
+    /// Non-flush values from `eval_n` are lookup-table ordinals, so their card fields
+    /// are not meaningful. Use this when the kickers matter; values are only comparable
+    /// with other values from this function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use poker_eval_rs::deck::StdDeck;
+    /// use poker_eval_rs::evaluators::Eval;
+    /// use poker_eval_rs::rules::HandType;
+    ///
+    /// let (mask, count) = StdDeck::string_to_mask("AhKcKd7s2h").unwrap();
+    /// let val = Eval::eval_n_detailed(&mask, count);
+    /// assert_eq!(val.hand_type(), HandType::OnePair as u8);
+    /// assert_eq!(val.top_card(), 11); // pair of kings
+    /// assert_eq!(val.second_card(), 12); // ace kicker
+    /// ```
+    pub fn eval_n_detailed(cards: &StdDeckCardMask, n_cards: usize) -> HandVal {
+        let ss = cards.spades();
+        let sc = cards.clubs();
+        let sd = cards.diamonds();
+        let sh = cards.hearts();
+        let ranks = ss | sc | sd | sh;
+        let n_dups = n_cards.saturating_sub(ranks.count_ones() as usize);
+
+        let flush = Self::find_flush_or_straight_flush(ss, sc, sd, sh);
+        if let Some(val) = flush {
+            if val.hand_type() == HandType::StFlush as u8 || n_dups < 3 {
+                return val;
+            }
+        }
+
+        let dups = if n_dups > 0 {
+            Some(Self::evaluate_duplicates(n_dups, ranks, sc, sd, sh, ss))
+        } else {
+            None
+        };
+        if let Some(val) = dups {
+            if val.hand_type() >= HandType::FullHouse as u8 {
+                return val;
+            }
+        }
+        if let Some(val) = flush {
+            return val;
+        }
+        if let Some(val) = Self::find_straight(ranks) {
+            return val;
+        }
+
+        dups.unwrap_or_else(|| {
+            let (top, second, third, fourth, fifth) =
+                crate::rules::std::extract_top_five_cards(ranks);
+            HandVal::new(HandType::NoPair as u8, top, second, third, fourth, fifth)
+        })
+    }
+
     /// Optimized 5-card evaluation that skips flush and straight flush checks.
     /// Useful for Omaha when the board triplet is already known to be non-monotone.
     #[inline]
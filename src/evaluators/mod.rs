@@ -1,5 +1,6 @@
 //! Evaluation algorithms and HandEvaluator trait implementations.
 
+pub mod batch;
 pub mod holdem;
 pub mod joker;
 pub mod joker_low;
@@ -93,6 +94,42 @@ impl HandEvaluator for OmahaHiEvaluator {
     }
 }
 
+impl OmahaHiEvaluator {
+    /// Evaluates the best Omaha high hand with [`Eval::eval_n_detailed`], so the card
+    /// fields of the result hold the hand's ranks and kickers.
+    ///
+    /// Exactly two hole cards and three board cards are used. Returns `None` if the board
+    /// has fewer than three cards or the hole fewer than two. Slower than `evaluate_hand`,
+    /// and values are only comparable with other detailed values.
+    pub fn evaluate_detailed(hole: &StdDeckCardMask, board: &StdDeckCardMask) -> Option<HandVal> {
+        let cards = |m: &StdDeckCardMask| -> Vec<StdDeckCardMask> {
+            (0..crate::deck::STD_DECK_N_CARDS)
+                .filter(|&i| m.card_is_set(i))
+                .map(StdDeckCardMask::from_card_index)
+                .collect()
+        };
+        let hole = cards(hole);
+        let board = cards(board);
+        let mut best: Option<HandVal> = None;
+        for (i, h1) in hole.iter().enumerate() {
+            for h2 in &hole[i + 1..] {
+                for (j, b1) in board.iter().enumerate() {
+                    for (k, b2) in board.iter().enumerate().skip(j + 1) {
+                        for b3 in &board[k + 1..] {
+                            let hand = *h1 | *h2 | *b1 | *b2 | *b3;
+                            let val = Eval::eval_n_detailed(&hand, 5);
+                            if best.map_or(true, |b| val.value > b.value) {
+                                best = Some(val);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        best
+    }
+}
+
 /// Evaluator for Omaha Hi/Lo (8-or-better).
 /// Returns a tuple of (High Hand, Low Hand).
 pub struct OmahaHiLoEvaluator;
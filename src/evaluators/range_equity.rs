@@ -1,6 +1,9 @@
+use crate::combinations::for_each_combination;
 use crate::deck::{StdDeckCardMask, STD_DECK_N_CARDS};
+use crate::enumdefs::ENUM_MAXPLAYERS;
 use crate::enumerate::CardMask;
-use crate::evaluators::{HandEvaluator, HoldemEvaluator};
+use crate::evaluators::{Eval, HandEvaluator, HoldemEvaluator};
+use crate::query::Progress;
 use crate::range::HandRange;
 use rand::distributions::WeightedIndex;
 use rand::prelude::*;
@@ -12,9 +15,18 @@ pub struct EquityResult {
     pub ties: usize,
     pub losses: usize,
     pub samples: usize,
+    /// Hero's share of the pot, from 0 to 1.
     pub equity: f64,
+    /// True if every deal was enumerated rather than sampled.
+    pub exact: bool,
 }
 
+/// Deals [`equity_vs_random`] enumerates exhaustively before switching to sampling.
+const VS_RANDOM_EXACT_LIMIT: usize = 1_200_000;
+
+/// Deals evaluated between two progress reports of the chunked functions.
+const CHUNK_SIZE: usize = 50_000;
+
 /// Calculates equity between two ranges using Monte Carlo simulation.
 ///
 /// # Arguments
@@ -25,7 +37,7 @@ pub struct EquityResult {
 ///
 /// # Returns
 ///
-/// `EquityResult` containing win/tie/loss counts and equity percentage for player 1.
+/// `EquityResult` containing win/tie/loss counts and player 1's equity as a fraction of the pot.
 pub fn calculate_equity(
     range1: &HandRange,
     range2: &HandRange,
@@ -154,9 +166,234 @@ pub fn calculate_equity(
         losses,
         samples,
         equity: (wins as f64 + (ties as f64 / 2.0)) / samples as f64,
+        exact: false,
     })
 }
 
+/// Like [`calculate_equity`], but samples in chunks and calls `on_chunk` with the
+/// progress after each one. Returns `Ok(None)` as soon as `on_chunk` returns `false`.
+pub fn calculate_equity_chunked<F>(
+    range1: &HandRange,
+    range2: &HandRange,
+    board: &StdDeckCardMask,
+    iterations: usize,
+    mut on_chunk: F,
+) -> Result<Option<EquityResult>, String>
+where
+    F: FnMut(Progress) -> bool,
+{
+    let mut total = EquityResult::default();
+    let mut remaining = iterations;
+    loop {
+        let n = CHUNK_SIZE.min(remaining);
+        let chunk = calculate_equity(range1, range2, board, n)?;
+        total.wins += chunk.wins;
+        total.ties += chunk.ties;
+        total.losses += chunk.losses;
+        total.samples += chunk.samples;
+        remaining -= n;
+        let progress = Progress {
+            done: (iterations - remaining) as u64,
+            total: iterations as u64,
+        };
+        if !on_chunk(progress) {
+            return Ok(None);
+        }
+        if remaining == 0 {
+            break;
+        }
+    }
+    total.equity = (total.wins as f64 + total.ties as f64 / 2.0) / total.samples as f64;
+    Ok(Some(total))
+}
+
+/// Calculates the Hold'em equity of `hero` against `opponents` random hands.
+///
+/// Opponent hands are dealt straight from the deck rather than sampled from
+/// ranges. Heads-up spots with few unknown deals (a complete board, a turn, or a
+/// flop) are enumerated exactly, ignoring `iterations`; everything else uses
+/// `iterations` Monte Carlo deals.
+///
+/// Wins count deals where hero alone has the best hand, ties deals where hero
+/// shares it; `equity` gives hero an equal share of each split pot.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::deck::{StdDeck, StdDeckCardMask};
+/// use poker_eval_rs::evaluators::range_equity::equity_vs_random;
+///
+/// let (hero, _) = StdDeck::string_to_mask("AhAd").unwrap();
+/// let (board, _) = StdDeck::string_to_mask("Kc7s2h9d").unwrap();
+/// let res = equity_vs_random(&hero, 1, &board, &StdDeckCardMask::new(), 0).unwrap();
+/// assert!(res.exact);
+/// assert!(res.equity > 0.85);
+/// ```
+pub fn equity_vs_random(
+    hero: &StdDeckCardMask,
+    opponents: usize,
+    board: &StdDeckCardMask,
+    dead: &StdDeckCardMask,
+    iterations: usize,
+) -> Result<EquityResult, String> {
+    equity_vs_random_chunked(hero, opponents, board, dead, iterations, |_| true)
+        .map(|res| res.expect("runs without a stop request finish"))
+}
+
+/// Like [`equity_vs_random`], but calls `on_chunk` with the progress after each
+/// chunk of deals. Returns `Ok(None)` as soon as `on_chunk` returns `false`.
+pub fn equity_vs_random_chunked<F>(
+    hero: &StdDeckCardMask,
+    opponents: usize,
+    board: &StdDeckCardMask,
+    dead: &StdDeckCardMask,
+    iterations: usize,
+    mut on_chunk: F,
+) -> Result<Option<EquityResult>, String>
+where
+    F: FnMut(Progress) -> bool,
+{
+    if hero.num_cards() != 2 {
+        return Err("Hero hand must have exactly 2 cards".to_string());
+    }
+    if opponents == 0 || opponents + 1 > ENUM_MAXPLAYERS {
+        return Err(format!(
+            "Number of opponents must be between 1 and {}",
+            ENUM_MAXPLAYERS - 1
+        ));
+    }
+    let nboard = board.num_cards();
+    if nboard > 5 {
+        return Err("Board cannot have more than 5 cards".to_string());
+    }
+    if hero.overlaps(board) || hero.overlaps(dead) || board.overlaps(dead) {
+        return Err("Hero, board and dead cards must not overlap".to_string());
+    }
+
+    let used = *hero | *board | *dead;
+    let mut deck: Vec<usize> = (0..STD_DECK_N_CARDS)
+        .filter(|&i| !used.card_is_set(i))
+        .collect();
+    let missing = 5 - nboard;
+    let needed = 2 * opponents + missing;
+    if deck.len() < needed {
+        return Err("Not enough cards left to deal".to_string());
+    }
+
+    let mut tally = Tally::default();
+    let deals = n_choose_k(deck.len(), 2) * n_choose_k(deck.len() - 2, missing);
+    let exact = opponents == 1 && deals <= VS_RANDOM_EXACT_LIMIT;
+
+    if exact {
+        // Reports come between opponent holdings, once a chunk of deals is done.
+        let (mut reported, mut stopped) = (0, false);
+        for_each_combination(deck.len(), 2, |opp_idx| {
+            if stopped {
+                return;
+            }
+            let mut opp = StdDeckCardMask::new();
+            opp.set(deck[opp_idx[0]]);
+            opp.set(deck[opp_idx[1]]);
+            let rest: Vec<usize> = deck
+                .iter()
+                .copied()
+                .filter(|&c| !opp.card_is_set(c))
+                .collect();
+            for_each_combination(rest.len(), missing, |board_idx| {
+                let mut full = *board;
+                for &i in board_idx {
+                    full.set(rest[i]);
+                }
+                tally.record(hand_value(hero, &full), &[hand_value(&opp, &full)]);
+            });
+            if tally.samples - reported >= CHUNK_SIZE || tally.samples == deals {
+                reported = tally.samples;
+                stopped = !on_chunk(Progress {
+                    done: reported as u64,
+                    total: deals as u64,
+                });
+            }
+        });
+        if stopped {
+            return Ok(None);
+        }
+    } else {
+        if iterations == 0 {
+            return Err("Iterations must be positive".to_string());
+        }
+        let mut rng = thread_rng();
+        let mut values = vec![0u32; opponents];
+        while tally.samples < iterations {
+            for _ in 0..CHUNK_SIZE.min(iterations - tally.samples) {
+                let (dealt, _) = deck.partial_shuffle(&mut rng, needed);
+                let mut full = *board;
+                for &c in &dealt[2 * opponents..] {
+                    full.set(c);
+                }
+                for (value, cards) in values.iter_mut().zip(dealt[..2 * opponents].chunks(2)) {
+                    let mut opp = StdDeckCardMask::new();
+                    opp.set(cards[0]);
+                    opp.set(cards[1]);
+                    *value = hand_value(&opp, &full);
+                }
+                tally.record(hand_value(hero, &full), &values);
+            }
+            let progress = Progress {
+                done: tally.samples as u64,
+                total: iterations as u64,
+            };
+            if !on_chunk(progress) {
+                return Ok(None);
+            }
+        }
+    }
+
+    Ok(Some(EquityResult {
+        wins: tally.wins,
+        ties: tally.ties,
+        losses: tally.losses,
+        samples: tally.samples,
+        equity: tally.share / tally.samples as f64,
+        exact,
+    }))
+}
+
+/// Win/tie/loss counts for hero over a series of showdowns.
+#[derive(Default)]
+struct Tally {
+    wins: usize,
+    ties: usize,
+    losses: usize,
+    samples: usize,
+    share: f64,
+}
+
+impl Tally {
+    fn record(&mut self, hero: u32, opponents: &[u32]) {
+        self.samples += 1;
+        let best = opponents.iter().copied().max().unwrap_or(0);
+        if hero > best {
+            self.wins += 1;
+            self.share += 1.0;
+        } else if hero == best {
+            self.ties += 1;
+            let split = opponents.iter().filter(|&&v| v == hero).count() + 1;
+            self.share += 1.0 / split as f64;
+        } else {
+            self.losses += 1;
+        }
+    }
+}
+
+fn hand_value(pocket: &StdDeckCardMask, board: &StdDeckCardMask) -> u32 {
+    let hand = *pocket | *board;
+    Eval::eval_n(&hand, hand.num_cards()).value
+}
+
+fn n_choose_k(n: usize, k: usize) -> usize {
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
 // Helpers needed in StdDeckCardMask:
 // - all_cards() -> 0..52 set
 // - from_card_index(usize) -> mask
@@ -200,4 +437,93 @@ mod tests {
         assert!(result.equity > 0.40);
         assert!(result.equity < 0.60);
     }
+
+    #[test]
+    fn test_vs_random_exact_river() {
+        use crate::deck::StdDeck;
+
+        // The nut straight only chops with the other AK combos.
+        let (hero, _) = StdDeck::string_to_mask("AhKc").unwrap();
+        let (board, _) = StdDeck::string_to_mask("QdJsTc4h2d").unwrap();
+        let res = equity_vs_random(&hero, 1, &board, &StdDeckCardMask::new(), 0).unwrap();
+        assert!(res.exact);
+        assert_eq!(res.samples, 990);
+        assert_eq!(res.ties, 9);
+        assert_eq!(res.losses, 0);
+        assert!((res.equity - (981.0 + 4.5) / 990.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_vs_random_multiway() {
+        use crate::deck::StdDeck;
+
+        let (aa, _) = StdDeck::string_to_mask("AsAd").unwrap();
+        let (board, dead) = (StdDeckCardMask::new(), StdDeckCardMask::new());
+        let heads_up = equity_vs_random(&aa, 1, &board, &dead, 20000).unwrap();
+        let five_way = equity_vs_random(&aa, 4, &board, &dead, 20000).unwrap();
+        assert!(!heads_up.exact);
+        // AA is ~85% heads-up and ~56% five-way.
+        assert!((heads_up.equity - 0.85).abs() < 0.02);
+        assert!((five_way.equity - 0.56).abs() < 0.03);
+        assert_eq!(five_way.wins + five_way.ties + five_way.losses, 20000);
+
+        assert!(equity_vs_random(&aa, 0, &board, &dead, 100).is_err());
+        assert!(equity_vs_random(&aa, 1, &aa, &dead, 100).is_err());
+    }
+
+    #[test]
+    fn test_chunked_progress_and_stop() {
+        use crate::deck::StdDeck;
+
+        let (aa, kk) = (
+            HandRange::from_str("AA").unwrap(),
+            HandRange::from_str("KK").unwrap(),
+        );
+        let board = StdDeckCardMask::new();
+        let mut reports = Vec::new();
+        let res = calculate_equity_chunked(&aa, &kk, &board, 120_000, |p| {
+            reports.push((p.done, p.total));
+            true
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            reports,
+            [(50_000, 120_000), (100_000, 120_000), (120_000, 120_000)]
+        );
+        assert_eq!(res.samples, 120_000);
+        assert!(
+            calculate_equity_chunked(&aa, &kk, &board, 120_000, |_| false)
+                .unwrap()
+                .is_none()
+        );
+
+        // The turn leaves 1035 opponent holdings times 44 rivers to enumerate.
+        let (hero, _) = StdDeck::string_to_mask("AhKc").unwrap();
+        let (board, _) = StdDeck::string_to_mask("QdJsTc4h").unwrap();
+        let dead = StdDeckCardMask::new();
+        let mut reports = Vec::new();
+        let res = equity_vs_random_chunked(&hero, 1, &board, &dead, 0, |p| {
+            reports.push((p.done, p.total));
+            true
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(res.samples, 1035 * 44);
+        assert_eq!(reports.last(), Some(&(45_540, 45_540)));
+        let exact = equity_vs_random(&hero, 1, &board, &dead, 0).unwrap();
+        assert_eq!(
+            (res.wins, res.ties, res.losses),
+            (exact.wins, exact.ties, exact.losses)
+        );
+
+        let mut calls = 0;
+        let stopped = equity_vs_random_chunked(&hero, 3, &board, &dead, 200_000, |_| {
+            calls += 1;
+            false
+        })
+        .unwrap();
+        assert!(stopped.is_none());
+        assert_eq!(calls, 1);
+    }
 }
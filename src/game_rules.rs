@@ -0,0 +1,699 @@
+//! Game variants described by a trait, and a registry of them by name.
+//!
+//! [`GameRules`] captures everything the generic enumeration engine
+//! ([`enum_rules_exhaustive`](crate::enumerate::enum_rules_exhaustive) and
+//! [`enum_rules_sample`](crate::enumerate::enum_rules_sample)) needs to know about a
+//! game: which cards are in play, how many cards players and the board hold, and how
+//! the high and low hands are evaluated. Every built-in [`Game`] implements it, and
+//! hooks in its faster specialised enumerators where it has them. Other crates can
+//! add their own games with [`register_game`]; front-ends look games up by name
+//! through [`GameRef`], so registered games work wherever a built-in one does:
+//!
+//! ```
+//! use std::sync::Arc;
+//! use poker_eval_rs::deck::StdDeckCardMask;
+//! use poker_eval_rs::errors::PokerError;
+//! use poker_eval_rs::evaluators::Eval;
+//! use poker_eval_rs::game_rules::{game_rules, register_game, EnumOrderingMode, GameRules};
+//! use poker_eval_rs::handval::HandVal;
+//!
+//! /// Hold'em with three hole cards, all of which may play.
+//! struct ThreeCardHoldem;
+//!
+//! impl GameRules for ThreeCardHoldem {
+//!     fn name(&self) -> &str {
+//!         "holdem3"
+//!     }
+//!     fn pocket_cards(&self) -> (usize, usize) {
+//!         (3, 3)
+//!     }
+//!     fn board_cards(&self) -> usize {
+//!         5
+//!     }
+//!     fn ordering_mode(&self) -> EnumOrderingMode {
+//!         EnumOrderingMode::Hi
+//!     }
+//!     fn eval_hi(
+//!         &self,
+//!         pocket: &StdDeckCardMask,
+//!         board: &StdDeckCardMask,
+//!     ) -> Result<Option<HandVal>, PokerError> {
+//!         let all = *pocket | *board;
+//!         Ok(Some(Eval::eval_n(&all, all.num_cards())))
+//!     }
+//! }
+//!
+//! register_game(Arc::new(ThreeCardHoldem)).unwrap();
+//! assert_eq!(game_rules("holdem3").unwrap().board_cards(), 5);
+//! assert_eq!(game_rules("omaha8").unwrap().pocket_cards(), (4, 4));
+//! ```
+
+use crate::deck::{Rank, StdDeck, StdDeckCardMask, STD_DECK_N_CARDS};
+use crate::enumdefs::{EnumResult, Game};
+use crate::errors::PokerError;
+use crate::evaluators::{
+    std_deck_lowball27_eval, std_deck_lowball8_eval, std_deck_lowball_eval, Eval, HandEvaluator,
+    OmahaHiEvaluator, OmahaHiLoEvaluator, ShortDeckEvaluator,
+};
+use crate::handval::HandVal;
+use crate::handval_low::{LowHandVal, LOW_HAND_VAL_NOTHING};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock, RwLock};
+
+pub use crate::enumord::EnumOrderingMode;
+
+/// The rules of a poker variant, as used by the generic enumeration engine.
+///
+/// Only the pot structure and the evaluators are required; the defaults describe a
+/// game dealt from a standard 52-card deck where every player ends up with the
+/// maximum number of pocket cards.
+pub trait GameRules: Send + Sync {
+    /// The registry key, in lowercase (e.g. `"holdem"`).
+    fn name(&self) -> &str;
+
+    /// A human-readable name; defaults to [`name`](Self::name).
+    fn description(&self) -> &str {
+        self.name()
+    }
+
+    /// The built-in [`Game`] these rules describe, if any.
+    fn game(&self) -> Option<Game> {
+        None
+    }
+
+    /// The cards in play.
+    fn deck(&self) -> StdDeckCardMask {
+        (0..STD_DECK_N_CARDS).fold(StdDeckCardMask::new(), |deck, c| {
+            deck | StdDeckCardMask::from_card_index(c)
+        })
+    }
+
+    /// The fewest and most pocket cards a player may be given.
+    fn pocket_cards(&self) -> (usize, usize);
+
+    /// The pocket cards each player holds at showdown; shorter pockets are dealt up
+    /// to this many. Defaults to the maximum of [`pocket_cards`](Self::pocket_cards).
+    fn showdown_pocket_cards(&self) -> usize {
+        self.pocket_cards().1
+    }
+
+    /// Number of community cards at showdown; 0 for games without a board.
+    fn board_cards(&self) -> usize;
+
+    /// Which pots are played for: high only, low only, or split high/low.
+    fn ordering_mode(&self) -> EnumOrderingMode;
+
+    /// Evaluates a complete high hand, or `None` if it cannot be made. Only called
+    /// when the ordering mode includes a high pot.
+    fn eval_hi(
+        &self,
+        _pocket: &StdDeckCardMask,
+        _board: &StdDeckCardMask,
+    ) -> Result<Option<HandVal>, PokerError> {
+        Ok(None)
+    }
+
+    /// Evaluates a complete low hand, or `None` if it does not qualify. Only called
+    /// when the ordering mode includes a low pot.
+    fn eval_lo(
+        &self,
+        _pocket: &StdDeckCardMask,
+        _board: &StdDeckCardMask,
+    ) -> Result<Option<LowHandVal>, PokerError> {
+        Ok(None)
+    }
+
+    /// Enumerates every deal with a path written for this game, or returns `None`
+    /// (the default) to use [`enum_rules_exhaustive`](crate::enumerate::enum_rules_exhaustive).
+    /// `result` has been cleared for the run.
+    fn enum_exhaustive_specialised(
+        &self,
+        _pockets: &[StdDeckCardMask],
+        _board: StdDeckCardMask,
+        _dead: StdDeckCardMask,
+        _result: &mut EnumResult,
+    ) -> Option<Result<(), PokerError>> {
+        None
+    }
+
+    /// Samples `niter` deals with a path written for this game, or returns `None`
+    /// (the default) to use [`enum_rules_sample`](crate::enumerate::enum_rules_sample).
+    fn enum_sample_specialised(
+        &self,
+        _pockets: &[StdDeckCardMask],
+        _board: StdDeckCardMask,
+        _dead: StdDeckCardMask,
+        _niter: usize,
+        _result: &mut EnumResult,
+    ) -> Option<Result<(), PokerError>> {
+        None
+    }
+}
+
+/// The built-in games.
+impl GameRules for Game {
+    fn name(&self) -> &str {
+        self.as_str()
+    }
+
+    fn description(&self) -> &str {
+        self.game_params().map_or("", |p| p.name)
+    }
+
+    fn game(&self) -> Option<Game> {
+        Some(*self)
+    }
+
+    fn deck(&self) -> StdDeckCardMask {
+        (0..STD_DECK_N_CARDS)
+            .filter(|&c| *self != Game::ShortDeck || StdDeck::rank(c) >= Rank::SIX)
+            .fold(StdDeckCardMask::new(), |deck, c| {
+                deck | StdDeckCardMask::from_card_index(c)
+            })
+    }
+
+    fn pocket_cards(&self) -> (usize, usize) {
+        self.game_params()
+            .map_or((0, 0), |p| (p.minpocket as usize, p.maxpocket as usize))
+    }
+
+    fn board_cards(&self) -> usize {
+        self.game_params().map_or(0, |p| p.maxboard as usize)
+    }
+
+    fn ordering_mode(&self) -> EnumOrderingMode {
+        match self.game_params() {
+            Some(p) if p.hashipot == 1 && p.haslopot == 1 => EnumOrderingMode::Hilo,
+            Some(p) if p.haslopot == 1 => EnumOrderingMode::Lo,
+            Some(_) => EnumOrderingMode::Hi,
+            None => EnumOrderingMode::None,
+        }
+    }
+
+    fn eval_hi(
+        &self,
+        pocket: &StdDeckCardMask,
+        board: &StdDeckCardMask,
+    ) -> Result<Option<HandVal>, PokerError> {
+        match builtin(*self).and_then(|b| b.hi) {
+            Some(eval) => eval(pocket, board),
+            None => Ok(None),
+        }
+    }
+
+    fn eval_lo(
+        &self,
+        pocket: &StdDeckCardMask,
+        board: &StdDeckCardMask,
+    ) -> Result<Option<LowHandVal>, PokerError> {
+        match builtin(*self).and_then(|b| b.lo) {
+            Some(eval) => eval(pocket, board),
+            None => Ok(None),
+        }
+    }
+
+    fn enum_exhaustive_specialised(
+        &self,
+        pockets: &[StdDeckCardMask],
+        board: StdDeckCardMask,
+        dead: StdDeckCardMask,
+        result: &mut EnumResult,
+    ) -> Option<Result<(), PokerError>> {
+        match builtin(*self)?.exhaustive {
+            Some(enumerate) => Some(enumerate(result, pockets, board, dead)),
+            // Stud and draw games would have to enumerate every missing pocket card.
+            None if self.board_cards() == 0 => Some(Err(PokerError::UnsupportedGameType)),
+            None => None,
+        }
+    }
+
+    fn enum_sample_specialised(
+        &self,
+        pockets: &[StdDeckCardMask],
+        board: StdDeckCardMask,
+        dead: StdDeckCardMask,
+        niter: usize,
+        result: &mut EnumResult,
+    ) -> Option<Result<(), PokerError>> {
+        let sample = builtin(*self)?.sample?;
+        Some(sample(result, pockets, board, dead, niter))
+    }
+}
+
+type HiEval = fn(&StdDeckCardMask, &StdDeckCardMask) -> Result<Option<HandVal>, PokerError>;
+type LoEval = fn(&StdDeckCardMask, &StdDeckCardMask) -> Result<Option<LowHandVal>, PokerError>;
+type Exhaustive = fn(
+    &mut EnumResult,
+    &[StdDeckCardMask],
+    StdDeckCardMask,
+    StdDeckCardMask,
+) -> Result<(), PokerError>;
+type Sample = fn(
+    &mut EnumResult,
+    &[StdDeckCardMask],
+    StdDeckCardMask,
+    StdDeckCardMask,
+    usize,
+) -> Result<(), PokerError>;
+
+/// How a built-in game evaluates hands, and its specialised enumerators. Games
+/// without an exhaustive enumerator go through the generic engine.
+struct Builtin {
+    game: Game,
+    hi: Option<HiEval>,
+    lo: Option<LoEval>,
+    exhaustive: Option<Exhaustive>,
+    sample: Option<Sample>,
+}
+
+/// One entry per game, in [`Game::ALL`] order.
+static BUILTINS: [Builtin; 17] = [
+    Builtin {
+        game: Game::Holdem,
+        hi: Some(hi_best_five),
+        lo: None,
+        exhaustive: Some(|r, p, b, d| {
+            r.exhaustive_holdem_evaluation(p, b, d, p.len(), b.num_cards())
+        }),
+        sample: Some(|r, p, b, d, n| r.simulate_holdem_game(p, b, d, p.len(), b.num_cards(), n)),
+    },
+    Builtin {
+        game: Game::Holdem8,
+        hi: Some(hi_best_five),
+        lo: Some(lo_eight_or_better),
+        exhaustive: Some(|r, p, b, d| {
+            r.exhaustive_holdem8_evaluation(p, b, d, p.len(), b.num_cards())
+        }),
+        sample: Some(|r, p, b, d, n| r.simulate_holdem8_game(p, b, d, p.len(), b.num_cards(), n)),
+    },
+    Builtin {
+        game: Game::Omaha,
+        hi: Some(hi_omaha),
+        lo: None,
+        exhaustive: Some(|r, p, b, d| {
+            r.exhaustive_omaha_evaluation(p, b, d, p.len(), b.num_cards())
+        }),
+        sample: Some(|r, p, b, d, n| r.simulate_omaha_game(p, b, d, p.len(), b.num_cards(), n)),
+    },
+    Builtin {
+        game: Game::Omaha5,
+        hi: Some(hi_omaha),
+        lo: None,
+        exhaustive: None,
+        sample: Some(|r, p, b, d, n| r.simulate_omaha5_game(p, b, d, p.len(), b.num_cards(), n)),
+    },
+    Builtin {
+        game: Game::Omaha6,
+        hi: Some(hi_omaha),
+        lo: None,
+        exhaustive: None,
+        sample: Some(|r, p, b, d, n| r.simulate_omaha6_game(p, b, d, p.len(), b.num_cards(), n)),
+    },
+    Builtin {
+        game: Game::Omaha8,
+        hi: Some(hi_omaha),
+        lo: Some(lo_omaha8),
+        exhaustive: None,
+        sample: Some(|r, p, b, d, n| r.simulate_omaha8_game(p, b, d, p.len(), b.num_cards(), n)),
+    },
+    Builtin {
+        game: Game::Omaha85,
+        hi: Some(hi_omaha),
+        lo: Some(lo_omaha8),
+        exhaustive: None,
+        sample: Some(|r, p, b, d, n| r.simulate_omaha8_game(p, b, d, p.len(), b.num_cards(), n)),
+    },
+    Builtin {
+        game: Game::Stud7,
+        hi: Some(hi_best_five),
+        lo: None,
+        exhaustive: None,
+        sample: Some(|r, p, _, d, n| r.simulate_stud_game(p, d, p.len(), n)),
+    },
+    Builtin {
+        game: Game::Stud78,
+        hi: Some(hi_best_five),
+        lo: Some(lo_eight_or_better),
+        exhaustive: None,
+        sample: Some(|r, p, _, d, n| r.simulate_stud8_game(p, d, p.len(), n)),
+    },
+    Builtin {
+        game: Game::Stud7nsq,
+        hi: Some(hi_best_five),
+        lo: Some(lo_ace_to_five),
+        exhaustive: None,
+        sample: Some(|r, p, _, d, n| r.simulate_studnsq_game(p, d, p.len(), n)),
+    },
+    Builtin {
+        game: Game::Razz,
+        hi: None,
+        lo: Some(lo_ace_to_five),
+        exhaustive: None,
+        sample: Some(|r, p, _, d, n| r.simulate_razz_game(p, d, p.len(), n)),
+    },
+    Builtin {
+        game: Game::Draw5,
+        hi: Some(hi_best_five),
+        lo: None,
+        exhaustive: None,
+        sample: Some(|r, p, _, d, n| r.simulate_draw_game(p, d, p.len(), n)),
+    },
+    Builtin {
+        game: Game::Draw58,
+        hi: Some(hi_best_five),
+        lo: Some(lo_eight_or_better),
+        exhaustive: None,
+        sample: Some(|r, p, _, d, n| r.simulate_draw8_game(p, d, p.len(), n)),
+    },
+    Builtin {
+        game: Game::Draw5nsq,
+        hi: Some(hi_best_five),
+        lo: Some(lo_ace_to_five),
+        exhaustive: None,
+        sample: Some(|r, p, _, d, n| r.simulate_drawnsq_game(p, d, p.len(), n)),
+    },
+    Builtin {
+        game: Game::Lowball,
+        hi: None,
+        lo: Some(lo_ace_to_five),
+        exhaustive: None,
+        sample: Some(|r, p, _, d, n| r.simulate_lowball_game(p, d, p.len(), n)),
+    },
+    Builtin {
+        game: Game::Lowball27,
+        hi: None,
+        lo: Some(lo_deuce_to_seven),
+        exhaustive: None,
+        sample: Some(|r, p, _, d, n| r.simulate_lowball27_game(p, d, p.len(), n)),
+    },
+    Builtin {
+        game: Game::ShortDeck,
+        hi: Some(hi_short_deck),
+        lo: None,
+        exhaustive: Some(|r, p, b, d| {
+            r.exhaustive_short_deck_evaluation(p, b, d, p.len(), b.num_cards())
+        }),
+        sample: Some(|r, p, b, d, n| {
+            r.simulate_short_deck_game(p, b, d, p.len(), b.num_cards(), n)
+        }),
+    },
+];
+
+fn builtin(game: Game) -> Option<&'static Builtin> {
+    BUILTINS.get(game as usize).filter(|b| b.game == game)
+}
+
+/// The best five of all the cards; `None` below five cards.
+fn hi_best_five(
+    pocket: &StdDeckCardMask,
+    board: &StdDeckCardMask,
+) -> Result<Option<HandVal>, PokerError> {
+    let all = *pocket | *board;
+    let n = all.num_cards();
+    Ok((n >= 5).then(|| Eval::eval_n(&all, n)))
+}
+
+fn hi_omaha(
+    pocket: &StdDeckCardMask,
+    board: &StdDeckCardMask,
+) -> Result<Option<HandVal>, PokerError> {
+    OmahaHiEvaluator::evaluate_hand(pocket, board)
+}
+
+fn hi_short_deck(
+    pocket: &StdDeckCardMask,
+    board: &StdDeckCardMask,
+) -> Result<Option<HandVal>, PokerError> {
+    ShortDeckEvaluator::evaluate_hand(pocket, board).map(Some)
+}
+
+fn qualifying(lo: LowHandVal) -> Option<LowHandVal> {
+    (lo.value != LOW_HAND_VAL_NOTHING).then_some(lo)
+}
+
+fn lo_omaha8(
+    pocket: &StdDeckCardMask,
+    board: &StdDeckCardMask,
+) -> Result<Option<LowHandVal>, PokerError> {
+    let (_, lo) = OmahaHiLoEvaluator::evaluate_hand(pocket, board)?;
+    Ok(lo.and_then(qualifying))
+}
+
+/// The best 8-or-better low of all the cards; `None` below five cards.
+fn lo_eight_or_better(
+    pocket: &StdDeckCardMask,
+    board: &StdDeckCardMask,
+) -> Result<Option<LowHandVal>, PokerError> {
+    let all = *pocket | *board;
+    let n = all.num_cards();
+    Ok((n >= 5)
+        .then(|| std_deck_lowball8_eval(&all, n))
+        .flatten()
+        .and_then(qualifying))
+}
+
+fn lo_ace_to_five(
+    pocket: &StdDeckCardMask,
+    board: &StdDeckCardMask,
+) -> Result<Option<LowHandVal>, PokerError> {
+    let all = *pocket | *board;
+    let n = all.num_cards();
+    Ok((n >= 5)
+        .then(|| std_deck_lowball_eval(&all, n))
+        .and_then(qualifying))
+}
+
+fn lo_deuce_to_seven(
+    pocket: &StdDeckCardMask,
+    board: &StdDeckCardMask,
+) -> Result<Option<LowHandVal>, PokerError> {
+    let all = *pocket | *board;
+    let n = all.num_cards();
+    Ok((n >= 5).then(|| LowHandVal {
+        value: std_deck_lowball27_eval(&all, n).value,
+    }))
+}
+
+type Registry = RwLock<HashMap<String, Arc<dyn GameRules>>>;
+
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let games = Game::ALL
+            .iter()
+            .map(|&g| (g.as_str().to_string(), Arc::new(g) as Arc<dyn GameRules>))
+            .collect();
+        RwLock::new(games)
+    })
+}
+
+/// Adds a game to the registry under its lowercased [`GameRules::name`].
+///
+/// Fails with [`PokerError::InvalidInput`] if the name is empty or already taken;
+/// built-in games cannot be replaced.
+pub fn register_game(rules: Arc<dyn GameRules>) -> Result<(), PokerError> {
+    let key = rules.name().to_lowercase();
+    if key.is_empty() {
+        return Err(PokerError::InvalidInput(
+            "Game name must not be empty".to_string(),
+        ));
+    }
+    let mut games = registry()
+        .write()
+        .map_err(|_| PokerError::InternalError("Game registry poisoned".to_string()))?;
+    if games.contains_key(&key) {
+        return Err(PokerError::InvalidInput(format!(
+            "Game {} is already registered",
+            key
+        )));
+    }
+    games.insert(key, rules);
+    Ok(())
+}
+
+/// Looks up a registered game by name, ignoring case.
+pub fn game_rules(name: &str) -> Option<Arc<dyn GameRules>> {
+    let games = registry().read().ok()?;
+    games.get(&name.trim().to_lowercase()).cloned()
+}
+
+/// Names of every registered game, sorted.
+pub fn registered_games() -> Vec<String> {
+    let mut names: Vec<String> = registry()
+        .read()
+        .map(|games| games.keys().cloned().collect())
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Shared rules of a registered game, as taken by [`EquityQuery`](crate::query::EquityQuery)
+/// and the bindings.
+///
+/// Compares and serializes by its lowercase name, and parses from any name in the
+/// registry:
+///
+/// ```
+/// use poker_eval_rs::enumdefs::Game;
+/// use poker_eval_rs::game_rules::GameRef;
+///
+/// let game: GameRef = "Omaha8".parse().unwrap();
+/// assert_eq!(game, Game::Omaha8);
+/// assert_eq!(game.pocket_cards(), (4, 4));
+/// assert!("go".parse::<GameRef>().is_err());
+/// ```
+#[derive(Clone)]
+pub struct GameRef(Arc<dyn GameRules>);
+
+impl GameRef {
+    /// Wraps rules that need not be registered.
+    pub fn new(rules: Arc<dyn GameRules>) -> Self {
+        Self(rules)
+    }
+
+    /// Looks up a registered game by name, ignoring case.
+    pub fn from_name(name: &str) -> Result<Self, PokerError> {
+        game_rules(name)
+            .map(Self)
+            .ok_or_else(|| PokerError::InvalidInput(format!("Unsupported game variant: {}", name)))
+    }
+}
+
+impl From<Game> for GameRef {
+    fn from(game: Game) -> Self {
+        Self(Arc::new(game))
+    }
+}
+
+impl Deref for GameRef {
+    type Target = dyn GameRules;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl PartialEq for GameRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.name().eq_ignore_ascii_case(other.name())
+    }
+}
+
+impl Eq for GameRef {}
+
+impl PartialEq<Game> for GameRef {
+    fn eq(&self, other: &Game) -> bool {
+        self.game() == Some(*other)
+    }
+}
+
+impl fmt::Debug for GameRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("GameRef").field(&self.name()).finish()
+    }
+}
+
+impl fmt::Display for GameRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name().to_lowercase())
+    }
+}
+
+impl FromStr for GameRef {
+    type Err = PokerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s)
+    }
+}
+
+impl Serialize for GameRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for GameRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_rules_match_game_params() {
+        for game in Game::ALL {
+            let params = game.game_params().unwrap();
+            let rules = game_rules(game.as_str()).unwrap();
+            assert_eq!(rules.game(), Some(game));
+            assert_eq!(rules.description(), params.name);
+            assert_eq!(rules.board_cards(), params.maxboard as usize);
+            assert_eq!(
+                rules.pocket_cards(),
+                (params.minpocket as usize, params.maxpocket as usize)
+            );
+        }
+        assert_eq!(Game::ShortDeck.deck().num_cards(), 36);
+        assert_eq!(Game::Razz.ordering_mode(), EnumOrderingMode::Lo);
+        assert_eq!(Game::Stud78.ordering_mode(), EnumOrderingMode::Hilo);
+        assert!(registered_games().contains(&"lowball27".to_string()));
+    }
+
+    #[test]
+    fn test_builtin_table_matches_games() {
+        assert_eq!(BUILTINS.len(), Game::ALL.len());
+        for game in Game::ALL {
+            let params = game.game_params().unwrap();
+            let builtin = builtin(game).unwrap();
+            assert_eq!(builtin.hi.is_some(), params.hashipot == 1, "{}", game);
+            assert_eq!(builtin.lo.is_some(), params.haslopot == 1, "{}", game);
+            assert!(builtin.sample.is_some(), "{}", game);
+        }
+        assert!(builtin(Game::NumGames).is_none());
+    }
+
+    #[test]
+    fn test_game_ref_names() {
+        let game = GameRef::from_name(" HOLDEM ").unwrap();
+        assert_eq!(game, GameRef::from(Game::Holdem));
+        assert_eq!(game.to_string(), "holdem");
+        assert_eq!(serde_json::to_string(&game).unwrap(), r#""holdem""#);
+        assert!(matches!(
+            GameRef::from_name("go"),
+            Err(PokerError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_register_rejects_duplicates() {
+        struct Dummy(&'static str);
+        impl GameRules for Dummy {
+            fn name(&self) -> &str {
+                self.0
+            }
+            fn pocket_cards(&self) -> (usize, usize) {
+                (2, 2)
+            }
+            fn board_cards(&self) -> usize {
+                0
+            }
+            fn ordering_mode(&self) -> EnumOrderingMode {
+                EnumOrderingMode::Hi
+            }
+        }
+
+        assert!(register_game(Arc::new(Dummy("Holdem"))).is_err());
+        assert!(register_game(Arc::new(Dummy(""))).is_err());
+        register_game(Arc::new(Dummy("Dummy-Test"))).unwrap();
+        assert!(register_game(Arc::new(Dummy("dummy-test"))).is_err());
+        assert_eq!(game_rules("DUMMY-TEST").unwrap().name(), "Dummy-Test");
+    }
+}
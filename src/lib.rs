@@ -35,19 +35,41 @@
 //! ```bash
 //! maturin develop --features python
 //! ```
+//!
+//! ## C API
+//!
+//! The `capi` feature exports an `extern "C"` API declared in `include/poker_eval_rs.h`;
+//! the `capi` module documents the conventions.
+//!
+//! ## HTTP Server
+//!
+//! The `server` feature adds a small HTTP/JSON server over the handlers in
+//! [`service`], run with `poker_eval_rs_cli serve`.
 
 pub mod board;
+pub mod classify;
 pub mod combinations;
+pub mod decision;
 pub mod deck;
 pub mod enumdefs;
 pub mod enumerate;
 pub(crate) mod enumord;
 pub mod errors;
 pub mod evaluators;
+pub mod game_rules;
 pub mod handval;
 pub mod handval_low;
+pub mod nuts;
+pub mod pot;
+pub mod query;
 pub mod range;
+pub mod range_advantage;
+pub mod range_filter;
+pub mod range_format;
+pub mod ranking;
 pub mod rules;
+pub mod runouts;
+pub mod service;
 pub(crate) mod tables;
 
 // ===== Python Bindings (optional, enabled with "python" feature) =====
@@ -60,3 +82,11 @@ pub use python_bindings::poker_eval_rs;
 // ===== WASM Bindings (optional, enabled with "wasm" feature) =====
 #[cfg(feature = "wasm")]
 pub mod wasm_bindings;
+
+// ===== C ABI (optional, enabled with "capi" feature) =====
+#[cfg(feature = "capi")]
+pub mod capi;
+
+// ===== HTTP server (optional, enabled with "server" feature) =====
+#[cfg(feature = "server")]
+pub mod server;
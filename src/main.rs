@@ -3,16 +3,32 @@
 //! CLI for evaluating poker hands and calculating equity between hands.
 
 use clap::{Parser, Subcommand};
-use poker_eval_rs::board::BoardTexture;
+use poker_eval_rs::board::{
+    calculate_game_outs, calculate_outs_vs_range, calculate_outs_with_dead, BoardTexture,
+};
+use poker_eval_rs::classify::{HandCategory, HAND_CATEGORIES};
+use poker_eval_rs::decision::{decision_ev, DecisionSpot, Opponent, OptionEv};
 use poker_eval_rs::deck::StdDeck;
 use poker_eval_rs::deck::StdDeckCardMask;
 use poker_eval_rs::enumdefs::{EnumResult, Game, SampleType, ENUM_MAXPLAYERS};
 use poker_eval_rs::enumerate::{enum_exhaustive, enum_sample};
-use poker_eval_rs::evaluators::range_equity::calculate_equity;
+use poker_eval_rs::errors::PokerError;
+use poker_eval_rs::evaluators::range_equity::{calculate_equity, equity_vs_random};
 use poker_eval_rs::evaluators::{
     Eval, HandEvaluator, LowballEvaluator, OmahaHiEvaluator, OmahaHiLoEvaluator,
 };
+use poker_eval_rs::game_rules::GameRef;
+use poker_eval_rs::nuts::nut_rank;
+use poker_eval_rs::query::{EquityMode, EquityQuery, PlayerEquity};
 use poker_eval_rs::range::HandRange;
+use poker_eval_rs::range_advantage::{range_advantage, RangeReport};
+use poker_eval_rs::range_filter::{filter_range, FilterContext};
+use poker_eval_rs::range_format::RangeFormat;
+use poker_eval_rs::rules::HAND_TYPE_NAMES;
+use poker_eval_rs::runouts::{next_card_equity, GroupEquity};
+#[cfg(feature = "server")]
+use poker_eval_rs::server;
+use poker_eval_rs::service::{batch, Limits};
 use serde::Serialize;
 use std::str::FromStr;
 
@@ -61,6 +77,18 @@ enum Commands {
         #[arg(short, long, default_value = "100000")]
         iterations: usize,
 
+        /// Seed for Monte Carlo sampling, for reproducible results
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Play a single Hold'em hand against N random hands
+        #[arg(long, value_name = "N")]
+        vs_random: Option<usize>,
+
+        /// Show each player's final hand-type distribution
+        #[arg(long)]
+        hand_types: bool,
+
         /// Output results in JSON format
         #[arg(long)]
         json: bool,
@@ -92,6 +120,256 @@ enum Commands {
         /// Board cards (e.g., "AhKhQh")
         board: String,
     },
+
+    /// Compare two ranges on a board: equity, equity buckets, nut share and hand categories
+    Advantage {
+        /// First range (e.g., "TT+,AK")
+        range1: String,
+
+        /// Second range (e.g., "22-99,ATs+,KQs")
+        range2: String,
+
+        /// Board cards, 3 to 5 (e.g., "Kh8c3d")
+        #[arg(short, long)]
+        board: String,
+
+        /// Dead/removed cards
+        #[arg(short, long, default_value = "")]
+        dead: String,
+
+        /// Number of Monte Carlo iterations
+        #[arg(short, long, default_value = "100000")]
+        iterations: usize,
+
+        /// Output results in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Calculate outs, optionally against an opponent hand or range
+    Outs {
+        /// Pocket cards (e.g., "AsJs")
+        pocket: String,
+
+        /// Board cards, flop or turn (e.g., "Ks7s2d"); empty for stud and draw games
+        #[arg(short, long, default_value = "")]
+        board: String,
+
+        /// Opponent hand or range (e.g., "KdQc" or "KK+,AK"), Hold'em only
+        #[arg(short, long)]
+        vs: Option<String>,
+
+        /// Dead/removed cards
+        #[arg(short, long, default_value = "")]
+        dead: String,
+
+        /// Game variant (holdem, omaha8, shortdeck, razz, etc.)
+        #[arg(short, long, default_value = "holdem")]
+        game: String,
+
+        /// Output results in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Chip EV of calling, folding or jamming against one or more opponents
+    Decision {
+        /// Hero's hole cards (e.g., "AhKh")
+        hand: String,
+
+        /// Board cards (e.g., "Qh7h2c")
+        #[arg(short, long, default_value = "")]
+        board: String,
+
+        /// Dead/removed cards
+        #[arg(short, long, default_value = "")]
+        dead: String,
+
+        /// Chips in the pot before this street's bets
+        #[arg(long, default_value_t = 0.0)]
+        pot: f64,
+
+        /// Hero's stack
+        #[arg(long)]
+        stack: f64,
+
+        /// Opponent range, once per opponent (e.g., --vs "QQ+,AK" --vs "22+")
+        #[arg(long = "vs", required = true)]
+        ranges: Vec<String>,
+
+        /// Each opponent's bet, in the same order as --vs
+        #[arg(long = "bet", required = true)]
+        bets: Vec<f64>,
+
+        /// Each opponent's stack behind their bet (default: hero's stack minus the bet)
+        #[arg(long = "villain-stack")]
+        stacks: Vec<f64>,
+
+        /// Chance that every opponent folds to a jam; adds the jam EV
+        #[arg(long)]
+        fold_freq: Option<f64>,
+
+        /// Number of Monte Carlo iterations
+        #[arg(short, long, default_value = "100000")]
+        iterations: usize,
+
+        /// Output results in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show each player's equity after every possible next card (flop or turn)
+    Runouts {
+        /// Player hands separated by spaces (e.g., "AhKh" "7c7d")
+        #[arg(required = true)]
+        hands: Vec<String>,
+
+        /// Board cards, flop or turn (e.g., "Th9h2c")
+        #[arg(short, long)]
+        board: String,
+
+        /// Dead/removed cards
+        #[arg(short, long, default_value = "")]
+        dead: String,
+
+        /// Game variant (holdem, holdem8, omaha, shortdeck)
+        #[arg(short, long, default_value = "holdem")]
+        game: String,
+
+        /// Output results in JSON format
+        #[arg(long, conflicts_with = "csv")]
+        json: bool,
+
+        /// Output one CSV row per next card
+        #[arg(long)]
+        csv: bool,
+    },
+
+    /// Rank a hand among all possible holdings on the board (1 = the nuts)
+    Nuts {
+        /// Pocket cards (e.g., "AhKc", or 4 cards for Omaha)
+        pocket: String,
+
+        /// Board cards, 3 to 5 (e.g., "QdJsTc4h2d")
+        #[arg(short, long)]
+        board: String,
+
+        /// Dead/removed cards
+        #[arg(short, long, default_value = "")]
+        dead: String,
+
+        /// Maximum number of better hands to list
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
+
+        /// Output results in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Filter a range with a query (e.g., "flushdraw & !pair", "equity > 0.6 vs BTN")
+    Filter {
+        /// Range to filter (e.g., "22+,A2s+,KTo+")
+        range: String,
+
+        /// Filter query
+        #[arg(short, long)]
+        query: String,
+
+        /// Board cards
+        #[arg(short, long, default_value = "")]
+        board: String,
+
+        /// Named ranges for equity queries, as NAME=RANGE (e.g., "BTN=22+,A2s+")
+        #[arg(short, long = "range")]
+        ranges: Vec<String>,
+
+        /// Monte Carlo iterations per hand for equity queries
+        #[arg(short, long, default_value_t = 2000)]
+        iterations: usize,
+
+        /// Output results in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Convert a range between formats (standard, pio, gtoplus, flopzilla)
+    Convert {
+        /// Range to convert; omit to read it from --input
+        range: Option<String>,
+
+        /// Read the range from a file
+        #[arg(short, long)]
+        input: Option<String>,
+
+        /// Input format (auto, standard, pio, gtoplus, flopzilla)
+        #[arg(short, long, default_value = "auto")]
+        from: String,
+
+        /// Output format (standard, pio, gtoplus, flopzilla)
+        #[arg(short, long, default_value = "standard")]
+        to: String,
+
+        /// Write the converted range to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Enumerate with the legacy pokenum syntax and output format
+    /// (e.g. `pokenum -h As Ad - Kh Kd -- 2c 3c 4c / 5d`)
+    #[command(disable_help_flag = true)]
+    Pokenum {
+        /// pokenum arguments; run with no arguments for the usage
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Answer newline-delimited JSON queries, one JSON result line per query
+    /// (e.g. `{"op": "equity", "game": "holdem", "players": ["AsAd", "KsKd"]}`)
+    Batch {
+        /// File of queries; stdin when omitted or "-"
+        input: Option<String>,
+
+        /// Write the results to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Seconds a query may run before it fails (0: no limit)
+        #[arg(long, default_value_t = 0)]
+        timeout: u64,
+    },
+
+    /// Serve evaluation, equity, range equity, outs and texture as HTTP/JSON endpoints
+    #[cfg(feature = "server")]
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        /// Port to listen on
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+
+        /// Largest request body accepted, in bytes
+        #[arg(long, default_value_t = server::DEFAULT_MAX_BODY_BYTES)]
+        max_body: usize,
+
+        /// Most Monte Carlo iterations or exhaustive boards a request may ask for
+        #[arg(long, default_value_t = poker_eval_rs::service::DEFAULT_MAX_ITERATIONS)]
+        max_iterations: usize,
+
+        /// Seconds a calculation may run before it is stopped (0: no limit)
+        #[arg(long, default_value_t = server::DEFAULT_TIMEOUT.as_secs())]
+        timeout: u64,
+
+        /// Number of threads answering requests
+        #[arg(long, default_value_t = server::DEFAULT_WORKERS)]
+        workers: usize,
+
+        /// Requests that may wait for a free worker before the server answers 503
+        #[arg(long, default_value_t = server::DEFAULT_BACKLOG)]
+        backlog: usize,
+    },
 }
 
 #[derive(Serialize)]
@@ -104,38 +382,199 @@ struct EquityResultOutput {
 
 #[derive(Serialize)]
 struct PlayerStat {
+    #[serde(flatten)]
+    equity: PlayerEquity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hand_types: Option<Vec<HandTypeStat>>,
+}
+
+impl PlayerStat {
+    /// A high-only stat from win/tie/loss counts.
+    fn hi(hand: &str, wins: usize, ties: usize, losses: usize, ev: f64) -> Self {
+        let total = (wins + ties + losses).max(1) as f64;
+        Self {
+            equity: PlayerEquity {
+                hand: hand.to_string(),
+                win_pct: wins as f64 / total * 100.0,
+                tie_pct: ties as f64 / total * 100.0,
+                lose_pct: losses as f64 / total * 100.0,
+                ev,
+                scoop_pct: None,
+                win_lo_pct: None,
+                tie_lo_pct: None,
+                lose_lo_pct: None,
+            },
+            hand_types: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct HandTypeStat {
+    hand_type: String,
+    final_pct: f64,
+    won_pct: f64,
+    lost_pct: f64,
+    /// Most frequent hand type this one lost to
+    lost_to: Option<String>,
+}
+
+#[derive(Serialize)]
+struct VsRandomOutput {
+    game: String,
+    board: String,
+    opponents: usize,
+    samples: usize,
+    exact: bool,
+    hero: PlayerStat,
+}
+
+#[derive(Serialize)]
+struct DecisionOutput {
     hand: String,
-    win_pct: f64,
-    tie_pct: f64,
-    lose_pct: f64,
-    scoop_pct: Option<f64>,
+    board: String,
+    to_call: f64,
+    required_equity: f64,
+    equity: f64,
+    exact: bool,
+    fold_ev: f64,
+    call: OptionOutput,
+    jam: Option<OptionOutput>,
+}
+
+#[derive(Serialize)]
+struct OptionOutput {
+    risk: f64,
     ev: f64,
+    pots: Vec<PotOutput>,
+}
+
+#[derive(Serialize)]
+struct PotOutput {
+    amount: f64,
+    players: usize,
+    equity: f64,
+}
+
+#[derive(Serialize)]
+struct AdvantageOutput {
+    board: String,
+    texture: Vec<&'static str>,
+    equity: f64,
+    ranges: Vec<AdvantageRangeOutput>,
+}
+
+#[derive(Serialize)]
+struct AdvantageRangeOutput {
+    range: String,
+    combos: f64,
+    equity: f64,
+    /// Share of the range in equity buckets 0-20%, 20-40%, ..., 80-100%
+    equity_buckets: Vec<f64>,
+    nut_combos: f64,
+    nut_share: f64,
+    categories: Vec<CategoryShare>,
+}
+
+#[derive(Serialize)]
+struct CategoryShare {
+    category: &'static str,
+    share: f64,
+}
+
+#[derive(Serialize)]
+struct RunoutsOutput {
+    game: String,
+    board: String,
+    hands: Vec<String>,
+    equity: Vec<f64>,
+    /// 1-based player number, or none when tied
+    leader: Option<usize>,
+    cards: Vec<RunoutCardOutput>,
+    flips: Vec<String>,
+    by_suit: Vec<RunoutGroupOutput>,
+    by_rank: Vec<RunoutGroupOutput>,
+}
+
+#[derive(Serialize)]
+struct RunoutCardOutput {
+    card: String,
+    equity: Vec<f64>,
+    leader: Option<usize>,
+    flips: bool,
+}
+
+#[derive(Serialize)]
+struct RunoutGroupOutput {
+    group: String,
+    cards: usize,
+    equity: Vec<f64>,
+}
+
+#[derive(Serialize)]
+struct OutsOutput {
+    game: String,
+    pocket: String,
+    board: String,
+    opponent: Option<String>,
+    current_win_pct: Option<f64>,
+    current_tie_pct: Option<f64>,
+    outs_by_type: Vec<(String, Vec<String>)>,
+    low_outs: Vec<String>,
+    counterfeits: Vec<String>,
+    clean_outs: Vec<String>,
+    tie_outs: Vec<String>,
+    tainted_outs: Vec<String>,
+    discounted_outs: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct NutClassOutput {
+    hand: String,
+    example: String,
+    combos: usize,
+    blocked: usize,
+}
+
+#[derive(Serialize)]
+struct NutsOutput {
+    pocket: String,
+    board: String,
+    hand: String,
+    nut_rank: usize,
+    nut_rank_unblocked: usize,
+    beat_combos: usize,
+    tie_combos: usize,
+    blocked_beat_combos: usize,
+    total_combos: usize,
+    better: Vec<NutClassOutput>,
+}
+
+#[derive(Serialize)]
+struct FilterOutput {
+    query: String,
+    board: String,
+    combos: usize,
+    total_combos: usize,
+    weight: f64,
+    range: String,
+    hands: Vec<(String, f64)>,
 }
 
 fn parse_game(game_str: &str) -> Result<Game, String> {
-    match game_str.to_lowercase().as_str() {
-        "holdem" => Ok(Game::Holdem),
-        "holdem8" => Ok(Game::Holdem8),
-        "omaha" => Ok(Game::Omaha),
-        "omaha5" => Ok(Game::Omaha5),
-        "omaha6" => Ok(Game::Omaha6),
-        "omaha8" => Ok(Game::Omaha8),
-        "omaha85" => Ok(Game::Omaha85),
-        "stud7" => Ok(Game::Stud7),
-        "stud78" => Ok(Game::Stud78),
-        "stud7nsq" => Ok(Game::Stud7nsq),
-        "razz" => Ok(Game::Razz),
-        "draw5" => Ok(Game::Draw5),
-        "draw58" => Ok(Game::Draw58),
-        "draw5nsq" => Ok(Game::Draw5nsq),
-        "lowball" => Ok(Game::Lowball),
-        "lowball27" => Ok(Game::Lowball27),
-        "shortdeck" => Ok(Game::ShortDeck),
-        _ => Err(format!("Unsupported game variant: {}", game_str)),
-    }
+    game_str.parse().map_err(|e: PokerError| e.to_string())
 }
 
 fn main() {
+    // Installed or symlinked as `pokenum`, behave like the original tool.
+    let mut args = std::env::args();
+    let argv0 = args.next().unwrap_or_default();
+    let program = std::path::Path::new(&argv0).file_stem();
+    if program.is_some_and(|p| p == "pokenum") {
+        cmd_pokenum(&args.collect::<Vec<_>>());
+        return;
+    }
+
     let cli = Cli::parse();
 
     match cli.command {
@@ -147,15 +586,127 @@ fn main() {
             game,
             monte_carlo,
             iterations,
+            seed,
+            vs_random,
+            hand_types,
             json,
-        } => {
-            cmd_equity(&hands, &board, &dead, &game, monte_carlo, iterations, json);
-        }
+        } => match vs_random {
+            Some(n) => cmd_equity_vs_random(&hands, &board, &dead, &game, n, iterations, json),
+            None => cmd_equity(
+                &hands,
+                &board,
+                &dead,
+                &game,
+                monte_carlo,
+                iterations,
+                seed,
+                hand_types,
+                json,
+            ),
+        },
         Commands::Compare { hands, board, game } => {
             cmd_compare(&hands, &board, &game);
         }
         Commands::Parse { cards } => cmd_parse(&cards),
         Commands::Texture { board } => cmd_texture(&board),
+        Commands::Advantage {
+            range1,
+            range2,
+            board,
+            dead,
+            iterations,
+            json,
+        } => cmd_advantage(&range1, &range2, &board, &dead, iterations, json),
+        Commands::Outs {
+            pocket,
+            board,
+            vs,
+            dead,
+            game,
+            json,
+        } => cmd_outs(&pocket, &board, vs.as_deref(), &dead, &game, json),
+        Commands::Decision {
+            hand,
+            board,
+            dead,
+            pot,
+            stack,
+            ranges,
+            bets,
+            stacks,
+            fold_freq,
+            iterations,
+            json,
+        } => {
+            let opponents = DecisionOpponents {
+                ranges,
+                bets,
+                stacks,
+            };
+            cmd_decision(
+                &hand, &board, &dead, pot, stack, opponents, fold_freq, iterations, json,
+            )
+        }
+        Commands::Runouts {
+            hands,
+            board,
+            dead,
+            game,
+            json,
+            csv,
+        } => cmd_runouts(&hands, &board, &dead, &game, json, csv),
+        Commands::Nuts {
+            pocket,
+            board,
+            dead,
+            limit,
+            json,
+        } => cmd_nuts(&pocket, &board, &dead, limit, json),
+        Commands::Filter {
+            range,
+            query,
+            board,
+            ranges,
+            iterations,
+            json,
+        } => cmd_filter(&range, &query, &board, &ranges, iterations, json),
+        Commands::Convert {
+            range,
+            input,
+            from,
+            to,
+            output,
+        } => cmd_convert(
+            range.as_deref(),
+            input.as_deref(),
+            &from,
+            &to,
+            output.as_deref(),
+        ),
+        Commands::Pokenum { args } => cmd_pokenum(&args),
+        Commands::Batch {
+            input,
+            output,
+            timeout,
+        } => cmd_batch(input.as_deref(), output.as_deref(), timeout),
+        #[cfg(feature = "server")]
+        Commands::Serve {
+            host,
+            port,
+            max_body,
+            max_iterations,
+            timeout,
+            workers,
+            backlog,
+        } => cmd_serve(
+            &host,
+            port,
+            max_body,
+            max_iterations,
+            timeout,
+            workers,
+            backlog,
+        ),
     }
 }
 
@@ -183,6 +734,7 @@ fn cmd_eval(hand: &str) {
 }
 
 /// Calculate equity between hands
+#[allow(clippy::too_many_arguments)]
 fn cmd_equity(
     hands: &[String],
     board: &str,
@@ -190,6 +742,8 @@ fn cmd_equity(
     game_str: &str,
     monte_carlo: bool,
     iterations: usize,
+    seed: Option<u64>,
+    hand_types: bool,
     json: bool,
 ) {
     let npockets = hands.len();
@@ -204,7 +758,8 @@ fn cmd_equity(
         std::process::exit(1);
     }
 
-    let game_variant = match parse_game(game_str) {
+    // Any registered game; not just the built-in ones.
+    let game_variant = match game_str.parse::<GameRef>() {
         Ok(g) => g,
         Err(e) => {
             eprintln!("{}", e);
@@ -241,6 +796,10 @@ fn cmd_equity(
             eprintln!("Error: Range equity currently only supported for Hold'em.");
             std::process::exit(1);
         }
+        if hand_types {
+            eprintln!("Error: --hand-types is not supported for range equity.");
+            std::process::exit(1);
+        }
     }
 
     // Parse board
@@ -256,30 +815,6 @@ fn cmd_equity(
         }
     };
 
-    // Parse dead cards
-    let dead_mask = if dead.is_empty() {
-        StdDeckCardMask::new()
-    } else {
-        match StdDeck::string_to_mask(dead) {
-            Ok((mask, _)) => mask,
-            Err(e) => {
-                eprintln!("Error parsing dead cards: {}", e);
-                std::process::exit(1);
-            }
-        }
-    };
-
-    let nboard = board_mask.num_cards();
-
-    // Initialize result
-    let mut result = EnumResult::new(game_variant);
-    result.sample_type = if monte_carlo {
-        SampleType::Sample
-    } else {
-        SampleType::Exhaustive
-    };
-    result.nplayers = npockets as u32;
-
     // Run calculation
     if is_range_equity {
         // Use range_equity::calculate_equity
@@ -292,22 +827,20 @@ fn cmd_equity(
                 if json {
                     let total = equity_res.samples;
                     let players = vec![
-                        PlayerStat {
-                            hand: hands[0].clone(),
-                            win_pct: (equity_res.wins as f64 / total as f64) * 100.0,
-                            tie_pct: (equity_res.ties as f64 / total as f64) * 100.0,
-                            lose_pct: (equity_res.losses as f64 / total as f64) * 100.0,
-                            scoop_pct: None,
-                            ev: equity_res.equity,
-                        },
-                        PlayerStat {
-                            hand: hands[1].clone(),
-                            win_pct: (equity_res.losses as f64 / total as f64) * 100.0,
-                            tie_pct: (equity_res.ties as f64 / total as f64) * 100.0,
-                            lose_pct: (equity_res.wins as f64 / total as f64) * 100.0,
-                            scoop_pct: None,
-                            ev: 1.0 - equity_res.equity,
-                        },
+                        PlayerStat::hi(
+                            &hands[0],
+                            equity_res.wins,
+                            equity_res.ties,
+                            equity_res.losses,
+                            equity_res.equity,
+                        ),
+                        PlayerStat::hi(
+                            &hands[1],
+                            equity_res.losses,
+                            equity_res.ties,
+                            equity_res.wins,
+                            1.0 - equity_res.equity,
+                        ),
                     ];
 
                     let output = EquityResultOutput {
@@ -366,113 +899,202 @@ fn cmd_equity(
     }
 
     // Non-range equity (pockets)
-    let pockets: Vec<StdDeckCardMask> = ranges.iter().map(|r| r.hands()[0].0).collect();
-
-    let calc_result = if monte_carlo {
-        enum_sample(
-            game_variant,
-            &pockets,
-            board_mask,
-            dead_mask,
-            npockets,
-            nboard,
-            iterations,
-            false,
-            &mut result,
-        )
-    } else {
-        enum_exhaustive(
-            game_variant,
-            &pockets,
-            board_mask,
-            dead_mask,
-            npockets,
-            nboard,
-            false,
-            &mut result,
-        )
+    let query = EquityQuery {
+        game: game_variant,
+        players: hands.to_vec(),
+        board: board.to_string(),
+        dead: dead.to_string(),
+        mode: if monte_carlo {
+            EquityMode::MonteCarlo
+        } else {
+            EquityMode::Exhaustive
+        },
+        iterations,
+        seed,
     };
+    let result = match query.enumerate() {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error during calculation: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let report = query.report(&result);
+
+    if json {
+        let output = EquityResultOutput {
+            game: game_str.to_string(),
+            samples: report.samples,
+            board: board.to_string(),
+            players: report
+                .players
+                .into_iter()
+                .enumerate()
+                .map(|(i, equity)| PlayerStat {
+                    equity,
+                    hand_types: hand_types.then(|| hand_type_stats(&result, i)),
+                })
+                .collect(),
+        };
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+        return;
+    }
 
-    match calc_result {
-        Ok(_) => {
-            if json {
-                let mut players = Vec::new();
-                for (i, hand) in hands.iter().enumerate().take(npockets) {
-                    let total = result.nwinhi[i] + result.ntiehi[i] + result.nlosehi[i];
-                    if total > 0 {
-                        players.push(PlayerStat {
-                            hand: hand.clone(),
-                            win_pct: (result.nwinhi[i] as f64 / total as f64) * 100.0,
-                            tie_pct: (result.ntiehi[i] as f64 / total as f64) * 100.0,
-                            lose_pct: (result.nlosehi[i] as f64 / total as f64) * 100.0,
-                            scoop_pct: if game_variant == Game::Holdem8
-                                || game_variant == Game::Omaha8
-                            {
-                                Some((result.nscoop[i] as f64 / total as f64) * 100.0)
-                            } else {
-                                None
-                            },
-                            ev: result.ev[i],
-                        });
-                    }
-                }
-                let output = EquityResultOutput {
-                    game: game_str.to_string(),
-                    samples: result.nsamples,
-                    board: board.to_string(),
-                    players,
-                };
-                println!("{}", serde_json::to_string_pretty(&output).unwrap());
-            } else {
-                // Print results
-                println!("=== Poker Equity Calculator ===\n");
-                println!("Game: {}", game_str);
-                println!(
-                    "Mode: {}",
-                    if monte_carlo {
-                        "Monte Carlo"
-                    } else {
-                        "Exhaustive"
-                    }
-                );
-                println!("Samples: {}\n", result.nsamples);
-
-                println!("Board: {}", if board.is_empty() { "(none)" } else { board });
-                println!();
+    println!("=== Poker Equity Calculator ===\n");
+    println!("Game: {}", game_str);
+    println!(
+        "Mode: {}",
+        if monte_carlo {
+            "Monte Carlo"
+        } else {
+            "Exhaustive"
+        }
+    );
+    println!("Samples: {}\n", report.samples);
+
+    println!("Board: {}", if board.is_empty() { "(none)" } else { board });
+    println!();
+
+    println!(
+        "{:<10} {:<12} {:>8} {:>8} {:>8} {:>10}",
+        "Player", "Hand", "Win%", "Tie%", "Lose%", "EV"
+    );
+    println!("{}", "-".repeat(60));
 
-                // Print header
+    for (i, player) in report.players.iter().enumerate() {
+        println!(
+            "{:<10} {:<12} {:>7.2}% {:>7.2}% {:>7.2}% {:>10.4}",
+            format!("Player {}", i + 1),
+            player.hand,
+            player.win_pct,
+            player.tie_pct,
+            player.lose_pct,
+            player.ev
+        );
+    }
+
+    if hand_types {
+        for (i, hand) in hands.iter().enumerate() {
+            println!("\nPlayer {} ({}) final hands:", i + 1, hand);
+            println!(
+                "  {:<16} {:>8} {:>8} {:>8}  Lost to",
+                "Hand", "Final%", "Won%", "Lost%"
+            );
+            for stat in hand_type_stats(&result, i) {
                 println!(
-                    "{:<10} {:<12} {:>8} {:>8} {:>8} {:>10}",
-                    "Player", "Hand", "Win%", "Tie%", "Lose%", "EV"
+                    "  {:<16} {:>7.2}% {:>7.2}% {:>7.2}%  {}",
+                    stat.hand_type,
+                    stat.final_pct,
+                    stat.won_pct,
+                    stat.lost_pct,
+                    stat.lost_to.as_deref().unwrap_or("-")
                 );
-                println!("{}", "-".repeat(60));
-
-                // Print each player's results
-                for (i, hand) in hands.iter().enumerate().take(npockets) {
-                    let total = result.nwinhi[i] + result.ntiehi[i] + result.nlosehi[i];
-                    if total > 0 {
-                        let win_pct = (result.nwinhi[i] as f64 / total as f64) * 100.0;
-                        let tie_pct = (result.ntiehi[i] as f64 / total as f64) * 100.0;
-                        let lose_pct = (result.nlosehi[i] as f64 / total as f64) * 100.0;
-
-                        println!(
-                            "{:<10} {:<12} {:>7.2}% {:>7.2}% {:>7.2}% {:>10.4}",
-                            format!("Player {}", i + 1),
-                            hand,
-                            win_pct,
-                            tie_pct,
-                            lose_pct,
-                            result.ev[i]
-                        );
-                    }
-                }
             }
         }
+    }
+}
+
+/// Hand types a player finished with, best first, as percentages of all samples
+fn hand_type_stats(result: &EnumResult, player: usize) -> Vec<HandTypeStat> {
+    let pct = |count: u32| count as f64 / result.nsamples.max(1) as f64 * 100.0;
+    (0..HAND_TYPE_NAMES.len())
+        .rev()
+        .filter(|&t| result.nhandtype[player][t] > 0)
+        .map(|t| {
+            let losses = &result.nlosehandtype[player][t];
+            let lost_to = (0..HAND_TYPE_NAMES.len())
+                .filter(|&b| losses[b] > 0)
+                .max_by_key(|&b| losses[b])
+                .map(|b| HAND_TYPE_NAMES[b].to_string());
+            HandTypeStat {
+                hand_type: HAND_TYPE_NAMES[t].to_string(),
+                final_pct: pct(result.nhandtype[player][t]),
+                won_pct: pct(result.nwinhandtype[player][t]),
+                lost_pct: pct(losses.iter().sum()),
+                lost_to,
+            }
+        })
+        .collect()
+}
+
+/// Calculate the equity of one hand against N random hands
+fn cmd_equity_vs_random(
+    hands: &[String],
+    board: &str,
+    dead: &str,
+    game_str: &str,
+    opponents: usize,
+    iterations: usize,
+    json: bool,
+) {
+    if hands.len() != 1 {
+        eprintln!("Error: --vs-random takes exactly one hand");
+        std::process::exit(1);
+    }
+    if !matches!(parse_game(game_str), Ok(Game::Holdem)) {
+        eprintln!("Error: --vs-random is only supported for Hold'em");
+        std::process::exit(1);
+    }
+
+    let parse = |cards: &str, what: &str| -> StdDeckCardMask {
+        if cards.is_empty() {
+            return StdDeckCardMask::new();
+        }
+        match StdDeck::string_to_mask(cards) {
+            Ok((mask, _)) => mask,
+            Err(e) => {
+                eprintln!("Error parsing {}: {}", what, e);
+                std::process::exit(1);
+            }
+        }
+    };
+    let hero = parse(&hands[0], "hand");
+    let board_mask = parse(board, "board");
+    let dead_mask = parse(dead, "dead cards");
+
+    let res = match equity_vs_random(&hero, opponents, &board_mask, &dead_mask, iterations) {
+        Ok(r) => r,
         Err(e) => {
-            eprintln!("Error during calculation: {:?}", e);
+            eprintln!("Error calculating equity: {}", e);
             std::process::exit(1);
         }
+    };
+    let stat = PlayerStat::hi(&hands[0], res.wins, res.ties, res.losses, res.equity);
+
+    if json {
+        let output = VsRandomOutput {
+            game: game_str.to_string(),
+            board: board.to_string(),
+            opponents,
+            samples: res.samples,
+            exact: res.exact,
+            hero: stat,
+        };
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+        return;
     }
+
+    println!("=== Equity vs Random Hands ===\n");
+    println!("Hand: {}", hands[0]);
+    println!("Opponents: {} random", opponents);
+    println!("Board: {}", if board.is_empty() { "(none)" } else { board });
+    println!(
+        "Samples: {} ({})\n",
+        res.samples,
+        if res.exact {
+            "exhaustive"
+        } else {
+            "Monte Carlo"
+        }
+    );
+    println!(
+        "{:>8} {:>8} {:>8} {:>10}",
+        "Win%", "Tie%", "Lose%", "Equity"
+    );
+    println!(
+        "{:>7.2}% {:>7.2}% {:>7.2}% {:>10.4}",
+        stat.equity.win_pct, stat.equity.tie_pct, stat.equity.lose_pct, stat.equity.ev
+    );
 }
 
 /// Compare hands to find the winner
@@ -605,10 +1227,10 @@ fn cmd_compare(hands: &[String], board: &str, game_str: &str) {
     // For High and Hi/Lo variants, Higher value is Better (for the High hand).
     match game {
         Game::Lowball | Game::Lowball27 | Game::Razz => {
-            results.sort_by(|a, b| a.2.cmp(&b.2)); // Ascending
+            results.sort_by_key(|r| r.2); // Ascending
         }
         _ => {
-            results.sort_by(|a, b| b.2.cmp(&a.2)); // Descending
+            results.sort_by_key(|r| std::cmp::Reverse(r.2)); // Descending
         }
     }
 
@@ -637,6 +1259,272 @@ fn cmd_compare(hands: &[String], board: &str, game_str: &str) {
     }
 }
 
+const POKENUM_USAGE: &str = "usage: pokenum [-t] [-O] [-mc niter]
+\t[-h|-h8|-o|-o5|-o6|-o8|-o85|-7s|-7s8|-7snsq|-r|-5d|-5d8|-5dnsq|-l|-l27|-sd]
+\tpocket1 - pocket2 - ... [ -- board ] [ / dead ] ...";
+
+/// A deal and options parsed from pokenum arguments.
+struct PokenumArgs {
+    game: Game,
+    niter: Option<usize>,
+    orderflag: bool,
+    terse: bool,
+    pockets: Vec<StdDeckCardMask>,
+    board: StdDeckCardMask,
+    dead: StdDeckCardMask,
+}
+
+/// The game selected by a pokenum game flag.
+fn pokenum_game(flag: &str) -> Option<Game> {
+    Some(match flag {
+        "-h" => Game::Holdem,
+        "-h8" => Game::Holdem8,
+        "-o" => Game::Omaha,
+        "-o5" => Game::Omaha5,
+        "-o6" => Game::Omaha6,
+        "-o8" => Game::Omaha8,
+        "-o85" => Game::Omaha85,
+        "-7s" => Game::Stud7,
+        "-7s8" => Game::Stud78,
+        "-7snsq" => Game::Stud7nsq,
+        "-r" => Game::Razz,
+        "-5d" => Game::Draw5,
+        "-5d8" => Game::Draw58,
+        "-5dnsq" => Game::Draw5nsq,
+        "-l" => Game::Lowball,
+        "-l27" => Game::Lowball27,
+        "-sd" => Game::ShortDeck,
+        _ => return None,
+    })
+}
+
+/// Parses pokenum's grammar: options and a game flag, then pockets separated by `-`,
+/// board cards after `--` and dead cards after `/`. Cards may be given one per
+/// argument (`As Ad`) or run together (`AsAd`).
+fn parse_pokenum_args(args: &[String]) -> Result<PokenumArgs, String> {
+    let mut parsed = PokenumArgs {
+        game: Game::Holdem,
+        niter: None,
+        orderflag: false,
+        terse: false,
+        pockets: Vec::new(),
+        board: StdDeckCardMask::new(),
+        dead: StdDeckCardMask::new(),
+    };
+
+    let mut rest = args.iter();
+    let mut first_card = None;
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "-mc" => {
+                let niter = rest.next().ok_or("-mc needs an iteration count")?;
+                match niter.parse::<usize>() {
+                    Ok(n) if n > 0 => parsed.niter = Some(n),
+                    _ => return Err(format!("Invalid iteration count: {}", niter)),
+                }
+            }
+            "-t" => parsed.terse = true,
+            "-O" => parsed.orderflag = true,
+            flag if flag.starts_with('-') && flag != "-" && flag != "--" => {
+                parsed.game =
+                    pokenum_game(flag).ok_or_else(|| format!("Unknown option: {}", flag))?;
+            }
+            _ => {
+                first_card = Some(arg);
+                break;
+            }
+        }
+    }
+
+    #[derive(PartialEq)]
+    enum Section {
+        Pocket,
+        Board,
+        Dead,
+    }
+    let mut section = Section::Pocket;
+    let mut pocket = StdDeckCardMask::new();
+    let mut used = StdDeckCardMask::new();
+    for arg in first_card.into_iter().chain(rest) {
+        match arg.as_str() {
+            "-" if section == Section::Pocket => {
+                parsed.pockets.push(pocket);
+                pocket = StdDeckCardMask::new();
+            }
+            "--" if section == Section::Pocket => section = Section::Board,
+            "/" if section != Section::Dead => section = Section::Dead,
+            cards => {
+                let (mask, count) = StdDeck::string_to_mask(cards)?;
+                if mask.num_cards() != count || (mask.as_raw() & used.as_raw()) != 0 {
+                    return Err(format!("Duplicate card in {}", cards));
+                }
+                used = used | mask;
+                match section {
+                    Section::Pocket => pocket = pocket | mask,
+                    Section::Board => parsed.board = parsed.board | mask,
+                    Section::Dead => parsed.dead = parsed.dead | mask,
+                }
+            }
+        }
+    }
+    if !pocket.is_empty() {
+        parsed.pockets.push(pocket);
+    }
+
+    let gp = parsed
+        .game
+        .game_params()
+        .ok_or_else(|| format!("Unsupported game: {}", parsed.game))?;
+    if parsed.pockets.len() < 2 || parsed.pockets.len() > ENUM_MAXPLAYERS {
+        return Err(format!("Need 2 to {} pockets", ENUM_MAXPLAYERS));
+    }
+    for p in &parsed.pockets {
+        let n = p.num_cards() as i32;
+        if n < gp.minpocket || n > gp.maxpocket {
+            return Err(format!(
+                "{}: pockets need {} to {} cards",
+                gp.name, gp.minpocket, gp.maxpocket
+            ));
+        }
+    }
+    if parsed.board.num_cards() as i32 > gp.maxboard {
+        return Err(format!("{}: at most {} board cards", gp.name, gp.maxboard));
+    }
+    Ok(parsed)
+}
+
+/// Runs pokenum: enumerates (or samples with `-mc`) the deal and prints the result
+/// in the original tool's format.
+fn cmd_pokenum(args: &[String]) {
+    let parsed = match parse_pokenum_args(args) {
+        Ok(p) => p,
+        Err(e) => {
+            if !args.is_empty() {
+                eprintln!("{}", e);
+            }
+            eprintln!("{}", POKENUM_USAGE);
+            std::process::exit(1);
+        }
+    };
+
+    let npockets = parsed.pockets.len();
+    let nboard = parsed.board.num_cards();
+    let mut result = EnumResult::new(parsed.game);
+    // Legacy output counts each board once per player, however many players there are.
+    result.outright = true;
+    let status = match parsed.niter {
+        Some(niter) => enum_sample(
+            parsed.game,
+            &parsed.pockets,
+            parsed.board,
+            parsed.dead,
+            npockets,
+            nboard,
+            niter,
+            parsed.orderflag,
+            &mut result,
+        )
+        .map(|_| result.sample_type = SampleType::Sample),
+        None => enum_exhaustive(
+            parsed.game,
+            &parsed.pockets,
+            parsed.board,
+            parsed.dead,
+            npockets,
+            nboard,
+            parsed.orderflag,
+            &mut result,
+        ),
+    };
+    if let Err(e) = status {
+        eprintln!("enumeration function failed: {}", e);
+        std::process::exit(1);
+    }
+
+    if parsed.terse {
+        result.enum_result_print_terse(&parsed.pockets, parsed.board);
+        result.print_ordering(true);
+    } else if let Err(e) = result.enum_result_print(&parsed.pockets, parsed.board) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Answer JSONL queries from a file or stdin, one result line per query
+fn cmd_batch(input: Option<&str>, output: Option<&str>, timeout: u64) {
+    use std::io::{BufRead, BufReader, BufWriter, Write};
+
+    let reader: Box<dyn BufRead> = match input {
+        None | Some("-") => Box::new(std::io::stdin().lock()),
+        Some(path) => match std::fs::File::open(path) {
+            Ok(f) => Box::new(BufReader::new(f)),
+            Err(e) => {
+                eprintln!("Error reading {}: {}", path, e);
+                std::process::exit(1);
+            }
+        },
+    };
+    let writer: Box<dyn Write> = match output {
+        None => Box::new(BufWriter::new(std::io::stdout().lock())),
+        Some(path) => match std::fs::File::create(path) {
+            Ok(f) => Box::new(BufWriter::new(f)),
+            Err(e) => {
+                eprintln!("Error writing {}: {}", path, e);
+                std::process::exit(1);
+            }
+        },
+    };
+
+    // Nightly jobs ask for what they need, so only the time limit applies.
+    let limits = Limits {
+        max_iterations: usize::MAX,
+        timeout: (timeout > 0).then(|| std::time::Duration::from_secs(timeout)),
+    };
+    match batch(reader, writer, &limits) {
+        Ok(summary) if summary.errors > 0 => {
+            eprintln!("{} of {} queries failed", summary.errors, summary.queries);
+        }
+        Ok(_) => {}
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Run the HTTP/JSON server until it fails
+#[cfg(feature = "server")]
+fn cmd_serve(
+    host: &str,
+    port: u16,
+    max_body: usize,
+    max_iterations: usize,
+    timeout: u64,
+    workers: usize,
+    backlog: usize,
+) {
+    let config = server::ServerConfig {
+        max_body_bytes: max_body,
+        limits: Limits {
+            max_iterations,
+            timeout: (timeout > 0).then(|| std::time::Duration::from_secs(timeout)),
+        },
+        workers,
+        backlog,
+    };
+    let server = match server::Server::bind((host, port), config) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if let Some(addr) = server.local_addr() {
+        println!("Listening on http://{}", addr);
+    }
+    server.run();
+}
+
 /// Parse and display card information
 fn cmd_parse(cards: &str) {
     match StdDeck::string_to_mask(cards) {
@@ -718,6 +1606,866 @@ fn cmd_texture(board_str: &str) {
     );
 }
 
+/// Calculate outs, optionally against an opponent hand or range
+fn cmd_outs(
+    pocket_str: &str,
+    board_str: &str,
+    vs: Option<&str>,
+    dead_str: &str,
+    game_str: &str,
+    json: bool,
+) {
+    let game = match parse_game(game_str) {
+        Ok(g) => g,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    if vs.is_some() && game != Game::Holdem {
+        eprintln!("Error: --vs is only supported for holdem");
+        std::process::exit(1);
+    }
+
+    let parse = |label: &str, cards: &str| -> StdDeckCardMask {
+        if cards.is_empty() {
+            return StdDeckCardMask::new();
+        }
+        match StdDeck::string_to_mask(cards) {
+            Ok((mask, _)) => mask,
+            Err(e) => {
+                eprintln!("Error parsing {}: {}", label, e);
+                std::process::exit(1);
+            }
+        }
+    };
+    let pocket = parse("pocket", pocket_str);
+    let board = parse("board", board_str);
+    let dead = parse("dead cards", dead_str);
+
+    let cards_to_strings = |cards: &[StdDeckCardMask]| -> Vec<String> {
+        cards.iter().map(|c| c.to_string()).collect()
+    };
+
+    let (naive, low_outs, counterfeits) = if game == Game::Holdem {
+        (
+            calculate_outs_with_dead(&pocket, &board, &dead),
+            Vec::new(),
+            Vec::new(),
+        )
+    } else {
+        match calculate_game_outs(game, &pocket, &board, &dead) {
+            Ok(res) => (
+                res.hi,
+                cards_to_strings(&res.lo),
+                cards_to_strings(&res.counterfeits),
+            ),
+            Err(e) => {
+                eprintln!("Error calculating outs: {}", e);
+                std::process::exit(1);
+            }
+        }
+    };
+    let mut outs_by_type: Vec<(String, Vec<String>)> = naive
+        .named()
+        .map(|(name, cards)| (name.to_string(), cards_to_strings(cards)))
+        .collect();
+    outs_by_type.reverse();
+
+    let mut output = OutsOutput {
+        game: game_str.to_lowercase(),
+        pocket: pocket_str.to_string(),
+        board: board_str.to_string(),
+        opponent: vs.map(|v| v.to_string()),
+        current_win_pct: None,
+        current_tie_pct: None,
+        outs_by_type,
+        low_outs,
+        counterfeits,
+        clean_outs: Vec::new(),
+        tie_outs: Vec::new(),
+        tainted_outs: Vec::new(),
+        discounted_outs: None,
+    };
+
+    if let Some(range_str) = vs {
+        let range = match HandRange::from_str(range_str) {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("Error parsing opponent range: {}", e);
+                std::process::exit(1);
+            }
+        };
+        match calculate_outs_vs_range(&pocket, &board, &dead, &range) {
+            Ok(res) => {
+                output.current_win_pct = Some(res.current_win * 100.0);
+                output.current_tie_pct = Some(res.current_tie * 100.0);
+                output.clean_outs = cards_to_strings(&res.clean_outs());
+                output.tie_outs = cards_to_strings(&res.tie_outs());
+                output.tainted_outs = cards_to_strings(&res.tainted_outs());
+                output.discounted_outs = Some(res.discounted_outs());
+            }
+            Err(e) => {
+                eprintln!("Error calculating outs: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+        return;
+    }
+
+    println!("=== Outs ===\n");
+    println!("Game: {}", output.game);
+    println!("Pocket: {}", pocket_str);
+    println!("Board: {}", board_str);
+    println!();
+
+    println!("Improving cards by hand type:");
+    if output.outs_by_type.is_empty() {
+        println!("  (none)");
+    }
+    for (name, cards) in &output.outs_by_type {
+        println!("  {:<13} {:>2}  {}", name, cards.len(), cards.join(" "));
+    }
+
+    if !output.low_outs.is_empty() || !output.counterfeits.is_empty() {
+        println!();
+        println!(
+            "Low outs:     {:>2}  {}",
+            output.low_outs.len(),
+            output.low_outs.join(" ")
+        );
+        if !output.counterfeits.is_empty() {
+            println!(
+                "Counterfeits: {:>2}  {}",
+                output.counterfeits.len(),
+                output.counterfeits.join(" ")
+            );
+        }
+    }
+
+    if let Some(range_str) = vs {
+        println!();
+        println!("Versus: {}", range_str);
+        println!(
+            "Currently ahead: {:.2}%  tied: {:.2}%",
+            output.current_win_pct.unwrap_or(0.0),
+            output.current_tie_pct.unwrap_or(0.0)
+        );
+        println!(
+            "Clean outs:   {:>2}  {}",
+            output.clean_outs.len(),
+            output.clean_outs.join(" ")
+        );
+        println!(
+            "Tie outs:     {:>2}  {}",
+            output.tie_outs.len(),
+            output.tie_outs.join(" ")
+        );
+        println!(
+            "Tainted outs: {:>2}  {}",
+            output.tainted_outs.len(),
+            output.tainted_outs.join(" ")
+        );
+        println!(
+            "Discounted outs: {:.2}",
+            output.discounted_outs.unwrap_or(0.0)
+        );
+    }
+}
+
+/// Opponent arguments of the decision command, one entry per opponent
+struct DecisionOpponents {
+    ranges: Vec<String>,
+    bets: Vec<f64>,
+    stacks: Vec<f64>,
+}
+
+/// Chip EV of calling, folding or jamming
+#[allow(clippy::too_many_arguments)]
+fn cmd_decision(
+    hand_str: &str,
+    board_str: &str,
+    dead_str: &str,
+    pot: f64,
+    hero_stack: f64,
+    args: DecisionOpponents,
+    fold_frequency: Option<f64>,
+    iterations: usize,
+    json: bool,
+) {
+    let n = args.ranges.len();
+    if args.bets.len() != n || !(args.stacks.is_empty() || args.stacks.len() == n) {
+        eprintln!("Error: give one --bet (and optionally one --villain-stack) per --vs range");
+        std::process::exit(1);
+    }
+    let parse_cards = |label: &str, cards: &str| -> StdDeckCardMask {
+        if cards.is_empty() {
+            return StdDeckCardMask::new();
+        }
+        match StdDeck::string_to_mask(cards) {
+            Ok((mask, _)) => mask,
+            Err(e) => {
+                eprintln!("Error parsing {}: {}", label, e);
+                std::process::exit(1);
+            }
+        }
+    };
+    let mut opponents = Vec::with_capacity(n);
+    for (i, range_str) in args.ranges.iter().enumerate() {
+        let range = match HandRange::from_str(range_str) {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("Error parsing opponent range {}: {}", i + 1, e);
+                std::process::exit(1);
+            }
+        };
+        let bet = args.bets[i];
+        let stack = args
+            .stacks
+            .get(i)
+            .copied()
+            .unwrap_or((hero_stack - bet).max(0.0));
+        opponents.push(Opponent { range, bet, stack });
+    }
+    let spot = DecisionSpot {
+        hero: parse_cards("hand", hand_str),
+        board: parse_cards("board", board_str),
+        dead: parse_cards("dead cards", dead_str),
+        pot,
+        hero_stack,
+        opponents,
+        iterations,
+    };
+
+    let res = match decision_ev(&spot, fold_frequency) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error calculating decision EV: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if json {
+        let option = |o: &OptionEv| OptionOutput {
+            risk: o.risk,
+            ev: o.ev,
+            pots: o
+                .pots
+                .iter()
+                .map(|p| PotOutput {
+                    amount: p.amount,
+                    players: p.players,
+                    equity: p.equity,
+                })
+                .collect(),
+        };
+        let output = DecisionOutput {
+            hand: hand_str.to_string(),
+            board: board_str.to_string(),
+            to_call: res.to_call,
+            required_equity: res.required_equity,
+            equity: res.equity,
+            exact: res.exact,
+            fold_ev: res.fold_ev(),
+            call: option(&res.call),
+            jam: res.jam.as_ref().map(option),
+        };
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+        return;
+    }
+
+    println!("=== Decision EV ===\n");
+    println!("Hand: {}", hand_str);
+    println!(
+        "Board: {}",
+        if board_str.is_empty() {
+            "(none)"
+        } else {
+            board_str
+        }
+    );
+    println!("Pot: {}  Stack: {}", pot, hero_stack);
+    for (i, o) in spot.opponents.iter().enumerate() {
+        println!(
+            "Opponent {}: {} (bet {}, behind {})",
+            i + 1,
+            args.ranges[i],
+            o.bet,
+            o.stack
+        );
+    }
+    println!();
+    println!("To call:          {:.2}", res.to_call);
+    println!("Required equity:  {:.2}%", res.required_equity * 100.0);
+    println!(
+        "Equity:           {:.2}%{}",
+        res.equity * 100.0,
+        if res.exact { " (exact)" } else { "" }
+    );
+    println!();
+
+    let print_option = |name: &str, o: &OptionEv| {
+        println!("{:<6} EV {:>+12.2}  (risking {:.2})", name, o.ev, o.risk);
+        for (i, p) in o.pots.iter().enumerate() {
+            println!(
+                "       {} {:>10.2}  {} players  equity {:.2}%",
+                if i == 0 { "Main pot" } else { "Side pot" },
+                p.amount,
+                p.players,
+                p.equity * 100.0
+            );
+        }
+    };
+    println!("{:<6} EV {:>+12.2}", "Fold", res.fold_ev());
+    print_option("Call", &res.call);
+    if let Some(jam) = &res.jam {
+        print_option("Jam", jam);
+    }
+
+    let mut best = ("Fold", res.fold_ev());
+    for (name, ev) in [
+        ("Call", Some(res.call.ev)),
+        ("Jam", res.jam.as_ref().map(|j| j.ev)),
+    ] {
+        if let Some(ev) = ev {
+            if ev > best.1 {
+                best = (name, ev);
+            }
+        }
+    }
+    println!("\nBest: {}", best.0);
+}
+
+/// Labels for the board texture flags that are set
+fn texture_labels(texture: &BoardTexture) -> Vec<&'static str> {
+    [
+        (texture.is_rainbow, "rainbow"),
+        (texture.is_two_tone, "two-tone"),
+        (texture.is_monotone, "monotone"),
+        (texture.is_paired, "paired"),
+        (texture.is_trips, "trips"),
+        (texture.is_quads, "quads"),
+        (texture.is_full_house, "full house"),
+        (texture.has_straight_draw, "connected"),
+        (texture.has_flush_draw, "flush possible"),
+    ]
+    .into_iter()
+    .filter(|(set, _)| *set)
+    .map(|(_, label)| label)
+    .collect()
+}
+
+/// Compare two ranges on a board
+fn cmd_advantage(
+    range1_str: &str,
+    range2_str: &str,
+    board_str: &str,
+    dead_str: &str,
+    iterations: usize,
+    json: bool,
+) {
+    let parse_range = |label: &str, s: &str| -> HandRange {
+        match HandRange::from_str(s) {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("Error parsing {}: {}", label, e);
+                std::process::exit(1);
+            }
+        }
+    };
+    let parse_cards = |label: &str, cards: &str| -> StdDeckCardMask {
+        if cards.is_empty() {
+            return StdDeckCardMask::new();
+        }
+        match StdDeck::string_to_mask(cards) {
+            Ok((mask, _)) => mask,
+            Err(e) => {
+                eprintln!("Error parsing {}: {}", label, e);
+                std::process::exit(1);
+            }
+        }
+    };
+    let range1 = parse_range("range 1", range1_str);
+    let range2 = parse_range("range 2", range2_str);
+    let board = parse_cards("board", board_str);
+    let dead = parse_cards("dead cards", dead_str);
+
+    let report = match range_advantage(&range1, &range2, &board, &dead, iterations) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error calculating range advantage: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let names = [range1_str, range2_str];
+
+    if json {
+        let ranges = report
+            .ranges
+            .iter()
+            .enumerate()
+            .map(|(i, side)| AdvantageRangeOutput {
+                range: names[i].to_string(),
+                combos: side.total_weight(),
+                equity: side.equity(),
+                equity_buckets: side.distribution(5),
+                nut_combos: side.nut_combos(),
+                nut_share: report.nut_share(i),
+                categories: side
+                    .categories
+                    .iter()
+                    .map(|(c, share)| CategoryShare {
+                        category: c.name(),
+                        share: *share,
+                    })
+                    .collect(),
+            })
+            .collect();
+        let output = AdvantageOutput {
+            board: board_str.to_string(),
+            texture: texture_labels(&report.texture),
+            equity: report.equity.equity,
+            ranges,
+        };
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+        return;
+    }
+
+    let [side1, side2] = &report.ranges;
+    let row = |label: &str, a: String, b: String| println!("{:<24} {:>12} {:>12}", label, a, b);
+    let pct = |x: f64| format!("{:.2}%", x * 100.0);
+
+    println!("=== Range Advantage ===\n");
+    println!(
+        "Board: {} ({})",
+        board_str,
+        texture_labels(&report.texture).join(", ")
+    );
+    println!("Range 1: {}", range1_str);
+    println!("Range 2: {}\n", range2_str);
+
+    row("", "Range 1".to_string(), "Range 2".to_string());
+    println!("{}", "-".repeat(50));
+    row(
+        "Combos",
+        format!("{:.1}", side1.total_weight()),
+        format!("{:.1}", side2.total_weight()),
+    );
+    row(
+        "Equity",
+        pct(report.equity.equity),
+        pct(1.0 - report.equity.equity),
+    );
+    let (buckets1, buckets2) = (side1.distribution(5), side2.distribution(5));
+    for b in (0..5).rev() {
+        row(
+            &format!("  Equity {}-{}%", b * 20, (b + 1) * 20),
+            pct(buckets1[b]),
+            pct(buckets2[b]),
+        );
+    }
+    row(
+        "Nut combos",
+        format!("{:.1}", side1.nut_combos()),
+        format!("{:.1}", side2.nut_combos()),
+    );
+    row(
+        "Nut share",
+        pct(report.nut_share(0)),
+        pct(report.nut_share(1)),
+    );
+
+    println!("\nHand categories:");
+    let share = |side: &RangeReport, category: HandCategory| {
+        side.categories
+            .iter()
+            .find(|(c, _)| *c == category)
+            .map_or(0.0, |(_, s)| *s)
+    };
+    for category in HAND_CATEGORIES {
+        let (a, b) = (share(side1, category), share(side2, category));
+        if a > 0.0 || b > 0.0 {
+            row(&format!("  {}", category), pct(a), pct(b));
+        }
+    }
+}
+
+/// Show each player's equity after every possible next card
+fn cmd_runouts(
+    hands: &[String],
+    board_str: &str,
+    dead_str: &str,
+    game_str: &str,
+    json: bool,
+    csv: bool,
+) {
+    let game = match parse_game(game_str) {
+        Ok(g) => g,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let parse = |label: &str, cards: &str| -> StdDeckCardMask {
+        if cards.is_empty() {
+            return StdDeckCardMask::new();
+        }
+        match StdDeck::string_to_mask(cards) {
+            Ok((mask, _)) => mask,
+            Err(e) => {
+                eprintln!("Error parsing {}: {}", label, e);
+                std::process::exit(1);
+            }
+        }
+    };
+    let pockets: Vec<StdDeckCardMask> = hands.iter().map(|h| parse("hand", h)).collect();
+    let board = parse("board", board_str);
+    let dead = parse("dead cards", dead_str);
+
+    let report = match next_card_equity(game, &pockets, board, dead) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error calculating runouts: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let player = |leader: Option<usize>| leader.map(|p| p + 1);
+    let groups = |groups: Vec<GroupEquity>| -> Vec<RunoutGroupOutput> {
+        groups
+            .into_iter()
+            .map(|g| RunoutGroupOutput {
+                group: g.label,
+                cards: g.cards,
+                equity: g.equity,
+            })
+            .collect()
+    };
+
+    if json {
+        let output = RunoutsOutput {
+            game: game_str.to_lowercase(),
+            board: board_str.to_string(),
+            hands: hands.to_vec(),
+            equity: report.equity.clone(),
+            leader: player(report.leader),
+            cards: report
+                .cards
+                .iter()
+                .map(|c| RunoutCardOutput {
+                    card: c.card.to_string(),
+                    equity: c.equity.clone(),
+                    leader: player(c.leader),
+                    flips: c.flips,
+                })
+                .collect(),
+            flips: report.flips().iter().map(|c| c.to_string()).collect(),
+            by_suit: groups(report.by_suit()),
+            by_rank: groups(report.by_rank()),
+        };
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+        return;
+    }
+
+    if csv {
+        let mut header = vec!["card".to_string()];
+        header.extend((1..=hands.len()).map(|p| format!("p{}_equity", p)));
+        header.extend(["leader".to_string(), "flips".to_string()]);
+        println!("{}", header.join(","));
+        for c in &report.cards {
+            let mut row = vec![c.card.to_string()];
+            row.extend(c.equity.iter().map(|e| format!("{:.6}", e)));
+            row.push(player(c.leader).map_or(String::new(), |p| p.to_string()));
+            row.push(c.flips.to_string());
+            println!("{}", row.join(","));
+        }
+        return;
+    }
+
+    let leader_name = |leader: Option<usize>| match leader {
+        Some(p) => format!("Player {}", p + 1),
+        None => "tie".to_string(),
+    };
+    let equity_cols = |equity: &[f64]| -> String {
+        equity
+            .iter()
+            .map(|e| format!("{:>9.2}%", e * 100.0))
+            .collect::<Vec<_>>()
+            .join("")
+    };
+
+    println!("=== Next Card Equity ===\n");
+    println!("Game: {}", game_str);
+    println!("Board: {}", board_str);
+    for (i, hand) in hands.iter().enumerate() {
+        println!("Player {}: {}", i + 1, hand);
+    }
+    println!(
+        "Current: {} (leader: {})\n",
+        equity_cols(&report.equity).trim_start(),
+        leader_name(report.leader)
+    );
+
+    let header: String = (1..=hands.len())
+        .map(|p| format!("{:>10}", format!("P{}", p)))
+        .collect();
+    println!("{:<6}{}  Leader", "Card", header);
+    println!("{}", "-".repeat(6 + 10 * hands.len() + 12));
+    for c in &report.cards {
+        println!(
+            "{:<6}{}  {}{}",
+            c.card.to_string(),
+            equity_cols(&c.equity),
+            leader_name(c.leader),
+            if c.flips { " *" } else { "" }
+        );
+    }
+
+    let flips: Vec<String> = report.flips().iter().map(|c| c.to_string()).collect();
+    println!(
+        "\nLeader flips ({}): {}",
+        flips.len(),
+        if flips.is_empty() {
+            "none".to_string()
+        } else {
+            flips.join(" ")
+        }
+    );
+
+    for (title, groups) in [("suit", report.by_suit()), ("rank", report.by_rank())] {
+        println!("\nBy {}:", title);
+        for g in groups {
+            println!("  {:<4}({:>2}){}", g.label, g.cards, equity_cols(&g.equity));
+        }
+    }
+}
+
+/// Rank a hand among all possible holdings on the board
+fn cmd_nuts(pocket_str: &str, board_str: &str, dead_str: &str, limit: usize, json: bool) {
+    let parse = |label: &str, cards: &str| -> StdDeckCardMask {
+        if cards.is_empty() {
+            return StdDeckCardMask::new();
+        }
+        match StdDeck::string_to_mask(cards) {
+            Ok((mask, _)) => mask,
+            Err(e) => {
+                eprintln!("Error parsing {}: {}", label, e);
+                std::process::exit(1);
+            }
+        }
+    };
+    let pocket = parse("pocket", pocket_str);
+    let board = parse("board", board_str);
+    let dead = parse("dead cards", dead_str);
+
+    let res = match nut_rank(&pocket, &board, &dead) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error calculating nut rank: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let output = NutsOutput {
+        pocket: pocket_str.to_string(),
+        board: board_str.to_string(),
+        hand: res.value.std_rules_hand_val_to_string(),
+        nut_rank: res.nut_rank,
+        nut_rank_unblocked: res.nut_rank_unblocked,
+        beat_combos: res.beat_combos,
+        tie_combos: res.tie_combos,
+        blocked_beat_combos: res.blocked_beat_combos(),
+        total_combos: res.total_combos,
+        better: res
+            .better
+            .iter()
+            .take(limit)
+            .map(|c| NutClassOutput {
+                hand: c.value.std_rules_hand_val_to_string(),
+                example: c.example.to_string(),
+                combos: c.combos,
+                blocked: c.blocked,
+            })
+            .collect(),
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+        return;
+    }
+
+    println!("=== Nut Rank ===\n");
+    println!("Pocket: {}", pocket_str);
+    println!("Board: {}", board_str);
+    println!("Hand: {}", output.hand);
+    println!();
+    println!(
+        "Nut rank: {} ({} without blockers)",
+        output.nut_rank, output.nut_rank_unblocked
+    );
+    println!(
+        "Beaten by {} of {} combos, tied by {}; {} beating combos blocked",
+        output.beat_combos, output.total_combos, output.tie_combos, output.blocked_beat_combos
+    );
+
+    if !output.better.is_empty() {
+        println!();
+        println!("Better hands:");
+        for (i, c) in output.better.iter().enumerate() {
+            println!(
+                "  {:>2}. {:<24} e.g. {:<12} combos: {:>3}  blocked: {:>3}",
+                i + 1,
+                c.hand,
+                c.example,
+                c.combos,
+                c.blocked
+            );
+        }
+        if res.better.len() > limit {
+            println!("  ... {} more", res.better.len() - limit);
+        }
+    }
+}
+
+/// Filter a range with a query expression
+fn cmd_filter(
+    range_str: &str,
+    query: &str,
+    board_str: &str,
+    named: &[String],
+    iterations: usize,
+    json: bool,
+) {
+    let range = match HandRange::from_str(range_str) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error parsing range: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let board = if board_str.is_empty() {
+        StdDeckCardMask::new()
+    } else {
+        match StdDeck::string_to_mask(board_str) {
+            Ok((mask, _)) => mask,
+            Err(e) => {
+                eprintln!("Error parsing board: {}", e);
+                std::process::exit(1);
+            }
+        }
+    };
+
+    let mut ctx = FilterContext::new(board).with_iterations(iterations);
+    for entry in named {
+        let Some((name, spec)) = entry.split_once('=') else {
+            eprintln!("Error: named range must be NAME=RANGE, got '{}'", entry);
+            std::process::exit(1);
+        };
+        match HandRange::from_str(spec) {
+            Ok(r) => ctx = ctx.with_range(name.trim(), r),
+            Err(e) => {
+                eprintln!("Error parsing range '{}': {}", name, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let filtered = match filter_range(&range, query, &ctx) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error filtering range: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let output = FilterOutput {
+        query: query.to_string(),
+        board: board_str.to_string(),
+        combos: filtered.len(),
+        total_combos: range.len(),
+        weight: filtered.total_weight(),
+        range: filtered.to_notation(),
+        hands: filtered
+            .hands()
+            .iter()
+            .map(|(h, w)| (h.to_string(), *w))
+            .collect(),
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+        return;
+    }
+
+    println!("=== Range Filter ===\n");
+    println!("Query: {}", query);
+    println!("Board: {}", board_str);
+    println!(
+        "Matched: {} of {} combos (weight {:.2})",
+        output.combos, output.total_combos, output.weight
+    );
+    println!("Range: {}", output.range);
+}
+
+/// Convert a range between solver formats
+fn cmd_convert(
+    range: Option<&str>,
+    input: Option<&str>,
+    from: &str,
+    to: &str,
+    output: Option<&str>,
+) {
+    let text = match (range, input) {
+        (Some(r), None) => r.to_string(),
+        (None, Some(path)) => match std::fs::read_to_string(path) {
+            Ok(t) => t,
+            Err(e) => {
+                eprintln!("Error reading {}: {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        _ => {
+            eprintln!("Error: give either a range or --input FILE");
+            std::process::exit(1);
+        }
+    };
+
+    let from = if from.eq_ignore_ascii_case("auto") {
+        Ok(RangeFormat::detect(&text))
+    } else {
+        RangeFormat::from_str(from)
+    };
+    let (from, to) = match (from, RangeFormat::from_str(to)) {
+        (Ok(f), Ok(t)) => (f, t),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let parsed = match HandRange::parse_format(&text, from) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error parsing range: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let converted = parsed.to_format(to);
+
+    match output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, format!("{}\n", converted)) {
+                eprintln!("Error writing {}: {}", path, e);
+                std::process::exit(1);
+            }
+        }
+        None => println!("{}", converted),
+    }
+}
+
 /// Helper to format Short Deck hands where Flush > Full House
 /// In ShortDeckEvaluator, Flush is stored as FullHouse (type 6) and FullHouse as Flush (type 5) to coerce correct integer comparison.
 /// We need to swap them back for display.
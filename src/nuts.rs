@@ -0,0 +1,266 @@
+//! Nut-rank analysis.
+//!
+//! Enumerates every holding an opponent could have on a board and ranks our hand
+//! against them: a nut rank of 1 means nothing beats us. Holdings are grouped into
+//! classes of equal strength, and our own hole cards are tracked as blockers.
+
+use crate::combinations::for_each_combination;
+use crate::deck::{StdDeckCardMask, STD_DECK_N_CARDS};
+use crate::errors::PokerError;
+use crate::evaluators::{Eval, HandEvaluator, OmahaHiEvaluator};
+use crate::handval::HandVal;
+use std::collections::HashMap;
+
+/// A group of opponent holdings that all make the same hand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NutClass {
+    /// The hand value of the class, with kicker fields filled in
+    /// (see [`Eval::eval_n_detailed`]).
+    pub value: HandVal,
+    /// Holdings in this class that an opponent can still have.
+    pub combos: usize,
+    /// Holdings in this class that are impossible because we hold one of their cards.
+    pub blocked: usize,
+    /// One holding making this hand, preferring one we do not block.
+    pub example: StdDeckCardMask,
+}
+
+/// Where a hand ranks among all holdings on a board.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NutRankResult {
+    /// Our hand value, with kicker fields filled in.
+    pub value: HandVal,
+    /// 1 + the number of distinct hands that beat us and that an opponent can hold.
+    pub nut_rank: usize,
+    /// The nut rank if our hole cards did not block anything.
+    pub nut_rank_unblocked: usize,
+    /// Classes that beat us, best first. Includes fully blocked classes.
+    pub better: Vec<NutClass>,
+    /// Opponent holdings that make the same hand as ours.
+    pub tie_combos: usize,
+    /// Opponent holdings that beat us.
+    pub beat_combos: usize,
+    /// All opponent holdings left after removing board, dead and our cards.
+    pub total_combos: usize,
+}
+
+impl NutRankResult {
+    /// Holdings that would beat us but are blocked by our hole cards.
+    pub fn blocked_beat_combos(&self) -> usize {
+        self.better.iter().map(|c| c.blocked).sum()
+    }
+
+    /// Returns true if no opponent holding beats us.
+    pub fn is_nuts(&self) -> bool {
+        self.nut_rank == 1
+    }
+}
+
+/// Ranks `pocket` against every possible opponent holding on `board`.
+///
+/// Two hole cards are ranked with Hold'em rules against all 2-card holdings; four to
+/// six hole cards use Omaha rules against holdings of the same size. The board must
+/// have 3 to 5 cards; only the current board is considered, not future cards.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::deck::{StdDeck, StdDeckCardMask};
+/// use poker_eval_rs::nuts::nut_rank;
+///
+/// // The nut flush on a board with no straight-flush or full-house possibility.
+/// let (pocket, _) = StdDeck::string_to_mask("AhQh").unwrap();
+/// let (board, _) = StdDeck::string_to_mask("Kh8h4h2c9d").unwrap();
+/// let res = nut_rank(&pocket, &board, &StdDeckCardMask::new()).unwrap();
+/// assert_eq!(res.nut_rank, 1);
+/// ```
+pub fn nut_rank(
+    pocket: &StdDeckCardMask,
+    board: &StdDeckCardMask,
+    dead: &StdDeckCardMask,
+) -> Result<NutRankResult, PokerError> {
+    let npocket = pocket.num_cards();
+    if !(3..=5).contains(&board.num_cards()) {
+        return Err(PokerError::UnsupportedBoardConfiguration);
+    }
+    if npocket != 2 && !(4..=6).contains(&npocket) {
+        return Err(PokerError::InvalidCardConfiguration(
+            "Nut rank needs 2 (Hold'em) or 4-6 (Omaha) hole cards".to_string(),
+        ));
+    }
+    if (pocket.as_raw() & (board.as_raw() | dead.as_raw())) != 0
+        || (board.as_raw() & dead.as_raw()) != 0
+    {
+        return Err(PokerError::InvalidCardConfiguration(
+            "Pocket, board and dead cards must not overlap".to_string(),
+        ));
+    }
+
+    let omaha = npocket > 2;
+    let eval = |hole: &StdDeckCardMask| -> Result<u32, PokerError> {
+        if omaha {
+            Ok(OmahaHiEvaluator::evaluate_hand(hole, board)?
+                .map(|v| v.value)
+                .unwrap_or(0))
+        } else {
+            let hand = *hole | *board;
+            Ok(Eval::eval_n(&hand, hand.num_cards()).value)
+        }
+    };
+    let detailed = |hole: &StdDeckCardMask| -> HandVal {
+        if omaha {
+            OmahaHiEvaluator::evaluate_detailed(hole, board).unwrap_or_default()
+        } else {
+            let hand = *hole | *board;
+            Eval::eval_n_detailed(&hand, hand.num_cards())
+        }
+    };
+
+    let hero = eval(pocket)?;
+
+    // Opponent holdings include our own cards so we can count what we block.
+    let excluded = *board | *dead;
+    let deck: Vec<usize> = (0..STD_DECK_N_CARDS)
+        .filter(|&i| !excluded.card_is_set(i))
+        .collect();
+
+    // fast value -> (combos, blocked, example)
+    let mut classes: HashMap<u32, (usize, usize, StdDeckCardMask)> = HashMap::new();
+    let mut tie_combos = 0;
+    let mut total_combos = 0;
+    let mut error = None;
+
+    for_each_combination(deck.len(), npocket, |idx| {
+        if error.is_some() {
+            return;
+        }
+        let mut hole = StdDeckCardMask::new();
+        for &i in idx {
+            hole.set(deck[i]);
+        }
+        let is_blocked = (hole.as_raw() & pocket.as_raw()) != 0;
+        if !is_blocked {
+            total_combos += 1;
+        }
+        let value = match eval(&hole) {
+            Ok(v) => v,
+            Err(e) => {
+                error = Some(e);
+                return;
+            }
+        };
+        if value == hero {
+            if !is_blocked {
+                tie_combos += 1;
+            }
+        } else if value > hero {
+            let entry = classes.entry(value).or_insert((0, 0, hole));
+            if is_blocked {
+                entry.1 += 1;
+            } else {
+                if entry.0 == 0 {
+                    entry.2 = hole;
+                }
+                entry.0 += 1;
+            }
+        }
+    });
+    if let Some(e) = error {
+        return Err(e);
+    }
+
+    let mut ranked: Vec<(u32, (usize, usize, StdDeckCardMask))> = classes.into_iter().collect();
+    ranked.sort_by_key(|(v, _)| std::cmp::Reverse(*v));
+
+    let better: Vec<NutClass> = ranked
+        .into_iter()
+        .map(|(_, (combos, blocked, example))| NutClass {
+            value: detailed(&example),
+            combos,
+            blocked,
+            example,
+        })
+        .collect();
+
+    let available = better.iter().filter(|c| c.combos > 0).count();
+    Ok(NutRankResult {
+        value: detailed(pocket),
+        nut_rank: available + 1,
+        nut_rank_unblocked: better.len() + 1,
+        beat_combos: better.iter().map(|c| c.combos).sum(),
+        tie_combos,
+        total_combos,
+        better,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::StdDeck;
+    use crate::rules::HandType;
+
+    fn rank_of(pocket: &str, board: &str) -> NutRankResult {
+        let (p, _) = StdDeck::string_to_mask(pocket).unwrap();
+        let (b, _) = StdDeck::string_to_mask(board).unwrap();
+        nut_rank(&p, &b, &StdDeckCardMask::new()).unwrap()
+    }
+
+    #[test]
+    fn test_nut_straight_and_second_nuts() {
+        // On Q-J-T-4-2 rainbow, AK is the nut straight.
+        let res = rank_of("AhKc", "QdJsTc4h2d");
+        assert!(res.is_nuts());
+        assert_eq!(res.beat_combos, 0);
+        // Other AK combos chop.
+        assert_eq!(res.tie_combos, 9);
+        assert_eq!(res.total_combos, 990);
+
+        // K9 only loses to AK.
+        let res = rank_of("Kh9c", "QdJsTc4h2d");
+        assert_eq!(res.nut_rank, 2);
+        assert_eq!(res.better.len(), 1);
+        assert_eq!(res.better[0].value.get_hand_type(), HandType::Straight);
+        // We hold a king: 3 aces x 3 kings remain, 4 AK combos blocked.
+        assert_eq!(res.better[0].combos, 12);
+        assert_eq!(res.better[0].blocked, 4);
+    }
+
+    #[test]
+    fn test_blockers_change_nut_rank() {
+        // Ace-high flush on a four-heart board with no pair or straight flush
+        // possible. Only flushes holding the Ah and a second heart beat it, and we
+        // hold the Ah.
+        let res = rank_of("AhTc", "Kh9h5h2h7d");
+        assert_eq!(res.nut_rank, 1);
+        assert_eq!(res.nut_rank_unblocked, 9);
+        let better: Vec<String> = res.better.iter().map(|c| c.example.to_string()).collect();
+        assert_eq!(
+            better,
+            ["Qh Ah", "Jh Ah", "Th Ah", "8h Ah", "7h Ah", "6h Ah", "4h Ah", "3h Ah"]
+        );
+        assert!(res.better.iter().all(|c| c.combos == 0 && c.blocked == 1));
+        assert_eq!(res.blocked_beat_combos(), 8);
+    }
+
+    #[test]
+    fn test_omaha_nut_rank() {
+        // Omaha must use two hole cards: a single heart makes no flush.
+        let res = rank_of("AhKcQdJs", "Th9h5h2c3d");
+        assert!(res.nut_rank > 1);
+        assert!(res
+            .better
+            .iter()
+            .any(|c| c.value.get_hand_type() == HandType::Flush));
+    }
+
+    #[test]
+    fn test_nut_rank_rejects_bad_board() {
+        let (p, _) = StdDeck::string_to_mask("AhKc").unwrap();
+        let (b, _) = StdDeck::string_to_mask("QdJs").unwrap();
+        assert_eq!(
+            nut_rank(&p, &b, &StdDeckCardMask::new()),
+            Err(PokerError::UnsupportedBoardConfiguration)
+        );
+    }
+}
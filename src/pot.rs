@@ -0,0 +1,297 @@
+//! Side-pot and split-pot distribution at showdown.
+//!
+//! [`side_pots`] splits the players' contributions into a main pot and side pots,
+//! and [`distribute`] awards each pot to the best hands among the players who can
+//! win it. Chips are integers, so every pot is paid out exactly:
+//!
+//! - A player can only win, from each opponent, as much as they put in; a bet nobody
+//!   matched comes back to its owner as a pot they alone can win.
+//! - Folded players' chips stay in the pots but they cannot win them.
+//! - In hi/lo games a pot is split in half between the best high and the best
+//!   qualifying low, with the odd chip going to the high half. If nobody has a
+//!   qualifying low, the high hand scoops. Each half is shared by tied hands, so a
+//!   player tying for one half wins a quarter.
+//! - Chips that do not divide evenly between tied hands go one at a time to the
+//!   tied players in seat order. Players are indexed in seat order starting with
+//!   the first seat left of the button.
+
+use crate::errors::PokerError;
+use crate::handval::HandVal;
+use crate::handval_low::{LowHandVal, LOW_HAND_VAL_NOTHING};
+
+/// A pot and the players who can win it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SidePot {
+    /// Chips in the pot.
+    pub amount: u64,
+    /// Indices of the players still in the hand who contributed to every layer of
+    /// this pot, in seat order.
+    pub eligible: Vec<usize>,
+}
+
+/// A player's hands at showdown.
+///
+/// `hi` is compared higher-is-better and `lo` lower-is-better. Leave `lo` as `None`
+/// (or [`LOW_HAND_VAL_NOTHING`]) when the player has no qualifying low, and `hi` as
+/// `None` in low-only games.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Showdown {
+    /// The high hand.
+    pub hi: Option<HandVal>,
+    /// The qualifying low hand.
+    pub lo: Option<LowHandVal>,
+}
+
+impl Showdown {
+    /// A showdown with only a high hand.
+    pub fn hi(hi: HandVal) -> Self {
+        Self {
+            hi: Some(hi),
+            lo: None,
+        }
+    }
+
+    /// A showdown for a hi/lo game; `lo` is `None` without a qualifying low.
+    pub fn hilo(hi: HandVal, lo: Option<LowHandVal>) -> Self {
+        Self { hi: Some(hi), lo }
+    }
+
+    /// A showdown with only a low hand, for lowball games.
+    pub fn lo(lo: LowHandVal) -> Self {
+        Self {
+            hi: None,
+            lo: Some(lo),
+        }
+    }
+
+    fn qualifying_lo(&self) -> Option<u32> {
+        self.lo
+            .map(|v| v.value)
+            .filter(|&v| v != LOW_HAND_VAL_NOTHING)
+    }
+}
+
+/// Splits `contributions` into a main pot followed by side pots.
+///
+/// `live[i]` is false for players who folded: their chips go into the pots they
+/// reached, but they are never eligible. Chips folded players put in above every
+/// live player's contribution go to the last pot, or to a single pot for every live
+/// player when none of them put in any chips.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::pot::side_pots;
+///
+/// // Player 0 is all-in for 50, players 1 and 2 put in 200, player 3 folded after 30.
+/// let pots = side_pots(&[50, 200, 200, 30], &[true, true, true, false]);
+/// assert_eq!(pots[0].amount, 180);
+/// assert_eq!(pots[0].eligible, vec![0, 1, 2]);
+/// assert_eq!(pots[1].amount, 300);
+/// assert_eq!(pots[1].eligible, vec![1, 2]);
+/// ```
+pub fn side_pots(contributions: &[u64], live: &[bool]) -> Vec<SidePot> {
+    let is_live = |p: usize| live.get(p).copied().unwrap_or(false);
+    let mut levels: Vec<u64> = (0..contributions.len())
+        .filter(|&p| is_live(p) && contributions[p] > 0)
+        .map(|p| contributions[p])
+        .collect();
+    levels.sort_unstable();
+    levels.dedup();
+
+    let mut pots = Vec::with_capacity(levels.len());
+    let mut floor = 0;
+    for level in levels {
+        let amount = contributions
+            .iter()
+            .map(|&c| c.min(level) - c.min(floor))
+            .sum();
+        let eligible = (0..contributions.len())
+            .filter(|&p| is_live(p) && contributions[p] >= level)
+            .collect();
+        pots.push(SidePot { amount, eligible });
+        floor = level;
+    }
+    let rest: u64 = contributions.iter().map(|&c| c - c.min(floor)).sum();
+    if let Some(last) = pots.last_mut() {
+        last.amount += rest;
+    } else if rest > 0 {
+        let eligible: Vec<usize> = (0..contributions.len()).filter(|&p| is_live(p)).collect();
+        if !eligible.is_empty() {
+            pots.push(SidePot {
+                amount: rest,
+                eligible,
+            });
+        }
+    }
+    pots
+}
+
+/// Distributes every pot and returns the chips each player collects.
+///
+/// `hands[i]` is `None` for a player who folded. The payouts always add up to the
+/// total of `contributions`.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::handval::HandVal;
+/// use poker_eval_rs::pot::{distribute, Showdown};
+///
+/// // A short stack wins the main pot; the side pot goes to the better of the others.
+/// let hands = [
+///     Some(Showdown::hi(HandVal { value: 300 })),
+///     Some(Showdown::hi(HandVal { value: 200 })),
+///     Some(Showdown::hi(HandVal { value: 100 })),
+/// ];
+/// let payouts = distribute(&[50, 200, 200], &hands).unwrap();
+/// assert_eq!(payouts, vec![150, 300, 0]);
+/// ```
+pub fn distribute(
+    contributions: &[u64],
+    hands: &[Option<Showdown>],
+) -> Result<Vec<u64>, PokerError> {
+    if contributions.len() != hands.len() {
+        return Err(PokerError::InvalidInput(format!(
+            "Got {} contributions but {} hands",
+            contributions.len(),
+            hands.len()
+        )));
+    }
+    let live: Vec<bool> = hands.iter().map(Option::is_some).collect();
+    if !live.contains(&true) && contributions.iter().any(|&c| c > 0) {
+        return Err(PokerError::InvalidInput(
+            "At least one player must reach showdown".to_string(),
+        ));
+    }
+
+    let mut payouts = vec![0; contributions.len()];
+    for pot in side_pots(contributions, &live) {
+        let showdown = |p: &usize| hands[*p].unwrap_or_default();
+        let his: Vec<(usize, u32)> = pot
+            .eligible
+            .iter()
+            .filter_map(|p| showdown(p).hi.map(|v| (*p, v.value)))
+            .collect();
+        let los: Vec<(usize, u32)> = pot
+            .eligible
+            .iter()
+            .filter_map(|p| showdown(p).qualifying_lo().map(|v| (*p, v)))
+            .collect();
+
+        match (his.is_empty(), los.is_empty()) {
+            (false, false) => {
+                let lo_half = pot.amount / 2;
+                award(pot.amount - lo_half, &best(&his, true), &mut payouts);
+                award(lo_half, &best(&los, false), &mut payouts);
+            }
+            (false, true) => award(pot.amount, &best(&his, true), &mut payouts),
+            (true, false) => award(pot.amount, &best(&los, false), &mut payouts),
+            (true, true) => award(pot.amount, &pot.eligible, &mut payouts),
+        }
+    }
+    Ok(payouts)
+}
+
+/// Players holding the best value, in seat order.
+fn best(values: &[(usize, u32)], higher_wins: bool) -> Vec<usize> {
+    let target = if higher_wins {
+        values.iter().map(|(_, v)| *v).max()
+    } else {
+        values.iter().map(|(_, v)| *v).min()
+    };
+    values
+        .iter()
+        .filter(|(_, v)| Some(*v) == target)
+        .map(|(p, _)| *p)
+        .collect()
+}
+
+/// Splits `amount` evenly between `winners`, odd chips going to the first seats.
+fn award(amount: u64, winners: &[usize], payouts: &mut [u64]) {
+    if winners.is_empty() {
+        return;
+    }
+    let n = winners.len() as u64;
+    let odd = (amount % n) as usize;
+    for (i, &p) in winners.iter().enumerate() {
+        payouts[p] += amount / n + u64::from(i < odd);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hi(v: u32) -> Option<Showdown> {
+        Some(Showdown::hi(HandVal { value: v }))
+    }
+
+    fn hilo(h: u32, l: Option<u32>) -> Option<Showdown> {
+        Some(Showdown::hilo(
+            HandVal { value: h },
+            l.map(|value| LowHandVal { value }),
+        ))
+    }
+
+    #[test]
+    fn test_uncalled_bet_and_folded_chips() {
+        // Player 2 folded after putting in 40; player 1 over-bet to 300.
+        let payouts = distribute(&[100, 300, 40], &[hi(2), hi(1), None]).unwrap();
+        assert_eq!(payouts, vec![240, 200, 0]);
+
+        // A folded player's chips above every live contribution go to the last pot.
+        let pots = side_pots(&[10, 10, 50], &[true, true, false]);
+        assert_eq!(pots.len(), 1);
+        assert_eq!(pots[0].amount, 70);
+
+        // Only folded players put chips in; the live players still contest them.
+        let pots = side_pots(&[0, 30, 0], &[true, false, true]);
+        assert_eq!(pots.len(), 1);
+        assert_eq!(pots[0].amount, 30);
+        assert_eq!(pots[0].eligible, vec![0, 2]);
+        let payouts = distribute(&[0, 30, 0], &[hi(1), None, hi(2)]).unwrap();
+        assert_eq!(payouts, vec![0, 0, 30]);
+    }
+
+    #[test]
+    fn test_split_odd_chips_in_seat_order() {
+        let payouts = distribute(&[35, 35, 35], &[hi(5), hi(1), hi(5)]).unwrap();
+        assert_eq!(payouts, vec![53, 0, 52]);
+    }
+
+    #[test]
+    fn test_hilo_quartering() {
+        // Players 0 and 1 tie for high; player 1 also has the only low.
+        let payouts = distribute(
+            &[100, 100, 100],
+            &[hilo(9, None), hilo(9, Some(3)), hilo(1, None)],
+        )
+        .unwrap();
+        assert_eq!(payouts, vec![75, 225, 0]);
+
+        // Two identical lows quarter the low half; the odd chip goes high.
+        let payouts = distribute(
+            &[51, 50, 50],
+            &[hilo(9, Some(3)), hilo(1, Some(3)), hilo(2, None)],
+        )
+        .unwrap();
+        // Main pot 150: high 75 to player 0, low 75 split 38/37.
+        // Side pot of 1 chip is player 0's uncalled bet.
+        assert_eq!(payouts, vec![75 + 38 + 1, 37, 0]);
+        assert_eq!(payouts.iter().sum::<u64>(), 151);
+    }
+
+    #[test]
+    fn test_no_qualifying_low_scoops() {
+        let nothing = LowHandVal {
+            value: LOW_HAND_VAL_NOTHING,
+        };
+        let hands = [
+            Some(Showdown::hilo(HandVal { value: 4 }, Some(nothing))),
+            hilo(3, None),
+        ];
+        assert_eq!(distribute(&[20, 20], &hands).unwrap(), vec![40, 0]);
+        assert!(distribute(&[20, 20], &hands[..1]).is_err());
+    }
+}
@@ -1,18 +1,33 @@
 // Python bindings module
 
-use crate::board::{calculate_outs as calc_outs_rust, BoardTexture};
+use crate::board::{
+    calculate_game_outs as calc_game_outs_rust, calculate_outs_vs_range,
+    calculate_outs_with_dead as calc_outs_rust, BoardTexture, OutsResult,
+};
 use crate::deck::*;
-use crate::enumdefs::{EnumResult, Game, SampleType, ENUM_MAXPLAYERS};
-use crate::enumerate::{enum_exhaustive, enum_sample, CardMask};
-use crate::evaluators::range_equity::calculate_equity as calc_equity_rust;
+use crate::enumdefs::Game;
+use crate::enumerate::CardMask;
+use crate::errors::PokerError;
+use crate::evaluators::range_equity::calculate_equity_chunked as calc_equity_rust;
+use crate::evaluators::range_equity::equity_vs_random_chunked as calc_vs_random_rust;
 use crate::evaluators::{
-    Eval, EvalJoker, HandEvaluator, OmahaHiEvaluator, OmahaHiLoEvaluator, ShortDeckEvaluator,
+    batch, Eval, EvalJoker, HandEvaluator, OmahaHiEvaluator, OmahaHiLoEvaluator, ShortDeckEvaluator,
 };
+use crate::game_rules::GameRef;
+use crate::handval::HandVal;
+use crate::handval_low::{LowHandVal, LOW_HAND_VAL_NOTHING};
+use crate::query::{EquityMode, EquityQuery, EquityReport, PlayerEquity, Progress};
 use crate::range::HandRange;
-use crate::tables::t_cardmasks::StdDeckCardMask;
+use crate::rules::joker::{JOKER_RULES_HAND_TYPE_NAMES, JOKER_RULES_N_SIG_CARDS};
+use crate::rules::{HandType, HAND_TYPE_NAMES};
+use crate::tables::t_cardmasks::{StdDeckCardMask, STD_DECK_N_CARDS};
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1, PyReadonlyArray2};
+use pyo3::basic::CompareOp;
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyCFunction, PyDict};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 /// Helper to parse card string and validate no duplicates
 fn parse_and_validate(input: &str) -> PyResult<(StdDeckCardMask, usize)> {
@@ -162,6 +177,230 @@ impl PyHand {
     }
 }
 
+/// Rank characters of the significant cards of a high hand, best first. The joker
+/// tables extend the standard ones with five of a kind.
+fn hand_val_ranks(val: &HandVal) -> Vec<String> {
+    let cards = [
+        val.top_card(),
+        val.second_card(),
+        val.third_card(),
+        val.fourth_card(),
+        val.fifth_card(),
+    ];
+    let n_sig = JOKER_RULES_N_SIG_CARDS[val.get_joker_hand_type().as_usize()];
+    cards[..n_sig]
+        .iter()
+        .filter_map(|&r| STD_DECK_RANK_CHARS.chars().nth(r as usize))
+        .map(String::from)
+        .collect()
+}
+
+/// Rank characters of a low hand, worst first. Low ranks count the ace as 1.
+fn low_hand_val_ranks(val: &LowHandVal) -> Vec<String> {
+    [
+        val.top_card(),
+        val.second_card(),
+        val.third_card(),
+        val.fourth_card(),
+        val.fifth_card(),
+    ]
+    .iter()
+    .filter_map(|&r| match r {
+        0 => None,
+        1 => Some('A'),
+        r => STD_DECK_RANK_CHARS.chars().nth(r as usize - 2),
+    })
+    .map(String::from)
+    .collect()
+}
+
+/// A high hand value. Better hands compare greater; values are comparable when
+/// they come from the same evaluation function.
+#[pyclass(name = "HandVal", frozen)]
+#[derive(Clone, Copy, Debug)]
+pub struct PyHandVal {
+    inner: HandVal,
+    /// The same hand from a detailed evaluator, whose card fields hold real ranks.
+    detail: HandVal,
+}
+
+impl PyHandVal {
+    fn detailed(inner: HandVal, detail: HandVal) -> Self {
+        PyHandVal { inner, detail }
+    }
+}
+
+#[pymethods]
+impl PyHandVal {
+    /// The raw encoded value; higher is better.
+    #[getter]
+    pub fn value(&self) -> u32 {
+        self.inner.value
+    }
+
+    /// The hand type name (e.g. "Flush").
+    #[getter]
+    pub fn hand_type(&self) -> &'static str {
+        JOKER_RULES_HAND_TYPE_NAMES[self.detail.get_joker_hand_type().as_usize()]
+    }
+
+    /// The hand type index, from 0 (NoPair) to 8 (StFlush), or 9 (Quints) with a joker.
+    #[getter]
+    pub fn hand_type_id(&self) -> u8 {
+        self.detail.hand_type()
+    }
+
+    /// Ranks of the significant cards (e.g. ["A", "K"] for aces full of kings).
+    #[getter]
+    pub fn ranks(&self) -> Vec<String> {
+        hand_val_ranks(&self.detail)
+    }
+
+    /// Rank index (0 = deuce, 12 = ace) of the top significant card, as in `Card.rank`.
+    #[getter]
+    pub fn top_card(&self) -> u8 {
+        self.detail.top_card()
+    }
+
+    #[getter]
+    pub fn second_card(&self) -> u8 {
+        self.detail.second_card()
+    }
+
+    #[getter]
+    pub fn third_card(&self) -> u8 {
+        self.detail.third_card()
+    }
+
+    #[getter]
+    pub fn fourth_card(&self) -> u8 {
+        self.detail.fourth_card()
+    }
+
+    #[getter]
+    pub fn fifth_card(&self) -> u8 {
+        self.detail.fifth_card()
+    }
+
+    pub fn __richcmp__(&self, other: PyRef<'_, Self>, op: CompareOp) -> bool {
+        op.matches(self.inner.value.cmp(&other.inner.value))
+    }
+
+    pub fn __hash__(&self) -> u64 {
+        self.inner.value as u64
+    }
+
+    pub fn __int__(&self) -> u32 {
+        self.inner.value
+    }
+
+    pub fn __str__(&self) -> String {
+        self.detail.joker_rules_hand_val_to_string()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("<HandVal {}>", self.__str__())
+    }
+}
+
+/// Evaluates a high hand, keeping the detailed value for display.
+fn eval_hand_val(mask: &StdDeckCardMask, num_cards: usize) -> PyHandVal {
+    PyHandVal::detailed(
+        Eval::eval_n(mask, num_cards),
+        Eval::eval_n_detailed(mask, num_cards),
+    )
+}
+
+/// A low hand value. Better lows compare greater, so `max()` picks the winning low
+/// just as it picks the winning high hand; `value` keeps the raw encoding, where
+/// lower is better.
+#[pyclass(name = "LowHandVal", frozen)]
+#[derive(Clone, Copy, Debug)]
+pub struct PyLowHandVal {
+    inner: LowHandVal,
+}
+
+#[pymethods]
+impl PyLowHandVal {
+    /// The raw encoded value; lower is better.
+    #[getter]
+    pub fn value(&self) -> u32 {
+        self.inner.value
+    }
+
+    /// The hand type name; "NoPair" for a qualifying low.
+    #[getter]
+    pub fn hand_type(&self) -> &'static str {
+        HAND_TYPE_NAMES
+            .get(self.inner.hand_type() as usize)
+            .copied()
+            .unwrap_or("Unknown")
+    }
+
+    /// The hand type index, from 0 (NoPair) to 8.
+    #[getter]
+    pub fn hand_type_id(&self) -> u8 {
+        self.inner.hand_type()
+    }
+
+    /// Ranks of the hand, highest first (e.g. ["5", "4", "3", "2", "A"] for a wheel).
+    #[getter]
+    pub fn ranks(&self) -> Vec<String> {
+        low_hand_val_ranks(&self.inner)
+    }
+
+    #[getter]
+    pub fn top_card(&self) -> u8 {
+        self.inner.top_card()
+    }
+
+    #[getter]
+    pub fn second_card(&self) -> u8 {
+        self.inner.second_card()
+    }
+
+    #[getter]
+    pub fn third_card(&self) -> u8 {
+        self.inner.third_card()
+    }
+
+    #[getter]
+    pub fn fourth_card(&self) -> u8 {
+        self.inner.fourth_card()
+    }
+
+    #[getter]
+    pub fn fifth_card(&self) -> u8 {
+        self.inner.fifth_card()
+    }
+
+    pub fn __richcmp__(&self, other: PyRef<'_, Self>, op: CompareOp) -> bool {
+        op.matches(other.inner.value.cmp(&self.inner.value))
+    }
+
+    pub fn __hash__(&self) -> u64 {
+        self.inner.value as u64
+    }
+
+    pub fn __int__(&self) -> u32 {
+        self.inner.value
+    }
+
+    pub fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("<LowHandVal {}>", self.inner)
+    }
+}
+
+impl From<LowHandVal> for PyLowHandVal {
+    fn from(inner: LowHandVal) -> Self {
+        PyLowHandVal { inner }
+    }
+}
+
 #[pyfunction]
 pub fn string_to_mask(input: &str) -> PyResult<String> {
     let (mask, _) = parse_and_validate(input)?;
@@ -169,22 +408,21 @@ pub fn string_to_mask(input: &str) -> PyResult<String> {
 }
 
 #[pyfunction]
-pub fn eval_n(input: &str) -> PyResult<String> {
+pub fn eval_n(input: &str) -> PyResult<PyHandVal> {
     let (mask, num_cards) = parse_and_validate(input)?;
-    let hand_val = Eval::eval_n(&mask, num_cards);
-    Ok(hand_val.std_rules_hand_val_to_string())
+    Ok(eval_hand_val(&mask, num_cards))
 }
 
 #[pyfunction]
-pub fn eval_low_func(input: &str) -> PyResult<String> {
+pub fn eval_low_func(input: &str) -> PyResult<PyLowHandVal> {
     let (mask, num_cards) = parse_and_validate(input)?;
-    let low_hand_val = crate::evaluators::std_deck_lowball_eval(&mask, num_cards);
-    Ok(low_hand_val.to_string())
+    Ok(crate::evaluators::std_deck_lowball_eval(&mask, num_cards).into())
 }
 
 /// Evaluate an Omaha hand (4 hole cards + 5 board cards)
+/// Returns None if no valid hand can be made
 #[pyfunction]
-pub fn eval_omaha_hi(hole_cards: &str, board: &str) -> PyResult<String> {
+pub fn eval_omaha_hi(hole_cards: &str, board: &str) -> PyResult<Option<PyHandVal>> {
     let (hole_mask, hole_count) = parse_and_validate(hole_cards).map_err(|e| {
         PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing hole cards: {}", e))
     })?;
@@ -206,10 +444,9 @@ pub fn eval_omaha_hi(hole_cards: &str, board: &str) -> PyResult<String> {
     }
 
     match OmahaHiEvaluator::evaluate_hand(&hole_mask, &board_mask) {
-        Ok(hival) => match hival {
-            Some(hand_val) => Ok(hand_val.std_rules_hand_val_to_string()),
-            None => Ok("No valid hand".to_string()),
-        },
+        Ok(hival) => Ok(hival
+            .zip(OmahaHiEvaluator::evaluate_detailed(&hole_mask, &board_mask))
+            .map(|(hi, detail)| PyHandVal::detailed(hi, detail))),
         Err(e) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
             "Omaha evaluation error: {:?}",
             e
@@ -218,10 +455,13 @@ pub fn eval_omaha_hi(hole_cards: &str, board: &str) -> PyResult<String> {
 }
 
 /// Evaluate an Omaha Hi/Lo hand (4 hole cards + 5 board cards)
-/// Returns a tuple (hi_hand_string, lo_hand_string)
-/// lo_hand_string is "No valid low" if no low hand qualifies
+/// Returns a tuple (HandVal, LowHandVal)
+/// The low is None if no low hand qualifies
 #[pyfunction]
-pub fn eval_omaha_hi_lo(hole_cards: &str, board: &str) -> PyResult<(String, String)> {
+pub fn eval_omaha_hi_lo(
+    hole_cards: &str,
+    board: &str,
+) -> PyResult<(Option<PyHandVal>, Option<PyLowHandVal>)> {
     let (hole_mask, hole_count) = parse_and_validate(hole_cards).map_err(|e| {
         PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing hole cards: {}", e))
     })?;
@@ -244,15 +484,10 @@ pub fn eval_omaha_hi_lo(hole_cards: &str, board: &str) -> PyResult<(String, Stri
 
     match OmahaHiLoEvaluator::evaluate_hand(&hole_mask, &board_mask) {
         Ok((hival, loval)) => {
-            let hi_str = match hival {
-                Some(hand_val) => hand_val.std_rules_hand_val_to_string(),
-                None => "No valid hand".to_string(),
-            };
-            let lo_str = match loval {
-                Some(hand_val) => hand_val.to_string(),
-                None => "No valid low".to_string(),
-            };
-            Ok((hi_str, lo_str))
+            let hival = hival
+                .zip(OmahaHiEvaluator::evaluate_detailed(&hole_mask, &board_mask))
+                .map(|(hi, detail)| PyHandVal::detailed(hi, detail));
+            Ok((hival, loval.map(PyLowHandVal::from)))
         }
         Err(e) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
             "Omaha Hi/Lo evaluation error: {:?}",
@@ -263,7 +498,7 @@ pub fn eval_omaha_hi_lo(hole_cards: &str, board: &str) -> PyResult<(String, Stri
 
 /// Evaluate a Short Deck (Six Plus) Hold'em hand
 #[pyfunction]
-pub fn eval_short_deck(hole_cards: &str, board: &str) -> PyResult<String> {
+pub fn eval_short_deck(hole_cards: &str, board: &str) -> PyResult<PyHandVal> {
     let (hole_mask, hole_count) = parse_and_validate(hole_cards).map_err(|e| {
         PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing hole cards: {}", e))
     })?;
@@ -285,7 +520,21 @@ pub fn eval_short_deck(hole_cards: &str, board: &str) -> PyResult<String> {
     }
 
     match ShortDeckEvaluator::evaluate_hand(&hole_mask, &board_mask) {
-        Ok(val) => Ok(val.std_rules_hand_val_to_string()),
+        Ok(val) => {
+            let hand = hole_mask | board_mask;
+            let detail = Eval::eval_n_detailed(&hand, hand.num_cards());
+            // The A-6-7-8-9 straights are only known to the short deck evaluator,
+            // which builds them with their ranks already set.
+            let straight = |v: &HandVal| {
+                v.hand_type() == HandType::Straight as u8
+                    || v.hand_type() == HandType::StFlush as u8
+            };
+            if straight(&val) && !straight(&detail) {
+                Ok(PyHandVal::detailed(val, val))
+            } else {
+                Ok(PyHandVal::detailed(val, detail))
+            }
+        }
         Err(e) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
             "Short Deck evaluation error: {:?}",
             e
@@ -295,23 +544,23 @@ pub fn eval_short_deck(hole_cards: &str, board: &str) -> PyResult<String> {
 
 /// Evaluate a hand using joker rules (High hand)
 #[pyfunction]
-pub fn eval_joker(input: &str) -> PyResult<String> {
+pub fn eval_joker(input: &str) -> PyResult<PyHandVal> {
     let (mask, num_cards) = parse_and_validate_joker(input)?;
-    let hand_val = EvalJoker::eval_n(mask, num_cards);
-    Ok(hand_val.std_rules_hand_val_to_string())
+    // The joker evaluator sets the card fields itself.
+    let val = EvalJoker::eval_n(mask, num_cards);
+    Ok(PyHandVal::detailed(val, val))
 }
 
 /// Evaluate a Lowball hand using joker rules (A-5 Lowball with Joker)
 #[pyfunction]
-pub fn eval_lowball_joker(input: &str) -> PyResult<String> {
+pub fn eval_lowball_joker(input: &str) -> PyResult<PyLowHandVal> {
     let (mask, num_cards) = parse_and_validate_joker(input)?;
-    let low_hand_val = crate::evaluators::joker_lowball_eval(&mask, num_cards);
-    Ok(low_hand_val.to_string())
+    Ok(crate::evaluators::joker_lowball_eval(&mask, num_cards).into())
 }
 
-/// Batch evaluate multiple hands
+/// Batch evaluate multiple hands of 5 to 7 cards
 #[pyfunction]
-pub fn eval_n_batch(hands: Vec<String>) -> PyResult<Vec<String>> {
+pub fn eval_n_batch(hands: Vec<String>) -> PyResult<Vec<PyHandVal>> {
     let mut results = Vec::with_capacity(hands.len());
 
     for hand_str in hands {
@@ -321,214 +570,587 @@ pub fn eval_n_batch(hands: Vec<String>) -> PyResult<Vec<String>> {
                 hand_str, e
             ))
         })?;
-        let hand_val = Eval::eval_n(&mask, num_cards);
-        results.push(hand_val.std_rules_hand_val_to_string());
+        if !(5..=7).contains(&num_cards) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Hand '{}' has {} cards; expected 5 to 7",
+                hand_str, num_cards
+            )));
+        }
+        results.push(eval_hand_val(&mask, num_cards));
     }
 
     Ok(results)
 }
 
-/// Calculate equity between multiple hands (Texas Hold'em)
-/// Returns a dictionary with win%, tie%, and EV for each player
-#[pyfunction]
-#[pyo3(signature = (hands, board="", dead="", game="holdem", monte_carlo=false, iterations=100000))]
-pub fn calculate_equity(
-    py: Python<'_>,
-    hands: Vec<String>,
-    board: &str,
-    dead: &str,
-    game: &str,
-    monte_carlo: bool,
-    iterations: usize,
-) -> PyResult<PyObject> {
-    let npockets = hands.len();
-
-    let game_variant = match game.to_lowercase().as_str() {
-        "holdem" => Game::Holdem,
-        "holdem8" => Game::Holdem8,
-        "omaha" => Game::Omaha,
-        "omaha5" => Game::Omaha5,
-        "omaha6" => Game::Omaha6,
-        "omaha8" => Game::Omaha8,
-        "omaha85" => Game::Omaha85,
-        "stud7" => Game::Stud7,
-        "stud78" => Game::Stud78,
-        "stud7nsq" => Game::Stud7nsq,
-        "razz" => Game::Razz,
-        "draw5" => Game::Draw5,
-        "draw58" => Game::Draw58,
-        "draw5nsq" => Game::Draw5nsq,
-        "lowball" => Game::Lowball,
-        "lowball27" => Game::Lowball27,
-        "shortdeck" => Game::ShortDeck,
-        _ => {
-            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                "Unsupported game variant: {}",
-                game
-            )));
-        }
-    };
+/// Reads a NumPy array of 64-bit card masks, rejecting bits outside the 52-card deck.
+fn masks_from_array(masks: &PyReadonlyArray1<'_, u64>) -> PyResult<Vec<StdDeckCardMask>> {
+    let raw = masks.as_slice().map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Mask array error: {}", e))
+    })?;
+    let deck = StdDeckCardMask::all_cards().as_raw();
+    if let Some(i) = raw.iter().position(|&m| m & !deck != 0) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Mask {:#x} at index {} has bits outside the 52-card deck",
+            raw[i], i
+        )));
+    }
+    Ok(raw.iter().map(|&m| StdDeckCardMask::from_raw(m)).collect())
+}
 
-    if !(2..=ENUM_MAXPLAYERS).contains(&npockets) {
+/// Checks each Omaha hand has 4 hole cards, 3-5 board cards and no card in both.
+fn check_omaha_masks(holes: &[StdDeckCardMask], boards: &[StdDeckCardMask]) -> PyResult<()> {
+    if holes.len() != boards.len() {
         return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-            "Need 2-{} hands",
-            ENUM_MAXPLAYERS
+            "Got {} hole masks but {} board masks",
+            holes.len(),
+            boards.len()
         )));
     }
+    for (i, (hole, board)) in holes.iter().zip(boards).enumerate() {
+        let err = |msg: &str| {
+            Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Hand {}: {}",
+                i, msg
+            )))
+        };
+        if hole.num_cards() != 4 {
+            return err("Omaha requires exactly 4 hole cards");
+        }
+        if !(3..=5).contains(&board.num_cards()) {
+            return err("Board must have 3-5 cards");
+        }
+        if hole.overlaps(board) {
+            return err("Hole cards and board share a card");
+        }
+    }
+    Ok(())
+}
 
-    // Parse hands
-    let mut pockets: Vec<StdDeckCardMask> = Vec::new();
-    for hand in &hands {
-        let (mask, _) = parse_and_validate(hand).map_err(|e| {
-            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                "Error parsing hand '{}': {}",
-                hand, e
-            ))
-        })?;
-        pockets.push(mask);
+/// Converts an `(n, k)` array of card indices (0-51) to `n` card masks.
+#[pyfunction]
+pub fn cards_to_masks<'py>(
+    py: Python<'py>,
+    cards: PyReadonlyArray2<'py, u8>,
+) -> PyResult<Bound<'py, PyArray1<u64>>> {
+    let cards = cards.as_array();
+    let masks = py.allow_threads(|| {
+        cards
+            .rows()
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut mask = StdDeckCardMask::new();
+                for &card in row {
+                    let card = card as usize;
+                    if card >= STD_DECK_N_CARDS || mask.card_is_set(card) {
+                        return Err(format!("Hand {}: invalid or duplicate card {}", i, card));
+                    }
+                    mask.set(card);
+                }
+                Ok(mask.as_raw())
+            })
+            .collect::<Result<Vec<u64>, String>>()
+    });
+    masks
+        .map(|m| m.into_pyarray(py))
+        .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)
+}
+
+/// Evaluate high hands of 5 to 7 cards from an array of card masks.
+/// Returns the raw `HandVal` integers; higher is better. Raises ValueError naming
+/// the first mask with another number of cards.
+#[pyfunction]
+pub fn eval_n_masks<'py>(
+    py: Python<'py>,
+    masks: PyReadonlyArray1<'py, u64>,
+) -> PyResult<Bound<'py, PyArray1<u32>>> {
+    let hands = masks_from_array(&masks)?;
+    let vals = py
+        .allow_threads(|| batch::eval_n_batch(&hands))
+        .map_err(poker_error)?;
+    let vals: Vec<u32> = vals.into_iter().map(|v| v.value).collect();
+    Ok(vals.into_pyarray(py))
+}
+
+/// Evaluate A-5 lowball hands from an array of card masks.
+/// Returns the raw `LowHandVal` integers; lower is better.
+#[pyfunction]
+pub fn eval_low_masks<'py>(
+    py: Python<'py>,
+    masks: PyReadonlyArray1<'py, u64>,
+) -> PyResult<Bound<'py, PyArray1<u32>>> {
+    let hands = masks_from_array(&masks)?;
+    let vals = py.allow_threads(|| {
+        batch::eval_batch(&hands, |h| {
+            crate::evaluators::std_deck_lowball_eval(h, h.num_cards()).value
+        })
+    });
+    Ok(vals.into_pyarray(py))
+}
+
+/// Evaluate Omaha high hands from matching arrays of hole and board masks.
+/// Returns the raw `HandVal` integers; higher is better.
+#[pyfunction]
+pub fn eval_omaha_hi_masks<'py>(
+    py: Python<'py>,
+    holes: PyReadonlyArray1<'py, u64>,
+    boards: PyReadonlyArray1<'py, u64>,
+) -> PyResult<Bound<'py, PyArray1<u32>>> {
+    let holes = masks_from_array(&holes)?;
+    let boards = masks_from_array(&boards)?;
+    check_omaha_masks(&holes, &boards)?;
+    let vals = py
+        .allow_threads(|| {
+            batch::eval_pairs_batch(&holes, &boards, |h, b| {
+                OmahaHiEvaluator::evaluate_hand(h, b).map(|v| v.map_or(0, |v| v.value))
+            })
+            .into_iter()
+            .collect::<Result<Vec<u32>, PokerError>>()
+        })
+        .map_err(poker_error)?;
+    Ok(vals.into_pyarray(py))
+}
+
+/// A pair of high and low value arrays returned to Python.
+type HiLoArrays<'py> = (Bound<'py, PyArray1<u32>>, Bound<'py, PyArray1<u32>>);
+
+/// Evaluate Omaha Hi/Lo hands from matching arrays of hole and board masks.
+/// Returns `(hi, lo)` arrays of raw values; a hand without a qualifying low
+/// gets `LOW_HAND_VAL_NOTHING`, which loses to every low.
+#[pyfunction]
+pub fn eval_omaha_hi_lo_masks<'py>(
+    py: Python<'py>,
+    holes: PyReadonlyArray1<'py, u64>,
+    boards: PyReadonlyArray1<'py, u64>,
+) -> PyResult<HiLoArrays<'py>> {
+    let holes = masks_from_array(&holes)?;
+    let boards = masks_from_array(&boards)?;
+    check_omaha_masks(&holes, &boards)?;
+    let (his, los): (Vec<u32>, Vec<u32>) = py
+        .allow_threads(|| {
+            batch::eval_pairs_batch(&holes, &boards, |h, b| {
+                OmahaHiLoEvaluator::evaluate_hand(h, b).map(|(hi, lo)| {
+                    (
+                        hi.map_or(0, |v| v.value),
+                        lo.map_or(LOW_HAND_VAL_NOTHING, |v| v.value),
+                    )
+                })
+            })
+            .into_iter()
+            .collect::<Result<Vec<(u32, u32)>, PokerError>>()
+        })
+        .map_err(poker_error)?
+        .into_iter()
+        .unzip();
+    Ok((his.into_pyarray(py), los.into_pyarray(py)))
+}
+
+/// Helper to map a game name (e.g. "omaha8") to its `Game` variant
+fn parse_game(game: &str) -> PyResult<Game> {
+    game.parse().map_err(poker_error)
+}
+
+pyo3::create_exception!(
+    poker_eval_rs,
+    CalculationCancelled,
+    pyo3::exceptions::PyRuntimeError,
+    "Raised when a `CancelToken` stops `calculate_equity`."
+);
+
+/// Maps a `PokerError` to `ValueError` for bad input, `CalculationCancelled` for a
+/// cancelled run and `RuntimeError` otherwise.
+fn poker_error(e: PokerError) -> PyErr {
+    match e {
+        PokerError::InvalidInput(_)
+        | PokerError::TooManyPlayers
+        | PokerError::UnsupportedGameType
+        | PokerError::UnsupportedBoardConfiguration
+        | PokerError::InvalidCardConfiguration(_) => {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string())
+        }
+        PokerError::Cancelled => CalculationCancelled::new_err(e.to_string()),
+        _ => PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Calculation error: {}", e)),
     }
+}
 
-    // Parse board
-    let board_mask = if board.is_empty() {
-        StdDeckCardMask::new()
-    } else {
-        match parse_and_validate(board) {
-            Ok((mask, _)) => mask,
-            Err(e) => {
-                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                    "Error parsing board: {}",
-                    e
-                )));
-            }
+/// One player's equity in an `EquityResult`. Percentages are 0-100; the low
+/// and scoop stats are None outside hi/lo games.
+#[pyclass(name = "PlayerEquity", frozen)]
+#[derive(Clone, Debug)]
+pub struct PyPlayerEquity {
+    inner: PlayerEquity,
+}
+
+#[pymethods]
+impl PyPlayerEquity {
+    #[getter]
+    pub fn hand(&self) -> &str {
+        &self.inner.hand
+    }
+
+    #[getter]
+    pub fn win(&self) -> f64 {
+        self.inner.win_pct
+    }
+
+    #[getter]
+    pub fn tie(&self) -> f64 {
+        self.inner.tie_pct
+    }
+
+    #[getter]
+    pub fn lose(&self) -> f64 {
+        self.inner.lose_pct
+    }
+
+    #[getter]
+    pub fn ev(&self) -> f64 {
+        self.inner.ev
+    }
+
+    #[getter]
+    pub fn scoop(&self) -> Option<f64> {
+        self.inner.scoop_pct
+    }
+
+    #[getter]
+    pub fn win_lo(&self) -> Option<f64> {
+        self.inner.win_lo_pct
+    }
+
+    #[getter]
+    pub fn tie_lo(&self) -> Option<f64> {
+        self.inner.tie_lo_pct
+    }
+
+    #[getter]
+    pub fn lose_lo(&self) -> Option<f64> {
+        self.inner.lose_lo_pct
+    }
+
+    /// The stats as a dict; the low and scoop keys are only present in hi/lo games.
+    pub fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("hand", &self.inner.hand)?;
+        dict.set_item("win", self.inner.win_pct)?;
+        dict.set_item("tie", self.inner.tie_pct)?;
+        dict.set_item("lose", self.inner.lose_pct)?;
+        dict.set_item("ev", self.inner.ev)?;
+        if let Some(scoop) = self.inner.scoop_pct {
+            dict.set_item("scoop", scoop)?;
+            dict.set_item("win_lo", self.inner.win_lo_pct)?;
+            dict.set_item("tie_lo", self.inner.tie_lo_pct)?;
+            dict.set_item("lose_lo", self.inner.lose_lo_pct)?;
         }
-    };
+        Ok(dict)
+    }
 
-    // Parse dead cards
-    let dead_mask = if dead.is_empty() {
-        StdDeckCardMask::new()
-    } else {
-        match parse_and_validate(dead) {
-            Ok((mask, _)) => mask,
-            Err(e) => {
-                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                    "Error parsing dead cards: {}",
-                    e
-                )));
-            }
+    pub fn __repr__(&self) -> String {
+        format!(
+            "<PlayerEquity {} win={:.2} tie={:.2} ev={:.4}>",
+            self.inner.hand, self.inner.win_pct, self.inner.tie_pct, self.inner.ev
+        )
+    }
+}
+
+/// The result of `calculate_equity`.
+#[pyclass(name = "EquityResult", frozen)]
+#[derive(Clone, Debug)]
+pub struct PyEquityResult {
+    report: EquityReport,
+}
+
+#[pymethods]
+impl PyEquityResult {
+    #[getter]
+    pub fn game(&self) -> String {
+        self.report.game.to_string()
+    }
+
+    /// "exhaustive" or "monte_carlo".
+    #[getter]
+    pub fn mode(&self) -> &'static str {
+        match self.report.mode {
+            EquityMode::Exhaustive => "exhaustive",
+            EquityMode::MonteCarlo => "monte_carlo",
         }
-    };
+    }
+
+    #[getter]
+    pub fn samples(&self) -> u32 {
+        self.report.samples
+    }
+
+    #[getter]
+    pub fn board(&self) -> &str {
+        &self.report.board
+    }
+
+    #[getter]
+    pub fn dead(&self) -> &str {
+        &self.report.dead
+    }
+
+    #[getter]
+    pub fn players(&self) -> Vec<PyPlayerEquity> {
+        self.report
+            .players
+            .iter()
+            .map(|p| PyPlayerEquity { inner: p.clone() })
+            .collect()
+    }
+
+    /// The whole result as nested dicts and lists.
+    pub fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("game", self.game())?;
+        dict.set_item("mode", self.mode())?;
+        dict.set_item("samples", self.report.samples)?;
+        dict.set_item("board", &self.report.board)?;
+        dict.set_item("dead", &self.report.dead)?;
+        let players = self
+            .players()
+            .iter()
+            .map(|p| p.to_dict(py))
+            .collect::<PyResult<Vec<_>>>()?;
+        dict.set_item("players", players)?;
+        Ok(dict)
+    }
+
+    /// One flat dict per player, with every key present, for
+    /// `pandas.DataFrame(result.to_records())`.
+    pub fn to_records<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.report
+            .players
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let dict = PyDict::new(py);
+                dict.set_item("player", i)?;
+                dict.set_item("hand", &p.hand)?;
+                dict.set_item("win", p.win_pct)?;
+                dict.set_item("tie", p.tie_pct)?;
+                dict.set_item("lose", p.lose_pct)?;
+                dict.set_item("ev", p.ev)?;
+                dict.set_item("scoop", p.scoop_pct)?;
+                dict.set_item("win_lo", p.win_lo_pct)?;
+                dict.set_item("tie_lo", p.tie_lo_pct)?;
+                dict.set_item("lose_lo", p.lose_lo_pct)?;
+                Ok(dict)
+            })
+            .collect()
+    }
 
-    // Check for duplicate cards across hands, board, and dead cards
-    let mut collision_check = board_mask;
-    if !collision_check.is_empty() && !dead_mask.is_empty() {
-        let mut board_clone = board_mask;
-        board_clone.and(&dead_mask);
-        if !board_clone.is_empty() {
-            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                "Overlap between board and dead cards",
+    pub fn __len__(&self) -> usize {
+        self.report.players.len()
+    }
+
+    pub fn __getitem__(&self, index: isize) -> PyResult<PyPlayerEquity> {
+        let len = self.report.players.len() as isize;
+        let i = if index < 0 { index + len } else { index };
+        if !(0..len).contains(&i) {
+            return Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>(
+                "player index out of range",
             ));
         }
+        Ok(PyPlayerEquity {
+            inner: self.report.players[i as usize].clone(),
+        })
     }
-    collision_check.or(&dead_mask);
 
-    for (i, pocket) in pockets.iter().enumerate() {
-        let mut intersection = *pocket;
-        intersection.and(&collision_check);
-        if !intersection.is_empty() {
-            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                "Hand {} contains cards already used in board, dead cards, or other hands",
-                hands[i]
-            )));
-        }
-        collision_check.or(pocket);
+    pub fn __repr__(&self) -> String {
+        format!(
+            "<EquityResult {} players={} samples={}>",
+            self.report.game,
+            self.report.players.len(),
+            self.report.samples
+        )
     }
+}
 
-    let nboard = board_mask.num_cards();
+/// A flag for stopping an equity calculation from another thread or task.
+/// `calculate_equity`, `calculate_range_equity` and `calculate_equity_vs_random`
+/// check it between chunks and raise `CalculationCancelled`, or
+/// `asyncio.CancelledError` from `calculate_equity_async`.
+#[pyclass(name = "CancelToken", frozen)]
+#[derive(Clone, Default)]
+pub struct PyCancelToken {
+    flag: Arc<AtomicBool>,
+}
 
-    // Initialize result
-    let mut result = EnumResult::new(game_variant);
-    result.sample_type = if monte_carlo {
-        SampleType::Sample
-    } else {
-        SampleType::Exhaustive
-    };
-    result.nplayers = npockets as u32;
-
-    // Run calculation
-    let calc_result = if monte_carlo {
-        enum_sample(
-            game_variant,
-            &pockets,
-            board_mask,
-            dead_mask,
-            npockets,
-            nboard,
-            iterations,
-            false,
-            &mut result,
-        )
-    } else {
-        enum_exhaustive(
-            game_variant,
-            &pockets,
-            board_mask,
-            dead_mask,
-            npockets,
-            nboard,
-            false,
-            &mut result,
-        )
-    };
+#[pymethods]
+impl PyCancelToken {
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    match calc_result {
-        Ok(_) => {
-            let dict = PyDict::new(py);
-            dict.set_item("samples", result.nsamples)?;
-
-            let mut players = Vec::new();
-            for (i, hand) in hands.iter().enumerate().take(npockets) {
-                let total = result.nwinhi[i] + result.ntiehi[i] + result.nlosehi[i];
-                if total > 0 {
-                    let player_dict = PyDict::new(py);
-                    player_dict.set_item("hand", hand)?;
-                    player_dict
-                        .set_item("win", (result.nwinhi[i] as f64 / total as f64) * 100.0)?;
-                    player_dict
-                        .set_item("tie", (result.ntiehi[i] as f64 / total as f64) * 100.0)?;
-                    player_dict
-                        .set_item("lose", (result.nlosehi[i] as f64 / total as f64) * 100.0)?;
-                    player_dict.set_item("ev", result.ev[i])?;
-                    // Add scoop and lo stats if applicable (e.g. for hi-lo games)
-                    if result.game == Game::Omaha8
-                        || result.game == Game::Stud78
-                        || result.game == Game::Omaha85
-                        || result.game == Game::Holdem8
-                        || result.game == Game::Draw58
-                    {
-                        player_dict
-                            .set_item("scoop", (result.nscoop[i] as f64 / total as f64) * 100.0)?;
-                        player_dict
-                            .set_item("win_lo", (result.nwinlo[i] as f64 / total as f64) * 100.0)?;
-                        player_dict
-                            .set_item("tie_lo", (result.ntielo[i] as f64 / total as f64) * 100.0)?;
-                        player_dict.set_item(
-                            "lose_lo",
-                            (result.nloselo[i] as f64 / total as f64) * 100.0,
-                        )?;
-                    }
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
 
-                    players.push(player_dict);
+    #[getter]
+    pub fn cancelled(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("<CancelToken cancelled={}>", self.cancelled())
+    }
+}
+
+/// Helper to build the `EquityQuery` behind `calculate_equity` and `calculate_equity_async`
+fn equity_query(
+    hands: Vec<String>,
+    board: &str,
+    dead: &str,
+    game: &str,
+    monte_carlo: bool,
+    iterations: usize,
+    seed: Option<u64>,
+) -> PyResult<EquityQuery> {
+    Ok(EquityQuery {
+        game: GameRef::from_name(game).map_err(poker_error)?,
+        players: hands,
+        board: board.to_string(),
+        dead: dead.to_string(),
+        mode: if monte_carlo {
+            EquityMode::MonteCarlo
+        } else {
+            EquityMode::Exhaustive
+        },
+        iterations,
+        seed,
+    })
+}
+
+/// Runs `query` with the GIL released. With a progress callback or a cancel token
+/// the query runs in chunks; the callback gets `(done, total)` deals after each one
+/// and an exception it raises stops the run.
+fn run_query(
+    py: Python<'_>,
+    query: &EquityQuery,
+    progress: Option<PyObject>,
+    cancel: Option<PyCancelToken>,
+) -> PyResult<EquityReport> {
+    if progress.is_none() && cancel.is_none() {
+        return py.allow_threads(|| query.run()).map_err(poker_error);
+    }
+    with_progress(py, progress, cancel, |on_chunk| {
+        query.enumerate_chunked(on_chunk)
+    })?
+    .map(|r| query.report(&r))
+    .map_err(poker_error)
+}
+
+/// Runs `work` with the GIL released, handing it a chunk callback that reports to
+/// `progress(done, total)` and returns `false` once `cancel` is set or `progress`
+/// raises. An exception from `progress` is returned in place of the work's result.
+fn with_progress<T, W>(
+    py: Python<'_>,
+    progress: Option<PyObject>,
+    cancel: Option<PyCancelToken>,
+    work: W,
+) -> PyResult<T>
+where
+    T: Send,
+    W: FnOnce(&mut dyn FnMut(Progress) -> bool) -> T + Send,
+{
+    let (result, callback_err) = py.allow_threads(|| {
+        let mut callback_err = None;
+        let result = work(&mut |p: Progress| {
+            if let Some(cb) = &progress {
+                if let Err(e) = Python::with_gil(|py| cb.call1(py, (p.done, p.total))) {
+                    callback_err = Some(e);
+                    return false;
                 }
             }
-            dict.set_item("players", players)?;
+            !cancel.as_ref().is_some_and(|c| c.cancelled())
+        });
+        (result, callback_err)
+    });
+    match callback_err {
+        Some(e) => Err(e),
+        None => Ok(result),
+    }
+}
 
-            Ok(dict.into())
+/// Calculate equity between multiple hands (Texas Hold'em by default)
+/// Returns an EquityResult with win%, tie% and EV for each player
+///
+/// Runs with the GIL released. `progress(done, total)` is called between chunks of
+/// deals and `cancel` (a `CancelToken`) stops the run.
+#[pyfunction]
+#[pyo3(signature = (hands, board="", dead="", game="holdem", monte_carlo=false, iterations=100000, seed=None, progress=None, cancel=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_equity(
+    py: Python<'_>,
+    hands: Vec<String>,
+    board: &str,
+    dead: &str,
+    game: &str,
+    monte_carlo: bool,
+    iterations: usize,
+    seed: Option<u64>,
+    progress: Option<PyObject>,
+    cancel: Option<PyCancelToken>,
+) -> PyResult<PyEquityResult> {
+    let query = equity_query(hands, board, dead, game, monte_carlo, iterations, seed)?;
+    let report = run_query(py, &query, progress, cancel)?;
+    Ok(PyEquityResult { report })
+}
+
+/// Like `calculate_equity`, but returns an asyncio future run on the event loop's
+/// default executor. Must be called from a running event loop; cancelling the
+/// future stops the calculation at its next chunk.
+#[pyfunction]
+#[pyo3(signature = (hands, board="", dead="", game="holdem", monte_carlo=false, iterations=100000, seed=None, progress=None, cancel=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_equity_async<'py>(
+    py: Python<'py>,
+    hands: Vec<String>,
+    board: &str,
+    dead: &str,
+    game: &str,
+    monte_carlo: bool,
+    iterations: usize,
+    seed: Option<u64>,
+    progress: Option<PyObject>,
+    cancel: Option<PyCancelToken>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let query = equity_query(hands, board, dead, game, monte_carlo, iterations, seed)?;
+    let cancel = cancel.unwrap_or_default();
+    let event_loop = py.import("asyncio")?.call_method0("get_running_loop")?;
+
+    // The default executor's threads are joined at exit, unlike our own would be.
+    let token = cancel.clone();
+    let work = PyCFunction::new_closure(
+        py,
+        None,
+        None,
+        move |args, _kwargs| -> PyResult<PyEquityResult> {
+            let py = args.py();
+            let progress = progress.as_ref().map(|cb| cb.clone_ref(py));
+            let report = run_query(py, &query, progress, Some(token.clone())).map_err(|e| {
+                if e.is_instance_of::<CalculationCancelled>(py) {
+                    pyo3::exceptions::asyncio::CancelledError::new_err(e.value(py).to_string())
+                } else {
+                    e
+                }
+            })?;
+            Ok(PyEquityResult { report })
+        },
+    )?;
+    let future = event_loop.call_method1("run_in_executor", (py.None(), work))?;
+
+    let on_done = PyCFunction::new_closure(py, None, None, move |args, _kwargs| -> PyResult<()> {
+        if args.get_item(0)?.call_method0("cancelled")?.is_truthy()? {
+            cancel.cancel();
         }
-        Err(e) => Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-            "Calculation error: {:?}",
-            e
-        ))),
-    }
+        Ok(())
+    })?;
+    future.call_method1("add_done_callback", (on_done,))?;
+    Ok(future)
 }
 
 /// A wrapper class for HandRange to be used in Python.
@@ -573,15 +1195,19 @@ impl PyHandRange {
 }
 
 /// Calculate equity between two ranges
-/// Returns a dictionary with equity stats
+/// Returns a dictionary with equity stats; "equity" is range1's share of the pot, from 0 to 1
+///
+/// Runs with the GIL released; `progress` and `cancel` work as in `calculate_equity`.
 #[pyfunction]
-#[pyo3(signature = (range1, range2, board="", iterations=10000))]
+#[pyo3(signature = (range1, range2, board="", iterations=10000, progress=None, cancel=None))]
 pub fn calculate_range_equity(
     py: Python<'_>,
     range1: &PyHandRange,
     range2: &PyHandRange,
     board: &str,
     iterations: usize,
+    progress: Option<PyObject>,
+    cancel: Option<PyCancelToken>,
 ) -> PyResult<PyObject> {
     let board_mask = if board.is_empty() {
         StdDeckCardMask::new()
@@ -597,10 +1223,15 @@ pub fn calculate_range_equity(
         }
     };
 
-    match calc_equity_rust(&range1.inner, &range2.inner, &board_mask, iterations) {
-        Ok(res) => {
+    let (range1, range2) = (&range1.inner, &range2.inner);
+    let res = with_progress(py, progress, cancel, |on_chunk| {
+        calc_equity_rust(range1, range2, &board_mask, iterations, on_chunk)
+    })?;
+    match res {
+        Ok(None) => Err(poker_error(PokerError::Cancelled)),
+        Ok(Some(res)) => {
             let dict = PyDict::new(py);
-            dict.set_item("equity", res.equity * 100.0)?;
+            dict.set_item("equity", res.equity)?;
             dict.set_item("wins", res.wins)?;
             dict.set_item("ties", res.ties)?;
             dict.set_item("losses", res.losses)?;
@@ -614,6 +1245,57 @@ pub fn calculate_range_equity(
     }
 }
 
+/// Calculate the equity of a Hold'em hand against N random hands
+/// Returns a dictionary with equity stats: "equity" is the hand's share of the pot, from 0 to 1,
+/// and "exact" is True when every deal was enumerated
+///
+/// Runs with the GIL released; `progress` and `cancel` work as in `calculate_equity`.
+#[pyfunction]
+#[pyo3(signature = (hand, opponents=1, board="", dead="", iterations=100000, progress=None, cancel=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_equity_vs_random(
+    py: Python<'_>,
+    hand: &str,
+    opponents: usize,
+    board: &str,
+    dead: &str,
+    iterations: usize,
+    progress: Option<PyObject>,
+    cancel: Option<PyCancelToken>,
+) -> PyResult<PyObject> {
+    let (hand_mask, _) = parse_and_validate(hand).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing hand: {}", e))
+    })?;
+    let (board_mask, _) = parse_and_validate(board).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing board: {}", e))
+    })?;
+    let (dead_mask, _) = parse_and_validate(dead).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing dead cards: {}", e))
+    })?;
+
+    let res = with_progress(py, progress, cancel, |on_chunk| {
+        calc_vs_random_rust(
+            &hand_mask,
+            opponents,
+            &board_mask,
+            &dead_mask,
+            iterations,
+            on_chunk,
+        )
+    })?
+    .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?
+    .ok_or_else(|| poker_error(PokerError::Cancelled))?;
+
+    let dict = PyDict::new(py);
+    dict.set_item("equity", res.equity)?;
+    dict.set_item("wins", res.wins)?;
+    dict.set_item("ties", res.ties)?;
+    dict.set_item("losses", res.losses)?;
+    dict.set_item("samples", res.samples)?;
+    dict.set_item("exact", res.exact)?;
+    Ok(dict.into())
+}
+
 /// Exposure of BoardTexture to Python
 #[pyclass(name = "BoardTexture")]
 #[derive(Clone)]
@@ -674,9 +1356,11 @@ impl PyBoardTexture {
 
 /// Calculate outs for a given hand and board.
 /// Returns a dictionary where keys are HandType strings (e.g. "Flush", "Straight")
-/// and values are lists of card strings (e.g. ["Ah", "Kh"]).
+/// and values are lists of card strings (e.g. ["Ah", "Kh"]). Cards in `dead` are
+/// never counted as outs.
 #[pyfunction]
-pub fn calculate_outs(pocket: &str, board: &str) -> PyResult<PyObject> {
+#[pyo3(signature = (pocket, board, dead=""))]
+pub fn calculate_outs(pocket: &str, board: &str, dead: &str) -> PyResult<PyObject> {
     let (pocket_mask, _) = parse_and_validate(pocket).map_err(|e| {
         PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing pocket: {}", e))
     })?;
@@ -685,55 +1369,150 @@ pub fn calculate_outs(pocket: &str, board: &str) -> PyResult<PyObject> {
         PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing board: {}", e))
     })?;
 
-    let outs_result = calc_outs_rust(&pocket_mask, &board_mask);
+    let (dead_mask, _) = parse_and_validate(dead).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing dead cards: {}", e))
+    })?;
+
+    let outs_result = calc_outs_rust(&pocket_mask, &board_mask, &dead_mask);
+
+    Python::with_gil(|py| {
+        let dict = outs_by_type_dict(py, &outs_result)?;
+        Ok(dict.into())
+    })
+}
+
+/// Helper to build a `{hand_type: [cards]}` dictionary from an `OutsResult`
+fn outs_by_type_dict<'py>(py: Python<'py>, outs: &OutsResult) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    for (type_name, cards) in outs.named() {
+        let card_strs: Vec<String> = cards.iter().map(|c| c.to_string()).collect();
+        dict.set_item(type_name, card_strs)?;
+    }
+
+    Ok(dict)
+}
+
+/// Calculate outs against an opponent hand or range.
+/// Returns a dictionary with the current win/tie share, the clean, tie and tainted
+/// outs as lists of card strings, and the discounted out count.
+#[pyfunction]
+#[pyo3(signature = (pocket, board, opponent, dead=""))]
+pub fn calculate_outs_vs(
+    pocket: &str,
+    board: &str,
+    opponent: &str,
+    dead: &str,
+) -> PyResult<PyObject> {
+    let (pocket_mask, _) = parse_and_validate(pocket).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing pocket: {}", e))
+    })?;
+
+    let (board_mask, _) = parse_and_validate(board).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing board: {}", e))
+    })?;
+
+    let (dead_mask, _) = parse_and_validate(dead).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing dead cards: {}", e))
+    })?;
+
+    let range = HandRange::from_str(opponent).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid range string: {}", e))
+    })?;
+
+    let res = calculate_outs_vs_range(&pocket_mask, &board_mask, &dead_mask, &range)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+
+    let to_strings = |cards: Vec<StdDeckCardMask>| -> Vec<String> {
+        cards.iter().map(|c| c.to_string()).collect()
+    };
 
     Python::with_gil(|py| {
         let dict = PyDict::new(py);
+        dict.set_item("current_win", res.current_win * 100.0)?;
+        dict.set_item("current_tie", res.current_tie * 100.0)?;
+        dict.set_item("clean_outs", to_strings(res.clean_outs()))?;
+        dict.set_item("tie_outs", to_strings(res.tie_outs()))?;
+        dict.set_item("tainted_outs", to_strings(res.tainted_outs()))?;
+        dict.set_item("discounted_outs", res.discounted_outs())?;
+        Ok(dict.into())
+    })
+}
 
-        for (hand_type_idx, cards) in outs_result.outs_by_type.iter().enumerate() {
-            if !cards.is_empty() {
-                // Map numeric HandType to string?
-                // We need HandType enum to be accessible or replicate standard naming.
-                // Assuming standard mappings:
-                // 0=HighCard, 1=Pair, 2=TwoPair, 3=Trips, 4=Straight, 5=Flush, 6=FullHouse, 7=Quads, 8=StraightFlush
-                let type_name = match hand_type_idx {
-                    0 => "HighCard",
-                    1 => "Pair",
-                    2 => "TwoPair",
-                    3 => "Trips",
-                    4 => "Straight",
-                    5 => "Flush",
-                    6 => "FullHouse",
-                    7 => "Quads",
-                    8 => "StraightFlush",
-                    _ => "Unknown",
-                };
-
-                let card_strs: Vec<String> = cards.iter().map(|c| c.to_string()).collect();
-
-                dict.set_item(type_name, card_strs)?;
-            }
-        }
+/// Calculate outs for a given game (e.g. "omaha8", "shortdeck", "razz").
+/// Returns a dictionary with "hi" (HandType string -> list of cards, as in
+/// `calculate_outs`), "lo" (low outs) and "counterfeits" (cards counterfeiting
+/// our low in hi/lo board games).
+#[pyfunction]
+#[pyo3(signature = (pocket, board="", game="holdem", dead=""))]
+pub fn calculate_game_outs(
+    pocket: &str,
+    board: &str,
+    game: &str,
+    dead: &str,
+) -> PyResult<PyObject> {
+    let game_variant = parse_game(game)?;
 
+    let (pocket_mask, _) = parse_and_validate(pocket).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing pocket: {}", e))
+    })?;
+
+    let (board_mask, _) = parse_and_validate(board).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing board: {}", e))
+    })?;
+
+    let (dead_mask, _) = parse_and_validate(dead).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Error parsing dead cards: {}", e))
+    })?;
+
+    let res = calc_game_outs_rust(game_variant, &pocket_mask, &board_mask, &dead_mask)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+
+    let to_strings = |cards: &[StdDeckCardMask]| -> Vec<String> {
+        cards.iter().map(|c| c.to_string()).collect()
+    };
+
+    Python::with_gil(|py| {
+        let dict = PyDict::new(py);
+        dict.set_item("hi", outs_by_type_dict(py, &res.hi)?)?;
+        dict.set_item("lo", to_strings(&res.lo))?;
+        dict.set_item("counterfeits", to_strings(&res.counterfeits))?;
         Ok(dict.into())
     })
 }
 
 #[pymodule]
-pub fn poker_eval_rs(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+pub fn poker_eval_rs(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add(
+        "CalculationCancelled",
+        py.get_type::<CalculationCancelled>(),
+    )?;
     m.add_function(wrap_pyfunction!(string_to_mask, m)?)?;
     m.add_function(wrap_pyfunction!(eval_n, m)?)?;
     m.add_function(wrap_pyfunction!(eval_n_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(cards_to_masks, m)?)?;
+    m.add_function(wrap_pyfunction!(eval_n_masks, m)?)?;
+    m.add_function(wrap_pyfunction!(eval_low_masks, m)?)?;
+    m.add_function(wrap_pyfunction!(eval_omaha_hi_masks, m)?)?;
+    m.add_function(wrap_pyfunction!(eval_omaha_hi_lo_masks, m)?)?;
     m.add_function(wrap_pyfunction!(eval_low_func, m)?)?;
     m.add_function(wrap_pyfunction!(eval_omaha_hi, m)?)?;
     m.add_function(wrap_pyfunction!(eval_omaha_hi_lo, m)?)?;
     m.add_function(wrap_pyfunction!(calculate_equity, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_equity_async, m)?)?;
     m.add_function(wrap_pyfunction!(calculate_range_equity, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_equity_vs_random, m)?)?;
     m.add_class::<PyHandRange>()?;
     m.add_class::<PyBoardTexture>()?;
     m.add_class::<PyCard>()?;
     m.add_class::<PyHand>()?;
+    m.add_class::<PyHandVal>()?;
+    m.add_class::<PyLowHandVal>()?;
+    m.add_class::<PyPlayerEquity>()?;
+    m.add_class::<PyEquityResult>()?;
+    m.add_class::<PyCancelToken>()?;
     m.add_function(wrap_pyfunction!(calculate_outs, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_outs_vs, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_game_outs, m)?)?;
     m.add_function(wrap_pyfunction!(eval_short_deck, m)?)?;
     m.add_function(wrap_pyfunction!(eval_joker, m)?)?;
     m.add_function(wrap_pyfunction!(eval_lowball_joker, m)?)?;
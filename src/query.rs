@@ -0,0 +1,808 @@
+//! A self-contained equity request shared by the CLI and the language bindings.
+//!
+//! An [`EquityQuery`] names the game, the players' cards, the board and dead cards,
+//! and how to enumerate. [`EquityQuery::validate`] checks it against the game's
+//! [`GameRules`](crate::game_rules::GameRules) and [`EquityQuery::run`] produces an
+//! [`EquityReport`]. Both types round-trip through serde, with games written by
+//! their registered names (`"holdem"`, `"omaha8"`, ...):
+//!
+//! ```
+//! use poker_eval_rs::query::EquityQuery;
+//!
+//! let query: EquityQuery = serde_json::from_str(
+//!     r#"{"game": "holdem", "players": ["AsAd", "KsKd"], "board": "2c7d9h"}"#,
+//! )
+//! .unwrap();
+//! let report = query.run().unwrap();
+//! assert_eq!(report.samples, 990);
+//! assert!(report.players[0].ev > 0.9);
+//! ```
+//!
+//! Long runs can go through [`EquityQuery::enumerate_chunked`] instead, which reports
+//! [`Progress`] between chunks of work and stops when asked to.
+
+use crate::combinations::for_each_combination;
+use crate::deck::{StdDeck, StdDeckCardMask, STD_DECK_N_CARDS};
+use crate::enumdefs::{EnumResult, Game, SampleType, ENUM_MAXPLAYERS};
+use crate::enumerate::{enum_game_exhaustive, enum_game_sample};
+use crate::enumord::EnumOrderingMode;
+use crate::errors::PokerError;
+use crate::game_rules::{GameRef, GameRules};
+use serde::{Deserialize, Serialize};
+
+/// Default number of Monte Carlo iterations.
+pub const DEFAULT_ITERATIONS: usize = 100_000;
+
+/// Monte Carlo iterations sampled between two progress reports of
+/// [`EquityQuery::enumerate_chunked`].
+const SAMPLE_CHUNK_SIZE: usize = 50_000;
+
+/// Progress reports an exhaustive [`EquityQuery::enumerate_chunked`] run makes at
+/// most, beyond one per batch of parallel chunks.
+const PROGRESS_REPORTS: usize = 100;
+
+/// How an [`EquityQuery`] deals the unknown cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EquityMode {
+    /// Every remaining deal is enumerated.
+    #[default]
+    Exhaustive,
+    /// `iterations` random deals are sampled.
+    MonteCarlo,
+}
+
+/// An equity calculation request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EquityQuery {
+    /// The game variant.
+    pub game: GameRef,
+    /// Each player's known cards (e.g. `"AsKs"`).
+    pub players: Vec<String>,
+    /// Board cards; empty for none.
+    #[serde(default)]
+    pub board: String,
+    /// Dead cards; empty for none.
+    #[serde(default)]
+    pub dead: String,
+    /// Exhaustive enumeration or Monte Carlo sampling.
+    #[serde(default)]
+    pub mode: EquityMode,
+    /// Number of Monte Carlo iterations; ignored for exhaustive enumeration.
+    #[serde(default = "default_iterations")]
+    pub iterations: usize,
+    /// Seed for Monte Carlo sampling, for reproducible results. `None` samples
+    /// from entropy.
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+fn default_iterations() -> usize {
+    DEFAULT_ITERATIONS
+}
+
+/// One player's results in an [`EquityReport`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerEquity {
+    /// The player's cards as given in the query.
+    pub hand: String,
+    /// Percentage of matchups won: the high half in hi/lo games, the low in lowball.
+    pub win_pct: f64,
+    /// Percentage of matchups tied.
+    pub tie_pct: f64,
+    /// Percentage of matchups lost.
+    pub lose_pct: f64,
+    /// The enumerator's `ev` averaged over all deals: the pot share in high-only and
+    /// low-only games, the high share plus the low share in hi/lo games.
+    pub ev: f64,
+    /// Percentage of deals where the player scoops both halves (hi/lo games only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scoop_pct: Option<f64>,
+    /// Percentage of low matchups won (hi/lo games only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub win_lo_pct: Option<f64>,
+    /// Percentage of low matchups tied (hi/lo games only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tie_lo_pct: Option<f64>,
+    /// Percentage of low matchups lost (hi/lo games only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lose_lo_pct: Option<f64>,
+}
+
+/// The result of [`EquityQuery::run`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EquityReport {
+    /// The game variant.
+    pub game: GameRef,
+    /// How the deals were generated.
+    pub mode: EquityMode,
+    /// Number of deals evaluated.
+    pub samples: u32,
+    /// Board cards as given in the query.
+    pub board: String,
+    /// Dead cards as given in the query.
+    pub dead: String,
+    /// One entry per player, in query order.
+    pub players: Vec<PlayerEquity>,
+}
+
+/// How far [`EquityQuery::enumerate_chunked`] has got, in deals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Deals evaluated so far.
+    pub done: u64,
+    /// Deals the whole run evaluates.
+    pub total: u64,
+}
+
+/// Parsed and validated cards of a query.
+struct QueryCards {
+    pockets: Vec<StdDeckCardMask>,
+    board: StdDeckCardMask,
+    dead: StdDeckCardMask,
+}
+
+impl EquityQuery {
+    /// An exhaustive query with no board or dead cards.
+    pub fn new(game: impl Into<GameRef>, players: Vec<String>) -> Self {
+        Self {
+            game: game.into(),
+            players,
+            board: String::new(),
+            dead: String::new(),
+            mode: EquityMode::Exhaustive,
+            iterations: DEFAULT_ITERATIONS,
+            seed: None,
+        }
+    }
+
+    /// Checks the query against the game's rules without running it.
+    ///
+    /// Fails with [`PokerError::TooManyPlayers`] above [`ENUM_MAXPLAYERS`] players,
+    /// [`PokerError::UnsupportedBoardConfiguration`] for a board the game cannot
+    /// have, [`PokerError::InvalidCardConfiguration`] for unparsable, repeated or
+    /// off-deck cards and [`PokerError::InvalidInput`] for anything else.
+    pub fn validate(&self) -> Result<(), PokerError> {
+        self.cards().map(|_| ())
+    }
+
+    /// Validates and runs the query, returning the raw enumeration counters.
+    ///
+    /// This is [`enumerate_chunked`](Self::enumerate_chunked) run to the end, so a
+    /// seeded Monte Carlo query gives the same counts either way.
+    pub fn enumerate(&self) -> Result<EnumResult, PokerError> {
+        self.enumerate_chunked(|_| true)
+    }
+
+    /// The number of deals the query covers: its iterations for Monte Carlo, or
+    /// every possible board for exhaustive enumeration. Validates the query.
+    pub fn deals(&self) -> Result<u64, PokerError> {
+        let cards = self.cards()?;
+        Ok(match self.mode {
+            EquityMode::MonteCarlo => self.iterations as u64,
+            EquityMode::Exhaustive => {
+                binomial(self.live_cards(&cards).len(), self.missing_cards(&cards))
+            }
+        })
+    }
+
+    /// Runs the query in one go over already validated cards.
+    fn enumerate_whole(&self, cards: &QueryCards) -> Result<EnumResult, PokerError> {
+        let mut result = self.new_result();
+        match self.mode {
+            EquityMode::Exhaustive => enum_game_exhaustive(
+                &*self.game,
+                &cards.pockets,
+                cards.board,
+                cards.dead,
+                false,
+                &mut result,
+            )?,
+            EquityMode::MonteCarlo => enum_game_sample(
+                &*self.game,
+                &cards.pockets,
+                cards.board,
+                cards.dead,
+                self.iterations,
+                false,
+                &mut result,
+            )?,
+        }
+        Ok(result)
+    }
+
+    /// Like [`enumerate`](Self::enumerate), but in chunks, calling `on_chunk` after
+    /// each one. The run stops with [`PokerError::Cancelled`] as soon as `on_chunk`
+    /// returns `false`.
+    ///
+    /// Exhaustive runs are split by the lowest board cards still to come and each chunk
+    /// goes through [`enum_game_exhaustive`], so they give the same counts as
+    /// `enumerate` and keep the game's specialised enumerator. Monte
+    /// Carlo runs sample a fixed number of iterations per chunk, each chunk with its
+    /// own seed derived from [`seed`](Self::seed), so seeded runs are reproducible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use poker_eval_rs::enumdefs::Game;
+    /// use poker_eval_rs::query::EquityQuery;
+    ///
+    /// let mut query = EquityQuery::new(Game::Holdem, vec!["AsAd".into(), "KsKd".into()]);
+    /// query.board = "2c7d9h".to_string();
+    /// let mut reports = Vec::new();
+    /// let result = query
+    ///     .enumerate_chunked(|progress| {
+    ///         reports.push(progress);
+    ///         true
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(result.nsamples, 990);
+    /// assert_eq!(reports.last().unwrap().done, 990);
+    /// ```
+    pub fn enumerate_chunked<F>(&self, mut on_chunk: F) -> Result<EnumResult, PokerError>
+    where
+        F: FnMut(Progress) -> bool,
+    {
+        let cards = self.cards()?;
+        let mut result = self.new_result();
+        result.nplayers = cards.pockets.len() as u32;
+        let mut report = |result: &EnumResult, total: u64| {
+            let done = result.nsamples as u64;
+            if on_chunk(Progress { done, total }) {
+                Ok(())
+            } else {
+                Err(PokerError::Cancelled)
+            }
+        };
+
+        match self.mode {
+            EquityMode::MonteCarlo => {
+                result.sample_type = SampleType::Sample;
+                let total = self.iterations as u64;
+                let mut remaining = self.iterations;
+                for i in 0u64.. {
+                    if remaining == 0 {
+                        break;
+                    }
+                    let chunk = EquityQuery {
+                        iterations: SAMPLE_CHUNK_SIZE.min(remaining),
+                        seed: self
+                            .seed
+                            .map(|s| s.wrapping_add(i.wrapping_mul(0x9E37_79B9_7F4A_7C15))),
+                        ..self.clone()
+                    };
+                    result.merge(&chunk.enumerate_whole(&cards)?);
+                    remaining -= chunk.iterations;
+                    report(&result, total)?;
+                }
+            }
+            EquityMode::Exhaustive => {
+                result.sample_type = SampleType::Exhaustive;
+                let missing = self.missing_cards(&cards);
+                if missing == 0 {
+                    result.merge(&self.enumerate_whole(&cards)?);
+                    let total = result.nsamples as u64;
+                    report(&result, total)?;
+                    return Ok(result);
+                }
+
+                // Every board has exactly one set of lowest `fixed` new cards, so
+                // dealing each such set in turn splits the enumeration. Chunks hold at
+                // least a flop, which the specialised enumerators need.
+                let live = self.live_cards(&cards);
+                let total = binomial(live.len(), missing);
+                let fixed = 3usize
+                    .saturating_sub(cards.board.num_cards())
+                    .clamp(1, missing);
+                let mut lowest: Vec<Vec<usize>> = Vec::new();
+                for_each_combination(live.len(), fixed, |idx| {
+                    if live.len() - 1 - idx[fixed - 1] >= missing - fixed {
+                        lowest.push(idx.to_vec());
+                    }
+                });
+                let nchunks = lowest.len();
+
+                let run_chunk = |i: usize| -> Result<EnumResult, PokerError> {
+                    let idx = &lowest[i];
+                    let (mut board, mut below) = (cards.board, cards.dead);
+                    for (j, &c) in live[..=idx[fixed - 1]].iter().enumerate() {
+                        if idx.contains(&j) {
+                            board.set(c);
+                        } else {
+                            below.set(c);
+                        }
+                    }
+                    let mut chunk = self.new_result();
+                    enum_game_exhaustive(
+                        &*self.game,
+                        &cards.pockets,
+                        board,
+                        below,
+                        false,
+                        &mut chunk,
+                    )?;
+                    Ok(chunk)
+                };
+                let batch_size = chunk_batch_size().max(nchunks / PROGRESS_REPORTS);
+                let mut start = 0;
+                while start < nchunks {
+                    let batch = start..(start + batch_size).min(nchunks);
+                    start = batch.end;
+                    #[cfg(feature = "parallel")]
+                    let chunks: Vec<EnumResult> = {
+                        use rayon::prelude::*;
+                        batch
+                            .into_par_iter()
+                            .map(run_chunk)
+                            .collect::<Result<_, _>>()?
+                    };
+                    #[cfg(not(feature = "parallel"))]
+                    let chunks: Vec<EnumResult> = batch.map(run_chunk).collect::<Result<_, _>>()?;
+                    for chunk in &chunks {
+                        result.merge(chunk);
+                    }
+                    report(&result, total)?;
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Board cards still to be dealt.
+    fn missing_cards(&self, cards: &QueryCards) -> usize {
+        self.game.board_cards() - cards.board.num_cards()
+    }
+
+    /// The deck's cards not held, on the board or dead, in index order.
+    fn live_cards(&self, cards: &QueryCards) -> Vec<usize> {
+        let mut used = cards.board | cards.dead;
+        for pocket in &cards.pockets {
+            used = used | *pocket;
+        }
+        let deck = self.game.deck();
+        (0..STD_DECK_N_CARDS)
+            .filter(|&c| deck.card_is_set(c) && !used.card_is_set(c))
+            .collect()
+    }
+
+    /// Validates and runs the query.
+    pub fn run(&self) -> Result<EquityReport, PokerError> {
+        let result = self.enumerate()?;
+        Ok(self.report(&result))
+    }
+
+    /// Builds the report for this query from counters returned by
+    /// [`enumerate`](Self::enumerate).
+    pub fn report(&self, result: &EnumResult) -> EquityReport {
+        let mode = self.game.ordering_mode();
+        let has_hi = mode != EnumOrderingMode::Lo;
+        let has_lo = matches!(mode, EnumOrderingMode::Lo | EnumOrderingMode::Hilo);
+        let nsamples = result.nsamples.max(1) as f64;
+
+        let players = self
+            .players
+            .iter()
+            .enumerate()
+            .map(|(i, hand)| {
+                let (win, tie, lose) = if has_hi {
+                    (result.nwinhi[i], result.ntiehi[i], result.nlosehi[i])
+                } else {
+                    (result.nwinlo[i], result.ntielo[i], result.nloselo[i])
+                };
+                let lo = (has_hi && has_lo)
+                    .then(|| percentages(result.nwinlo[i], result.ntielo[i], result.nloselo[i]));
+                let (win_pct, tie_pct, lose_pct) = percentages(win, tie, lose);
+                PlayerEquity {
+                    hand: hand.clone(),
+                    win_pct,
+                    tie_pct,
+                    lose_pct,
+                    ev: result.ev[i] / nsamples,
+                    scoop_pct: lo.map(|_| result.nscoop[i] as f64 / nsamples * 100.0),
+                    win_lo_pct: lo.map(|l| l.0),
+                    tie_lo_pct: lo.map(|l| l.1),
+                    lose_lo_pct: lo.map(|l| l.2),
+                }
+            })
+            .collect();
+
+        EquityReport {
+            game: self.game.clone(),
+            mode: self.mode,
+            samples: result.nsamples,
+            board: self.board.clone(),
+            dead: self.dead.clone(),
+            players,
+        }
+    }
+
+    /// Empty counters for this query's game and seed.
+    fn new_result(&self) -> EnumResult {
+        let mut result = EnumResult::new(self.game.game().unwrap_or(Game::Holdem));
+        result.seed = self.seed;
+        result
+    }
+
+    fn cards(&self) -> Result<QueryCards, PokerError> {
+        let rules = &*self.game;
+        if rules.ordering_mode() == EnumOrderingMode::None {
+            return Err(PokerError::UnsupportedGameType);
+        }
+        let nplayers = self.players.len();
+        if nplayers > ENUM_MAXPLAYERS {
+            return Err(PokerError::TooManyPlayers);
+        }
+        if nplayers < 2 {
+            return Err(PokerError::InvalidInput(
+                "Need at least 2 players for an equity calculation".to_string(),
+            ));
+        }
+        if self.mode == EquityMode::MonteCarlo && self.iterations == 0 {
+            return Err(PokerError::InvalidInput(
+                "Monte Carlo needs at least one iteration".to_string(),
+            ));
+        }
+
+        let board = parse_cards(&self.board, "board")?;
+        let dead = parse_cards(&self.dead, "dead cards")?;
+        let pockets = self
+            .players
+            .iter()
+            .enumerate()
+            .map(|(i, hand)| {
+                let what = format!("player {} ({})", i + 1, hand);
+                parse_cards(hand, &what).map(|pocket| (what, pocket))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        check_deal(rules, &pockets, board, dead)?;
+
+        Ok(QueryCards {
+            pockets: pockets.into_iter().map(|(_, pocket)| pocket).collect(),
+            board,
+            dead,
+        })
+    }
+}
+
+/// Checks a deal against `rules`: the board size, the size of each pocket, cards
+/// used twice and cards off the game's deck. Each pocket comes with the name it
+/// has in error messages.
+pub(crate) fn check_deal(
+    rules: &dyn GameRules,
+    pockets: &[(String, StdDeckCardMask)],
+    board: StdDeckCardMask,
+    dead: StdDeckCardMask,
+) -> Result<(), PokerError> {
+    if board.num_cards() > rules.board_cards() {
+        return Err(PokerError::UnsupportedBoardConfiguration);
+    }
+    let mut used = board;
+    claim(&mut used, dead, "dead cards")?;
+
+    let (min_pocket, max_pocket) = rules.pocket_cards();
+    for (what, pocket) in pockets {
+        let n = pocket.num_cards();
+        if n < min_pocket || n > max_pocket {
+            return Err(PokerError::InvalidInput(format!(
+                "{} has {} cards; {} takes {}",
+                what,
+                n,
+                rules.description(),
+                card_count(min_pocket, max_pocket)
+            )));
+        }
+        claim(&mut used, *pocket, what)?;
+    }
+
+    let deck = rules.deck();
+    let off_deck = (0..STD_DECK_N_CARDS).find(|&c| used.card_is_set(c) && !deck.card_is_set(c));
+    if let Some(c) = off_deck {
+        return Err(PokerError::InvalidCardConfiguration(format!(
+            "{} is not in the {} deck",
+            StdDeckCardMask::from_card_index(c),
+            rules.description()
+        )));
+    }
+    Ok(())
+}
+
+/// Parses a card string, rejecting repeated cards. An empty string is no cards.
+pub(crate) fn parse_cards(cards: &str, what: &str) -> Result<StdDeckCardMask, PokerError> {
+    if cards.trim().is_empty() {
+        return Ok(StdDeckCardMask::new());
+    }
+    let (mask, count) = StdDeck::string_to_mask(cards).map_err(|e| {
+        PokerError::InvalidCardConfiguration(format!("Error parsing {}: {}", what, e))
+    })?;
+    if mask.num_cards() != count {
+        return Err(PokerError::InvalidCardConfiguration(format!(
+            "Duplicate cards in {}",
+            what
+        )));
+    }
+    Ok(mask)
+}
+
+/// Adds `cards` to `used`, failing if any of them is already taken.
+pub(crate) fn claim(
+    used: &mut StdDeckCardMask,
+    cards: StdDeckCardMask,
+    what: &str,
+) -> Result<(), PokerError> {
+    let overlap = StdDeckCardMask::from_raw(used.as_raw() & cards.as_raw());
+    if !overlap.is_empty() {
+        return Err(PokerError::InvalidCardConfiguration(format!(
+            "{} in {} already used elsewhere",
+            overlap, what
+        )));
+    }
+    *used = *used | cards;
+    Ok(())
+}
+
+pub(crate) fn card_count(min: usize, max: usize) -> String {
+    if min == max {
+        min.to_string()
+    } else {
+        format!("{} to {}", min, max)
+    }
+}
+
+/// Number of exhaustive chunks run between two progress reports.
+fn chunk_batch_size() -> usize {
+    #[cfg(feature = "parallel")]
+    {
+        rayon::current_num_threads()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        1
+    }
+}
+
+/// Number of ways to choose `k` of `n` items.
+fn binomial(n: usize, k: usize) -> u64 {
+    (0..k.min(n)).fold(1, |acc, i| acc * (n - i) as u64 / (i + 1) as u64)
+}
+
+/// Win/tie/loss counts as percentages of their total.
+fn percentages(win: u32, tie: u32, lose: u32) -> (f64, f64, f64) {
+    let total = (win + tie + lose).max(1) as f64;
+    (
+        win as f64 / total * 100.0,
+        tie as f64 / total * 100.0,
+        lose as f64 / total * 100.0,
+    )
+}
+
+/// Serializes a [`Game`] by its short name and parses it with [`FromStr`](std::str::FromStr).
+pub(crate) mod game_name {
+    use crate::enumdefs::Game;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(game: &Game, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(game.as_str())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Game, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(game: Game, players: &[&str], board: &str) -> EquityQuery {
+        let mut q = EquityQuery::new(game, players.iter().map(|p| p.to_string()).collect());
+        q.board = board.to_string();
+        q
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let ok = query(Game::Holdem, &["AsKs", "QdQc"], "2h3h4h");
+        assert!(ok.validate().is_ok());
+
+        let mut q = ok.clone();
+        q.players.truncate(1);
+        assert!(matches!(q.validate(), Err(PokerError::InvalidInput(_))));
+
+        let q = query(Game::Holdem, &["AsKs"; ENUM_MAXPLAYERS + 1], "");
+        assert_eq!(q.validate(), Err(PokerError::TooManyPlayers));
+
+        let q = query(Game::Holdem, &["AsKs", "QdQc"], "2h3h4h5h6h7h");
+        assert_eq!(q.validate(), Err(PokerError::UnsupportedBoardConfiguration));
+
+        let q = query(Game::Omaha, &["AsKs", "QdQcJhTh"], "");
+        assert!(matches!(q.validate(), Err(PokerError::InvalidInput(_))));
+
+        for (players, board, dead) in [
+            (["AsKs", "AsQc"], "", ""),
+            (["AsKs", "QdQc"], "Qd2h3h", ""),
+            (["AsKs", "QdQc"], "2h3h4h", "4h"),
+            (["AsAs", "QdQc"], "", ""),
+            (["AsXx", "QdQc"], "", ""),
+        ] {
+            let mut q = query(Game::Holdem, &players, board);
+            q.dead = dead.to_string();
+            assert!(
+                matches!(q.validate(), Err(PokerError::InvalidCardConfiguration(_))),
+                "{:?}",
+                q
+            );
+        }
+
+        let q = query(Game::ShortDeck, &["AsKs", "5d5c"], "");
+        assert!(matches!(
+            q.validate(),
+            Err(PokerError::InvalidCardConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_run_hilo_and_razz() {
+        let report = query(Game::Holdem8, &["As2s", "KdKc"], "3h4h9c")
+            .run()
+            .unwrap();
+        let p = &report.players[0];
+        assert!(p.scoop_pct.is_some() && p.win_lo_pct.unwrap() > 0.0);
+        assert!(report.players[1].lose_lo_pct.unwrap() > 0.0);
+
+        // Razz results live in the low counters.
+        let mut q = query(Game::Razz, &["As2d3c4h6s8c9d", "KsKdQhJh9cTcTd"], "");
+        q.mode = EquityMode::MonteCarlo;
+        q.iterations = 500;
+        let report = q.run().unwrap();
+        assert_eq!(report.players.len(), 2);
+        assert_eq!(report.players[0].win_pct, 100.0);
+        assert!(report.players[0].scoop_pct.is_none());
+    }
+
+    #[test]
+    fn test_seeded_monte_carlo_is_reproducible() {
+        let mut q = query(Game::Holdem, &["AsKs", "QdQc", "7h6h"], "");
+        q.mode = EquityMode::MonteCarlo;
+        q.iterations = 20_000;
+        q.seed = Some(7);
+        let a = q.run().unwrap();
+        assert_eq!(a, q.run().unwrap());
+        q.seed = Some(8);
+        assert_ne!(a, q.run().unwrap());
+    }
+
+    #[test]
+    fn test_chunked_matches_enumerate() {
+        for q in [
+            query(Game::Holdem, &["AsKs", "QdQc", "7h6h"], "2h3h9c"),
+            query(Game::Omaha8, &["As2sKdKc", "QhQd3c4c"], "5h7d8s"),
+            query(Game::Omaha, &["AsKsQdJd", "9d9c8h7h"], "6h7cTc"),
+            query(Game::Holdem, &["AsKs", "QdQc"], "2h3h9c8d"),
+            // Preflop chunks deal a whole flop.
+            query(Game::ShortDeck, &["AsKs", "QdQc"], ""),
+        ] {
+            let mut reports = Vec::new();
+            let chunked = q
+                .enumerate_chunked(|p| {
+                    reports.push(p);
+                    true
+                })
+                .unwrap();
+            let whole = q.enumerate_whole(&q.cards().unwrap()).unwrap();
+            assert_eq!(chunked.nsamples, whole.nsamples, "{:?}", q.game);
+            assert_eq!(chunked.nwinhi, whole.nwinhi, "{:?}", q.game);
+            assert_eq!(chunked.nwinlo, whole.nwinlo, "{:?}", q.game);
+            assert_eq!(chunked.nscoop, whole.nscoop, "{:?}", q.game);
+            let last = reports.last().unwrap();
+            assert_eq!(last.done, last.total);
+            assert!(reports.windows(2).all(|w| w[0].done < w[1].done));
+        }
+    }
+
+    #[test]
+    fn test_chunked_cancel_and_sampling() {
+        let mut q = query(Game::Holdem, &["AsKs", "QdQc"], "");
+        let mut calls = 0;
+        let res = q.enumerate_chunked(|_| {
+            calls += 1;
+            false
+        });
+        assert_eq!(res.unwrap_err(), PokerError::Cancelled);
+        assert_eq!(calls, 1);
+
+        q.mode = EquityMode::MonteCarlo;
+        q.iterations = SAMPLE_CHUNK_SIZE * 2 + 10;
+        q.seed = Some(3);
+        let mut chunks = 0;
+        let a = q
+            .enumerate_chunked(|p| {
+                chunks += 1;
+                assert_eq!(p.total, q.iterations as u64);
+                true
+            })
+            .unwrap();
+        assert_eq!(chunks, 3);
+        assert_eq!(a.nsamples as usize, q.iterations);
+        assert_eq!(a.nwinhi, q.enumerate_chunked(|_| true).unwrap().nwinhi);
+    }
+
+    #[test]
+    fn test_registered_game_by_name() {
+        use crate::combinations::for_each_combination;
+        use crate::evaluators::Eval;
+        use crate::game_rules::{register_game, GameRules};
+        use crate::handval::HandVal;
+        use std::sync::Arc;
+
+        /// Hold'em where both hole cards must play.
+        struct HoldemBothCards;
+        impl GameRules for HoldemBothCards {
+            fn name(&self) -> &str {
+                "holdem-both-cards"
+            }
+            fn pocket_cards(&self) -> (usize, usize) {
+                (2, 2)
+            }
+            fn board_cards(&self) -> usize {
+                5
+            }
+            fn ordering_mode(&self) -> EnumOrderingMode {
+                EnumOrderingMode::Hi
+            }
+            fn eval_hi(
+                &self,
+                pocket: &StdDeckCardMask,
+                board: &StdDeckCardMask,
+            ) -> Result<Option<HandVal>, PokerError> {
+                let cards: Vec<usize> = (0..STD_DECK_N_CARDS)
+                    .filter(|&c| board.card_is_set(c))
+                    .collect();
+                let mut best = None;
+                for_each_combination(cards.len(), 3, |idx| {
+                    let mut hand = *pocket;
+                    idx.iter().for_each(|&i| hand.set(cards[i]));
+                    let val = Eval::eval_n(&hand, 5);
+                    best = best.max(Some(val.value));
+                });
+                Ok(best.map(|value| HandVal { value }))
+            }
+        }
+        register_game(Arc::new(HoldemBothCards)).unwrap();
+
+        let q: EquityQuery = serde_json::from_str(
+            r#"{"game": "Holdem-Both-Cards", "players": ["Ah3c", "KsKd"], "board": "2h5h8hJh"}"#,
+        )
+        .unwrap();
+        assert_eq!(q.game.to_string(), "holdem-both-cards");
+        let report = q.run().unwrap();
+        assert_eq!(report.samples, 44);
+        // The lone heart makes a flush in Hold'em but not when both cards must play.
+        assert!(report.players[1].win_pct > 50.0);
+        let holdem = EquityQuery {
+            game: Game::Holdem.into(),
+            ..q.clone()
+        };
+        assert!(holdem.run().unwrap().players[0].win_pct > 50.0);
+        assert!(serde_json::to_string(&report)
+            .unwrap()
+            .contains(r#""game":"holdem-both-cards""#));
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let mut q = query(Game::Omaha8, &["As2sKdKc", "QhQd3c4c"], "");
+        q.mode = EquityMode::MonteCarlo;
+        q.seed = Some(1);
+        let json = serde_json::to_string(&q).unwrap();
+        assert!(json.contains(r#""game":"omaha8""#));
+        assert!(json.contains(r#""mode":"monte_carlo""#));
+        assert_eq!(serde_json::from_str::<EquityQuery>(&json).unwrap(), q);
+
+        let q: EquityQuery =
+            serde_json::from_str(r#"{"game": "Razz", "players": ["As2d3c", "KsKdQh"]}"#).unwrap();
+        assert_eq!(q.game, Game::Razz);
+        assert_eq!(q.iterations, DEFAULT_ITERATIONS);
+        assert!(serde_json::from_str::<EquityQuery>(r#"{"game": "go", "players": []}"#).is_err());
+    }
+}
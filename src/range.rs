@@ -3,10 +3,33 @@
 //! A `HandRange` represents a set of specific hand combinations (e.g., "AKs" expands
 //! into 4 specific suited combinations).
 
-use crate::deck::{Rank, StdDeck, StdDeckCardMask, Suit};
+use crate::deck::{Rank, StdDeck, StdDeckCardMask, Suit, STD_DECK_N_CARDS};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::ops::{BitAnd, BitOr, Sub};
 use std::str::FromStr;
 
+/// How to combine the weights of a hand present in both ranges of a set operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightMode {
+    /// Keep the smaller weight.
+    Min,
+    /// Keep the larger weight.
+    Max,
+    /// Multiply the weights.
+    Product,
+}
+
+impl WeightMode {
+    fn combine(&self, a: f64, b: f64) -> f64 {
+        match self {
+            WeightMode::Min => a.min(b),
+            WeightMode::Max => a.max(b),
+            WeightMode::Product => a * b,
+        }
+    }
+}
+
 /// Represents a range of poker hands.
 ///
 /// A range is essentially a collection of specific hand combinations (2-card hole cards).
@@ -64,12 +87,332 @@ impl HandRange {
         }
     }
 
+    /// Returns a new range with the hands for which `predicate` returns true, keeping weights.
+    pub fn filter<F>(&self, mut predicate: F) -> HandRange
+    where
+        F: FnMut(&StdDeckCardMask) -> bool,
+    {
+        Self {
+            hands: self
+                .hands
+                .iter()
+                .filter(|(h, _)| predicate(h))
+                .copied()
+                .collect(),
+        }
+    }
+
     /// Merges another range into this one.
     pub fn merge(&mut self, other: HandRange) {
         for (hand, weight) in other.hands {
             self.push_weighted(hand, weight);
         }
     }
+
+    /// Returns the hands of both ranges. Hands present in both get their weights
+    /// combined with `mode`; the others keep their own weight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use poker_eval_rs::range::{HandRange, WeightMode};
+    /// use std::str::FromStr;
+    ///
+    /// let a = HandRange::from_str("QQ+").unwrap();
+    /// let b = HandRange::from_str("JJ+").unwrap();
+    /// assert_eq!(a.union(&b, WeightMode::Max).len(), 24);
+    /// ```
+    pub fn union(&self, other: &HandRange, mode: WeightMode) -> HandRange {
+        let mut result = self.clone();
+        let index = result.index();
+        for (hand, weight) in &other.hands {
+            match index.get(hand) {
+                Some(&i) => result.hands[i].1 = mode.combine(result.hands[i].1, *weight),
+                None => result.hands.push((*hand, *weight)),
+            }
+        }
+        result
+    }
+
+    /// Returns the hands present in both ranges, with weights combined by `mode`.
+    pub fn intersection(&self, other: &HandRange, mode: WeightMode) -> HandRange {
+        let index = other.index();
+        HandRange {
+            hands: self
+                .hands
+                .iter()
+                .filter_map(|(hand, weight)| {
+                    index
+                        .get(hand)
+                        .map(|&i| (*hand, mode.combine(*weight, other.hands[i].1)))
+                })
+                .collect(),
+        }
+    }
+
+    /// Removes `other` from this range.
+    ///
+    /// Weights are frequencies: a hand at weight 1.0 minus the same hand at 0.25 stays
+    /// at 0.75. Hands whose weight drops to zero are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use poker_eval_rs::range::HandRange;
+    /// use std::str::FromStr;
+    ///
+    /// let open = HandRange::from_str("TT+,AK").unwrap();
+    /// let three_bet = HandRange::from_str("QQ+,AKs").unwrap();
+    /// let flat = open.subtract(&three_bet);
+    /// assert_eq!(flat.len(), 12 + 12); // TT-JJ and AKo
+    /// ```
+    pub fn subtract(&self, other: &HandRange) -> HandRange {
+        let index = other.index();
+        HandRange {
+            hands: self
+                .hands
+                .iter()
+                .filter_map(|(hand, weight)| {
+                    let remaining = match index.get(hand) {
+                        Some(&i) => weight - other.hands[i].1,
+                        None => *weight,
+                    };
+                    (remaining > 0.0).then_some((*hand, remaining))
+                })
+                .collect(),
+        }
+    }
+
+    /// Multiplies every weight by `factor`. Hands whose weight becomes zero are removed.
+    pub fn scale(&mut self, factor: f64) {
+        for (_, weight) in &mut self.hands {
+            *weight *= factor;
+        }
+        self.hands.retain(|(_, w)| *w > 0.0);
+    }
+
+    /// Scales weights so that they sum to 1.0, turning the range into a probability
+    /// distribution over its hands. Does nothing if the total weight is zero.
+    pub fn normalize(&mut self) {
+        let total = self.total_weight();
+        if total > 0.0 {
+            self.scale(1.0 / total);
+        }
+    }
+
+    /// Scales weights so that the largest one is 1.0. Does nothing if all weights are zero.
+    pub fn normalize_max(&mut self) {
+        let max = self.hands.iter().map(|(_, w)| *w).fold(0.0, f64::max);
+        if max > 0.0 {
+            self.scale(1.0 / max);
+        }
+    }
+
+    /// Removes every hand that shares a card with `dead` (board or known cards).
+    pub fn remove_dead(&mut self, dead: &StdDeckCardMask) {
+        self.hands
+            .retain(|(hand, _)| (hand.as_raw() & dead.as_raw()) == 0);
+    }
+
+    /// Sum of the weights, i.e. the number of combos counting partial weights.
+    pub fn total_weight(&self) -> f64 {
+        self.hands.iter().map(|(_, w)| w).sum()
+    }
+
+    /// Weighted combos as a percentage of all possible hands of the same size
+    /// (1326 for two-card hands, 270725 for four-card Omaha hands).
+    pub fn percentage(&self) -> f64 {
+        let Some((first, _)) = self.hands.first() else {
+            return 0.0;
+        };
+        let k = first.num_cards();
+        let total = (0..k).fold(1.0, |acc, i| {
+            acc * (STD_DECK_N_CARDS - i) as f64 / (i + 1) as f64
+        });
+        self.total_weight() / total * 100.0
+    }
+
+    /// Formats the range in compact standard notation, e.g. `JJ+,AQs+,KQo,T9s-76s`.
+    ///
+    /// Complete hand classes are grouped into `+` and dash runs (including
+    /// constant-gap connector runs), classes whose combos share a weight other than 1
+    /// get a `:weight` suffix, and hands that do not complete a class are listed as
+    /// explicit cards. Parsing the result with [`HandRange::from_str`] gives back
+    /// exactly the same hands and weights, possibly in a different order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use poker_eval_rs::range::HandRange;
+    /// use std::str::FromStr;
+    ///
+    /// let range = HandRange::from_str("AA,KK,QQ,AKs,AQs:0.5,KhQh").unwrap();
+    /// assert_eq!(range.to_notation(), "QQ+,AKs,AQs:0.5,KhQh");
+    /// ```
+    pub fn to_notation(&self) -> String {
+        let parts: Vec<String> = self
+            .notation_tokens(true)
+            .into_iter()
+            .map(|(text, w)| with_weight(text, w))
+            .collect();
+        parts.join(",")
+    }
+
+    /// Tokens of [`HandRange::to_notation`] with their weights, before any `:weight`
+    /// suffix is added. With `runs` false every class is listed on its own.
+    pub(crate) fn notation_tokens(&self, runs: bool) -> Vec<(String, f64)> {
+        let weights: HashMap<StdDeckCardMask, f64> = self.hands.iter().copied().collect();
+        let mut covered: HashSet<StdDeckCardMask> = HashSet::new();
+
+        // Weight shared by every combo of a class, or None if the class is incomplete.
+        let mut full_weight = |combos: HandRange| -> Option<f64> {
+            let w = *weights.get(&combos.hands[0].0)?;
+            let complete = combos
+                .hands
+                .iter()
+                .all(|(h, _)| weights.get(h).is_some_and(|x| x.to_bits() == w.to_bits()));
+            if complete {
+                covered.extend(combos.hands.iter().map(|(h, _)| *h));
+            }
+            complete.then_some(w)
+        };
+
+        // Class weights indexed by [high][low]; the non-pair grid by shape too.
+        let mut pairs = [None; 13];
+        let mut grid = [[[None; 13]; 13]; 3];
+        for high in 0..13u8 {
+            pairs[high as usize] = full_weight(class_combos(Shape::Pair, high, high));
+            for low in 0..high {
+                let suited = full_weight(class_combos(Shape::Suited, high, low));
+                let offsuit = full_weight(class_combos(Shape::Offsuit, high, low));
+                let (h, l) = (high as usize, low as usize);
+                match (suited, offsuit) {
+                    (Some(a), Some(b)) if a.to_bits() == b.to_bits() => {
+                        grid[Shape::Any as usize][h][l] = Some(a)
+                    }
+                    _ => {
+                        grid[Shape::Suited as usize][h][l] = suited;
+                        grid[Shape::Offsuit as usize][h][l] = offsuit;
+                    }
+                }
+            }
+        }
+
+        // (pairs first, high desc, shape, low desc)
+        type SortKey = (u8, u8, usize, u8);
+        let mut tokens: Vec<(SortKey, (String, f64))> = Vec::new();
+        for (top, bottom, w) in weight_runs(&pairs, 12, runs) {
+            let (t, b) = (Rank::new(top), Rank::new(bottom));
+            let text = if top == bottom {
+                format!("{}{}", t, t)
+            } else if top == 12 {
+                format!("{}{}+", b, b)
+            } else {
+                format!("{}{}-{}{}", t, t, b, b)
+            };
+            tokens.push(((0, 12 - top, 0, 12 - top), (text, w)));
+        }
+
+        for shape in [Shape::Any, Shape::Suited, Shape::Offsuit] {
+            let sfx = shape.suffix();
+            // Lone classes, later joined into connector runs: (high, low, weight)
+            let mut singles: Vec<(u8, u8, f64)> = Vec::new();
+            for high in 1..13u8 {
+                let h = Rank::new(high);
+                for (top, bottom, w) in
+                    weight_runs(&grid[shape as usize][high as usize], high - 1, runs)
+                {
+                    if top == bottom {
+                        singles.push((high, top, w));
+                        continue;
+                    }
+                    let b = Rank::new(bottom);
+                    let text = if top == high - 1 {
+                        format!("{}{}{}+", h, b, sfx)
+                    } else {
+                        format!("{}{}{}-{}{}{}", h, Rank::new(top), sfx, h, b, sfx)
+                    };
+                    tokens.push(((1, 12 - high, shape as usize, 12 - top), (text, w)));
+                }
+            }
+
+            singles.sort_by_key(|&(high, low, w)| (high - low, w.to_bits(), 12 - high));
+            let mut i = 0;
+            while i < singles.len() {
+                let (high, low, w) = singles[i];
+                let mut j = i + 1;
+                while runs
+                    && j < singles.len()
+                    && singles[j].0 - singles[j].1 == high - low
+                    && singles[j].2.to_bits() == w.to_bits()
+                    && singles[j].0 + (j - i) as u8 == high
+                {
+                    j += 1;
+                }
+                let (end_high, end_low, _) = singles[j - 1];
+                let text = if j - i == 1 {
+                    format!("{}{}{}", Rank::new(high), Rank::new(low), sfx)
+                } else {
+                    format!(
+                        "{}{}{}-{}{}{}",
+                        Rank::new(high),
+                        Rank::new(low),
+                        sfx,
+                        Rank::new(end_high),
+                        Rank::new(end_low),
+                        sfx
+                    )
+                };
+                tokens.push(((1, 12 - high, shape as usize, 12 - low), (text, w)));
+                i = j;
+            }
+        }
+        tokens.sort_by_key(|(key, _)| *key);
+
+        let mut parts: Vec<(String, f64)> = tokens.into_iter().map(|(_, t)| t).collect();
+        for (hand, w) in &self.hands {
+            if !covered.contains(hand) {
+                parts.push((explicit_cards(hand), *w));
+            }
+        }
+        parts
+    }
+
+    /// Maps each hand to its position in `hands`.
+    fn index(&self) -> HashMap<StdDeckCardMask, usize> {
+        self.hands
+            .iter()
+            .enumerate()
+            .map(|(i, (hand, _))| (*hand, i))
+            .collect()
+    }
+}
+
+/// Union keeping the larger weight.
+impl BitOr for &HandRange {
+    type Output = HandRange;
+
+    fn bitor(self, rhs: &HandRange) -> HandRange {
+        self.union(rhs, WeightMode::Max)
+    }
+}
+
+/// Intersection keeping the smaller weight.
+impl BitAnd for &HandRange {
+    type Output = HandRange;
+
+    fn bitand(self, rhs: &HandRange) -> HandRange {
+        self.intersection(rhs, WeightMode::Min)
+    }
+}
+
+/// See [`HandRange::subtract`].
+impl Sub for &HandRange {
+    type Output = HandRange;
+
+    fn sub(self, rhs: &HandRange) -> HandRange {
+        self.subtract(rhs)
+    }
 }
 
 impl fmt::Debug for HandRange {
@@ -92,12 +435,36 @@ impl FromStr for HandRange {
             if part.is_empty() {
                 continue;
             }
-            range.merge(parse_range_part(part)?);
+            // Optional weight suffix: "AKs:0.5"
+            let (body, weight) = match part.rsplit_once(':') {
+                Some((body, w)) => (body.trim(), Some(parse_weight(w.trim())?)),
+                None => (part, None),
+            };
+            let mut sub = parse_range_part(body)?;
+            if let Some(weight) = weight {
+                for (_, w) in &mut sub.hands {
+                    *w = weight;
+                }
+            }
+            range.merge(sub);
         }
         Ok(range)
     }
 }
 
+impl fmt::Display for HandRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_notation())
+    }
+}
+
+fn parse_weight(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(w) if w.is_finite() && w >= 0.0 => Ok(w),
+        _ => Err(format!("Invalid weight: {}", s)),
+    }
+}
+
 fn parse_range_part(s: &str) -> Result<HandRange, String> {
     let mut range = HandRange::new();
 
@@ -234,6 +601,7 @@ fn parse_dash_notation(start_str: &str, end_str: &str) -> Result<HandRange, Stri
     // "88-66" -> 88, 77, 66
     // "KJs-K9s" -> KJs, KTs, K9s
     // "KJo-K9o" -> KJo, KTo, K9o
+    // "T9s-76s" -> T9s, 98s, 87s, 76s
 
     // Parse start and end to get ranks and type.
     // They must match in type (pair vs non-pair, suited vs offsuit).
@@ -284,12 +652,9 @@ fn parse_dash_notation(start_str: &str, end_str: &str) -> Result<HandRange, Stri
             add_pair_combos(&mut range, Rank::new(r));
         }
     } else {
-        // "KJs-K9s"
-        // High cards must match? Usually yes. "KJs-QJs" is weird.
-        // Assume high cards match: "KJs-K9s".
-        // If high cards don't match, like "AK-KQ", that's usually interpreted as a range of strong hands...
-        // But standard notation usually implies keeping one card fixed.
-        // Let's enforce fixed high card for now.
+        // "KJs-K9s" keeps the high card fixed and walks the kicker.
+        // "T9s-76s" keeps the gap fixed and walks both cards (connectors).
+        // Anything else, like "AK-KQ", is rejected.
 
         // Canonicalize
         let (s_high, s_low) = if s_r1 > s_r2 {
@@ -304,7 +669,29 @@ fn parse_dash_notation(start_str: &str, end_str: &str) -> Result<HandRange, Stri
         };
 
         if s_high != e_high {
-            return Err("Range bounds must share the same high card".into());
+            // Connector run with a constant gap: "T9s-76s" -> T9s, 98s, 87s, 76s
+            let s_gap = s_high.as_u8() - s_low.as_u8();
+            if s_gap != e_high.as_u8() - e_low.as_u8() {
+                return Err("Range bounds must share the same high card or gap".into());
+            }
+            let (top, bottom) = if s_high > e_high {
+                (s_high.as_u8(), e_high.as_u8())
+            } else {
+                (e_high.as_u8(), s_high.as_u8())
+            };
+            for h in bottom..=top {
+                let (high, low) = (Rank::new(h), Rank::new(h - s_gap));
+                match s_suff {
+                    Some('s') => add_suited_combos(&mut range, high, low),
+                    Some('o') => add_offsuit_combos(&mut range, high, low),
+                    None => {
+                        add_suited_combos(&mut range, high, low);
+                        add_offsuit_combos(&mut range, high, low);
+                    }
+                    Some(c) => return Err(format!("Invalid suffix: {}", c)),
+                }
+            }
+            return Ok(range);
         }
 
         let start_kicker = s_low.as_u8();
@@ -335,6 +722,74 @@ fn parse_dash_notation(start_str: &str, end_str: &str) -> Result<HandRange, Stri
     Ok(range)
 }
 
+/// Shape of a starting-hand class in range notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    Any,
+    Suited,
+    Offsuit,
+    Pair,
+}
+
+impl Shape {
+    fn suffix(self) -> &'static str {
+        match self {
+            Shape::Suited => "s",
+            Shape::Offsuit => "o",
+            Shape::Any | Shape::Pair => "",
+        }
+    }
+}
+
+fn class_combos(shape: Shape, high: u8, low: u8) -> HandRange {
+    let mut range = HandRange::new();
+    let (high, low) = (Rank::new(high), Rank::new(low));
+    match shape {
+        Shape::Pair => add_pair_combos(&mut range, high),
+        Shape::Suited => add_suited_combos(&mut range, high, low),
+        Shape::Offsuit => add_offsuit_combos(&mut range, high, low),
+        Shape::Any => {
+            add_suited_combos(&mut range, high, low);
+            add_offsuit_combos(&mut range, high, low);
+        }
+    }
+    range
+}
+
+/// Splits `weights[..=top]`, walked from `top` down, into runs of consecutive ranks
+/// sharing a weight (or single ranks if `join` is false). Returns (top, bottom,
+/// weight) for each run.
+fn weight_runs(weights: &[Option<f64>; 13], top: u8, join: bool) -> Vec<(u8, u8, f64)> {
+    let mut runs: Vec<(u8, u8, f64)> = Vec::new();
+    for r in (0..=top).rev() {
+        let Some(w) = weights[r as usize] else {
+            continue;
+        };
+        match runs.last_mut() {
+            Some(run) if join && run.1 == r + 1 && run.2.to_bits() == w.to_bits() => run.1 = r,
+            _ => runs.push((r, r, w)),
+        }
+    }
+    runs
+}
+
+fn with_weight(text: String, weight: f64) -> String {
+    if weight == 1.0 {
+        text
+    } else {
+        format!("{}:{}", text, weight)
+    }
+}
+
+/// Cards of a hand, highest rank first, written without separators ("AhKh").
+fn explicit_cards(hand: &StdDeckCardMask) -> String {
+    let mut cards: Vec<usize> = (0..STD_DECK_N_CARDS)
+        .filter(|&i| hand.card_is_set(i))
+        .collect();
+    cards.sort_by_key(|&i| (std::cmp::Reverse(StdDeck::rank(i)), StdDeck::suit(i)));
+    cards.into_iter().map(StdDeck::card_to_string).collect()
+}
+
 fn add_pair_combos(range: &mut HandRange, rank: Rank) {
     // 6 combos: h d, h c, h s, d c, d s, c s
     let suits = [Suit::HEARTS, Suit::DIAMONDS, Suit::CLUBS, Suit::SPADES];
@@ -472,4 +927,103 @@ mod tests {
         let (mask, _) = range.hands()[0];
         assert_eq!(mask.num_cards(), 4);
     }
+
+    fn weighted(spec: &str, weight: f64) -> HandRange {
+        let mut range = HandRange::from_str(spec).unwrap();
+        range.scale(weight);
+        range
+    }
+
+    #[test]
+    fn test_union_and_intersection_weights() {
+        let a = weighted("AA,KK", 1.0);
+        let b = weighted("KK,QQ", 0.5);
+        let (kk, _) = StdDeck::string_to_mask("KhKd").unwrap();
+        let weight_of = |r: &HandRange| r.hands().iter().find(|(h, _)| *h == kk).unwrap().1;
+
+        let max = a.union(&b, WeightMode::Max);
+        assert_eq!(max.len(), 18);
+        assert_eq!(weight_of(&max), 1.0);
+        assert_eq!(weight_of(&a.union(&b, WeightMode::Min)), 0.5);
+
+        let both = a.intersection(&b, WeightMode::Product);
+        assert_eq!(both.len(), 6);
+        assert_eq!(weight_of(&both), 0.5);
+
+        assert_eq!((&a | &b).len(), 18);
+        assert_eq!((&a & &b).len(), 6);
+    }
+
+    #[test]
+    fn test_subtract_partial_weights() {
+        let open = HandRange::from_str("AKs,AKo").unwrap();
+        let three_bet = weighted("AKs", 0.25);
+        let rest = &open - &three_bet;
+        assert_eq!(rest.len(), 16);
+        assert!((rest.total_weight() - (12.0 + 4.0 * 0.75)).abs() < 1e-9);
+
+        let gone = &open - &open;
+        assert!(gone.is_empty());
+    }
+
+    #[test]
+    fn test_normalize_and_counts() {
+        let mut range = HandRange::from_str("AA,KK").unwrap();
+        assert_eq!(range.total_weight(), 12.0);
+        assert!((range.percentage() - 12.0 / 1326.0 * 100.0).abs() < 1e-9);
+
+        range.scale(0.5);
+        range.normalize_max();
+        assert!(range.hands().iter().all(|(_, w)| *w == 1.0));
+
+        range.normalize();
+        assert!((range.total_weight() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_remove_dead() {
+        let mut range = HandRange::from_str("AA,KK").unwrap();
+        let (dead, _) = StdDeck::string_to_mask("AsKh").unwrap();
+        range.remove_dead(&dead);
+        // 3 AA combos + 3 KK combos remain.
+        assert_eq!(range.len(), 6);
+    }
+
+    #[test]
+    fn test_parse_weights_and_connectors() {
+        let range = HandRange::from_str("AKs:0.5, QQ").unwrap();
+        assert_eq!(range.len(), 10);
+        assert!((range.total_weight() - 8.0).abs() < 1e-9);
+
+        // T9s, 98s, 87s, 76s
+        let range = HandRange::from_str("T9s-76s").unwrap();
+        assert_eq!(range.len(), 16);
+        assert!(HandRange::from_str("T9s-75s").is_err());
+        assert!(HandRange::from_str("AKs:-1").is_err());
+        assert!(HandRange::from_str("AKs:x").is_err());
+    }
+
+    #[test]
+    fn test_to_notation_compresses_runs() {
+        let range = HandRange::from_str("AA,KK,QQ,JJ,AKs,AQs,KQo,T9s,98s,87s,76s").unwrap();
+        assert_eq!(range.to_notation(), "JJ+,AQs+,KQo,T9s-76s");
+
+        let range = HandRange::from_str("88,77,66,KJs,KTs,K9s,AK,AQ").unwrap();
+        assert_eq!(range.to_notation(), "88-66,AQ+,KJs-K9s");
+        assert_eq!(range.to_string(), range.to_notation());
+    }
+
+    #[test]
+    fn test_to_notation_weights_and_partial_classes() {
+        let range = HandRange::from_str("QQ+:0.5,AKs,AhKd").unwrap();
+        assert_eq!(range.to_notation(), "QQ+:0.5,AKs,AhKd");
+
+        // Three of the four suited combos is not a class.
+        let range = HandRange::from_str("AhKh,AdKd,AcKc").unwrap();
+        assert_eq!(range.to_notation(), "AhKh,AdKd,AcKc");
+
+        let range = HandRange::from_str("AsKsQsJs:0.25").unwrap();
+        assert_eq!(range.to_notation(), "AsKsQsJs:0.25");
+        assert_eq!(HandRange::new().to_notation(), "");
+    }
 }
@@ -0,0 +1,403 @@
+//! Range and nut advantage on a board.
+//!
+//! [`range_advantage`] compares two Hold'em ranges on a flop, turn or river. The
+//! report combines the overall range-vs-range equity from [`calculate_equity`], the
+//! equity of every combo against the opposing range, the share of nut-class hands
+//! each range holds, a [`HandCategory`] breakdown from [`classify`], and the
+//! [`BoardTexture`].
+
+use crate::board::BoardTexture;
+use crate::classify::{classify, HandCategory, HAND_CATEGORIES};
+use crate::deck::{StdDeckCardMask, STD_DECK_N_CARDS};
+use crate::errors::PokerError;
+use crate::evaluators::range_equity::{calculate_equity, EquityResult};
+use crate::evaluators::Eval;
+use crate::range::HandRange;
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use std::collections::BTreeSet;
+
+/// Number of distinct best hands on the board that count as nut-class.
+pub const NUT_CLASSES: usize = 3;
+
+/// Fewest runouts sampled per combo when estimating combo equities.
+const MIN_COMBO_SAMPLES: usize = 200;
+
+/// A combo's equity against the opposing range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComboEquity {
+    /// The two hole cards.
+    pub hand: StdDeckCardMask,
+    /// The combo's weight in its range.
+    pub weight: f64,
+    /// All-in equity against the opposing range (0 to 1).
+    pub equity: f64,
+    /// True if the combo currently makes one of the [`NUT_CLASSES`] best hands.
+    pub nut: bool,
+}
+
+/// One range's side of a [`RangeAdvantageReport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeReport {
+    /// Every live combo with its equity, in range order.
+    pub combos: Vec<ComboEquity>,
+    /// Share of the range's weight in each category, in [`HAND_CATEGORIES`] order.
+    /// Made hands sum to 1; draws overlap with made hands and each other.
+    pub categories: Vec<(HandCategory, f64)>,
+}
+
+impl RangeReport {
+    /// Weighted number of live combos.
+    pub fn total_weight(&self) -> f64 {
+        self.combos.iter().map(|c| c.weight).sum()
+    }
+
+    /// Weighted average equity of the combos.
+    pub fn equity(&self) -> f64 {
+        self.weighted_share(|_| true, |c| c.equity)
+    }
+
+    /// Share of the range's weight with equity of at least `threshold`.
+    pub fn share_above(&self, threshold: f64) -> f64 {
+        self.weighted_share(|c| c.equity >= threshold, |_| 1.0)
+    }
+
+    /// Share of the range's weight in each of `buckets` equal equity intervals,
+    /// lowest first. An equity of exactly 1 falls in the last bucket.
+    pub fn distribution(&self, buckets: usize) -> Vec<f64> {
+        let mut shares = vec![0.0; buckets];
+        let total = self.total_weight();
+        if buckets == 0 || total == 0.0 {
+            return shares;
+        }
+        for c in &self.combos {
+            let b = ((c.equity * buckets as f64) as usize).min(buckets - 1);
+            shares[b] += c.weight / total;
+        }
+        shares
+    }
+
+    /// Weighted number of nut-class combos.
+    pub fn nut_combos(&self) -> f64 {
+        self.combos.iter().filter(|c| c.nut).map(|c| c.weight).sum()
+    }
+
+    fn weighted_share(
+        &self,
+        keep: impl Fn(&ComboEquity) -> bool,
+        value: impl Fn(&ComboEquity) -> f64,
+    ) -> f64 {
+        let total = self.total_weight();
+        if total == 0.0 {
+            return 0.0;
+        }
+        self.combos
+            .iter()
+            .filter(|c| keep(c))
+            .map(|c| c.weight * value(c))
+            .sum::<f64>()
+            / total
+    }
+}
+
+/// Range advantage report from [`range_advantage`].
+#[derive(Debug, Clone)]
+pub struct RangeAdvantageReport {
+    /// Texture of the board.
+    pub texture: BoardTexture,
+    /// Range-vs-range result from [`calculate_equity`], from the first range's side.
+    pub equity: EquityResult,
+    /// The first and second range.
+    pub ranges: [RangeReport; 2],
+}
+
+impl RangeAdvantageReport {
+    /// Share of all nut-class combos, across both ranges, held by range `player`
+    /// (0 or 1). Zero if neither range has any.
+    pub fn nut_share(&self, player: usize) -> f64 {
+        let total = self.ranges[0].nut_combos() + self.ranges[1].nut_combos();
+        if total == 0.0 {
+            0.0
+        } else {
+            self.ranges[player].nut_combos() / total
+        }
+    }
+}
+
+/// Compares two Hold'em ranges on `board`.
+///
+/// The board must have 3 to 5 cards. Combos that conflict with the board or `dead`
+/// are removed first. `iterations` Monte Carlo deals are used for the overall
+/// equity and are spread over the combos of each range for per-combo equities,
+/// with at least a few hundred runouts per combo; on the river combo equities are
+/// exact.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::deck::{StdDeck, StdDeckCardMask};
+/// use poker_eval_rs::range::HandRange;
+/// use poker_eval_rs::range_advantage::range_advantage;
+/// use std::str::FromStr;
+///
+/// let raiser = HandRange::from_str("TT+,AK").unwrap();
+/// let caller = HandRange::from_str("22-99,AJs-ATs,KQs").unwrap();
+/// let (board, _) = StdDeck::string_to_mask("Kh8c3d").unwrap();
+/// let report = range_advantage(&raiser, &caller, &board, &StdDeckCardMask::new(), 10_000).unwrap();
+///
+/// assert!(report.ranges[0].equity() > 0.5);
+/// assert!(report.nut_share(0) > 0.0);
+/// ```
+pub fn range_advantage(
+    range1: &HandRange,
+    range2: &HandRange,
+    board: &StdDeckCardMask,
+    dead: &StdDeckCardMask,
+    iterations: usize,
+) -> Result<RangeAdvantageReport, PokerError> {
+    if !(3..=5).contains(&board.num_cards()) {
+        return Err(PokerError::UnsupportedBoardConfiguration);
+    }
+    if (board.as_raw() & dead.as_raw()) != 0 {
+        return Err(PokerError::InvalidCardConfiguration(
+            "Board and dead cards must not overlap".to_string(),
+        ));
+    }
+    if iterations == 0 {
+        return Err(PokerError::InvalidInput(
+            "Iterations must be positive".to_string(),
+        ));
+    }
+
+    let known = *board | *dead;
+    let live = |range: &HandRange| -> Result<Vec<(StdDeckCardMask, f64)>, PokerError> {
+        let hands: Vec<(StdDeckCardMask, f64)> = range
+            .hands()
+            .iter()
+            .filter(|(h, w)| *w > 0.0 && h.num_cards() == 2 && (h.as_raw() & known.as_raw()) == 0)
+            .copied()
+            .collect();
+        if hands.is_empty() {
+            return Err(PokerError::InvalidInput(
+                "Range is empty once board and dead cards are removed".to_string(),
+            ));
+        }
+        Ok(hands)
+    };
+    let hands1 = live(range1)?;
+    let hands2 = live(range2)?;
+
+    let equity = calculate_equity(
+        &HandRange {
+            hands: hands1.clone(),
+        },
+        &HandRange {
+            hands: hands2.clone(),
+        },
+        board,
+        iterations,
+    )
+    .map_err(PokerError::InvalidInput)?;
+
+    let nut_floor = nut_floor(&known, board);
+    let side = |hero: &[(StdDeckCardMask, f64)], villain: &[(StdDeckCardMask, f64)]| {
+        let samples = (iterations / hero.len()).max(MIN_COMBO_SAMPLES);
+        range_report(hero, villain, board, dead, samples, nut_floor)
+    };
+    let ranges = [side(&hands1, &hands2)?, side(&hands2, &hands1)?];
+
+    Ok(RangeAdvantageReport {
+        texture: BoardTexture::analyze(board),
+        equity,
+        ranges,
+    })
+}
+
+/// The weakest hand value that is still one of the [`NUT_CLASSES`] best hands any
+/// holding can make on `board`.
+fn nut_floor(known: &StdDeckCardMask, board: &StdDeckCardMask) -> u32 {
+    let deck: Vec<usize> = (0..STD_DECK_N_CARDS)
+        .filter(|&i| !known.card_is_set(i))
+        .collect();
+    let mut values = BTreeSet::new();
+    for (n, &a) in deck.iter().enumerate() {
+        for &b in &deck[n + 1..] {
+            let mut hand = *board;
+            hand.set(a);
+            hand.set(b);
+            values.insert(Eval::eval_n(&hand, hand.num_cards()).value);
+        }
+    }
+    values
+        .iter()
+        .rev()
+        .take(NUT_CLASSES)
+        .min()
+        .copied()
+        .unwrap_or(0)
+}
+
+fn range_report(
+    hero: &[(StdDeckCardMask, f64)],
+    villain: &[(StdDeckCardMask, f64)],
+    board: &StdDeckCardMask,
+    dead: &StdDeckCardMask,
+    samples: usize,
+    nut_floor: u32,
+) -> Result<RangeReport, PokerError> {
+    let mut rng = thread_rng();
+    let mut combos = Vec::with_capacity(hero.len());
+    let mut category_weight = vec![0.0; HAND_CATEGORIES.len()];
+    let mut total = 0.0;
+
+    for &(hand, weight) in hero {
+        let opponents: Vec<(StdDeckCardMask, f64)> = villain
+            .iter()
+            .filter(|(v, _)| (v.as_raw() & hand.as_raw()) == 0)
+            .copied()
+            .collect();
+        if opponents.is_empty() {
+            continue;
+        }
+        let equity = combo_equity(&hand, &opponents, board, dead, samples, &mut rng)?;
+
+        let class = classify(&hand, board)?;
+        for (i, category) in HAND_CATEGORIES.iter().enumerate() {
+            if class.made == *category || (category.is_draw() && class.matches(*category)) {
+                category_weight[i] += weight;
+            }
+        }
+        total += weight;
+
+        let full = hand | *board;
+        combos.push(ComboEquity {
+            hand,
+            weight,
+            equity,
+            nut: Eval::eval_n(&full, full.num_cards()).value >= nut_floor,
+        });
+    }
+
+    let categories = HAND_CATEGORIES
+        .iter()
+        .zip(category_weight)
+        .filter(|(_, w)| *w > 0.0)
+        .map(|(c, w)| (*c, w / total))
+        .collect();
+    Ok(RangeReport { combos, categories })
+}
+
+/// Equity of `hand` against the weighted `opponents`, exact on the river and
+/// sampled over `samples` runouts otherwise.
+fn combo_equity(
+    hand: &StdDeckCardMask,
+    opponents: &[(StdDeckCardMask, f64)],
+    board: &StdDeckCardMask,
+    dead: &StdDeckCardMask,
+    samples: usize,
+    rng: &mut impl Rng,
+) -> Result<f64, PokerError> {
+    let value = |pocket: &StdDeckCardMask, board: &StdDeckCardMask| {
+        let full = *pocket | *board;
+        Eval::eval_n(&full, full.num_cards()).value
+    };
+    let share = |hero: u32, villain: u32| match hero.cmp(&villain) {
+        std::cmp::Ordering::Greater => 1.0,
+        std::cmp::Ordering::Equal => 0.5,
+        std::cmp::Ordering::Less => 0.0,
+    };
+
+    let missing = 5 - board.num_cards();
+    if missing == 0 {
+        let hero = value(hand, board);
+        let (won, total) = opponents.iter().fold((0.0, 0.0), |(won, total), (v, w)| {
+            (won + w * share(hero, value(v, board)), total + w)
+        });
+        return Ok(won / total);
+    }
+
+    let dist = WeightedIndex::new(opponents.iter().map(|(_, w)| *w))
+        .map_err(|e| PokerError::InvalidInput(format!("Invalid range weights: {}", e)))?;
+    let used = *hand | *board | *dead;
+    let mut deck: Vec<usize> = (0..STD_DECK_N_CARDS)
+        .filter(|&i| !used.card_is_set(i))
+        .collect();
+
+    let mut won = 0.0;
+    for _ in 0..samples {
+        let villain = opponents[dist.sample(rng)].0;
+        let (dealt, _) = deck.partial_shuffle(rng, missing + 2);
+        let mut full = *board;
+        for &c in dealt
+            .iter()
+            .filter(|&&c| !villain.card_is_set(c))
+            .take(missing)
+        {
+            full.set(c);
+        }
+        won += share(value(hand, &full), value(&villain, &full));
+    }
+    Ok(won / samples as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::StdDeck;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_river_report_is_exact() {
+        let range1 = HandRange::from_str("AA,KQs").unwrap();
+        let range2 = HandRange::from_str("JJ").unwrap();
+        let (board, _) = StdDeck::string_to_mask("Ah9h4c2d7s").unwrap();
+        let report =
+            range_advantage(&range1, &range2, &board, &StdDeckCardMask::new(), 1000).unwrap();
+
+        // Three sets of aces beat JJ; KQs does not.
+        let side = &report.ranges[0];
+        assert_eq!(side.combos.len(), 7);
+        assert_eq!(side.total_weight(), 7.0);
+        assert!((side.equity() - 3.0 / 7.0).abs() < 1e-12);
+        assert!((side.share_above(0.8) - 3.0 / 7.0).abs() < 1e-12);
+        assert_eq!(side.distribution(5)[4], side.share_above(0.8));
+
+        // The wheel (53) is the nuts, but top set is still one of the three best
+        // hands on this board; JJ has none of them.
+        assert_eq!(report.nut_share(0), 1.0);
+        assert_eq!(report.ranges[1].nut_combos(), 0.0);
+        assert!(!report.texture.is_monotone);
+        let set = side
+            .categories
+            .iter()
+            .find(|(c, _)| *c == HandCategory::Set)
+            .unwrap();
+        assert!((set.1 - 3.0 / 7.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_flop_report() {
+        let range1 = HandRange::from_str("AhKh").unwrap();
+        let range2 = HandRange::from_str("QQ").unwrap();
+        let (board, _) = StdDeck::string_to_mask("Qh7h2c").unwrap();
+        let report =
+            range_advantage(&range1, &range2, &board, &StdDeckCardMask::new(), 2000).unwrap();
+        assert_eq!(report.ranges[1].combos.len(), 3);
+        assert!(report.ranges[1].equity() > 0.5);
+        assert!(report.ranges[0]
+            .categories
+            .iter()
+            .any(|(c, _)| *c == HandCategory::NutFlushDraw));
+        assert!((report.ranges[0].equity() + report.ranges[1].equity() - 1.0).abs() < 0.15);
+    }
+
+    #[test]
+    fn test_range_advantage_errors() {
+        let range = HandRange::from_str("AA").unwrap();
+        let (flop, _) = StdDeck::string_to_mask("AhAdAc").unwrap();
+        let none = StdDeckCardMask::new();
+        assert!(range_advantage(&range, &range, &flop, &none, 100).is_err());
+        let (preflop, _) = StdDeck::string_to_mask("Kh").unwrap();
+        assert!(range_advantage(&range, &range, &preflop, &none, 100).is_err());
+    }
+}
@@ -0,0 +1,519 @@
+//! Range filtering with a small query language.
+//!
+//! Selects the hands of a [`HandRange`] that match an expression on a given board,
+//! keeping their weights. Expressions combine atoms with `&`, `|`, `!` (or `and`,
+//! `or`, `not`) and parentheses:
+//!
+//! - any [`HandCategory`] name: `flushdraw`, `nfd`, `tptk`, `overpair`, `set`, `oesd`, ...
+//! - `pair`: a one-pair hand made with our own cards; `draw`: any draw
+//! - `hand_type <op> <type>`, e.g. `hand_type >= twopair`
+//! - `contains(As)`: the hand holds all the given cards; `contains(A)`: any ace
+//! - `equity <op> <x> vs <range>`, e.g. `equity > 0.6 vs BTN`, where the range is a
+//!   name registered in the [`FilterContext`] or range notation (`vs QQ+,AK`)
+//!
+//! Comparison operators are `>`, `>=`, `<`, `<=`, `==` and `!=`.
+
+use crate::classify::{classify, HandCategory};
+use crate::deck::{Rank, StdDeck, StdDeckCardMask, STD_DECK_N_CARDS};
+use crate::errors::PokerError;
+use crate::evaluators::range_equity::calculate_equity;
+use crate::evaluators::{Eval, HandEvaluator, OmahaHiEvaluator};
+use crate::range::HandRange;
+use crate::rules::HandType;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// A comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmpOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl CmpOp {
+    fn apply<T: PartialOrd>(&self, lhs: T, rhs: T) -> bool {
+        match self {
+            CmpOp::Gt => lhs > rhs,
+            CmpOp::Ge => lhs >= rhs,
+            CmpOp::Lt => lhs < rhs,
+            CmpOp::Le => lhs <= rhs,
+            CmpOp::Eq => lhs == rhs,
+            CmpOp::Ne => lhs != rhs,
+        }
+    }
+}
+
+/// A parsed filter expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    Not(Box<FilterExpr>),
+    /// The hand belongs to a made-hand or draw category.
+    Category(HandCategory),
+    /// One pair made with our own cards.
+    Pair,
+    /// Any draw.
+    Draw,
+    /// Compares the hand type of our best hand on the board.
+    HandType(CmpOp, HandType),
+    /// The hand holds all of these cards.
+    Contains(StdDeckCardMask),
+    /// The hand holds a card of this rank.
+    ContainsRank(Rank),
+    /// Compares our equity (0 to 1) against a named or literal range.
+    Equity(CmpOp, f64, String),
+}
+
+/// Board and named ranges used to evaluate filter expressions.
+#[derive(Debug, Clone)]
+pub struct FilterContext {
+    /// The current board.
+    pub board: StdDeckCardMask,
+    /// Ranges that `equity ... vs NAME` can refer to.
+    pub ranges: HashMap<String, HandRange>,
+    /// Monte Carlo iterations per hand for equity comparisons.
+    pub iterations: usize,
+}
+
+impl FilterContext {
+    /// Creates a context for `board` with no named ranges and 2000 equity iterations.
+    pub fn new(board: StdDeckCardMask) -> Self {
+        Self {
+            board,
+            ranges: HashMap::new(),
+            iterations: 2000,
+        }
+    }
+
+    /// Registers a named range for equity comparisons.
+    pub fn with_range(mut self, name: &str, range: HandRange) -> Self {
+        self.ranges.insert(name.to_string(), range);
+        self
+    }
+
+    /// Sets the Monte Carlo iterations used per hand for equity comparisons.
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    fn lookup_range(&self, spec: &str) -> Result<HandRange, PokerError> {
+        if let Some(range) = self.ranges.get(spec) {
+            return Ok(range.clone());
+        }
+        HandRange::from_str(spec)
+            .map_err(|e| PokerError::InvalidInput(format!("Unknown range '{}': {}", spec, e)))
+    }
+}
+
+impl FilterExpr {
+    /// Returns true if `hand` matches this expression in `ctx`.
+    pub fn matches(&self, hand: &StdDeckCardMask, ctx: &FilterContext) -> Result<bool, PokerError> {
+        match self {
+            FilterExpr::And(a, b) => Ok(a.matches(hand, ctx)? && b.matches(hand, ctx)?),
+            FilterExpr::Or(a, b) => Ok(a.matches(hand, ctx)? || b.matches(hand, ctx)?),
+            FilterExpr::Not(a) => Ok(!a.matches(hand, ctx)?),
+            FilterExpr::Category(c) => Ok(classify(hand, &ctx.board)?.matches(*c)),
+            FilterExpr::Pair => Ok(matches!(
+                classify(hand, &ctx.board)?.made,
+                HandCategory::Overpair
+                    | HandCategory::TopPairTopKicker
+                    | HandCategory::TopPair
+                    | HandCategory::SecondPair
+                    | HandCategory::WeakPair
+                    | HandCategory::Underpair
+            )),
+            FilterExpr::Draw => Ok(classify(hand, &ctx.board)?.has_draw()),
+            FilterExpr::HandType(op, hand_type) => {
+                let value = if hand.num_cards() > 2 {
+                    OmahaHiEvaluator::evaluate_hand(hand, &ctx.board)?
+                        .ok_or(PokerError::UnsupportedBoardConfiguration)?
+                } else {
+                    if ctx.board.num_cards() < 3 {
+                        return Err(PokerError::UnsupportedBoardConfiguration);
+                    }
+                    let all = *hand | ctx.board;
+                    Eval::eval_n(&all, all.num_cards())
+                };
+                Ok(op.apply(value.get_hand_type().as_usize(), hand_type.as_usize()))
+            }
+            FilterExpr::Contains(cards) => Ok((hand.as_raw() & cards.as_raw()) == cards.as_raw()),
+            FilterExpr::ContainsRank(rank) => {
+                Ok((0..STD_DECK_N_CARDS).any(|i| hand.card_is_set(i) && StdDeck::rank(i) == *rank))
+            }
+            FilterExpr::Equity(op, threshold, spec) => {
+                let villain = ctx.lookup_range(spec)?;
+                let hero = HandRange::from_hand_masks(vec![*hand]);
+                let res = calculate_equity(&hero, &villain, &ctx.board, ctx.iterations)
+                    .map_err(PokerError::ExecutionError)?;
+                Ok(op.apply(res.equity, *threshold))
+            }
+        }
+    }
+
+    /// Returns the hands of `range` matching this expression, keeping weights.
+    ///
+    /// Hands that share a card with the board are dropped.
+    pub fn apply(&self, range: &HandRange, ctx: &FilterContext) -> Result<HandRange, PokerError> {
+        let mut result = HandRange::new();
+        for (hand, weight) in range.hands() {
+            if (hand.as_raw() & ctx.board.as_raw()) != 0 {
+                continue;
+            }
+            if self.matches(hand, ctx)? {
+                result.hands.push((*hand, *weight));
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl FromStr for FilterExpr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            chars: s.chars().collect(),
+            pos: 0,
+        };
+        let expr = parser.parse_or()?;
+        parser.skip_ws();
+        if parser.pos < parser.chars.len() {
+            return Err(format!(
+                "Unexpected input at position {}: '{}'",
+                parser.pos,
+                parser.chars[parser.pos..].iter().collect::<String>()
+            ));
+        }
+        Ok(expr)
+    }
+}
+
+/// Filters `range` with a query string such as `"flushdraw & !pair"`.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::deck::StdDeck;
+/// use poker_eval_rs::range::HandRange;
+/// use poker_eval_rs::range_filter::{filter_range, FilterContext};
+/// use std::str::FromStr;
+///
+/// let range = HandRange::from_str("AhKh,QhJh,AsAd,7c6c").unwrap();
+/// let (board, _) = StdDeck::string_to_mask("Th9h2c").unwrap();
+/// let ctx = FilterContext::new(board);
+///
+/// let naked = filter_range(&range, "flushdraw & !pair", &ctx).unwrap();
+/// assert_eq!(naked.len(), 2);
+/// let aces = filter_range(&range, "contains(A) & hand_type >= pair", &ctx).unwrap();
+/// assert_eq!(aces.len(), 1);
+/// ```
+pub fn filter_range(
+    range: &HandRange,
+    query: &str,
+    ctx: &FilterContext,
+) -> Result<HandRange, PokerError> {
+    let expr = FilterExpr::from_str(query).map_err(PokerError::InvalidInput)?;
+    expr.apply(range, ctx)
+}
+
+/// Recursive-descent parser over the query characters.
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn skip_ws(&mut self) {
+        while self.pos < self.chars.len() && self.chars[self.pos].is_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_ws();
+        self.chars.get(self.pos).copied()
+    }
+
+    /// Consumes `token` if the input continues with it.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_ws();
+        let end = self.pos + token.chars().count();
+        if end <= self.chars.len() && self.chars[self.pos..end].iter().copied().eq(token.chars()) {
+            self.pos = end;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes a keyword, only if it is not the prefix of a longer word.
+    fn eat_word(&mut self, word: &str) -> bool {
+        let start = self.pos;
+        if !self.eat(word) {
+            return false;
+        }
+        if self
+            .chars
+            .get(self.pos)
+            .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+        {
+            self.pos = start;
+            return false;
+        }
+        true
+    }
+
+    fn ident(&mut self) -> Result<String, String> {
+        self.skip_ws();
+        let start = self.pos;
+        while self.pos < self.chars.len()
+            && (self.chars[self.pos].is_alphanumeric() || self.chars[self.pos] == '_')
+        {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(format!("Expected a name at position {}", start));
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    /// Reads a quoted string, or raw text up to whitespace, `)`, `&` or `|`.
+    fn raw(&mut self) -> Result<String, String> {
+        self.skip_ws();
+        if self.eat("\"") {
+            let start = self.pos;
+            while self.pos < self.chars.len() && self.chars[self.pos] != '"' {
+                self.pos += 1;
+            }
+            if self.pos == self.chars.len() {
+                return Err("Unterminated string".to_string());
+            }
+            let text: String = self.chars[start..self.pos].iter().collect();
+            self.pos += 1;
+            return Ok(text);
+        }
+        let start = self.pos;
+        while self.pos < self.chars.len()
+            && !self.chars[self.pos].is_whitespace()
+            && !matches!(self.chars[self.pos], ')' | '&' | '|')
+        {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(format!("Expected a value at position {}", start));
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    fn cmp_op(&mut self) -> Result<CmpOp, String> {
+        // Two-character operators first.
+        for (token, op) in [
+            (">=", CmpOp::Ge),
+            ("<=", CmpOp::Le),
+            ("==", CmpOp::Eq),
+            ("!=", CmpOp::Ne),
+            (">", CmpOp::Gt),
+            ("<", CmpOp::Lt),
+            ("=", CmpOp::Eq),
+        ] {
+            if self.eat(token) {
+                return Ok(op);
+            }
+        }
+        Err(format!(
+            "Expected a comparison operator at position {}",
+            self.pos
+        ))
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpr, String> {
+        let mut lhs = self.parse_and()?;
+        while self.eat("||") || self.eat("|") || self.eat_word("or") {
+            let rhs = self.parse_and()?;
+            lhs = FilterExpr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, String> {
+        let mut lhs = self.parse_unary()?;
+        while self.eat("&&") || self.eat("&") || self.eat_word("and") {
+            let rhs = self.parse_unary()?;
+            lhs = FilterExpr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpr, String> {
+        if self.peek() == Some('!') && !self.eat("!=") {
+            self.pos += 1;
+            return Ok(FilterExpr::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat_word("not") {
+            return Ok(FilterExpr::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat("(") {
+            let expr = self.parse_or()?;
+            if !self.eat(")") {
+                return Err(format!("Expected ')' at position {}", self.pos));
+            }
+            return Ok(expr);
+        }
+        self.parse_atom()
+    }
+
+    fn parse_atom(&mut self) -> Result<FilterExpr, String> {
+        let name = self.ident()?.to_lowercase();
+        match name.as_str() {
+            "hand_type" | "handtype" => {
+                let op = self.cmp_op()?;
+                let type_name = self.ident()?;
+                Ok(FilterExpr::HandType(op, parse_hand_type(&type_name)?))
+            }
+            "equity" => {
+                let op = self.cmp_op()?;
+                let value = self.raw()?;
+                let threshold = match value.strip_suffix('%') {
+                    Some(pct) => pct.parse::<f64>().map(|v| v / 100.0),
+                    None => value.parse::<f64>(),
+                }
+                .map_err(|_| format!("Invalid equity threshold: {}", value))?;
+                if !self.eat_word("vs") {
+                    return Err(format!("Expected 'vs' at position {}", self.pos));
+                }
+                Ok(FilterExpr::Equity(op, threshold, self.raw()?))
+            }
+            "contains" => {
+                if !self.eat("(") {
+                    return Err(format!("Expected '(' at position {}", self.pos));
+                }
+                let arg = self.raw()?;
+                if !self.eat(")") {
+                    return Err(format!("Expected ')' at position {}", self.pos));
+                }
+                let mut chars = arg.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Rank::from_char(c)
+                        .map(FilterExpr::ContainsRank)
+                        .ok_or_else(|| format!("Invalid rank: {}", arg)),
+                    _ => StdDeck::string_to_mask(&arg)
+                        .map(|(mask, _)| FilterExpr::Contains(mask))
+                        .map_err(|e| format!("Invalid cards '{}': {}", arg, e)),
+                }
+            }
+            "pair" => Ok(FilterExpr::Pair),
+            "draw" => Ok(FilterExpr::Draw),
+            _ => HandCategory::from_str(&name).map(FilterExpr::Category),
+        }
+    }
+}
+
+fn parse_hand_type(name: &str) -> Result<HandType, String> {
+    match name.to_lowercase().replace('_', "").as_str() {
+        "nopair" | "highcard" => Ok(HandType::NoPair),
+        "pair" | "onepair" => Ok(HandType::OnePair),
+        "twopair" => Ok(HandType::TwoPair),
+        "trips" | "set" => Ok(HandType::Trips),
+        "straight" => Ok(HandType::Straight),
+        "flush" => Ok(HandType::Flush),
+        "fullhouse" | "flhouse" | "boat" => Ok(HandType::FullHouse),
+        "quads" => Ok(HandType::Quads),
+        "straightflush" | "stflush" => Ok(HandType::StFlush),
+        _ => Err(format!("Unknown hand type: {}", name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(s: &str) -> StdDeckCardMask {
+        StdDeck::string_to_mask(s).unwrap().0
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        let expr = FilterExpr::from_str("set | flushdraw & !pair").unwrap();
+        assert_eq!(
+            expr,
+            FilterExpr::Or(
+                Box::new(FilterExpr::Category(HandCategory::Set)),
+                Box::new(FilterExpr::And(
+                    Box::new(FilterExpr::Category(HandCategory::FlushDraw)),
+                    Box::new(FilterExpr::Not(Box::new(FilterExpr::Pair))),
+                )),
+            )
+        );
+        assert_eq!(
+            FilterExpr::from_str("not (pair or draw)").unwrap(),
+            FilterExpr::Not(Box::new(FilterExpr::Or(
+                Box::new(FilterExpr::Pair),
+                Box::new(FilterExpr::Draw)
+            )))
+        );
+        assert_eq!(
+            FilterExpr::from_str("equity >= 55% vs QQ+,AK").unwrap(),
+            FilterExpr::Equity(CmpOp::Ge, 0.55, "QQ+,AK".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(FilterExpr::from_str("flushdraw &").is_err());
+        assert!(FilterExpr::from_str("(set").is_err());
+        assert!(FilterExpr::from_str("monster").is_err());
+        assert!(FilterExpr::from_str("hand_type >= sevens").is_err());
+        assert!(FilterExpr::from_str("equity > 0.5").is_err());
+        assert!(FilterExpr::from_str("set set").is_err());
+    }
+
+    #[test]
+    fn test_hand_type_and_contains() {
+        let range = HandRange::from_str("AA,KK,AK,76s").unwrap();
+        let ctx = FilterContext::new(board("Ks8c5d"));
+
+        let two_pair_plus = filter_range(&range, "hand_type >= twopair", &ctx).unwrap();
+        // Only the three remaining KK combos make a set.
+        assert_eq!(two_pair_plus.len(), 3);
+
+        let spade_ace = filter_range(&range, "contains(As)", &ctx).unwrap();
+        // AsAh, AsAd, AsAc + AsKh, AsKd, AsKc
+        assert_eq!(spade_ace.len(), 6);
+
+        let straight_draws = filter_range(&range, "oesd", &ctx).unwrap();
+        assert_eq!(straight_draws.len(), 4);
+    }
+
+    #[test]
+    fn test_equity_vs_named_range() {
+        let range = HandRange::from_str("AA,22").unwrap();
+        let ctx = FilterContext::new(StdDeckCardMask::new())
+            .with_range("BTN", HandRange::from_str("KK").unwrap())
+            .with_iterations(500);
+        let strong = filter_range(&range, "equity > 0.6 vs BTN", &ctx).unwrap();
+        assert_eq!(strong.len(), 6);
+        assert!(strong.hands().iter().all(|(h, _)| {
+            FilterExpr::ContainsRank(Rank::new(12))
+                .matches(h, &ctx)
+                .unwrap()
+        }));
+
+        assert!(filter_range(&range, "equity > 0.6 vs UTG", &ctx).is_err());
+    }
+
+    #[test]
+    fn test_filter_keeps_weights() {
+        let mut range = HandRange::new();
+        let (hand, _) = StdDeck::string_to_mask("AhKh").unwrap();
+        range.push_weighted(hand, 0.25);
+        let ctx = FilterContext::new(board("Qh7h2c"));
+        let res = filter_range(&range, "nfd", &ctx).unwrap();
+        assert_eq!(res.hands(), &[(hand, 0.25)]);
+    }
+}
@@ -0,0 +1,340 @@
+//! Import and export of solver range formats.
+//!
+//! All formats store weighted ranges; they differ in how weights are written:
+//!
+//! - [`RangeFormat::Standard`]: compact notation from [`HandRange::to_notation`],
+//!   e.g. `JJ+,AQs+:0.5,T9s-76s`.
+//! - [`RangeFormat::Pio`]: PioSOLVER, one token per hand class with a `:weight`
+//!   suffix, e.g. `AA:0.75,AKs,AsKs:0.25`.
+//! - [`RangeFormat::GtoPlus`]: GTO+, one token per hand class with percentage
+//!   weight groups, e.g. `AKs,[75]AA,KK[/75]`.
+//! - [`RangeFormat::Flopzilla`]: Flopzilla, compact `+` and dash runs with the same
+//!   percentage groups, e.g. `AQs+,[50]TT-88[/50]`.
+//!
+//! Standard and Pio ranges are read by the same parser, as are GTO+ and Flopzilla.
+
+use crate::errors::PokerError;
+use crate::range::HandRange;
+use std::fmt;
+use std::str::FromStr;
+
+/// A text format for weighted ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeFormat {
+    Standard,
+    Pio,
+    GtoPlus,
+    Flopzilla,
+}
+
+impl RangeFormat {
+    /// Short name used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            RangeFormat::Standard => "standard",
+            RangeFormat::Pio => "pio",
+            RangeFormat::GtoPlus => "gtoplus",
+            RangeFormat::Flopzilla => "flopzilla",
+        }
+    }
+
+    /// Guesses the format of `s`. Percentage groups mean GTO+, or Flopzilla when
+    /// they come with `+` or dash runs; `:weight` tokens without runs mean Pio, and
+    /// anything else is read as standard notation.
+    pub fn detect(s: &str) -> Self {
+        let has_runs = s.contains('+') || s.contains('-');
+        if s.contains("[/") {
+            if has_runs {
+                RangeFormat::Flopzilla
+            } else {
+                RangeFormat::GtoPlus
+            }
+        } else if s.contains(':') && !has_runs {
+            RangeFormat::Pio
+        } else {
+            RangeFormat::Standard
+        }
+    }
+
+    fn uses_groups(&self) -> bool {
+        matches!(self, RangeFormat::GtoPlus | RangeFormat::Flopzilla)
+    }
+}
+
+impl fmt::Display for RangeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for RangeFormat {
+    type Err = PokerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "standard" | "notation" => Ok(RangeFormat::Standard),
+            "pio" | "piosolver" => Ok(RangeFormat::Pio),
+            "gtoplus" | "gto+" | "gto" => Ok(RangeFormat::GtoPlus),
+            "flopzilla" | "fz" => Ok(RangeFormat::Flopzilla),
+            _ => Err(PokerError::InvalidInput(format!(
+                "Unknown range format '{}'",
+                s
+            ))),
+        }
+    }
+}
+
+impl HandRange {
+    /// Parses a range written in `format`.
+    ///
+    /// Newlines are treated as separators, so ranges pasted from multi-line solver
+    /// files parse as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use poker_eval_rs::range::HandRange;
+    /// use poker_eval_rs::range_format::RangeFormat;
+    ///
+    /// let range = HandRange::parse_format("AKs,[50]QQ,JJ[/50]", RangeFormat::GtoPlus).unwrap();
+    /// assert_eq!(range.len(), 16);
+    /// assert_eq!(range.total_weight(), 10.0);
+    /// ```
+    pub fn parse_format(s: &str, format: RangeFormat) -> Result<HandRange, PokerError> {
+        let s = s.replace(['\n', '\r'], ",");
+        if format.uses_groups() {
+            parse_groups(&s)
+        } else {
+            parse_plain(&s)
+        }
+    }
+
+    /// Writes the range in `format`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use poker_eval_rs::range::HandRange;
+    /// use poker_eval_rs::range_format::RangeFormat;
+    /// use std::str::FromStr;
+    ///
+    /// let range = HandRange::from_str("KK+:0.75,AKs").unwrap();
+    /// assert_eq!(range.to_format(RangeFormat::Pio), "AA:0.75,KK:0.75,AKs");
+    /// assert_eq!(range.to_format(RangeFormat::GtoPlus), "AKs,[75]AA,KK[/75]");
+    /// ```
+    pub fn to_format(&self, format: RangeFormat) -> String {
+        match format {
+            RangeFormat::Standard => self.to_notation(),
+            RangeFormat::Pio => {
+                let parts: Vec<String> = self
+                    .notation_tokens(false)
+                    .into_iter()
+                    .map(|(text, w)| {
+                        if w == 1.0 {
+                            text
+                        } else {
+                            format!("{}:{}", text, w)
+                        }
+                    })
+                    .collect();
+                parts.join(",")
+            }
+            RangeFormat::GtoPlus => write_groups(self.notation_tokens(false)),
+            RangeFormat::Flopzilla => write_groups(self.notation_tokens(true)),
+        }
+    }
+}
+
+fn parse_plain(s: &str) -> Result<HandRange, PokerError> {
+    HandRange::from_str(s).map_err(PokerError::InvalidInput)
+}
+
+/// Parses text with `[pct]...[/pct]` weight groups; text outside groups has weight 1
+/// unless it carries its own `:weight`.
+fn parse_groups(s: &str) -> Result<HandRange, PokerError> {
+    let unclosed = || PokerError::InvalidInput(format!("Unclosed weight group in '{}'", s));
+    let mut range = HandRange::new();
+    let mut rest = s;
+    while let Some(open) = rest.find('[') {
+        range.merge(parse_plain(&rest[..open])?);
+
+        let after = &rest[open + 1..];
+        let close = after.find(']').ok_or_else(unclosed)?;
+        let pct = after[..close].trim();
+        let weight = parse_percent(pct)
+            .ok_or_else(|| PokerError::InvalidInput(format!("Invalid weight group '[{}]'", pct)))?;
+
+        let body = &after[close + 1..];
+        let end = body.find("[/").ok_or_else(unclosed)?;
+        let mut group = parse_plain(&body[..end])?;
+        for (_, w) in &mut group.hands {
+            *w = weight;
+        }
+        range.merge(group);
+
+        let tail = &body[end + 2..];
+        let close = tail.find(']').ok_or_else(unclosed)?;
+        rest = &tail[close + 1..];
+    }
+    range.merge(parse_plain(rest)?);
+    Ok(range)
+}
+
+/// Weight `w` as a percentage: its shortest decimal form with the point moved two
+/// places, so `0.07` is written `7` rather than `7.000000000000001`. Read back with
+/// [`parse_percent`], every weight round-trips exactly.
+fn percent(w: f64) -> String {
+    let text = w.to_string();
+    let (int, frac) = text.split_once('.').unwrap_or((&text, ""));
+    let frac = format!("{:0<2}", frac);
+    let (moved, rest) = frac.split_at(2);
+    let whole = format!("{}{}", int, moved);
+    let whole = match whole.trim_start_matches('0') {
+        "" => "0",
+        trimmed => trimmed,
+    };
+    if rest.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, rest)
+    }
+}
+
+/// The weight of a `[pct]` group: the decimal `pct` / 100, rounded once.
+fn parse_percent(pct: &str) -> Option<f64> {
+    let p = pct.parse::<f64>().ok()?;
+    if !p.is_finite() || p < 0.0 {
+        return None;
+    }
+    Some(format!("{}e-2", pct).parse().unwrap_or(p / 100.0))
+}
+
+/// Writes full-weight tokens first, then one `[pct]...[/pct]` group per weight.
+fn write_groups(tokens: Vec<(String, f64)>) -> String {
+    let mut plain = Vec::new();
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    for (text, w) in tokens {
+        let pct = percent(w);
+        if pct == "100" {
+            plain.push(text);
+        } else if let Some((_, group)) = groups.iter_mut().find(|(gp, _)| *gp == pct) {
+            group.push(text);
+        } else {
+            groups.push((pct, vec![text]));
+        }
+    }
+    for (pct, group) in groups {
+        plain.push(format!("[{}]{}[/{}]", pct, group.join(","), pct));
+    }
+    plain.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(range: &HandRange) -> Vec<(u64, u64)> {
+        let mut hands: Vec<(u64, u64)> = range
+            .hands()
+            .iter()
+            .map(|(h, w)| (h.as_raw(), w.to_bits()))
+            .collect();
+        hands.sort_unstable();
+        hands
+    }
+
+    #[test]
+    fn test_pio_import_and_export() {
+        let range = HandRange::parse_format("AA:0.75,AKs:1,\nAsKh:0.25", RangeFormat::Pio).unwrap();
+        assert_eq!(range.len(), 11);
+        assert!((range.total_weight() - (4.5 + 4.0 + 0.25)).abs() < 1e-9);
+
+        let text = range.to_format(RangeFormat::Pio);
+        assert_eq!(text, "AA:0.75,AKs,AsKh:0.25");
+        let back = HandRange::parse_format(&text, RangeFormat::Pio).unwrap();
+        assert_eq!(sorted(&back), sorted(&range));
+    }
+
+    #[test]
+    fn test_group_formats_round_trip() {
+        let range = HandRange::from_str("QQ+,AKs,TT-88:0.5,AQo:0.25").unwrap();
+        assert_eq!(
+            range.to_format(RangeFormat::Flopzilla),
+            "QQ+,AKs,[50]TT-88[/50],[25]AQo[/25]"
+        );
+        assert_eq!(
+            range.to_format(RangeFormat::GtoPlus),
+            "AA,KK,QQ,AKs,[50]TT,99,88[/50],[25]AQo[/25]"
+        );
+        for format in [RangeFormat::GtoPlus, RangeFormat::Flopzilla] {
+            let text = range.to_format(format);
+            assert_eq!(RangeFormat::detect(&text), format);
+            let back = HandRange::parse_format(&text, format).unwrap();
+            assert_eq!(sorted(&back), sorted(&range));
+        }
+    }
+
+    #[test]
+    fn test_weights_round_trip() {
+        // 0.07 * 100.0 and 0.29 * 100.0 are not exact in binary.
+        let range = HandRange::from_str("AA:0.07,KK:0.29").unwrap();
+        assert_eq!(
+            range.to_format(RangeFormat::GtoPlus),
+            "[7]AA[/7],[29]KK[/29]"
+        );
+        assert_eq!(
+            range.to_format(RangeFormat::Flopzilla),
+            "[7]AA[/7],[29]KK[/29]"
+        );
+        assert_eq!(range.to_format(RangeFormat::Pio), "AA:0.07,KK:0.29");
+
+        // Weights with no short decimal form are written in full and read back
+        // bit for bit.
+        let mut thirds = HandRange::from_str("AA,KK:0.7").unwrap();
+        thirds.scale(1.0 / 3.0);
+        assert_eq!(
+            thirds.to_format(RangeFormat::Pio),
+            "AA:0.3333333333333333,KK:0.2333333333333333"
+        );
+        for range in [range, thirds] {
+            for format in [
+                RangeFormat::Pio,
+                RangeFormat::GtoPlus,
+                RangeFormat::Flopzilla,
+            ] {
+                let text = range.to_format(format);
+                let back = HandRange::parse_format(&text, format).unwrap();
+                assert_eq!(sorted(&back), sorted(&range), "{}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            RangeFormat::detect("AA:0.75,AKs,AsKh:0.25"),
+            RangeFormat::Pio
+        );
+        assert_eq!(
+            RangeFormat::detect("JJ+,AQs+:0.5,T9s-76s"),
+            RangeFormat::Standard
+        );
+        assert_eq!(RangeFormat::detect("AA,KK"), RangeFormat::Standard);
+        assert_eq!(
+            RangeFormat::detect("AKs,[75]AA,KK[/75]"),
+            RangeFormat::GtoPlus
+        );
+        assert_eq!(
+            RangeFormat::detect("AQs+,[50]TT-88[/50]"),
+            RangeFormat::Flopzilla
+        );
+    }
+
+    #[test]
+    fn test_format_errors() {
+        assert!(HandRange::parse_format("[50]AA", RangeFormat::GtoPlus).is_err());
+        assert!(HandRange::parse_format("[x]AA[/x]", RangeFormat::GtoPlus).is_err());
+        assert!("ppt".parse::<RangeFormat>().is_err());
+        assert_eq!("GTO+".parse::<RangeFormat>().unwrap(), RangeFormat::GtoPlus);
+    }
+}
@@ -0,0 +1,556 @@
+//! Preflop starting-hand rankings and top-X% ranges.
+//!
+//! A ranking orders the 169 Hold'em starting-hand classes (`AA`, `AKs`, `AKo`, ...)
+//! from best to worst. Built-in rankings sort classes by their all-in equity against
+//! one or more random hands. Against one hand the equities are exact (every board
+//! against every opponent holding) and built in; against more, they are estimated
+//! with [`enum_sample`] over seeded random opponent deals, once per process, and
+//! cached.
+
+use crate::deck::{Rank, StdDeckCardMask, STD_DECK_N_CARDS};
+use crate::enumdefs::{EnumResult, Game, ENUM_MAXPLAYERS};
+use crate::enumerate::enum_sample;
+use crate::errors::PokerError;
+use crate::range::HandRange;
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Opponent deals sampled per class when building a multi-way ranking.
+const RANKING_DEALS: usize = 2_000;
+/// Boards sampled per opponent deal.
+const RANKING_BOARDS: usize = 25;
+
+/// Exact heads-up equity of each class against one random hand, best first.
+#[rustfmt::skip]
+const VS_ONE_RANDOM: [(&str, f64); 169] = [
+    ("AA", 0.852037),
+    ("KK", 0.823957),
+    ("QQ", 0.799252),
+    ("JJ", 0.774695),
+    ("TT", 0.750118),
+    ("99", 0.720573),
+    ("88", 0.691630),
+    ("AKs", 0.670446),
+    ("77", 0.662360),
+    ("AQs", 0.662089),
+    ("AJs", 0.653927),
+    ("AKo", 0.653201),
+    ("ATs", 0.646024),
+    ("AQo", 0.644318),
+    ("AJo", 0.635633),
+    ("KQs", 0.634004),
+    ("66", 0.632847),
+    ("A9s", 0.627812),
+    ("ATo", 0.627217),
+    ("KJs", 0.625673),
+    ("A8s", 0.619438),
+    ("KTs", 0.617886),
+    ("KQo", 0.614558),
+    ("A7s", 0.609840),
+    ("A9o", 0.607728),
+    ("KJo", 0.605687),
+    ("55", 0.603249),
+    ("QJs", 0.602592),
+    ("K9s", 0.599885),
+    ("A5s", 0.599229),
+    ("A6s", 0.599058),
+    ("A8o", 0.598726),
+    ("KTo", 0.597389),
+    ("QTs", 0.594676),
+    ("A4s", 0.590336),
+    ("A7o", 0.588412),
+    ("K8s", 0.583123),
+    ("A3s", 0.582203),
+    ("QJo", 0.581347),
+    ("K9o", 0.578119),
+    ("A5o", 0.576965),
+    ("A6o", 0.576825),
+    ("Q9s", 0.576643),
+    ("K7s", 0.575377),
+    ("JTs", 0.575279),
+    ("A2s", 0.573789),
+    ("QTo", 0.572908),
+    ("44", 0.570228),
+    ("A4o", 0.567297),
+    ("K6s", 0.566407),
+    ("K8o", 0.560202),
+    ("Q8s", 0.560177),
+    ("A3o", 0.558446),
+    ("K5s", 0.557929),
+    ("J9s", 0.556625),
+    ("Q9o", 0.553604),
+    ("JTo", 0.552477),
+    ("K7o", 0.551874),
+    ("A2o", 0.549286),
+    ("K4s", 0.548846),
+    ("Q7s", 0.543023),
+    ("K6o", 0.542233),
+    ("K3s", 0.540550),
+    ("T9s", 0.540275),
+    ("J8s", 0.540156),
+    ("33", 0.536931),
+    ("Q6s", 0.536126),
+    ("Q8o", 0.535998),
+    ("K5o", 0.533140),
+    ("J9o", 0.532512),
+    ("K2s", 0.532117),
+    ("Q5s", 0.527694),
+    ("T8s", 0.523344),
+    ("K4o", 0.523275),
+    ("J7s", 0.523248),
+    ("Q4s", 0.518553),
+    ("Q7o", 0.517657),
+    ("T9o", 0.515317),
+    ("J8o", 0.514902),
+    ("K3o", 0.514257),
+    ("Q6o", 0.510241),
+    ("Q3s", 0.510192),
+    ("98s", 0.508008),
+    ("T7s", 0.506390),
+    ("J6s", 0.506059),
+    ("K2o", 0.505087),
+    ("22", 0.503340),
+    ("Q2s", 0.501690),
+    ("Q5o", 0.501201),
+    ("J5s", 0.499868),
+    ("T8o", 0.497213),
+    ("J7o", 0.496819),
+    ("Q4o", 0.491277),
+    ("97s", 0.491177),
+    ("J4s", 0.490705),
+    ("T6s", 0.489407),
+    ("J3s", 0.482316),
+    ("Q3o", 0.482194),
+    ("98o", 0.480970),
+    ("87s", 0.479363),
+    ("T7o", 0.479081),
+    ("J6o", 0.478443),
+    ("96s", 0.474283),
+    ("J2s", 0.473782),
+    ("Q2o", 0.472954),
+    ("T5s", 0.472163),
+    ("J5o", 0.471809),
+    ("T4s", 0.465305),
+    ("97o", 0.462978),
+    ("86s", 0.462433),
+    ("J4o", 0.461864),
+    ("T6o", 0.460920),
+    ("95s", 0.457219),
+    ("T3s", 0.456925),
+    ("76s", 0.453718),
+    ("J3o", 0.452755),
+    ("87o", 0.450508),
+    ("T2s", 0.448395),
+    ("85s", 0.445450),
+    ("96o", 0.444913),
+    ("J2o", 0.443485),
+    ("T5o", 0.442509),
+    ("94s", 0.438620),
+    ("75s", 0.436755),
+    ("T4o", 0.435041),
+    ("93s", 0.432643),
+    ("86o", 0.432409),
+    ("65s", 0.431334),
+    ("84s", 0.427016),
+    ("95o", 0.426691),
+    ("T3o", 0.425946),
+    ("92s", 0.424152),
+    ("76o", 0.423227),
+    ("74s", 0.418493),
+    ("T2o", 0.416684),
+    ("54s", 0.414534),
+    ("85o", 0.414275),
+    ("64s", 0.413333),
+    ("83s", 0.408735),
+    ("94o", 0.406711),
+    ("75o", 0.405120),
+    ("82s", 0.402716),
+    ("73s", 0.400359),
+    ("93o", 0.400195),
+    ("65o", 0.399443),
+    ("53s", 0.396930),
+    ("63s", 0.395336),
+    ("84o", 0.394468),
+    ("92o", 0.390979),
+    ("43s", 0.386419),
+    ("74o", 0.385498),
+    ("72s", 0.381559),
+    ("54o", 0.381553),
+    ("64o", 0.380105),
+    ("52s", 0.378493),
+    ("62s", 0.376690),
+    ("83o", 0.374838),
+    ("42s", 0.368290),
+    ("82o", 0.368277),
+    ("73o", 0.366023),
+    ("53o", 0.362648),
+    ("63o", 0.360776),
+    ("32s", 0.359844),
+    ("43o", 0.351459),
+    ("72o", 0.345836),
+    ("52o", 0.342846),
+    ("62o", 0.340751),
+    ("42o", 0.331998),
+    ("32o", 0.323032),
+];
+
+/// How starting hands are ordered for [`HandRange::top_percent`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum HandRanking {
+    /// All-in equity against one random hand.
+    VsRandom,
+    /// All-in equity against N random hands.
+    VsRandomN(usize),
+    /// A user-supplied list of tiers, best first. Each tier is a range such as
+    /// `AA` or `QQ+,AKs`; hands keep the position of the first tier they appear in.
+    Custom(Vec<HandRange>),
+}
+
+impl HandRanking {
+    /// Builds a custom ranking from comma-separated tiers, best first (e.g.
+    /// `"AA,KK,AKs,QQ"`).
+    pub fn from_list(list: &str) -> Result<Self, PokerError> {
+        let tiers = list
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(|t| HandRange::from_str(t).map_err(PokerError::InvalidInput))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(HandRanking::Custom(tiers))
+    }
+
+    /// The ranking as tiers of hands, best first.
+    fn tiers(&self) -> Result<Vec<HandRange>, PokerError> {
+        let opponents = match self {
+            HandRanking::Custom(tiers) => return Ok(tiers.clone()),
+            HandRanking::VsRandom => 1,
+            HandRanking::VsRandomN(n) => *n,
+        };
+        preflop_ranking(opponents)?
+            .iter()
+            .map(|c| HandRange::from_str(&c.class).map_err(PokerError::InvalidInput))
+            .collect()
+    }
+}
+
+/// A starting-hand class and its estimated preflop equity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassEquity {
+    /// Class in range notation: `AA`, `AKs` or `AKo`.
+    pub class: String,
+    /// All-in equity against the ranking's random opponents.
+    pub equity: f64,
+}
+
+/// Returns the 169 starting-hand classes sorted by equity against `opponents`
+/// random hands, best first.
+///
+/// The result is cached, so only the first call for a given number of opponents
+/// runs the simulation.
+pub fn preflop_ranking(opponents: usize) -> Result<Arc<Vec<ClassEquity>>, PokerError> {
+    if opponents == 0 || opponents + 1 > ENUM_MAXPLAYERS {
+        return Err(PokerError::TooManyPlayers);
+    }
+    static CACHE: OnceLock<Mutex<HashMap<usize, Arc<Vec<ClassEquity>>>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(ranking) = cache.lock().unwrap().get(&opponents) {
+        return Ok(Arc::clone(ranking));
+    }
+
+    let ranking = Arc::new(compute_ranking(opponents)?);
+    cache
+        .lock()
+        .unwrap()
+        .insert(opponents, Arc::clone(&ranking));
+    Ok(ranking)
+}
+
+fn compute_ranking(opponents: usize) -> Result<Vec<ClassEquity>, PokerError> {
+    if opponents == 1 {
+        return Ok(VS_ONE_RANDOM
+            .iter()
+            .map(|&(class, equity)| ClassEquity {
+                class: class.to_string(),
+                equity,
+            })
+            .collect());
+    }
+    let mut result = EnumResult::new(Game::Holdem);
+    let mut rng = SmallRng::seed_from_u64(opponents as u64);
+    let mut ranking = Vec::with_capacity(169);
+
+    for class in starting_hand_classes() {
+        let range = HandRange::from_str(&class).map_err(PokerError::InvalidInput)?;
+        let hero = range.hands()[0].0;
+        let mut deck: Vec<usize> = (0..STD_DECK_N_CARDS)
+            .filter(|&i| !hero.card_is_set(i))
+            .collect();
+
+        let mut pockets = vec![hero; opponents + 1];
+        let (mut ev, mut samples) = (0.0, 0u64);
+        for _ in 0..RANKING_DEALS {
+            let (dealt, _) = deck.partial_shuffle(&mut rng, 2 * opponents);
+            for (pocket, cards) in pockets[1..].iter_mut().zip(dealt.chunks(2)) {
+                *pocket = StdDeckCardMask::new();
+                pocket.set(cards[0]);
+                pocket.set(cards[1]);
+            }
+            enum_sample(
+                Game::Holdem,
+                &pockets,
+                StdDeckCardMask::new(),
+                StdDeckCardMask::new(),
+                opponents + 1,
+                0,
+                RANKING_BOARDS,
+                false,
+                &mut result,
+            )?;
+            ev += result.ev[0];
+            samples += result.nsamples as u64;
+        }
+        ranking.push(ClassEquity {
+            class,
+            equity: ev / samples as f64,
+        });
+    }
+
+    ranking.sort_by(|a, b| b.equity.total_cmp(&a.equity));
+    Ok(ranking)
+}
+
+/// The 169 starting-hand classes: pairs, then suited and offsuit hands.
+fn starting_hand_classes() -> Vec<String> {
+    let mut classes = Vec::with_capacity(169);
+    for high in (0..13u8).rev() {
+        let h = Rank::new(high);
+        classes.push(format!("{}{}", h, h));
+        for low in (0..high).rev() {
+            let l = Rank::new(low);
+            classes.push(format!("{}{}s", h, l));
+            classes.push(format!("{}{}o", h, l));
+        }
+    }
+    classes
+}
+
+impl HandRange {
+    /// The top `percent`% of starting hands under `ranking`.
+    ///
+    /// Equivalent to [`HandRange::top_percent_with_dead`] with no dead cards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use poker_eval_rs::range::HandRange;
+    /// use poker_eval_rs::ranking::HandRanking;
+    ///
+    /// let ranking = HandRanking::from_list("AA,KK,QQ,AKs").unwrap();
+    /// let range = HandRange::top_percent(1.0, &ranking).unwrap();
+    /// // 1% of 1326 combos: AA, KK and a third of QQ.
+    /// assert!((range.total_weight() - 13.26).abs() < 1e-9);
+    /// ```
+    pub fn top_percent(percent: f64, ranking: &HandRanking) -> Result<HandRange, PokerError> {
+        Self::top_percent_with_dead(percent, ranking, &StdDeckCardMask::new())
+    }
+
+    /// The top `percent`% of the combos still possible once `dead` (board and other
+    /// known cards) is removed, taking hands in `ranking` order.
+    ///
+    /// Percentages are combo-accurate: they count the combos left after card
+    /// removal, and the tier on the boundary is included with a partial weight so
+    /// the total weight is exactly `percent`% of the available combos (or every
+    /// ranked hand, if the ranking runs out first).
+    pub fn top_percent_with_dead(
+        percent: f64,
+        ranking: &HandRanking,
+        dead: &StdDeckCardMask,
+    ) -> Result<HandRange, PokerError> {
+        if !(0.0..=100.0).contains(&percent) {
+            return Err(PokerError::InvalidInput(format!(
+                "Percentage must be between 0 and 100, got {}",
+                percent
+            )));
+        }
+        let live = STD_DECK_N_CARDS - dead.num_cards();
+        let target = percent / 100.0 * (live * (live - 1) / 2) as f64;
+
+        let mut range = HandRange::new();
+        let mut total = 0.0;
+        for mut tier in ranking.tiers()? {
+            if total >= target {
+                break;
+            }
+            tier.remove_dead(dead);
+            let tier = tier.filter(|h| !range.hands().iter().any(|(r, _)| r == h));
+            let combos = tier.len() as f64;
+            if combos == 0.0 {
+                continue;
+            }
+            let weight = ((target - total) / combos).min(1.0);
+            for (hand, _) in tier.hands() {
+                range.push_weighted(*hand, weight);
+            }
+            total += weight * combos;
+        }
+        Ok(range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::StdDeck;
+
+    #[test]
+    fn test_custom_ranking_partial_tier() {
+        let ranking = HandRanking::from_list("AA,KK,AKs").unwrap();
+        let range = HandRange::top_percent(0.5, &ranking).unwrap();
+        // 6.63 combos: all of AA and 0.63 / 6 of each KK combo.
+        assert_eq!(range.len(), 12);
+        assert!((range.total_weight() - 6.63).abs() < 1e-9);
+
+        // The ranking runs out before 50%.
+        let range = HandRange::top_percent(50.0, &ranking).unwrap();
+        assert_eq!(range.total_weight(), 16.0);
+        assert!(HandRange::top_percent(120.0, &ranking).is_err());
+    }
+
+    #[test]
+    fn test_top_percent_with_dead_cards() {
+        let ranking = HandRanking::from_list("AA,KK").unwrap();
+        let (dead, _) = StdDeck::string_to_mask("AsAhKd").unwrap();
+        // 49 live cards -> 1176 combos; 1% is 11.76, but only 1 AA and 3 KK remain.
+        let range = HandRange::top_percent_with_dead(1.0, &ranking, &dead).unwrap();
+        assert_eq!(range.len(), 4);
+        assert_eq!(range.total_weight(), 4.0);
+    }
+
+    /// Exact equity of each class against one random hand: every board, with every
+    /// opponent holding evaluated on it.
+    fn exact_heads_up_equities() -> Vec<ClassEquity> {
+        use crate::combinations::for_each_combination;
+        use crate::evaluators::Eval;
+
+        let reps: Vec<(usize, usize)> = starting_hand_classes()
+            .iter()
+            .map(|class| {
+                let hand = HandRange::from_str(class).unwrap().hands()[0].0;
+                let mut cards = (0..STD_DECK_N_CARDS).filter(|&c| hand.card_is_set(c));
+                (cards.next().unwrap(), cards.next().unwrap())
+            })
+            .collect();
+        let mut points = vec![0u64; reps.len()];
+        let mut deals = vec![0u64; reps.len()];
+        let mut value = vec![0u32; STD_DECK_N_CARDS * STD_DECK_N_CARDS];
+        let mut sorted = Vec::with_capacity(1081);
+        for_each_combination(STD_DECK_N_CARDS, 5, |b| {
+            let board = b.iter().fold(StdDeckCardMask::new(), |m, &c| {
+                m | StdDeckCardMask::from_card_index(c)
+            });
+            let live: Vec<usize> = (0..STD_DECK_N_CARDS)
+                .filter(|&c| !board.card_is_set(c))
+                .collect();
+            sorted.clear();
+            for (i, &c1) in live.iter().enumerate() {
+                for &c2 in &live[i + 1..] {
+                    let hand = board
+                        | StdDeckCardMask::from_card_index(c1)
+                        | StdDeckCardMask::from_card_index(c2);
+                    let v = Eval::eval_n(&hand, 7).value;
+                    value[c1 * STD_DECK_N_CARDS + c2] = v;
+                    value[c2 * STD_DECK_N_CARDS + c1] = v;
+                    sorted.push(v);
+                }
+            }
+            sorted.sort_unstable();
+            for (k, &(h1, h2)) in reps.iter().enumerate() {
+                if board.card_is_set(h1) || board.card_is_set(h2) {
+                    continue;
+                }
+                let hv = value[h1 * STD_DECK_N_CARDS + h2];
+                let mut below = sorted.partition_point(|&v| v < hv) as u64;
+                let mut equal = sorted.partition_point(|&v| v <= hv) as u64 - below;
+                // Take out the holdings that share a card with ours.
+                for &c in &live {
+                    for (h, other) in [(h1, h2), (h2, h1)] {
+                        if c == h || (h == h2 && c == other) {
+                            continue;
+                        }
+                        let v = value[h * STD_DECK_N_CARDS + c];
+                        below -= (v < hv) as u64;
+                        equal -= (v == hv) as u64;
+                    }
+                }
+                points[k] += 2 * below + equal;
+                deals[k] += 990;
+            }
+        });
+        starting_hand_classes()
+            .into_iter()
+            .zip(points.iter().zip(&deals))
+            .map(|(class, (&p, &n))| ClassEquity {
+                class,
+                equity: p as f64 / (2 * n) as f64,
+            })
+            .collect()
+    }
+
+    /// Takes a couple of minutes in release builds: `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore]
+    fn test_vs_one_random_table_is_exact() {
+        let mut exact = exact_heads_up_equities();
+        exact.sort_by(|a, b| b.equity.total_cmp(&a.equity));
+        for (e, &(class, equity)) in exact.iter().zip(VS_ONE_RANDOM.iter()) {
+            assert_eq!(e.class, class);
+            assert!((e.equity - equity).abs() < 5e-7, "{}: {}", class, e.equity);
+        }
+    }
+
+    #[test]
+    fn test_close_classes_keep_exact_order() {
+        let ranking = preflop_ranking(1).unwrap();
+        let pos = |c: &str| ranking.iter().position(|e| e.class == c).unwrap();
+        // Neighbours less than 0.02% equity apart, which sampling used to swap.
+        for (better, worse) in [
+            ("A5s", "A6s"),
+            ("A5o", "A6o"),
+            ("K7s", "JTs"),
+            ("K8o", "Q8s"),
+            ("T8s", "K4o"),
+            ("K4o", "J7s"),
+            ("72s", "54o"),
+            ("42s", "82o"),
+        ] {
+            assert!(
+                pos(better) < pos(worse),
+                "{} should rank above {}",
+                better,
+                worse
+            );
+        }
+        assert_eq!(pos("AKs"), 7);
+    }
+
+    #[test]
+    fn test_vs_random_ranking() {
+        let ranking = preflop_ranking(1).unwrap();
+        assert_eq!(ranking.len(), 169);
+        assert_eq!(ranking[0].class, "AA");
+        assert!(ranking[0].equity > 0.8);
+        let pos = |c: &str| ranking.iter().position(|e| e.class == c).unwrap();
+        assert!(pos("KK") < pos("AKs"));
+        assert!(pos("AKs") < pos("72o"));
+        assert!(Arc::ptr_eq(&ranking, &preflop_ranking(1).unwrap()));
+
+        let range = HandRange::top_percent(10.0, &HandRanking::VsRandom).unwrap();
+        assert!((range.total_weight() - 132.6).abs() < 1e-9);
+        assert!(range.hands().iter().all(|(_, w)| *w > 0.0 && *w <= 1.0));
+        assert!(preflop_ranking(ENUM_MAXPLAYERS).is_err());
+    }
+}
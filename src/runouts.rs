@@ -0,0 +1,299 @@
+//! Equity breakdown by the next board card.
+//!
+//! On the flop or turn, [`next_card_equity`] deals each unseen card in turn and runs
+//! [`enum_exhaustive`] on the resulting board. The report gives every player's equity
+//! after each card, the cards that change the equity leader, and averages grouped by
+//! the suit or rank of the card.
+
+use crate::deck::{Rank, StdDeck, StdDeckCardMask, Suit, STD_DECK_N_CARDS};
+use crate::enumdefs::{EnumResult, Game, ENUM_MAXPLAYERS};
+use crate::enumerate::enum_exhaustive;
+use crate::errors::PokerError;
+
+/// Equities closer than this are treated as equal when picking a leader.
+const LEADER_EPSILON: f64 = 1e-9;
+
+/// Equities after one possible next card.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CardEquity {
+    /// The unseen card dealt next.
+    pub card: StdDeckCardMask,
+    /// Each player's equity (pot share, 0 to 1) once `card` is on the board.
+    pub equity: Vec<f64>,
+    /// The player with the highest equity, or `None` if several share it.
+    pub leader: Option<usize>,
+    /// True if `leader` differs from the leader on the current board.
+    pub flips: bool,
+}
+
+/// Average equities over a group of next cards, each card equally likely.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupEquity {
+    /// The suit or rank character shared by the group (e.g. `"s"` or `"A"`).
+    pub label: String,
+    /// Number of unseen cards in the group.
+    pub cards: usize,
+    /// Each player's average equity over those cards.
+    pub equity: Vec<f64>,
+}
+
+/// Per-card equity report from [`next_card_equity`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunoutReport {
+    /// The game the report was computed for.
+    pub game: Game,
+    /// Each player's equity on the current board.
+    pub equity: Vec<f64>,
+    /// The equity leader on the current board, or `None` if tied.
+    pub leader: Option<usize>,
+    /// One entry per unseen card, in card index order.
+    pub cards: Vec<CardEquity>,
+}
+
+impl RunoutReport {
+    /// Cards after which a different player (or nobody) leads.
+    pub fn flips(&self) -> Vec<StdDeckCardMask> {
+        self.cards
+            .iter()
+            .filter(|c| c.flips)
+            .map(|c| c.card)
+            .collect()
+    }
+
+    /// Average equities grouped by the suit of the next card.
+    pub fn by_suit(&self) -> Vec<GroupEquity> {
+        self.group_by(
+            Suit::COUNT,
+            |i| StdDeck::suit(i).as_usize(),
+            |g| Suit::new(g as u8).to_string(),
+        )
+    }
+
+    /// Average equities grouped by the rank of the next card, highest first.
+    pub fn by_rank(&self) -> Vec<GroupEquity> {
+        let mut groups = self.group_by(
+            Rank::COUNT,
+            |i| StdDeck::rank(i).as_usize(),
+            |g| Rank::new(g as u8).to_string(),
+        );
+        groups.reverse();
+        groups
+    }
+
+    fn group_by(
+        &self,
+        ngroups: usize,
+        key: impl Fn(usize) -> usize,
+        label: impl Fn(usize) -> String,
+    ) -> Vec<GroupEquity> {
+        let nplayers = self.equity.len();
+        let mut sums = vec![(0usize, vec![0.0; nplayers]); ngroups];
+        for card in &self.cards {
+            let Some(index) = StdDeck::mask_to_index(&card.card) else {
+                continue;
+            };
+            let (count, sum) = &mut sums[key(index)];
+            *count += 1;
+            for (s, e) in sum.iter_mut().zip(&card.equity) {
+                *s += e;
+            }
+        }
+        sums.into_iter()
+            .enumerate()
+            .filter(|(_, (count, _))| *count > 0)
+            .map(|(g, (count, sum))| GroupEquity {
+                label: label(g),
+                cards: count,
+                equity: sum.into_iter().map(|s| s / count as f64).collect(),
+            })
+            .collect()
+    }
+}
+
+/// Computes each player's equity after every possible next board card.
+///
+/// The board must be a flop or a turn. Each unseen card that does not conflict with
+/// the pockets, board or `dead` is added to the board and the rest of the hand is
+/// enumerated exhaustively, so the game must be supported by [`enum_exhaustive`]
+/// (Hold'em, Hold'em Hi/Lo, Omaha and Short Deck).
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::deck::{StdDeck, StdDeckCardMask};
+/// use poker_eval_rs::enumdefs::Game;
+/// use poker_eval_rs::runouts::next_card_equity;
+///
+/// let pockets = [
+///     StdDeck::string_to_mask("AsKs").unwrap().0,
+///     StdDeck::string_to_mask("QdQc").unwrap().0,
+/// ];
+/// let (board, _) = StdDeck::string_to_mask("Qs7s2h3d").unwrap();
+/// let report = next_card_equity(Game::Holdem, &pockets, board, StdDeckCardMask::new()).unwrap();
+///
+/// // Seven of the 44 rivers give AsKs the winning flush.
+/// assert_eq!(report.cards.len(), 44);
+/// assert_eq!(report.leader, Some(1));
+/// assert_eq!(report.flips().len(), 7);
+/// ```
+pub fn next_card_equity(
+    game: Game,
+    pockets: &[StdDeckCardMask],
+    board: StdDeckCardMask,
+    dead: StdDeckCardMask,
+) -> Result<RunoutReport, PokerError> {
+    let npockets = pockets.len();
+    if npockets < 2 {
+        return Err(PokerError::InvalidInput(
+            "Need at least 2 hands for a runout report".to_string(),
+        ));
+    }
+    if npockets > ENUM_MAXPLAYERS {
+        return Err(PokerError::TooManyPlayers);
+    }
+    let params = game.game_params().ok_or(PokerError::UnsupportedGameType)?;
+    let nboard = board.num_cards();
+    if params.maxboard == 0 || !(3..=4).contains(&nboard) {
+        return Err(PokerError::UnsupportedBoardConfiguration);
+    }
+
+    let mut used = board | dead;
+    if (board.as_raw() & dead.as_raw()) != 0 {
+        return Err(PokerError::InvalidCardConfiguration(
+            "Board and dead cards must not overlap".to_string(),
+        ));
+    }
+    for pocket in pockets {
+        if (pocket.as_raw() & used.as_raw()) != 0 {
+            return Err(PokerError::InvalidCardConfiguration(
+                "Pockets, board and dead cards must not overlap".to_string(),
+            ));
+        }
+        used = used | *pocket;
+    }
+
+    let mut result = EnumResult::new(game);
+    let mut equities = |board: StdDeckCardMask, nboard: usize| -> Result<Vec<f64>, PokerError> {
+        enum_exhaustive(
+            game,
+            pockets,
+            board,
+            dead,
+            npockets,
+            nboard,
+            false,
+            &mut result,
+        )?;
+        let nsamples = result.nsamples.max(1) as f64;
+        Ok(result.ev[..npockets]
+            .iter()
+            .map(|ev| ev / nsamples)
+            .collect())
+    };
+
+    let equity = equities(board, nboard)?;
+    let leader = leader_of(&equity);
+
+    let mut cards = Vec::new();
+    for i in 0..STD_DECK_N_CARDS {
+        if used.card_is_set(i) || (game == Game::ShortDeck && StdDeck::rank(i) < Rank::SIX) {
+            continue;
+        }
+        let card = StdDeckCardMask::from_card_index(i);
+        let equity = equities(board | card, nboard + 1)?;
+        let card_leader = leader_of(&equity);
+        cards.push(CardEquity {
+            card,
+            equity,
+            leader: card_leader,
+            flips: card_leader != leader,
+        });
+    }
+
+    Ok(RunoutReport {
+        game,
+        equity,
+        leader,
+        cards,
+    })
+}
+
+/// The player with the strictly highest equity, if any.
+fn leader_of(equity: &[f64]) -> Option<usize> {
+    let (best, &max) = equity
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))?;
+    let tied = equity
+        .iter()
+        .filter(|&&e| (max - e).abs() < LEADER_EPSILON)
+        .count();
+    (tied == 1).then_some(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn masks(hands: &[&str]) -> Vec<StdDeckCardMask> {
+        hands
+            .iter()
+            .map(|h| StdDeck::string_to_mask(h).unwrap().0)
+            .collect()
+    }
+
+    #[test]
+    fn test_flop_runouts_average_to_current_equity() {
+        let pockets = masks(&["AhKh", "7c7d"]);
+        let (board, _) = StdDeck::string_to_mask("Th9h2c").unwrap();
+        let report =
+            next_card_equity(Game::Holdem, &pockets, board, StdDeckCardMask::new()).unwrap();
+        assert_eq!(report.cards.len(), 45);
+
+        // Every turn card is equally likely, so the per-card equities average out
+        // to the flop equity, and so do the suit and rank groups.
+        for p in 0..2 {
+            let mean: f64 =
+                report.cards.iter().map(|c| c.equity[p]).sum::<f64>() / report.cards.len() as f64;
+            assert!((mean - report.equity[p]).abs() < 1e-9);
+        }
+        let suits = report.by_suit();
+        assert_eq!(suits.len(), 4);
+        assert_eq!(suits.iter().map(|g| g.cards).sum::<usize>(), 45);
+        let hearts = suits.iter().find(|g| g.label == "h").unwrap();
+        assert_eq!(hearts.cards, 9);
+        // A heart turn makes the nut flush; only a paired river saves 7c7d.
+        assert!(hearts.equity[0] > 0.95);
+
+        let ranks = report.by_rank();
+        assert_eq!(ranks[0].label, "A");
+        assert_eq!(ranks.len(), 13);
+    }
+
+    #[test]
+    fn test_leader_flips_and_ties() {
+        assert_eq!(leader_of(&[0.5, 0.5]), None);
+        assert_eq!(leader_of(&[0.2, 0.8]), Some(1));
+
+        let pockets = masks(&["AsKs", "QdQc"]);
+        let (board, _) = StdDeck::string_to_mask("Qs7s2h3d").unwrap();
+        let report =
+            next_card_equity(Game::Holdem, &pockets, board, StdDeckCardMask::new()).unwrap();
+        let flips: Vec<String> = report.flips().iter().map(|c| c.to_string()).collect();
+        assert!(flips.contains(&"4s".to_string()));
+        // 2s gives AsKs a flush but QdQc a full house.
+        assert!(!flips.contains(&"2s".to_string()));
+    }
+
+    #[test]
+    fn test_runout_errors() {
+        let pockets = masks(&["AsKs", "QdQc"]);
+        let (river, _) = StdDeck::string_to_mask("Qs7s2h3d4c").unwrap();
+        assert!(next_card_equity(Game::Holdem, &pockets, river, StdDeckCardMask::new()).is_err());
+        let (board, _) = StdDeck::string_to_mask("Qd7s2h").unwrap();
+        assert!(next_card_equity(Game::Holdem, &pockets, board, StdDeckCardMask::new()).is_err());
+        assert!(
+            next_card_equity(Game::Holdem, &pockets[..1], board, StdDeckCardMask::new()).is_err()
+        );
+    }
+}
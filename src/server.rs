@@ -0,0 +1,221 @@
+//! A small HTTP/JSON server over the [`service`](crate::service) handlers.
+//!
+//! Every [`Endpoint`] is served as `POST /<name>` with the request as the JSON body,
+//! e.g. `POST /equity` with an [`EquityQuery`](crate::query::EquityQuery);
+//! `GET /health` answers `{"status": "ok"}`. Successful requests return `200` and the
+//! handler's JSON. Failures return `{"error": "..."}` with the status from
+//! [`status_code`], or `404`, `405` and `413` for unknown paths, wrong methods and
+//! oversized bodies.
+//!
+//! Requests are read and answered by a fixed pool of I/O threads, and only the
+//! calculation runs on the rayon thread pool, so a long equity run does not hold up
+//! the other workers. Up to [`ServerConfig::backlog`] requests wait for a free worker;
+//! beyond that the server answers `503` at once rather than queueing without bound.
+//!
+//! ```no_run
+//! use poker_eval_rs::server::{Server, ServerConfig};
+//!
+//! let server = Server::bind("127.0.0.1:8080", ServerConfig::default()).unwrap();
+//! server.run();
+//! ```
+
+use crate::errors::PokerError;
+use crate::service::{Endpoint, Limits};
+use std::io::Read;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+use tiny_http::{Header, Method, Request, Response};
+
+/// Default cap on the size of a request body, in bytes.
+pub const DEFAULT_MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Default time limit of a single calculation.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default number of I/O worker threads.
+pub const DEFAULT_WORKERS: usize = 16;
+
+/// Default number of requests that may wait for a free worker.
+pub const DEFAULT_BACKLOG: usize = 64;
+
+/// Settings of a [`Server`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerConfig {
+    /// Largest request body accepted, in bytes.
+    pub max_body_bytes: usize,
+    /// Caps on the work of each request.
+    pub limits: Limits,
+    /// Number of I/O worker threads, at least one.
+    pub workers: usize,
+    /// Requests that may wait for a free worker before the server answers `503`.
+    pub backlog: usize,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            limits: Limits {
+                timeout: Some(DEFAULT_TIMEOUT),
+                ..Limits::default()
+            },
+            workers: DEFAULT_WORKERS,
+            backlog: DEFAULT_BACKLOG,
+        }
+    }
+}
+
+/// An HTTP server bound to a local address.
+pub struct Server {
+    http: tiny_http::Server,
+    config: ServerConfig,
+}
+
+impl Server {
+    /// Binds the server to `addr`; port 0 picks a free port.
+    ///
+    /// Fails with [`PokerError::ExecutionError`] if the address cannot be bound.
+    pub fn bind<A: ToSocketAddrs>(addr: A, config: ServerConfig) -> Result<Self, PokerError> {
+        let http = tiny_http::Server::http(addr)
+            .map_err(|e| PokerError::ExecutionError(format!("Cannot bind server: {}", e)))?;
+        Ok(Self { http, config })
+    }
+
+    /// The address the server listens on.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.http.server_addr().to_ip()
+    }
+
+    /// Serves requests on the worker pool until the listener fails.
+    ///
+    /// A request that finds every worker busy and the backlog full is answered `503`.
+    pub fn run(self) {
+        let (tx, rx) = mpsc::sync_channel::<Request>(self.config.backlog);
+        let rx = Arc::new(Mutex::new(rx));
+        for _ in 0..self.config.workers.max(1) {
+            let rx = Arc::clone(&rx);
+            let config = self.config;
+            std::thread::spawn(move || loop {
+                // Hold the lock only while waiting, so the other workers keep serving.
+                let next = rx.lock().map(|rx| rx.recv());
+                match next {
+                    Ok(Ok(request)) => handle(request, &config),
+                    _ => break,
+                }
+            });
+        }
+        for request in self.http.incoming_requests() {
+            if let Err(
+                mpsc::TrySendError::Full(request) | mpsc::TrySendError::Disconnected(request),
+            ) = tx.try_send(request)
+            {
+                let (status, body) = error(503, "Server busy, try again later".to_string());
+                respond(request, status, body);
+            }
+        }
+    }
+}
+
+/// The HTTP status a failed request is answered with.
+///
+/// Malformed or contradictory input is `400`, requests the library cannot handle
+/// (an unsupported game or board) `422`, and calculations stopped by the time limit
+/// `503`. Anything else is a server-side failure, `500`.
+pub fn status_code(err: &PokerError) -> u16 {
+    match err {
+        PokerError::InvalidInput(_)
+        | PokerError::InvalidCardConfiguration(_)
+        | PokerError::TooManyPlayers => 400,
+        PokerError::UnsupportedGameType | PokerError::UnsupportedBoardConfiguration => 422,
+        PokerError::Cancelled => 503,
+        PokerError::ExecutionError(_) | PokerError::InternalError(_) | PokerError::Other(_) => 500,
+    }
+}
+
+fn handle(mut request: Request, config: &ServerConfig) {
+    let path = request.url().split('?').next().unwrap_or("").to_string();
+    let (status, body) = match (request.method(), path.as_str()) {
+        (Method::Get, "/health") => (200, serde_json::json!({ "status": "ok" })),
+        (method, path) => match path.trim_start_matches('/').parse::<Endpoint>() {
+            Err(_) => error(404, format!("Not found: {}", path)),
+            Ok(_) if *method != Method::Post => error(405, format!("Use POST for {}", path)),
+            Ok(endpoint) => match read_body(&mut request, config.max_body_bytes) {
+                Err(response) => response,
+                Ok(body) => match compute(endpoint, body, config.limits) {
+                    Ok(value) => (200, value),
+                    Err(PokerError::Cancelled) => error(
+                        503,
+                        format!(
+                            "Timed out after {:?}",
+                            config.limits.timeout.unwrap_or_default()
+                        ),
+                    ),
+                    Err(e) => error(status_code(&e), e.to_string()),
+                },
+            },
+        },
+    };
+
+    respond(request, status, body);
+}
+
+fn respond(request: Request, status: u16, body: serde_json::Value) {
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("static header is valid");
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(content_type);
+    // The client may have gone away; there is nobody left to tell.
+    let _ = request.respond(response);
+}
+
+/// Runs the handler on the rayon thread pool and waits for its result.
+fn compute(
+    endpoint: Endpoint,
+    body: String,
+    limits: Limits,
+) -> Result<serde_json::Value, PokerError> {
+    let (tx, rx) = mpsc::channel();
+    rayon::spawn(move || {
+        let _ = tx.send(endpoint.handle(&body, &limits));
+    });
+    rx.recv()
+        .unwrap_or_else(|_| Err(PokerError::InternalError("Handler panicked".to_string())))
+}
+
+/// Reads the request body as UTF-8, or the error response to send instead.
+fn read_body(request: &mut Request, max: usize) -> Result<String, (u16, serde_json::Value)> {
+    let too_large = || error(413, format!("Request body exceeds {} bytes", max));
+    if request.body_length().is_some_and(|n| n > max) {
+        return Err(too_large());
+    }
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(max as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| error(400, format!("Cannot read request body: {}", e)))?;
+    if body.len() > max {
+        return Err(too_large());
+    }
+    String::from_utf8(body).map_err(|_| error(400, "Request body is not UTF-8".to_string()))
+}
+
+fn error(status: u16, message: String) -> (u16, serde_json::Value) {
+    (status, serde_json::json!({ "error": message }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_codes() {
+        assert_eq!(status_code(&PokerError::InvalidInput("x".into())), 400);
+        assert_eq!(status_code(&PokerError::TooManyPlayers), 400);
+        assert_eq!(status_code(&PokerError::UnsupportedGameType), 422);
+        assert_eq!(status_code(&PokerError::Cancelled), 503);
+        assert_eq!(status_code(&PokerError::InternalError("x".into())), 500);
+    }
+}
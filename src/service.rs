@@ -0,0 +1,895 @@
+//! JSON request handlers for evaluation, equity, outs and board texture.
+//!
+//! Each [`Endpoint`] reads one JSON request and answers with one JSON value:
+//!
+//! | Endpoint       | Request                | Response                |
+//! |----------------|------------------------|-------------------------|
+//! | `eval`         | [`EvalRequest`]        | [`EvalResponse`]        |
+//! | `equity`       | [`EquityQuery`]        | [`EquityReport`]        |
+//! | `range_equity` | [`RangeEquityRequest`] | [`RangeEquityResponse`] |
+//! | `outs`         | [`OutsRequest`]        | [`OutsResponse`]        |
+//! | `texture`      | [`TextureRequest`]     | [`BoardTexture`]        |
+//!
+//! Bad requests and failed calculations come back as [`PokerError`]s, and [`Limits`]
+//! caps the work a single request may ask for. The HTTP server behind the `serve`
+//! subcommand is a thin layer over these handlers, and [`batch`] answers a stream of
+//! newline-delimited queries for the `batch` subcommand.
+//!
+//! ```
+//! use poker_eval_rs::service::{Endpoint, Limits};
+//!
+//! let reply = Endpoint::Eval
+//!     .handle(r#"{"hand": "AsKs", "board": "QsJsTs"}"#, &Limits::default())
+//!     .unwrap();
+//! assert_eq!(reply["hi"]["description"], "StFlush ( A)");
+//! ```
+
+use crate::board::{calculate_game_outs, calculate_outs_vs_range, display_hand_type, BoardTexture};
+use crate::deck::{StdDeckCardMask, STD_DECK_N_CARDS};
+use crate::enumdefs::Game;
+use crate::enumord::EnumOrderingMode;
+use crate::errors::PokerError;
+use crate::evaluators::range_equity::calculate_equity_chunked;
+use crate::evaluators::{Eval, OmahaHiEvaluator};
+use crate::game_rules::GameRules;
+use crate::handval::HandVal;
+use crate::handval_low::LowHandVal;
+use crate::query::{
+    card_count, claim, game_name, parse_cards, EquityMode, EquityQuery, EquityReport,
+    DEFAULT_ITERATIONS,
+};
+use crate::range::HandRange;
+use crate::rules::HandType;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Default cap on the Monte Carlo iterations of one request.
+pub const DEFAULT_MAX_ITERATIONS: usize = 10_000_000;
+
+/// Caps on the work a single request may ask for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Most Monte Carlo iterations an equity or range equity request may ask for, and
+    /// most boards an exhaustive equity request may enumerate.
+    pub max_iterations: usize,
+    /// How long an equity calculation may run before it stops with
+    /// [`PokerError::Cancelled`]; `None` for no limit.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+            timeout: None,
+        }
+    }
+}
+
+impl Limits {
+    fn check_iterations(&self, iterations: u64, what: &str) -> Result<(), PokerError> {
+        if iterations > self.max_iterations as u64 {
+            return Err(PokerError::InvalidInput(format!(
+                "{} {} requested; the limit is {}",
+                iterations, what, self.max_iterations
+            )));
+        }
+        Ok(())
+    }
+
+    /// The moment a calculation started now has to stop, if any.
+    fn deadline(&self) -> Option<Instant> {
+        self.timeout.map(|t| Instant::now() + t)
+    }
+}
+
+fn expired(deadline: Option<Instant>) -> bool {
+    deadline.is_some_and(|d| Instant::now() >= d)
+}
+
+/// A kind of request, named as in [`Endpoint::name`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    /// Evaluates one hand.
+    Eval,
+    /// Runs an [`EquityQuery`].
+    Equity,
+    /// Hold'em equity of one range against another.
+    RangeEquity,
+    /// Outs of a hand on the flop or turn.
+    Outs,
+    /// Texture of a board.
+    Texture,
+}
+
+impl Endpoint {
+    /// Every endpoint.
+    pub const ALL: [Endpoint; 5] = [
+        Endpoint::Eval,
+        Endpoint::Equity,
+        Endpoint::RangeEquity,
+        Endpoint::Outs,
+        Endpoint::Texture,
+    ];
+
+    /// The endpoint's name (e.g. `"range_equity"`). [`FromStr`] accepts these names.
+    pub fn name(self) -> &'static str {
+        match self {
+            Endpoint::Eval => "eval",
+            Endpoint::Equity => "equity",
+            Endpoint::RangeEquity => "range_equity",
+            Endpoint::Outs => "outs",
+            Endpoint::Texture => "texture",
+        }
+    }
+
+    /// Parses `body` as this endpoint's request and runs it.
+    ///
+    /// Malformed JSON fails with [`PokerError::InvalidInput`]; everything else fails
+    /// as the handler does.
+    pub fn handle(self, body: &str, limits: &Limits) -> Result<Value, PokerError> {
+        self.handle_value(from_json(body)?, limits)
+    }
+
+    /// Like [`handle`](Self::handle), for a request that is already parsed.
+    pub fn handle_value(self, request: Value, limits: &Limits) -> Result<Value, PokerError> {
+        match self {
+            Endpoint::Eval => to_json(eval(&from_value(request)?)?),
+            Endpoint::Equity => to_json(equity(&from_value(request)?, limits)?),
+            Endpoint::RangeEquity => to_json(range_equity(&from_value(request)?, limits)?),
+            Endpoint::Outs => to_json(outs(&from_value(request)?)?),
+            Endpoint::Texture => to_json(texture(&from_value(request)?)?),
+        }
+    }
+}
+
+impl FromStr for Endpoint {
+    type Err = PokerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Endpoint::ALL
+            .into_iter()
+            .find(|e| e.name() == s)
+            .ok_or_else(|| PokerError::InvalidInput(format!("Unknown endpoint: {}", s)))
+    }
+}
+
+fn from_json<T: DeserializeOwned>(body: &str) -> Result<T, PokerError> {
+    serde_json::from_str(body)
+        .map_err(|e| PokerError::InvalidInput(format!("Invalid request: {}", e)))
+}
+
+fn from_value<T: DeserializeOwned>(request: Value) -> Result<T, PokerError> {
+    serde_json::from_value(request)
+        .map_err(|e| PokerError::InvalidInput(format!("Invalid request: {}", e)))
+}
+
+fn to_json<T: Serialize>(value: T) -> Result<Value, PokerError> {
+    serde_json::to_value(value).map_err(|e| PokerError::InternalError(e.to_string()))
+}
+
+fn default_game() -> Game {
+    Game::Holdem
+}
+
+fn default_iterations() -> usize {
+    DEFAULT_ITERATIONS
+}
+
+/// Fails if any of `cards` is not in `game`'s deck.
+fn check_deck(game: Game, cards: StdDeckCardMask) -> Result<(), PokerError> {
+    let deck = GameRules::deck(&game);
+    match (0..STD_DECK_N_CARDS).find(|&c| cards.card_is_set(c) && !deck.card_is_set(c)) {
+        Some(c) => Err(PokerError::InvalidCardConfiguration(format!(
+            "{} is not in the {} deck",
+            StdDeckCardMask::from_card_index(c),
+            game.as_str()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Request for [`Endpoint::Eval`]: one player's hand in a game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvalRequest {
+    /// The game variant; Hold'em when omitted.
+    #[serde(with = "game_name", default = "default_game")]
+    pub game: Game,
+    /// The player's own cards (e.g. `"AsKs"`); the whole hand in games without a board.
+    pub hand: String,
+    /// Board cards; empty for none.
+    #[serde(default)]
+    pub board: String,
+}
+
+/// A hand value in an [`EvalResponse`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandValue {
+    /// The raw evaluator value, comparable with other values of the same game: higher
+    /// is better for high hands, lower for low hands.
+    pub value: u32,
+    /// The hand type (`"Flush"`, `"TwoPair"`, ...).
+    pub hand_type: String,
+    /// The hand type and its ranks, e.g. `"Flush ( A K 9 7 4)"`.
+    pub description: String,
+}
+
+/// The result of [`Endpoint::Eval`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvalResponse {
+    /// The game variant.
+    #[serde(with = "game_name")]
+    pub game: Game,
+    /// The hand as given in the request.
+    pub hand: String,
+    /// The board as given in the request.
+    pub board: String,
+    /// The high hand; `None` in low-only games or when no hand can be made yet.
+    pub hi: Option<HandValue>,
+    /// The low hand; `None` in high-only games or without a qualifying low.
+    pub lo: Option<HandValue>,
+}
+
+/// Evaluates a hand under the rules of its game.
+///
+/// Omaha hands use exactly two hole cards, Short Deck ranks flushes above full
+/// houses, and games with a low pot report the low as well. The hand must have a
+/// legal number of pocket cards for the game and make at least five cards with
+/// the board.
+pub fn eval(request: &EvalRequest) -> Result<EvalResponse, PokerError> {
+    let game = request.game;
+    let params = game.game_params().ok_or(PokerError::UnsupportedGameType)?;
+    let hand = parse_cards(&request.hand, "hand")?;
+    let board = parse_cards(&request.board, "board")?;
+    let mut used = hand;
+    claim(&mut used, board, "board")?;
+    check_deck(game, used)?;
+    if board.num_cards() > game.board_cards() {
+        return Err(PokerError::UnsupportedBoardConfiguration);
+    }
+    let n = hand.num_cards() as i32;
+    if n < params.minpocket || n > params.maxpocket {
+        return Err(PokerError::InvalidInput(format!(
+            "Hand has {} cards; {} takes {}",
+            n,
+            params.name,
+            card_count(params.minpocket as usize, params.maxpocket as usize)
+        )));
+    }
+    if used.num_cards() < 5 {
+        return Err(PokerError::InvalidInput(
+            "Need at least 5 cards to evaluate a hand".to_string(),
+        ));
+    }
+
+    let mode = game.ordering_mode();
+    let hi = match mode {
+        EnumOrderingMode::Hi | EnumOrderingMode::Hilo => game
+            .eval_hi(&hand, &board)?
+            .map(|val| high_value(game, &hand, &board, val)),
+        _ => None,
+    };
+    let lo = match mode {
+        EnumOrderingMode::Lo | EnumOrderingMode::Hilo => {
+            game.eval_lo(&hand, &board)?.map(|val| low_value(game, val))
+        }
+        _ => None,
+    };
+    Ok(EvalResponse {
+        game,
+        hand: request.hand.clone(),
+        board: request.board.clone(),
+        hi,
+        lo,
+    })
+}
+
+fn high_value(
+    game: Game,
+    hand: &StdDeckCardMask,
+    board: &StdDeckCardMask,
+    val: HandVal,
+) -> HandValue {
+    let hand_type = display_hand_type(game, val);
+    // Fast evaluator values carry no ranks, so describe the hand from a detailed
+    // evaluation. Short Deck can rank a hand differently from the detailed
+    // (standard) one; then only the type is known.
+    let detail = match game {
+        Game::Omaha | Game::Omaha5 | Game::Omaha6 | Game::Omaha8 | Game::Omaha85 => {
+            OmahaHiEvaluator::evaluate_detailed(hand, board)
+        }
+        _ => {
+            let all = *hand | *board;
+            Some(Eval::eval_n_detailed(&all, all.num_cards()))
+        }
+    };
+    let description = match detail {
+        Some(d) if d.get_hand_type() == hand_type => d.std_rules_hand_val_to_string(),
+        _ => hand_type.to_string(),
+    };
+    HandValue {
+        value: val.value,
+        hand_type: hand_type.to_string(),
+        description,
+    }
+}
+
+fn low_value(game: Game, val: LowHandVal) -> HandValue {
+    if game == Game::Lowball27 {
+        // 2-7 values are high-hand values where lower is better.
+        let val = HandVal { value: val.value };
+        return HandValue {
+            value: val.value,
+            hand_type: val.get_hand_type().to_string(),
+            description: val.std_rules_hand_val_to_string(),
+        };
+    }
+    HandValue {
+        value: val.value,
+        hand_type: HandType::from_usize(val.hand_type() as usize)
+            .unwrap_or(HandType::NoPair)
+            .to_string(),
+        description: val.to_string(),
+    }
+}
+
+/// Validates and runs an equity query, stopping with [`PokerError::Cancelled`] once
+/// the time limit has passed. Otherwise the report is the one [`EquityQuery::run`]
+/// gives, seeded Monte Carlo runs included.
+pub fn equity(query: &EquityQuery, limits: &Limits) -> Result<EquityReport, PokerError> {
+    let what = match query.mode {
+        EquityMode::MonteCarlo => "iterations",
+        EquityMode::Exhaustive => "boards",
+    };
+    limits.check_iterations(query.deals()?, what)?;
+    let deadline = limits.deadline();
+    let result = query.enumerate_chunked(|_| !expired(deadline))?;
+    Ok(query.report(&result))
+}
+
+/// Request for [`Endpoint::RangeEquity`]: Hold'em equity of one range against another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeEquityRequest {
+    /// Hero's range (e.g. `"AKs,QQ+"`).
+    pub hero: String,
+    /// Villain's range.
+    pub villain: String,
+    /// Board cards, 0 to 5; empty for none.
+    #[serde(default)]
+    pub board: String,
+    /// Number of Monte Carlo iterations.
+    #[serde(default = "default_iterations")]
+    pub iterations: usize,
+}
+
+/// The result of [`Endpoint::RangeEquity`], from hero's side.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RangeEquityResponse {
+    /// Hero's range as given in the request.
+    pub hero: String,
+    /// Villain's range as given in the request.
+    pub villain: String,
+    /// The board as given in the request.
+    pub board: String,
+    /// Hero's share of the pot, from 0 to 1.
+    pub equity: f64,
+    /// Deals hero won.
+    pub wins: usize,
+    /// Deals that tied.
+    pub ties: usize,
+    /// Deals hero lost.
+    pub losses: usize,
+    /// Deals evaluated.
+    pub samples: usize,
+}
+
+/// Samples the Hold'em equity of two ranges, stopping with [`PokerError::Cancelled`]
+/// once the time limit has passed.
+pub fn range_equity(
+    request: &RangeEquityRequest,
+    limits: &Limits,
+) -> Result<RangeEquityResponse, PokerError> {
+    if request.iterations == 0 {
+        return Err(PokerError::InvalidInput(
+            "Monte Carlo needs at least one iteration".to_string(),
+        ));
+    }
+    limits.check_iterations(request.iterations as u64, "iterations")?;
+    let parse_range = |range: &str, what: &str| {
+        HandRange::from_str(range)
+            .map_err(|e| PokerError::InvalidInput(format!("Error parsing {} range: {}", what, e)))
+    };
+    let hero = parse_range(&request.hero, "hero")?;
+    let villain = parse_range(&request.villain, "villain")?;
+    let board = parse_cards(&request.board, "board")?;
+    if board.num_cards() > 5 {
+        return Err(PokerError::UnsupportedBoardConfiguration);
+    }
+
+    let deadline = limits.deadline();
+    let total = calculate_equity_chunked(&hero, &villain, &board, request.iterations, |_| {
+        !expired(deadline)
+    })
+    .map_err(PokerError::InvalidInput)?
+    .ok_or(PokerError::Cancelled)?;
+
+    Ok(RangeEquityResponse {
+        hero: request.hero.clone(),
+        villain: request.villain.clone(),
+        board: request.board.clone(),
+        equity: total.equity,
+        wins: total.wins,
+        ties: total.ties,
+        losses: total.losses,
+        samples: total.samples,
+    })
+}
+
+/// Request for [`Endpoint::Outs`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutsRequest {
+    /// The game variant; Hold'em when omitted.
+    #[serde(with = "game_name", default = "default_game")]
+    pub game: Game,
+    /// Hero's pocket cards.
+    pub pocket: String,
+    /// Board cards, flop or turn; empty for stud games.
+    #[serde(default)]
+    pub board: String,
+    /// Dead cards; empty for none.
+    #[serde(default)]
+    pub dead: String,
+    /// Opponent hand or range to count clean and tainted outs against (Hold'em only).
+    #[serde(default)]
+    pub vs: Option<String>,
+}
+
+/// The result of [`Endpoint::Outs`]. Cards are written like `"Ah"`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutsResponse {
+    /// The game variant.
+    #[serde(with = "game_name")]
+    pub game: Game,
+    /// The pocket as given in the request.
+    pub pocket: String,
+    /// The board as given in the request.
+    pub board: String,
+    /// Cards improving the high hand, as `(hand type, cards)` pairs, best type first.
+    /// Types are named as in [`OUTS_TYPE_NAMES`](crate::board::OUTS_TYPE_NAMES).
+    pub outs_by_type: Vec<(String, Vec<String>)>,
+    /// Cards making or improving a qualifying low (hi/lo and low games).
+    pub low_outs: Vec<String>,
+    /// Low cards that counterfeit hero's low (hi/lo board games).
+    pub counterfeits: Vec<String>,
+    /// The comparison with the opponent, when the request names one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vs: Option<OutsVsResponse>,
+}
+
+/// Outs against an opponent hand or range, in an [`OutsResponse`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutsVsResponse {
+    /// The opponent as given in the request.
+    pub opponent: String,
+    /// Percentage of opponent combos hero beats now.
+    pub current_win_pct: f64,
+    /// Percentage of opponent combos hero ties now.
+    pub current_tie_pct: f64,
+    /// Cards after which hero is ahead of the whole range.
+    pub clean_outs: Vec<String>,
+    /// Cards after which hero at best ties.
+    pub tie_outs: Vec<String>,
+    /// Cards that put hero ahead of only part of the range.
+    pub tainted_outs: Vec<String>,
+    /// Outs weighted by the share of the range each one beats.
+    pub discounted_outs: f64,
+}
+
+/// Counts the outs of a hand under the rules of its game and, for Hold'em, against an
+/// opponent hand or range.
+pub fn outs(request: &OutsRequest) -> Result<OutsResponse, PokerError> {
+    let game = request.game;
+    let pocket = parse_cards(&request.pocket, "pocket")?;
+    let board = parse_cards(&request.board, "board")?;
+    let dead = parse_cards(&request.dead, "dead cards")?;
+    let mut used = pocket;
+    claim(&mut used, board, "board")?;
+    claim(&mut used, dead, "dead cards")?;
+    check_deck(game, used)?;
+    if request.vs.is_some() && game != Game::Holdem {
+        return Err(PokerError::UnsupportedGameType);
+    }
+
+    let cards = |cards: &[StdDeckCardMask]| -> Vec<String> {
+        cards.iter().map(|c| c.to_string()).collect()
+    };
+    let res = calculate_game_outs(game, &pocket, &board, &dead)?;
+    let mut outs_by_type: Vec<(String, Vec<String>)> = res
+        .hi
+        .named()
+        .map(|(name, outs)| (name.to_string(), cards(outs)))
+        .collect();
+    outs_by_type.reverse();
+
+    let vs = match &request.vs {
+        Some(opponent) => {
+            let range = HandRange::from_str(opponent).map_err(|e| {
+                PokerError::InvalidInput(format!("Error parsing opponent range: {}", e))
+            })?;
+            let res = calculate_outs_vs_range(&pocket, &board, &dead, &range)?;
+            Some(OutsVsResponse {
+                opponent: opponent.clone(),
+                current_win_pct: res.current_win * 100.0,
+                current_tie_pct: res.current_tie * 100.0,
+                clean_outs: cards(&res.clean_outs()),
+                tie_outs: cards(&res.tie_outs()),
+                tainted_outs: cards(&res.tainted_outs()),
+                discounted_outs: res.discounted_outs(),
+            })
+        }
+        None => None,
+    };
+
+    Ok(OutsResponse {
+        game,
+        pocket: request.pocket.clone(),
+        board: request.board.clone(),
+        outs_by_type,
+        low_outs: cards(&res.lo),
+        counterfeits: cards(&res.counterfeits),
+        vs,
+    })
+}
+
+/// Request for [`Endpoint::Texture`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextureRequest {
+    /// Board cards, 3 to 5.
+    pub board: String,
+}
+
+/// Analyzes the texture of a 3 to 5 card board.
+pub fn texture(request: &TextureRequest) -> Result<BoardTexture, PokerError> {
+    let board = parse_cards(&request.board, "board")?;
+    if !(3..=5).contains(&board.num_cards()) {
+        return Err(PokerError::UnsupportedBoardConfiguration);
+    }
+    Ok(BoardTexture::analyze(&board))
+}
+
+/// Lines of a batch answered together before their results are written.
+const BATCH_CHUNK_LINES: usize = 1024;
+
+/// Totals of a [`batch`] run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BatchSummary {
+    /// Queries read, not counting blank lines.
+    pub queries: usize,
+    /// Queries answered with an error object.
+    pub errors: usize,
+}
+
+/// Answers newline-delimited JSON queries, writing one JSON line per query in input
+/// order.
+///
+/// A query is an endpoint request with an `"op"` field naming the [`Endpoint`], e.g.
+/// `{"op": "equity", "game": "holdem", "players": ["AsAd", "KsKd"]}`; an `"id"` field
+/// is copied to the answer. A query that fails is answered with
+/// `{"error": "...", "line": n}`, `n` counting input lines from 1, and the run goes on.
+/// Blank lines are skipped.
+///
+/// The input is read in chunks, and with the `parallel` feature the queries of a
+/// chunk run in parallel. Fails only if reading or writing fails.
+///
+/// # Examples
+///
+/// ```
+/// use poker_eval_rs::service::{batch, Limits};
+///
+/// let input = r#"{"op": "texture", "board": "AsKsQs", "id": 7}
+/// {"op": "eval", "hand": "AsAs"}
+/// "#;
+/// let mut output = Vec::new();
+/// let summary = batch(input.as_bytes(), &mut output, &Limits::default()).unwrap();
+/// assert_eq!((summary.queries, summary.errors), (2, 1));
+///
+/// let lines: Vec<serde_json::Value> = output
+///     .split(|&b| b == b'\n')
+///     .filter(|l| !l.is_empty())
+///     .map(|l| serde_json::from_slice(l).unwrap())
+///     .collect();
+/// assert_eq!(lines[0]["id"], 7);
+/// assert_eq!(lines[1]["line"], 2);
+/// ```
+pub fn batch<R: BufRead, W: Write>(
+    input: R,
+    mut output: W,
+    limits: &Limits,
+) -> io::Result<BatchSummary> {
+    let mut summary = BatchSummary::default();
+    let mut lines = input.split(b'\n').enumerate();
+    loop {
+        let mut chunk = Vec::with_capacity(BATCH_CHUNK_LINES);
+        for (i, line) in lines.by_ref() {
+            let line = String::from_utf8_lossy(&line?).into_owned();
+            if !line.trim().is_empty() {
+                chunk.push((i + 1, line));
+                if chunk.len() == BATCH_CHUNK_LINES {
+                    break;
+                }
+            }
+        }
+        if chunk.is_empty() {
+            return Ok(summary);
+        }
+
+        let answer = |(n, line): &(usize, String)| answer_line(*n, line, limits);
+        #[cfg(feature = "parallel")]
+        let answers: Vec<(Value, bool)> = {
+            use rayon::prelude::*;
+            chunk.par_iter().map(answer).collect()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let answers: Vec<(Value, bool)> = chunk.iter().map(answer).collect();
+
+        for (answer, failed) in answers {
+            summary.queries += 1;
+            summary.errors += failed as usize;
+            serde_json::to_writer(&mut output, &answer)?;
+            output.write_all(b"\n")?;
+        }
+        output.flush()?;
+    }
+}
+
+/// Answers the batch query on input line `n`; the flag is set for error answers.
+fn answer_line(n: usize, line: &str, limits: &Limits) -> (Value, bool) {
+    let mut id = None;
+    let result = from_json::<Value>(line).and_then(|mut request| {
+        let fields = request
+            .as_object_mut()
+            .ok_or_else(|| PokerError::InvalidInput("A query must be a JSON object".to_string()))?;
+        id = fields.remove("id");
+        let op = match fields.remove("op") {
+            Some(Value::String(op)) => op,
+            _ => {
+                return Err(PokerError::InvalidInput(
+                    "A query needs an \"op\" field naming the endpoint".to_string(),
+                ))
+            }
+        };
+        op.parse::<Endpoint>()?.handle_value(request, limits)
+    });
+
+    let failed = result.is_err();
+    let mut answer =
+        result.unwrap_or_else(|e| serde_json::json!({ "error": e.to_string(), "line": n }));
+    if let (Some(id), Some(fields)) = (id, answer.as_object_mut()) {
+        fields.insert("id".to_string(), id);
+    }
+    (answer, failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handle(endpoint: Endpoint, body: &str) -> Result<Value, PokerError> {
+        endpoint.handle(body, &Limits::default())
+    }
+
+    #[test]
+    fn test_endpoint_names_round_trip() {
+        for endpoint in Endpoint::ALL {
+            assert_eq!(endpoint.name().parse::<Endpoint>().unwrap(), endpoint);
+        }
+        assert!("nope".parse::<Endpoint>().is_err());
+    }
+
+    #[test]
+    fn test_eval_describes_hands_per_game() {
+        let reply = handle(Endpoint::Eval, r#"{"hand": "AhKc", "board": "Kd7s2h"}"#).unwrap();
+        assert_eq!(reply["hi"]["hand_type"], "OnePair");
+        assert_eq!(reply["hi"]["description"], "OnePair ( K A 7 2)");
+        assert!(reply["lo"].is_null());
+
+        // Exactly two hole cards: a wheel rather than a flush with the single spade.
+        let reply = handle(
+            Endpoint::Eval,
+            r#"{"game": "omaha8", "hand": "As2dKcKh", "board": "3s4s5d9s"}"#,
+        )
+        .unwrap();
+        assert_eq!(reply["hi"]["hand_type"], "Straight");
+        assert_eq!(reply["lo"]["hand_type"], "NoPair");
+
+        let reply = handle(
+            Endpoint::Eval,
+            r#"{"game": "razz", "hand": "As2d3c4h6sKcKd"}"#,
+        )
+        .unwrap();
+        assert!(reply["hi"].is_null());
+        assert_eq!(reply["lo"]["description"], "Low NoPair ( 6 4 3 2 1)");
+
+        // Short Deck stores flushes with the full house type; the reply names the hand.
+        let reply = handle(
+            Endpoint::Eval,
+            r#"{"game": "shortdeck", "hand": "AhKh", "board": "9h7h6hAs"}"#,
+        )
+        .unwrap();
+        assert_eq!(reply["hi"]["hand_type"], "Flush");
+        assert_eq!(reply["hi"]["description"], "Flush ( A K 9 7 6)");
+    }
+
+    #[test]
+    fn test_eval_rejects_bad_hands() {
+        let err = |body: &str| handle(Endpoint::Eval, body).unwrap_err();
+        assert!(matches!(
+            err(r#"{"hand": "AhKhQh"}"#),
+            PokerError::InvalidInput(_)
+        ));
+        assert!(matches!(
+            err(r#"{"hand": "AhKh", "board": "Ah7s2d"}"#),
+            PokerError::InvalidCardConfiguration(_)
+        ));
+        assert!(matches!(
+            err(r#"{"game": "shortdeck", "hand": "AhKh", "board": "2s7s9d"}"#),
+            PokerError::InvalidCardConfiguration(_)
+        ));
+        assert!(matches!(
+            err(r#"{"hand": 42}"#),
+            PokerError::InvalidInput(_)
+        ));
+        assert!(matches!(
+            err(r#"{"game": "bridge", "hand": "AhKh"}"#),
+            PokerError::InvalidInput(_)
+        ));
+    }
+
+    #[test]
+    fn test_equity_matches_query() {
+        let body = r#"{"game": "holdem", "players": ["AsAd", "KsKd"], "board": "2c7d9h"}"#;
+        let reply = handle(Endpoint::Equity, body).unwrap();
+        let query: EquityQuery = serde_json::from_str(body).unwrap();
+        assert_eq!(reply, serde_json::to_value(query.run().unwrap()).unwrap());
+
+        // Seeded runs longer than one sampling chunk give the library's numbers too.
+        let body = r#"{"game": "holdem", "players": ["AsAd", "KsKd"], "mode": "monte_carlo", "iterations": 60000, "seed": 5}"#;
+        let reply = handle(Endpoint::Equity, body).unwrap();
+        let query: EquityQuery = serde_json::from_str(body).unwrap();
+        assert_eq!(reply, serde_json::to_value(query.run().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_equity_limits() {
+        let limits = Limits {
+            max_iterations: 1000,
+            timeout: None,
+        };
+        let body = r#"{"game": "holdem", "players": ["AsAd", "KsKd"], "mode": "monte_carlo", "iterations": 5000}"#;
+        assert!(matches!(
+            Endpoint::Equity.handle(body, &limits),
+            Err(PokerError::InvalidInput(_))
+        ));
+        // Exhaustive runs are capped by their number of boards.
+        let body = r#"{"game": "holdem", "players": ["AsAd", "KsKd"]}"#;
+        assert!(matches!(
+            Endpoint::Equity.handle(body, &limits),
+            Err(PokerError::InvalidInput(_))
+        ));
+        let body = r#"{"game": "holdem", "players": ["AsAd", "KsKd"], "board": "2c7d9h"}"#;
+        assert!(Endpoint::Equity.handle(body, &limits).is_ok());
+
+        let limits = Limits {
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+            timeout: Some(Duration::ZERO),
+        };
+        let body = r#"{"game": "holdem", "players": ["AsAd", "KsKd"]}"#;
+        assert_eq!(
+            Endpoint::Equity.handle(body, &limits),
+            Err(PokerError::Cancelled)
+        );
+        let body = r#"{"hero": "AA", "villain": "KK", "iterations": 1000}"#;
+        assert_eq!(
+            Endpoint::RangeEquity.handle(body, &limits),
+            Err(PokerError::Cancelled)
+        );
+    }
+
+    #[test]
+    fn test_range_equity() {
+        let body = r#"{"hero": "AA", "villain": "KK", "board": "2c7d9h", "iterations": 120000}"#;
+        let reply: RangeEquityResponse =
+            serde_json::from_value(handle(Endpoint::RangeEquity, body).unwrap()).unwrap();
+        assert_eq!(reply.samples, 120_000);
+        assert_eq!(reply.wins + reply.ties + reply.losses, reply.samples);
+        assert!(reply.equity > 0.85 && reply.equity < 0.95);
+    }
+
+    #[test]
+    fn test_batch_keeps_order_and_reports_errors() {
+        let mut input = String::new();
+        for i in 0..BATCH_CHUNK_LINES + 10 {
+            input.push_str(&format!(
+                "{{\"op\": \"eval\", \"hand\": \"AhKh\", \"board\": \"2c3d4s5h{}\", \"id\": {}}}\n",
+                ["9c", "6d"][i % 2],
+                i
+            ));
+        }
+        input.push_str("\n{\"op\": \"fold\"}\nnot json\n[1]\n{\"hand\": \"AhKh\"}\n");
+
+        let mut output = Vec::new();
+        let summary = batch(input.as_bytes(), &mut output, &Limits::default()).unwrap();
+        assert_eq!(
+            summary,
+            BatchSummary {
+                queries: BATCH_CHUNK_LINES + 14,
+                errors: 4
+            }
+        );
+
+        let answers: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(answers.len(), BATCH_CHUNK_LINES + 14);
+        for (i, answer) in answers[..BATCH_CHUNK_LINES + 10].iter().enumerate() {
+            assert_eq!(answer["id"], i);
+            let straight = if i % 2 == 0 {
+                "Straight ( 5)"
+            } else {
+                "Straight ( 6)"
+            };
+            assert_eq!(answer["hi"]["description"], straight);
+        }
+        let errors = &answers[BATCH_CHUNK_LINES + 10..];
+        let lines: Vec<u64> = errors.iter().map(|e| e["line"].as_u64().unwrap()).collect();
+        let first = BATCH_CHUNK_LINES as u64 + 12;
+        assert_eq!(lines, [first, first + 1, first + 2, first + 3]);
+        assert!(errors[0]["error"]
+            .as_str()
+            .unwrap()
+            .contains("Unknown endpoint"));
+        assert!(errors[3]["error"].as_str().unwrap().contains("op"));
+    }
+
+    #[test]
+    fn test_outs_and_texture() {
+        let body = r#"{"pocket": "AsJs", "board": "Ks7s2d", "vs": "KdQc"}"#;
+        let reply: OutsResponse =
+            serde_json::from_value(handle(Endpoint::Outs, body).unwrap()).unwrap();
+        let flush = reply
+            .outs_by_type
+            .iter()
+            .find(|(t, _)| t == "Flush")
+            .unwrap();
+        assert_eq!(flush.1.len(), 9);
+        assert!(reply.vs.as_ref().unwrap().clean_outs.len() >= 9);
+        let names = |reply: &OutsResponse| -> Vec<String> {
+            reply.outs_by_type.iter().map(|(t, _)| t.clone()).collect()
+        };
+        assert_eq!(names(&reply), ["Flush", "Pair"]);
+
+        let body = r#"{"pocket": "7h7d", "board": "Ks7s2d"}"#;
+        let reply: OutsResponse =
+            serde_json::from_value(handle(Endpoint::Outs, body).unwrap()).unwrap();
+        assert_eq!(names(&reply), ["Quads", "FullHouse"]);
+
+        let body = r#"{"game": "omaha", "pocket": "AsKdQhJc", "board": "9s5s2s", "vs": "AA"}"#;
+        assert_eq!(
+            handle(Endpoint::Outs, body),
+            Err(PokerError::UnsupportedGameType)
+        );
+
+        let reply = handle(Endpoint::Texture, r#"{"board": "As Ks Qs"}"#).unwrap();
+        assert_eq!(reply["is_monotone"], true);
+        assert_eq!(
+            handle(Endpoint::Texture, r#"{"board": "AsKs"}"#),
+            Err(PokerError::UnsupportedBoardConfiguration)
+        );
+    }
+}
@@ -29,8 +29,8 @@ impl StdDeckCardMask {
     /// Returns a mask with all 52 cards set.
     pub const fn all_cards() -> Self {
         StdDeckCardMask {
-            mask: 0x000FFFFFFFFFFFFF,
-        } // 52 bits set
+            mask: 0x1FFF_1FFF_1FFF_1FFF,
+        } // 13 rank bits in each 16-bit suit group
     }
 
     /// Creates a mask with a single card set by index (0-51).
@@ -259,6 +259,15 @@ mod tests {
         println!("{} {}", mask_set.len(), STD_DECK_CARD_MASKS_TABLE.len());
     }
 
+    #[test]
+    fn test_all_cards_is_union_of_table() {
+        let union = STD_DECK_CARD_MASKS_TABLE
+            .iter()
+            .fold(0u64, |acc, m| acc | m.mask);
+        assert_eq!(StdDeckCardMask::all_cards().mask, union);
+        assert_eq!(StdDeckCardMask::all_cards().num_cards(), STD_DECK_N_CARDS);
+    }
+
     // You can add additional tests here if needed
     #[test]
     fn test_display_trait() {
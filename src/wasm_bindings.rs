@@ -1,34 +1,218 @@
-#![cfg(target_arch = "wasm32")]
+#![cfg(any(target_arch = "wasm32", test))]
 
 //! WebAssembly bindings for poker-eval-rs.
 //!
 //! This module exposes key functionality to WASM environments via `wasm-bindgen`.
+//! Functions returning plain objects declare their shapes in the TypeScript section
+//! below, so `wasm-pack` emits typed definitions for them.
 
-use crate::deck::{JokerDeck, StdDeck};
-use crate::deck::{JokerDeckCardMask, StdDeckCardMask};
-use crate::enumdefs::{EnumResult, Game, SampleType};
-use crate::enumerate::evaluation::{enum_exhaustive, enum_sample};
+use crate::board::{calculate_outs as calc_outs_rust, BoardTexture};
+use crate::deck::StdDeckCardMask;
+use crate::deck::{JokerDeck, StdDeck, STD_DECK_RANK_CHARS};
+use crate::evaluators::range_equity::{calculate_equity as calc_range_equity, equity_vs_random};
 use crate::evaluators::{
-    EvalJoker, HandEvaluator, LowballEvaluator, OmahaHiEvaluator, ShortDeckEvaluator,
+    std_deck_lowball27_eval, std_deck_lowball_eval, Eval, EvalJoker, HandEvaluator,
+    LowballEvaluator, OmahaHiEvaluator, ShortDeckEvaluator,
 };
+use crate::game_rules::GameRef;
+use crate::handval::HandVal;
+use crate::handval_low::LowHandVal;
+use crate::query::{claim, parse_cards as parse_query_cards, EquityMode, EquityQuery};
+use crate::range::HandRange;
+use crate::rules::{HAND_TYPE_NAMES, N_SIG_CARDS};
 use serde::Serialize;
 use serde_wasm_bindgen;
+use std::collections::BTreeMap;
+use std::str::FromStr;
 use wasm_bindgen::prelude::*;
 
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &'static str = r#"
+/** A hand value; `value` orders hands of the same evaluation function. */
+export interface HandVal {
+    value: number;
+    hand_type: string;
+    hand_type_id: number;
+    /** Ranks of the significant cards, e.g. ["A", "K"] for aces full of kings. */
+    ranks: string[];
+    description: string;
+}
+
+export interface BoardTexture {
+    is_rainbow: boolean;
+    is_two_tone: boolean;
+    is_monotone: boolean;
+    is_paired: boolean;
+    is_trips: boolean;
+    is_quads: boolean;
+    is_full_house: boolean;
+    has_straight_draw: boolean;
+    has_flush_draw: boolean;
+}
+
+/** Out cards keyed by the hand type they make, e.g. { Flush: ["2s", "3s"] }. */
+export type OutsByType = Record<string, string[]>;
+
+export interface RangeEquityResult {
+    /** The first range's share of the pot, from 0 to 1. */
+    equity: number;
+    wins: number;
+    ties: number;
+    losses: number;
+    samples: number;
+}
+
+export interface VsRandomResult {
+    hand: string;
+    opponents: number;
+    samples: number;
+    exact: boolean;
+    win_pct: number;
+    tie_pct: number;
+    lose_pct: number;
+    equity: number;
+}
+
+export interface EquityQuery {
+    game: string;
+    players: string[];
+    board?: string;
+    dead?: string;
+    mode?: "exhaustive" | "monte_carlo";
+    iterations?: number;
+    /** A u64; numbers above 2^53 must be passed as a bigint. */
+    seed?: bigint | number | null;
+}
+
+export interface PlayerEquity {
+    hand: string;
+    win_pct: number;
+    tie_pct: number;
+    lose_pct: number;
+    ev: number;
+    scoop_pct?: number;
+    win_lo_pct?: number;
+    tie_lo_pct?: number;
+    lose_lo_pct?: number;
+}
+
+export interface EquityReport {
+    game: string;
+    mode: "exhaustive" | "monte_carlo";
+    samples: number;
+    board: string;
+    dead: string;
+    players: PlayerEquity[];
+}
+"#;
+
+/// A hand value as seen from JavaScript; `HandVal` in the TypeScript definitions.
 #[derive(Serialize)]
-pub struct WasmPlayerStat {
-    pub hand: String,
-    pub win_pct: f64,
-    pub tie_pct: f64,
-    pub lose_pct: f64,
-    pub ev: f64,
+pub struct WasmHandVal {
+    pub value: u32,
+    pub hand_type: String,
+    pub hand_type_id: u8,
+    pub ranks: Vec<String>,
+    pub description: String,
+}
+
+impl WasmHandVal {
+    /// A high hand; `detail` is the same hand with its card fields filled in.
+    fn high(val: HandVal, detail: HandVal) -> Self {
+        let hand_type = detail.hand_type() as usize;
+        let cards = [
+            detail.top_card(),
+            detail.second_card(),
+            detail.third_card(),
+            detail.fourth_card(),
+            detail.fifth_card(),
+        ];
+        WasmHandVal {
+            value: val.value,
+            hand_type: HAND_TYPE_NAMES[hand_type].to_string(),
+            hand_type_id: hand_type as u8,
+            ranks: cards[..N_SIG_CARDS[hand_type]]
+                .iter()
+                .filter_map(|&r| STD_DECK_RANK_CHARS.chars().nth(r as usize))
+                .map(String::from)
+                .collect(),
+            description: detail.std_rules_hand_val_to_string(),
+        }
+    }
+
+    /// An A-5 low, whose ranks count the ace as 1.
+    fn low(val: LowHandVal) -> Self {
+        let hand_type = val.hand_type() as usize;
+        WasmHandVal {
+            value: val.value,
+            hand_type: HAND_TYPE_NAMES
+                .get(hand_type)
+                .copied()
+                .unwrap_or("Unknown")
+                .to_string(),
+            hand_type_id: hand_type as u8,
+            ranks: [
+                val.top_card(),
+                val.second_card(),
+                val.third_card(),
+                val.fourth_card(),
+                val.fifth_card(),
+            ]
+            .iter()
+            .filter_map(|&r| match r {
+                0 => None,
+                1 => Some('A'),
+                r => STD_DECK_RANK_CHARS.chars().nth(r as usize - 2),
+            })
+            .map(String::from)
+            .collect(),
+            description: val.to_string(),
+        }
+    }
+}
+
+/// Parses a card string, rejecting repeated cards. An empty string is no cards.
+fn parse_cards(cards: &str, what: &str) -> Result<(StdDeckCardMask, usize), JsValue> {
+    parse_card_str(cards, what).map_err(|e| JsValue::from_str(&e))
+}
+
+/// [`parse_cards`] with a plain error message.
+fn parse_card_str(cards: &str, what: &str) -> Result<(StdDeckCardMask, usize), String> {
+    if cards.trim().is_empty() {
+        return Ok((StdDeckCardMask::new(), 0));
+    }
+    let (mask, count) =
+        StdDeck::string_to_mask(cards).map_err(|e| format!("Error parsing {}: {}", what, e))?;
+    if mask.num_cards() != count {
+        return Err(format!("Duplicate cards in {}", what));
+    }
+    Ok((mask, count))
+}
+
+/// Serializes `value` with maps as plain objects.
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    Ok(value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
 }
 
 #[derive(Serialize)]
-pub struct WasmEquityResult {
-    pub game: String,
+pub struct WasmRangeEquityResult {
+    pub equity: f64,
+    pub wins: usize,
+    pub ties: usize,
+    pub losses: usize,
+    pub samples: usize,
+}
+
+#[derive(Serialize)]
+pub struct WasmVsRandomResult {
+    pub hand: String,
+    pub opponents: usize,
     pub samples: u64,
-    pub players: Vec<WasmPlayerStat>,
+    pub exact: bool,
+    pub win_pct: f64,
+    pub tie_pct: f64,
+    pub lose_pct: f64,
+    pub equity: f64,
 }
 
 /// Evaluates a single Omaha Hi hand.
@@ -80,7 +264,7 @@ pub fn eval_short_deck(hand: &str, board: &str) -> Result<String, JsValue> {
     if n_h != 2 {
         return Err(JsValue::from_str("Short Deck requires 2 hole cards"));
     }
-    if n_b < 3 || n_b > 5 {
+    if !(3..=5).contains(&n_b) {
         return Err(JsValue::from_str("Short Deck requires 3-5 board cards"));
     }
 
@@ -102,15 +286,18 @@ pub fn eval_joker(hand: &str) -> Result<String, JsValue> {
 
 /// Calculates equity for a set of hands.
 ///
+/// Returns a serialized [`EquityReport`](crate::query::EquityReport).
+///
 /// # Arguments
 ///
 /// * `hands_str` - Space-separated list of hands (e.g., "AsKs QdJd 22").
 /// * `board_str` - Board cards (e.g., "Qs Js Ts"). Can be empty.
 /// * `dead_str` - Dead cards (e.g., "2s 3s"). Can be empty.
-/// * `game` - Game variant ("holdem", "omaha", "omaha8", "stud7", "razz", "lowball", etc.).
+/// * `game` - Registered game name ("holdem", "omaha", "omaha8", "stud7", "razz", "lowball", etc.).
 /// * `monte_carlo` - If true, uses Monte Carlo simulation. Otherwise exhaustive.
 /// * `iterations` - Number of iterations for Monte Carlo simulation.
-#[wasm_bindgen]
+/// * `seed` - Optional seed for reproducible Monte Carlo results.
+#[wasm_bindgen(unchecked_return_type = "EquityReport")]
 pub fn calculate_equity(
     hands_str: &str,
     board_str: &str,
@@ -118,121 +305,305 @@ pub fn calculate_equity(
     game: &str,
     monte_carlo: bool,
     iterations: usize,
+    seed: Option<u64>,
 ) -> Result<JsValue, JsValue> {
-    let hands: Vec<String> = hands_str
-        .split_whitespace()
-        .map(|s| s.to_string())
-        .collect();
-
-    if hands.len() < 2 {
-        return Err(JsValue::from_str("Need at least 2 hands"));
-    }
-
-    let game_variant = match game.to_lowercase().as_str() {
-        "holdem" => Game::Holdem,
-        "holdem8" => Game::Holdem8,
-        "omaha" => Game::Omaha,
-        "omaha5" => Game::Omaha5,
-        "omaha6" => Game::Omaha6,
-        "omaha8" => Game::Omaha8,
-        "omaha85" => Game::Omaha85,
-        "stud7" => Game::Stud7,
-        "stud78" => Game::Stud78,
-        "stud7nsq" => Game::Stud7nsq,
-        "razz" => Game::Razz,
-        "draw5" => Game::Draw5,
-        "draw58" => Game::Draw58,
-        "draw5nsq" => Game::Draw5nsq,
-        "lowball" => Game::Lowball,
-        "lowball27" => Game::Lowball27,
-        "shortdeck" => Game::ShortDeck,
-        _ => return Err(JsValue::from_str("Unsupported game")),
+    let game = GameRef::from_name(game).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let query = EquityQuery {
+        game,
+        players: hands_str.split_whitespace().map(str::to_string).collect(),
+        board: board_str.to_string(),
+        dead: dead_str.to_string(),
+        mode: if monte_carlo {
+            EquityMode::MonteCarlo
+        } else {
+            EquityMode::Exhaustive
+        },
+        iterations,
+        seed,
     };
+    run_query(&query)
+}
+
+/// Runs a serialized [`EquityQuery`], e.g.
+/// `{game: "omaha8", players: ["As2sKdKc", "QhQd3c4c"], mode: "monte_carlo"}`,
+/// and returns the serialized [`EquityReport`](crate::query::EquityReport).
+#[wasm_bindgen(unchecked_return_type = "EquityReport")]
+pub fn calculate_equity_query(
+    #[wasm_bindgen(unchecked_param_type = "EquityQuery")] query: JsValue,
+) -> Result<JsValue, JsValue> {
+    let query: EquityQuery = serde_wasm_bindgen::from_value(query)?;
+    run_query(&query)
+}
+
+fn run_query(query: &EquityQuery) -> Result<JsValue, JsValue> {
+    let report = query.run().map_err(|e| JsValue::from_str(&e.to_string()))?;
+    Ok(serde_wasm_bindgen::to_value(&report)?)
+}
+
+/// Calculates the equity of a Hold'em hand against `opponents` random hands.
+///
+/// Heads-up spots with few unknown deals are enumerated exactly; otherwise
+/// `iterations` Monte Carlo deals are used.
+#[wasm_bindgen(unchecked_return_type = "VsRandomResult")]
+pub fn calculate_equity_vs_random(
+    hand: &str,
+    opponents: usize,
+    board_str: &str,
+    dead_str: &str,
+    iterations: usize,
+) -> Result<JsValue, JsValue> {
+    let output = vs_random(hand, opponents, board_str, dead_str, iterations)
+        .map_err(|e| JsValue::from_str(&e))?;
+    Ok(serde_wasm_bindgen::to_value(&output)?)
+}
+
+/// The result behind [`calculate_equity_vs_random`]. Cards are parsed and checked
+/// for overlaps as in an [`EquityQuery`].
+fn vs_random(
+    hand: &str,
+    opponents: usize,
+    board_str: &str,
+    dead_str: &str,
+    iterations: usize,
+) -> Result<WasmVsRandomResult, String> {
+    let cards = |cards: &str, what: &str| parse_query_cards(cards, what).map_err(|e| e.to_string());
+    let hero = cards(hand, "hand")?;
+    let board = cards(board_str, "board")?;
+    let dead = cards(dead_str, "dead cards")?;
+    let mut used = hero;
+    claim(&mut used, board, "board").map_err(|e| e.to_string())?;
+    claim(&mut used, dead, "dead cards").map_err(|e| e.to_string())?;
+
+    let res = equity_vs_random(&hero, opponents, &board, &dead, iterations)
+        .map_err(|e| format!("Calculation error: {}", e))?;
+    let total = res.samples as f64;
+    Ok(WasmVsRandomResult {
+        hand: hand.to_string(),
+        opponents,
+        samples: res.samples as u64,
+        exact: res.exact,
+        win_pct: res.wins as f64 / total * 100.0,
+        tie_pct: res.ties as f64 / total * 100.0,
+        lose_pct: res.losses as f64 / total * 100.0,
+        equity: res.equity,
+    })
+}
 
-    // Parse hands
-    let mut pockets: Vec<StdDeckCardMask> = Vec::new();
-    for hand in &hands {
-        let (mask, _) = StdDeck::string_to_mask(hand)
-            .map_err(|e| JsValue::from_str(&format!("Error parsing hand {}: {}", hand, e)))?;
-        pockets.push(mask);
+/// Evaluates a Hold'em hand: 2 hole cards and 3-5 board cards.
+#[wasm_bindgen(unchecked_return_type = "HandVal")]
+pub fn eval_holdem(hand: &str, board: &str) -> Result<JsValue, JsValue> {
+    let (h_mask, n_h) = parse_cards(hand, "hand")?;
+    let (b_mask, n_b) = parse_cards(board, "board")?;
+    if n_h != 2 {
+        return Err(JsValue::from_str("Hold'em requires 2 hole cards"));
     }
+    if !(3..=5).contains(&n_b) {
+        return Err(JsValue::from_str("Hold'em requires 3-5 board cards"));
+    }
+    if h_mask.as_raw() & b_mask.as_raw() != 0 {
+        return Err(JsValue::from_str("Hand and board share a card"));
+    }
+    let all = h_mask | b_mask;
+    let n = all.num_cards();
+    to_js(&WasmHandVal::high(
+        Eval::eval_n(&all, n),
+        Eval::eval_n_detailed(&all, n),
+    ))
+}
 
-    let board_mask = if board_str.is_empty() {
-        StdDeckCardMask::new()
-    } else {
-        StdDeck::string_to_mask(board_str)
-            .map_err(|e| JsValue::from_str(&format!("Error parsing board: {}", e)))?
-            .0
-    };
+/// Evaluates the best five-card high hand of a Stud hand of 5-7 cards.
+#[wasm_bindgen(unchecked_return_type = "HandVal")]
+pub fn eval_stud(hand: &str) -> Result<JsValue, JsValue> {
+    let (mask, n) = parse_cards(hand, "hand")?;
+    if !(5..=7).contains(&n) {
+        return Err(JsValue::from_str("Stud requires 5-7 cards"));
+    }
+    to_js(&WasmHandVal::high(
+        Eval::eval_n(&mask, n),
+        Eval::eval_n_detailed(&mask, n),
+    ))
+}
 
-    let dead_mask = if dead_str.is_empty() {
-        StdDeckCardMask::new()
-    } else {
-        StdDeck::string_to_mask(dead_str)
-            .map_err(|e| JsValue::from_str(&format!("Error parsing dead cards: {}", e)))?
-            .0
-    };
+/// Evaluates the best A-5 low of a Razz hand of 5-7 cards. Lower values are better.
+#[wasm_bindgen(unchecked_return_type = "HandVal")]
+pub fn eval_razz(hand: &str) -> Result<JsValue, JsValue> {
+    let (mask, n) = parse_cards(hand, "hand")?;
+    if !(5..=7).contains(&n) {
+        return Err(JsValue::from_str("Razz requires 5-7 cards"));
+    }
+    to_js(&WasmHandVal::low(std_deck_lowball_eval(&mask, n)))
+}
 
-    let npockets = pockets.len();
-    let nboard = board_mask.num_cards();
+/// Evaluates a 2-7 lowball hand of 5-7 cards. Lower values are better; straights
+/// and flushes count against the hand.
+#[wasm_bindgen(unchecked_return_type = "HandVal")]
+pub fn eval_lowball27(hand: &str) -> Result<JsValue, JsValue> {
+    let (mask, n) = parse_cards(hand, "hand")?;
+    if !(5..=7).contains(&n) {
+        return Err(JsValue::from_str("2-7 Lowball requires 5-7 cards"));
+    }
+    let val = std_deck_lowball27_eval(&mask, n);
+    to_js(&WasmHandVal::high(val, val))
+}
 
-    let mut result = EnumResult::new(game_variant);
-    result.sample_type = if monte_carlo {
-        SampleType::Sample
-    } else {
-        SampleType::Exhaustive
-    };
-    result.nplayers = npockets as u32;
-
-    let calc_result = if monte_carlo {
-        enum_sample(
-            game_variant,
-            &pockets,
-            board_mask,
-            dead_mask,
-            npockets,
-            nboard,
-            iterations,
-            false,
-            &mut result,
-        )
-    } else {
-        enum_exhaustive(
-            game_variant,
-            &pockets,
-            board_mask,
-            dead_mask,
-            npockets,
-            nboard,
-            false,
-            &mut result,
-        )
-    };
+/// Analyzes the texture of a board.
+#[wasm_bindgen(unchecked_return_type = "BoardTexture")]
+pub fn analyze_board(board: &str) -> Result<JsValue, JsValue> {
+    let (mask, _) = parse_cards(board, "board")?;
+    to_js(&BoardTexture::analyze(&mask))
+}
 
-    match calc_result {
-        Ok(_) => {
-            let mut player_stats = Vec::new();
-            for (i, hand) in hands.iter().enumerate() {
-                let total = result.nwinhi[i] + result.ntiehi[i] + result.nlosehi[i];
-                if total > 0 {
-                    player_stats.push(WasmPlayerStat {
-                        hand: hand.clone(),
-                        win_pct: (result.nwinhi[i] as f64 / total as f64) * 100.0,
-                        tie_pct: (result.ntiehi[i] as f64 / total as f64) * 100.0,
-                        lose_pct: (result.nlosehi[i] as f64 / total as f64) * 100.0,
-                        ev: result.ev[i],
-                    });
-                }
-            }
-
-            let output = WasmEquityResult {
-                game: game.to_string(),
-                samples: result.nsamples as u64,
-                players: player_stats,
-            };
-
-            Ok(serde_wasm_bindgen::to_value(&output)?)
-        }
-        Err(e) => Err(JsValue::from_str(&format!("Calculation error: {:?}", e))),
+/// Calculates the outs of a Hold'em hand, keyed by the hand type each out makes
+/// (`"Flush"`, `"Straight"`, ...). Takes 2 pocket cards and a 3-4 card board.
+#[wasm_bindgen(unchecked_return_type = "OutsByType")]
+pub fn calculate_outs(pocket: &str, board: &str) -> Result<JsValue, JsValue> {
+    to_js(&outs_by_type(pocket, board).map_err(|e| JsValue::from_str(&e))?)
+}
+
+/// The outs behind [`calculate_outs`], named as in the Python bindings.
+fn outs_by_type(pocket: &str, board: &str) -> Result<BTreeMap<&'static str, Vec<String>>, String> {
+    let (pocket_mask, n_pocket) = parse_card_str(pocket, "pocket")?;
+    let (board_mask, n_board) = parse_card_str(board, "board")?;
+    if n_pocket != 2 {
+        return Err("Outs require 2 pocket cards".to_string());
+    }
+    if !(3..=4).contains(&n_board) {
+        return Err("Outs require 3-4 board cards".to_string());
+    }
+    if pocket_mask.as_raw() & board_mask.as_raw() != 0 {
+        return Err("Pocket and board share a card".to_string());
+    }
+    let outs = calc_outs_rust(&pocket_mask, &board_mask);
+    Ok(outs
+        .named()
+        .map(|(name, cards)| (name, cards.iter().map(|c| c.to_string()).collect()))
+        .collect())
+}
+
+/// A weighted range of hands, parsed from notation such as `"QQ+,AKs,AQs:0.5"`.
+#[wasm_bindgen(js_name = HandRange)]
+pub struct WasmHandRange {
+    inner: HandRange,
+}
+
+#[wasm_bindgen(js_class = HandRange)]
+impl WasmHandRange {
+    #[wasm_bindgen(constructor)]
+    pub fn new(range_str: &str) -> Result<WasmHandRange, JsValue> {
+        HandRange::from_str(range_str)
+            .map(|inner| WasmHandRange { inner })
+            .map_err(|e| JsValue::from_str(&format!("Invalid range string: {}", e)))
+    }
+
+    /// Number of hand combinations in the range.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[wasm_bindgen(js_name = isEmpty)]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Adds a hand to the range with a weight (1.0 when omitted).
+    pub fn add(&mut self, hand: &str, weight: Option<f64>) -> Result<(), JsValue> {
+        let (mask, _) = parse_cards(hand, "hand")?;
+        self.inner.push_weighted(mask, weight.unwrap_or(1.0));
+        Ok(())
+    }
+
+    /// The range in compact notation.
+    #[wasm_bindgen(js_name = toNotation)]
+    pub fn to_notation(&self) -> String {
+        self.inner.to_notation()
+    }
+}
+
+/// Calculates the Hold'em equity of one range against another with `iterations`
+/// Monte Carlo deals. `equity` is the first range's share of the pot, from 0 to 1.
+#[wasm_bindgen(unchecked_return_type = "RangeEquityResult")]
+pub fn calculate_range_equity(
+    range1: &WasmHandRange,
+    range2: &WasmHandRange,
+    board: &str,
+    iterations: usize,
+) -> Result<JsValue, JsValue> {
+    let (board_mask, _) = parse_cards(board, "board")?;
+    let res = calc_range_equity(&range1.inner, &range2.inner, &board_mask, iterations)
+        .map_err(|e| JsValue::from_str(&format!("Equity calculation error: {}", e)))?;
+    to_js(&WasmRangeEquityResult {
+        equity: res.equity,
+        wins: res.wins,
+        ties: res.ties,
+        losses: res.losses,
+        samples: res.samples,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::OUTS_TYPE_NAMES;
+
+    #[test]
+    fn test_parse_card_str() {
+        assert_eq!(parse_card_str("  ", "board").unwrap().1, 0);
+        assert_eq!(parse_card_str("AsKd", "hand").unwrap().1, 2);
+        assert_eq!(
+            parse_card_str("AsAs", "hand").unwrap_err(),
+            "Duplicate cards in hand"
+        );
+        assert!(parse_card_str("Zz", "board")
+            .unwrap_err()
+            .starts_with("Error parsing board"));
+    }
+
+    #[test]
+    fn test_outs_by_type() {
+        let outs = outs_by_type("2s3s", "AsKs9d").unwrap();
+        assert_eq!(outs["Flush"].len(), 9);
+        assert!(outs.keys().all(|name| OUTS_TYPE_NAMES.contains(name)));
+
+        let outs = outs_by_type("5h6h", "7h8h2s").unwrap();
+        assert_eq!(outs["StraightFlush"], ["4h", "9h"]);
+        assert_eq!(outs["Straight"].len(), 6);
+    }
+
+    #[test]
+    fn test_outs_by_type_rejects_bad_input() {
+        assert!(outs_by_type("2s", "AsKs9d").is_err());
+        assert!(outs_by_type("2s3s4s", "AsKs9d").is_err());
+        assert!(outs_by_type("2s3s", "AsKs").is_err());
+        assert!(outs_by_type("2s3s", "AsKs9d8d7d").is_err());
+        assert_eq!(
+            outs_by_type("2s3s", "2sKs9d").unwrap_err(),
+            "Pocket and board share a card"
+        );
+    }
+
+    #[test]
+    fn test_vs_random_checks_cards() {
+        let res = vs_random("AhKc", 1, "QdJsTc4h2d", "", 0).unwrap();
+        assert!(res.exact);
+        assert_eq!(res.samples, 990);
+        assert!((res.equity - (981.0 + 4.5) / 990.0).abs() < 1e-12);
+
+        let err = |hand, board, dead| vs_random(hand, 1, board, dead, 100).err().unwrap();
+        assert!(err("AhAh", "", "").contains("Duplicate cards in hand"));
+        assert!(err("AhKc", "Ah7d2c", "").contains("Ah in board already used"));
+        assert!(err("AhKc", "Qd7d2c", "7d").contains("7d in dead cards already used"));
+    }
+
+    #[test]
+    fn test_wasm_hand_val() {
+        let (mask, n) = StdDeck::string_to_mask("KhKdKcAsAd").unwrap();
+        let val = WasmHandVal::high(Eval::eval_n(&mask, n), Eval::eval_n_detailed(&mask, n));
+        assert_eq!(val.hand_type, "FlHouse");
+        assert_eq!(val.ranks, ["K", "A"]);
+
+        let (mask, n) = StdDeck::string_to_mask("Ac2d3h4s5c").unwrap();
+        let low = WasmHandVal::low(std_deck_lowball_eval(&mask, n));
+        assert_eq!(low.ranks, ["5", "4", "3", "2", "A"]);
     }
 }
@@ -0,0 +1,82 @@
+//! The `batch` subcommand: JSONL queries in, one JSON result line per query out.
+
+use serde_json::Value;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn batch(args: &[&str], stdin: &str) -> (Vec<Value>, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_poker_eval_rs_cli"))
+        .arg("batch")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run the CLI");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let out = child.wait_with_output().unwrap();
+    assert!(
+        out.status.success(),
+        "batch failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let lines = String::from_utf8(out.stdout)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    (lines, String::from_utf8(out.stderr).unwrap())
+}
+
+#[test]
+fn test_batch_from_stdin() {
+    let input = r#"{"op": "eval", "game": "omaha", "hand": "AsKsQdJd", "board": "Ts9s2s"}
+{"op": "equity", "game": "holdem", "players": ["AsAd", "KsKd"], "board": "2c7d9h", "id": "aa-kk"}
+{"op": "range_equity", "hero": "AA", "villain": "KK", "iterations": 10000}
+{"op": "texture", "board": "AsKs"}
+{"op": "texture", "board": "AsKsQs"}
+"#;
+    let (lines, stderr) = batch(&[], input);
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0]["hi"]["hand_type"], "Flush");
+    assert_eq!(lines[1]["id"], "aa-kk");
+    assert_eq!(lines[1]["samples"], 990);
+    assert_eq!(lines[2]["samples"], 10000);
+    assert_eq!(lines[3]["line"], 4);
+    assert!(lines[3]["error"].is_string());
+    assert_eq!(lines[4]["is_monotone"], true);
+    assert!(stderr.contains("1 of 5 queries failed"));
+}
+
+#[test]
+fn test_batch_files() {
+    let dir = std::env::temp_dir();
+    let input = dir.join(format!("poker_eval_batch_in_{}.jsonl", std::process::id()));
+    let output = dir.join(format!("poker_eval_batch_out_{}.jsonl", std::process::id()));
+    std::fs::write(
+        &input,
+        "{\"op\": \"eval\", \"hand\": \"AhAd\", \"board\": \"AsKdKc\"}\n",
+    )
+    .unwrap();
+
+    let (stdout, _) = batch(
+        &[
+            input.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+        ],
+        "",
+    );
+    assert!(stdout.is_empty());
+    let written = std::fs::read_to_string(&output).unwrap();
+    let _ = std::fs::remove_file(&input);
+    let _ = std::fs::remove_file(&output);
+
+    let result: Value = serde_json::from_str(written.trim()).unwrap();
+    assert_eq!(result["hi"]["description"], "FlHouse ( A K)");
+}
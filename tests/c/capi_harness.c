@@ -0,0 +1,130 @@
+/* Exercises the C API through include/poker_eval_rs.h; built and run by tests/capi.rs. */
+#include <stdio.h>
+#include <string.h>
+
+#include "poker_eval_rs.h"
+
+static int failures = 0;
+
+#define CHECK(cond)                                                        \
+  do {                                                                     \
+    if (!(cond)) {                                                         \
+      fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #cond); \
+      failures++;                                                          \
+    }                                                                      \
+  } while (0)
+
+static uint64_t mask(const char *cards) {
+  uint64_t m = 0;
+  int rc = pe_string_to_mask(cards, &m, NULL);
+  if (rc != PE_OK) {
+    fprintf(stderr, "cannot parse \"%s\": %s\n", cards, pe_strerror(rc));
+    failures++;
+  }
+  return m;
+}
+
+int main(void) {
+  uint64_t m = 0;
+  int count = 0;
+  char buf[64];
+
+  CHECK(pe_string_to_mask("As Ks Qs Js Ts", &m, &count) == PE_OK);
+  CHECK(count == 5);
+  CHECK(pe_mask_num_cards(m) == 5);
+  CHECK(pe_string_to_mask("As As", &m, NULL) == PE_ERR_INVALID_CARDS);
+  CHECK(pe_string_to_mask("Zz", &m, NULL) == PE_ERR_INVALID_INPUT);
+  CHECK(pe_string_to_mask(NULL, &m, NULL) == PE_ERR_NULL_POINTER);
+  CHECK(strcmp(pe_strerror(PE_ERR_TOO_MANY_PLAYERS), "too many players") == 0);
+
+  /* High hands */
+  uint32_t royal = pe_eval_n(mask("As Ks Qs Js Ts"));
+  uint32_t quads = pe_eval_n(mask("Ah Ad Ac As 2d 7c 9h"));
+  CHECK(royal > quads);
+  CHECK(pe_eval_n(~(uint64_t)0) == 0);
+  /* Masks outside 5 to 7 cards are not hands. */
+  CHECK(pe_eval_n(0) == 0);
+  CHECK(pe_eval_n(mask("As Ks")) == 0);
+  CHECK(pe_eval_n_detailed(mask("As Ks Qs Js Ts 9s 8s 7s")) == 0);
+  int len = pe_hand_val_to_string(pe_eval_n_detailed(mask("As Ks Qs Js Ts")), buf, sizeof buf);
+  CHECK(len > 0 && (size_t)len < sizeof buf);
+  CHECK(strncmp(buf, "StFlush", 7) == 0);
+  CHECK(pe_hand_val_to_string(royal, buf, 4) == len && strlen(buf) == 3);
+
+  /* Lows: lower is better */
+  CHECK(pe_eval_lowball(mask("Ac 2d 3h 4s 5c")) < pe_eval_lowball(mask("8c 7d 6h 4s 2c")));
+  CHECK(pe_eval_lowball8(mask("Kc Qd Jh 9s 2c")) == PE_LOW_NOTHING);
+  CHECK(pe_eval_lowball8(mask("8c 7d 6h 4s 2c")) != PE_LOW_NOTHING);
+  CHECK(pe_eval_lowball27(mask("7c 5d 4h 3s 2c")) < pe_eval_lowball27(mask("Ac 2d 3h 4s 5c")));
+  CHECK(pe_eval_lowball(mask("Ac 2d 3h")) == PE_LOW_NOTHING);
+  CHECK(pe_eval_lowball8(0) == PE_LOW_NOTHING);
+  CHECK(pe_eval_lowball27(mask("7c 5d 4h 3s")) == UINT32_MAX);
+
+  /* Omaha */
+  uint32_t hi = 0, lo = 0;
+  CHECK(pe_eval_omaha_hi(mask("As 2s 3d 4d"), mask("5s 6s 7s 8d 9d"), &hi) == PE_OK);
+  CHECK(hi != 0);
+  CHECK(pe_eval_omaha_hi_lo8(mask("As 2s 3d 4d"), mask("5s 6s 7s 8d 9d"), &hi, &lo) == PE_OK);
+  CHECK(lo != PE_LOW_NOTHING);
+  CHECK(pe_eval_omaha_hi_lo8(mask("Ks Kd Qd Qh"), mask("5s 6s 7s 8d 9d"), &hi, &lo) == PE_OK);
+  CHECK(lo == PE_LOW_NOTHING);
+  CHECK(pe_eval_omaha_hi(mask("As 2s"), mask("5s 6s 7s"), NULL) == PE_ERR_NULL_POINTER);
+
+  /* Exhaustive enumeration */
+  PeEnumResult res;
+  uint64_t pockets[2] = {mask("As Ad"), mask("Ks Kd")};
+  uint64_t flop = mask("2c 7d 9h");
+  CHECK(pe_enum_exhaustive("holdem", pockets, 2, flop, 0, &res) == PE_OK);
+  CHECK(res.sample_type == 0);
+  CHECK(res.nplayers == 2);
+  CHECK(res.nsamples == 990);
+  CHECK(res.nwinhi[0] + res.ntiehi[0] + res.nlosehi[0] == res.nsamples);
+  CHECK(res.nwinhi[0] == res.nlosehi[1]);
+  double ev = (res.ev[0] + res.ev[1]) / res.nsamples;
+  CHECK(ev > 0.999 && ev < 1.001);
+
+  uint64_t omaha[2] = {mask("As 2s 3d 4d"), mask("Ks Kd 5h 6h")};
+  CHECK(pe_enum_exhaustive("omaha8", omaha, 2, mask("7c 8c Qh Jd"), 0, &res) == PE_OK);
+  CHECK(res.nsamples == 40);
+  CHECK(res.nwinlo[0] > 0);
+
+  uint64_t same[2] = {pockets[0], pockets[0]};
+  CHECK(pe_enum_exhaustive("holdem", same, 2, flop, 0, &res) == PE_ERR_INVALID_CARDS);
+  CHECK(pe_enum_exhaustive("holdem", pockets, 2, flop, pockets[0], &res) == PE_ERR_INVALID_CARDS);
+  CHECK(pe_enum_exhaustive("chess", pockets, 2, flop, 0, &res) == PE_ERR_UNSUPPORTED_GAME);
+  /* Pockets must have the game's number of cards */
+  uint64_t short_pocket[2] = {pockets[0], mask("Ks")};
+  CHECK(pe_enum_exhaustive("holdem", short_pocket, 2, flop, 0, &res) == PE_ERR_INVALID_INPUT);
+  CHECK(pe_enum_exhaustive("omaha", pockets, 2, flop, 0, &res) == PE_ERR_INVALID_INPUT);
+  CHECK(pe_enum_sample("omaha8", pockets, 2, 0, 0, 100, NULL, &res) == PE_ERR_INVALID_INPUT);
+  CHECK(pe_enum_exhaustive("holdem", pockets, PE_MAX_PLAYERS + 1, flop, 0, &res) ==
+        PE_ERR_TOO_MANY_PLAYERS);
+
+  /* Seeded sampling is reproducible */
+  PeEnumResult a, b;
+  uint64_t seed = 42;
+  CHECK(pe_enum_sample("holdem", pockets, 2, 0, 0, 20000, &seed, &a) == PE_OK);
+  CHECK(pe_enum_sample("holdem", pockets, 2, 0, 0, 20000, &seed, &b) == PE_OK);
+  CHECK(a.sample_type == 1);
+  CHECK(a.nsamples == 20000);
+  CHECK(a.nwinhi[0] == b.nwinhi[0] && a.ntiehi[0] == b.ntiehi[0]);
+  CHECK(a.ev[0] / a.nsamples > 0.75 && a.ev[0] / a.nsamples < 0.88);
+  CHECK(pe_enum_sample("holdem", pockets, 2, 0, 0, 1000, NULL, &b) == PE_OK);
+
+  /* Range equity */
+  PeRangeEquity eq;
+  CHECK(pe_range_equity("AA", "KK", 0, 5000, &eq) == PE_OK);
+  CHECK(eq.samples == eq.wins + eq.ties + eq.losses);
+  CHECK(eq.equity > 0.75 && eq.equity < 0.90);
+  CHECK(pe_range_equity("AA", "not a range", 0, 100, &eq) == PE_ERR_INVALID_INPUT);
+  CHECK(pe_range_equity("AA", "KK", mask("2c 7d 9h Th 3s 4s"), 100, &eq) ==
+        PE_ERR_UNSUPPORTED_BOARD);
+  CHECK(pe_range_equity("AsAd", "KK", mask("As 7d 9h"), 100, &eq) == PE_ERR_INVALID_CARDS);
+
+  if (failures) {
+    fprintf(stderr, "%d check(s) failed\n", failures);
+    return 1;
+  }
+  printf("capi harness: all checks passed\n");
+  return 0;
+}
@@ -0,0 +1,113 @@
+//! Checks the header in `include/` against `src/capi.rs`, then builds
+//! `tests/c/capi_harness.c` against the shared library and that header and runs it.
+//! Needs the `capi` feature; the harness also needs a C compiler (`cc`, or `$CC`).
+#![cfg(feature = "capi")]
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// `target/<profile>/deps`, where cargo builds the cdylib next to this test binary.
+/// The copy in `target/<profile>` is only refreshed by `cargo build`.
+fn deps_dir() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    exe.parent().unwrap().to_path_buf()
+}
+
+/// Regenerates the header with cbindgen and fails if the checked-in copy differs.
+/// Set `UPDATE_HEADER=1` to rewrite it instead.
+#[test]
+fn test_header_is_current() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(root.join("cbindgen.toml")).unwrap();
+    let bindings = cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(root.join("src/capi.rs"))
+        .generate()
+        .expect("cbindgen failed on src/capi.rs");
+
+    let header = root.join("include/poker_eval_rs.h");
+    if env::var_os("UPDATE_HEADER").is_some() {
+        bindings.write_to_file(&header);
+        return;
+    }
+    let mut generated = Vec::new();
+    bindings.write(&mut generated);
+    assert!(
+        generated == fs::read(&header).unwrap(),
+        "{} is out of date; regenerate it with UPDATE_HEADER=1 cargo test --features capi --test capi",
+        header.display()
+    );
+}
+
+#[test]
+fn test_c_harness() {
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    if Command::new(&cc).arg("--version").output().is_err() {
+        eprintln!("skipping C harness: no C compiler ({cc})");
+        return;
+    }
+
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let lib_dir = deps_dir();
+    let (lib, path_var) = if cfg!(target_os = "macos") {
+        ("libpoker_eval_rs.dylib", "DYLD_LIBRARY_PATH")
+    } else if cfg!(windows) {
+        eprintln!("skipping C harness: not supported on Windows");
+        return;
+    } else {
+        ("libpoker_eval_rs.so", "LD_LIBRARY_PATH")
+    };
+    assert!(
+        lib_dir.join(lib).exists(),
+        "{} not found; the cdylib is built alongside the tests",
+        lib_dir.join(lib).display()
+    );
+
+    let exe = lib_dir.join("capi_harness");
+    let status = Command::new(&cc)
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(root.join("include"))
+        .arg(root.join("tests/c/capi_harness.c"))
+        .arg("-o")
+        .arg(&exe)
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg("-lpoker_eval_rs")
+        .status()
+        .expect("failed to run the C compiler");
+    assert!(status.success(), "C harness failed to compile");
+
+    let output = Command::new(&exe)
+        .env(path_var, &lib_dir)
+        .output()
+        .expect("failed to run the C harness");
+    assert!(
+        output.status.success(),
+        "C harness failed:\n{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_single_hand_evaluators_check_card_counts() {
+    use poker_eval_rs::capi::*;
+    use poker_eval_rs::deck::StdDeck;
+
+    let mask = |s: &str| StdDeck::string_to_mask(s).unwrap().0.as_raw();
+    assert_ne!(pe_eval_n(mask("As Ks Qs Js Ts")), 0);
+    assert_ne!(pe_eval_n(mask("Ah Ad Ac As 2d 7c 9h")), 0);
+    // An empty mask used to evaluate as quads.
+    for bad in [0, mask("As Ks"), mask("As Ks Qs Js Ts 9s 8s 7s")] {
+        assert_eq!(pe_eval_n(bad), 0);
+        assert_eq!(pe_eval_n_detailed(bad), 0);
+        assert_eq!(pe_eval_lowball(bad), PE_LOW_NOTHING);
+        assert_eq!(pe_eval_lowball8(bad), PE_LOW_NOTHING);
+        assert_eq!(pe_eval_lowball27(bad), u32::MAX);
+    }
+}
@@ -0,0 +1,238 @@
+use poker_eval_rs::deck::{Rank, StdDeck, StdDeckCardMask, STD_DECK_N_CARDS};
+use poker_eval_rs::enumdefs::{EnumResult, Game};
+use poker_eval_rs::enumerate::{
+    enum_exhaustive, enum_rules_exhaustive, enum_rules_sample, enum_sample,
+};
+use poker_eval_rs::errors::PokerError;
+use poker_eval_rs::evaluators::Eval;
+use poker_eval_rs::game_rules::{
+    game_rules, register_game, registered_games, EnumOrderingMode, GameRules,
+};
+use poker_eval_rs::handval::HandVal;
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::sync::Arc;
+
+/// Hold'em dealt from the tens through the aces only.
+struct RoyalHoldem;
+
+impl GameRules for RoyalHoldem {
+    fn name(&self) -> &str {
+        "royal"
+    }
+
+    fn description(&self) -> &str {
+        "Royal Hold'em"
+    }
+
+    fn deck(&self) -> StdDeckCardMask {
+        (0..STD_DECK_N_CARDS)
+            .filter(|&c| StdDeck::rank(c) >= Rank::TEN)
+            .fold(StdDeckCardMask::new(), |deck, c| {
+                deck | StdDeckCardMask::from_card_index(c)
+            })
+    }
+
+    fn pocket_cards(&self) -> (usize, usize) {
+        (2, 2)
+    }
+
+    fn board_cards(&self) -> usize {
+        5
+    }
+
+    fn ordering_mode(&self) -> EnumOrderingMode {
+        EnumOrderingMode::Hi
+    }
+
+    fn eval_hi(
+        &self,
+        pocket: &StdDeckCardMask,
+        board: &StdDeckCardMask,
+    ) -> Result<Option<HandVal>, PokerError> {
+        let all = *pocket | *board;
+        Ok(Some(Eval::eval_n(&all, all.num_cards())))
+    }
+}
+
+fn masks(hands: &[&str]) -> Vec<StdDeckCardMask> {
+    hands
+        .iter()
+        .map(|h| StdDeck::string_to_mask(h).unwrap().0)
+        .collect()
+}
+
+/// Who won, tied and lost each half of the pot, per player.
+fn outcomes(result: &EnumResult) -> Vec<[u32; 7]> {
+    (0..result.nplayers as usize)
+        .map(|i| {
+            [
+                result.nwinhi[i],
+                result.ntiehi[i],
+                result.nlosehi[i],
+                result.nwinlo[i],
+                result.ntielo[i],
+                result.nloselo[i],
+                result.nscoop[i],
+            ]
+        })
+        .collect()
+}
+
+/// Runs `game` through its specialised enumerator and the generic engine and checks
+/// that both agree on every counter.
+fn assert_paths_agree(game: Game, pockets: &[StdDeckCardMask], board: StdDeckCardMask) {
+    let dead = StdDeckCardMask::new();
+    let (n, nboard) = (pockets.len(), board.num_cards());
+    let mut fast = EnumResult::new(game);
+    enum_exhaustive(game, pockets, board, dead, n, nboard, false, &mut fast).unwrap();
+    let mut generic = EnumResult::new(game);
+    enum_rules_exhaustive(&game, pockets, board, dead, &mut generic).unwrap();
+
+    let hands: Vec<String> = pockets.iter().map(|p| p.to_string()).collect();
+    let deal = format!("{:?} {:?} on {}", game, hands, board);
+    assert_eq!(fast.nsamples, generic.nsamples, "{}", deal);
+    assert_eq!(outcomes(&fast), outcomes(&generic), "{}", deal);
+    assert_eq!(fast.nsharehi, generic.nsharehi, "{}", deal);
+    assert_eq!(fast.nsharelo, generic.nsharelo, "{}", deal);
+    assert_eq!(fast.nhandtype, generic.nhandtype, "{}", deal);
+    for i in 0..n {
+        assert!((fast.ev[i] - generic.ev[i]).abs() < 1e-9, "{}", deal);
+    }
+}
+
+/// Deals `players` pockets and `nboard` board cards at random from the game's deck.
+fn random_deal(
+    game: Game,
+    players: usize,
+    nboard: usize,
+    rng: &mut SmallRng,
+) -> (Vec<StdDeckCardMask>, StdDeckCardMask) {
+    let deck = game.deck();
+    let mut cards: Vec<usize> = (0..STD_DECK_N_CARDS)
+        .filter(|&c| deck.card_is_set(c))
+        .collect();
+    let pocket = game.showdown_pocket_cards();
+    let (dealt, _) = cards.partial_shuffle(rng, players * pocket + nboard);
+    let mask = |cards: &[usize]| {
+        cards.iter().fold(StdDeckCardMask::new(), |m, &c| {
+            m | StdDeckCardMask::from_card_index(c)
+        })
+    };
+    let pockets = dealt[..players * pocket].chunks(pocket).map(mask).collect();
+    (pockets, mask(&dealt[players * pocket..]))
+}
+
+#[test]
+fn test_builtin_enumerators_match_generic_engine_hand_by_hand() {
+    let mut rng = SmallRng::seed_from_u64(42);
+    for game in [Game::Holdem, Game::Holdem8, Game::Omaha, Game::ShortDeck] {
+        // Complete deals: both paths score exactly one showdown.
+        for _ in 0..300 {
+            let (pockets, board) = random_deal(game, 3, 5, &mut rng);
+            assert_paths_agree(game, &pockets, board);
+        }
+        // And every turn and river from a few flops.
+        for players in [2, 3] {
+            let (pockets, board) = random_deal(game, players, 3, &mut rng);
+            assert_paths_agree(game, &pockets, board);
+        }
+    }
+}
+
+#[test]
+fn test_custom_game_through_registry() {
+    register_game(Arc::new(RoyalHoldem)).unwrap();
+    assert!(registered_games().contains(&"royal".to_string()));
+    let rules = game_rules("Royal").unwrap();
+    assert_eq!(rules.game(), None);
+
+    let pockets = masks(&["AhAd", "KhKd"]);
+    let empty = StdDeckCardMask::new();
+    let mut result = EnumResult::new(Game::Holdem);
+    enum_rules_exhaustive(rules.as_ref(), &pockets, empty, empty, &mut result).unwrap();
+    // 16 live cards, C(16, 5) boards.
+    assert_eq!(result.nsamples, 4368);
+    assert_eq!(
+        result.nwinhi[0] + result.ntiehi[0] + result.nlosehi[0],
+        result.nsamples
+    );
+    assert!(result.nwinhi[0] > result.nwinhi[1]);
+
+    // Cards outside the deck are rejected.
+    let low = masks(&["AhAd", "2c2d"]);
+    assert!(matches!(
+        enum_rules_exhaustive(rules.as_ref(), &low, empty, empty, &mut result),
+        Err(PokerError::InvalidCardConfiguration(_))
+    ));
+
+    let mut sampled = EnumResult::new(Game::Holdem);
+    sampled.seed = Some(11);
+    enum_rules_sample(rules.as_ref(), &pockets, empty, empty, 500, &mut sampled).unwrap();
+    assert_eq!(sampled.nsamples, 500);
+}
+
+#[test]
+fn test_exhaustive_falls_back_to_generic_engine() {
+    let pockets = masks(&["As2sKdKc", "QhQd3c4c"]);
+    let (board, nboard) = StdDeck::string_to_mask("5h7d8s9c").unwrap();
+    let dead = StdDeckCardMask::new();
+
+    let mut result = EnumResult::new(Game::Omaha8);
+    enum_exhaustive(
+        Game::Omaha8,
+        &pockets,
+        board,
+        dead,
+        2,
+        nboard,
+        false,
+        &mut result,
+    )
+    .unwrap();
+    assert_eq!(result.game, Game::Omaha8);
+    assert_eq!(result.nsamples, 40);
+
+    // Each river scored by the specialised Omaha hi/lo sampler adds up to the
+    // generic engine's counts.
+    let mut by_river = vec![[0u32; 7]; 2];
+    let mut ev = [0.0; 2];
+    for river in (0..STD_DECK_N_CARDS)
+        .filter(|&c| !board.card_is_set(c) && !pockets.iter().any(|p| p.card_is_set(c)))
+    {
+        let full = board | StdDeckCardMask::from_card_index(river);
+        let mut one = EnumResult::new(Game::Omaha8);
+        enum_sample(Game::Omaha8, &pockets, full, dead, 2, 5, 1, false, &mut one).unwrap();
+        for (total, counts) in by_river.iter_mut().zip(outcomes(&one)) {
+            for (t, c) in total.iter_mut().zip(counts) {
+                *t += c;
+            }
+        }
+        ev[0] += one.ev[0];
+        ev[1] += one.ev[1];
+    }
+    assert_eq!(outcomes(&result), by_river);
+    assert!((result.ev[0] - ev[0]).abs() < 1e-9 && (result.ev[1] - ev[1]).abs() < 1e-9);
+
+    // The ordering histogram allocated for the run survives it.
+    enum_exhaustive(
+        Game::Omaha8,
+        &pockets,
+        board,
+        dead,
+        2,
+        nboard,
+        true,
+        &mut result,
+    )
+    .unwrap();
+    let ordering = result.ordering.as_ref().unwrap();
+    assert_eq!(ordering.nplayers, 2);
+    assert_eq!(ordering.mode, EnumOrderingMode::Hilo);
+
+    // Games without a board still need the specialised samplers.
+    let stud = masks(&["AsAdKc", "QsQdJc"]);
+    let empty = StdDeckCardMask::new();
+    assert!(enum_exhaustive(Game::Stud7, &stud, empty, dead, 2, 0, false, &mut result).is_err());
+}
@@ -1,7 +1,8 @@
 use poker_eval_rs::deck::{StdDeck, StdDeckCardMask};
 use poker_eval_rs::enumdefs::{EnumResult, Game};
-use poker_eval_rs::enumerate::enum_exhaustive;
+use poker_eval_rs::enumerate::{enum_chip_ev, enum_exhaustive, enum_sample};
 use poker_eval_rs::evaluators::Eval;
+use poker_eval_rs::rules::HandType;
 
 #[test]
 fn test_holdem_royal_flush() {
@@ -130,3 +131,147 @@ fn test_unsupported_board_config() {
 
     assert!(res.is_err());
 }
+
+#[test]
+fn test_hand_type_distribution_exhaustive() {
+    // AsKs vs QdQc on Qs 7s 2h 3d: 44 rivers.
+    let (p1, _) = StdDeck::string_to_mask("AsKs").unwrap();
+    let (p2, _) = StdDeck::string_to_mask("QdQc").unwrap();
+    let (board, _) = StdDeck::string_to_mask("Qs7s2h3d").unwrap();
+    let mut result = EnumResult::new(Game::Holdem);
+    enum_exhaustive(
+        Game::Holdem,
+        &[p1, p2],
+        board,
+        StdDeckCardMask::new(),
+        2,
+        4,
+        false,
+        &mut result,
+    )
+    .unwrap();
+    assert_eq!(result.nsamples, 44);
+
+    for p in 0..2 {
+        assert_eq!(result.nhandtype[p].iter().sum::<u32>(), 44);
+        assert_eq!(result.nwinhandtype[p].iter().sum::<u32>(), result.nwinhi[p]);
+        let losses: u32 = result.nlosehandtype[p].iter().flatten().sum();
+        assert_eq!(losses, result.nlosehi[p]);
+    }
+    // Nine spades make AsKs a flush; 2s and 3s also fill up the queens.
+    assert_eq!(result.nhandtype[0][HandType::Flush.as_usize()], 9);
+    assert_eq!(result.nwinhandtype[0][HandType::Flush.as_usize()], 7);
+    assert_eq!(
+        result.nlosehandtype[0][HandType::Flush.as_usize()][HandType::FullHouse.as_usize()],
+        2
+    );
+    assert!((result.hand_type_probability(1, HandType::Trips) - 34.0 / 44.0).abs() < 1e-12);
+    assert!(result.lose_with_probability(0, HandType::NoPair, HandType::Trips) > 0.0);
+}
+
+#[test]
+fn test_hand_type_distribution_short_deck() {
+    // Short Deck stores flushes and full houses swapped; the distribution does not.
+    let (p1, _) = StdDeck::string_to_mask("AhKh").unwrap();
+    let (p2, _) = StdDeck::string_to_mask("9c9d").unwrap();
+    let (board, _) = StdDeck::string_to_mask("QhJh6h").unwrap();
+    let mut result = EnumResult::new(Game::ShortDeck);
+    enum_sample(
+        Game::ShortDeck,
+        &[p1, p2],
+        board,
+        StdDeckCardMask::new(),
+        2,
+        3,
+        2000,
+        false,
+        &mut result,
+    )
+    .unwrap();
+    assert_eq!(result.game, Game::ShortDeck);
+    assert!(result.win_with_probability(0, HandType::Flush) > 0.9);
+    assert_eq!(result.nhandtype[0][HandType::FullHouse.as_usize()], 0);
+}
+
+#[test]
+fn test_chip_ev_matches_equity_heads_up() {
+    // With equal stacks, chip EV is just pot share times the pot.
+    let (p1, _) = StdDeck::string_to_mask("AhKh").unwrap();
+    let (p2, _) = StdDeck::string_to_mask("3c2d").unwrap();
+    let (board, _) = StdDeck::string_to_mask("Qh4s5d9h").unwrap();
+    let mut result = EnumResult::new(Game::Holdem);
+    enum_exhaustive(
+        Game::Holdem,
+        &[p1, p2],
+        board,
+        StdDeckCardMask::new(),
+        2,
+        4,
+        false,
+        &mut result,
+    )
+    .unwrap();
+    let chip_ev = |game| {
+        enum_chip_ev(
+            game,
+            &[p1, p2],
+            board,
+            StdDeckCardMask::new(),
+            &[100, 100],
+            None,
+        )
+        .unwrap()
+    };
+    let holdem = chip_ev(Game::Holdem);
+    for (chips, ev) in holdem.iter().zip(&result.ev) {
+        assert!((chips - ev / result.nsamples as f64 * 200.0).abs() < 1e-9);
+    }
+
+    // In Hi/Lo the wheel draw also plays for half the pot on every low river.
+    let hilo = chip_ev(Game::Holdem8);
+    assert!((hilo.iter().sum::<f64>() - 200.0).abs() < 1e-9);
+    assert!(hilo[1] > holdem[1]);
+}
+
+#[test]
+fn test_chip_ev_short_stack_side_pot() {
+    // The short stack can only win the main pot, whatever the runout.
+    let (p1, _) = StdDeck::string_to_mask("AsAc").unwrap();
+    let (p2, _) = StdDeck::string_to_mask("KsKc").unwrap();
+    let (p3, _) = StdDeck::string_to_mask("7d6d").unwrap();
+    let (board, _) = StdDeck::string_to_mask("2h8d9s").unwrap();
+    let contributions = [50, 200, 200];
+    let exact = enum_chip_ev(
+        Game::Holdem,
+        &[p1, p2, p3],
+        board,
+        StdDeckCardMask::new(),
+        &contributions,
+        None,
+    )
+    .unwrap();
+    assert!((exact.iter().sum::<f64>() - 450.0).abs() < 1e-9);
+    assert!(exact[0] <= 150.0);
+
+    let sampled = enum_chip_ev(
+        Game::Holdem,
+        &[p1, p2, p3],
+        board,
+        StdDeckCardMask::new(),
+        &contributions,
+        Some(20_000),
+    )
+    .unwrap();
+    for (s, e) in sampled.iter().zip(&exact) {
+        assert!((s - e).abs() < 10.0);
+    }
+    assert!(enum_chip_ev(
+        Game::Holdem,
+        &[p1, p2],
+        board,
+        StdDeckCardMask::new(),
+        &contributions,
+        None,
+    )
+    .is_err());
+}
@@ -0,0 +1,37 @@
+//! The `outs` subcommand.
+
+use serde_json::Value;
+use std::process::Command;
+
+fn outs_json(args: &[&str]) -> Value {
+    let out = Command::new(env!("CARGO_BIN_EXE_poker_eval_rs_cli"))
+        .arg("outs")
+        .args(args)
+        .arg("--json")
+        .output()
+        .expect("failed to run the CLI");
+    assert!(
+        out.status.success(),
+        "outs {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&out.stderr)
+    );
+    serde_json::from_slice(&out.stdout).unwrap()
+}
+
+#[test]
+fn test_outs_named_like_the_library() {
+    let names = |reply: &Value| -> Vec<String> {
+        reply["outs_by_type"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|pair| pair[0].as_str().unwrap().to_string())
+            .collect()
+    };
+    let reply = outs_json(&["AsJs", "--board", "Ks7s2d"]);
+    assert_eq!(names(&reply), ["Flush", "Pair"]);
+    assert_eq!(reply["outs_by_type"][0][1].as_array().unwrap().len(), 9);
+    let reply = outs_json(&["7h7d", "--board", "Ks7s2d"]);
+    assert_eq!(names(&reply), ["Quads", "FullHouse"]);
+}
@@ -0,0 +1,147 @@
+//! The `pokenum` subcommand: legacy argument grammar and output format.
+
+use std::process::{Command, Output};
+
+fn pokenum(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_poker_eval_rs_cli"))
+        .arg("pokenum")
+        .args(args)
+        .output()
+        .expect("failed to run the CLI")
+}
+
+fn stdout(args: &[&str]) -> String {
+    let out = pokenum(args);
+    assert!(
+        out.status.success(),
+        "pokenum {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&out.stderr)
+    );
+    String::from_utf8(out.stdout).unwrap()
+}
+
+#[test]
+fn test_pokenum_holdem_output() {
+    let out = stdout(&[
+        "-h", "As", "Ad", "-", "Kh", "Kd", "--", "2c", "3c", "4c", "/", "5d",
+    ]);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(
+        lines[0],
+        "Holdem Hi: 946 enumerated boards containing 2c 3c 4c"
+    );
+    assert_eq!(
+        lines[1],
+        "cards     win   %win      lose  %lose       tie   %tie        EV"
+    );
+    assert_eq!(
+        lines[2],
+        "Ad As     817  86.36        68   7.19        61   6.45     0.896"
+    );
+    assert_eq!(lines.len(), 4);
+
+    // Cards may also be run together.
+    assert_eq!(
+        stdout(&["-h", "AsAd", "-", "KhKd", "--", "2c3c4c", "/", "5d"]),
+        out
+    );
+}
+
+/// The win, lose and tie counts and the EV of each player row.
+fn player_rows(out: &str) -> Vec<(u32, u32, u32, f64)> {
+    out.lines()
+        .skip(2)
+        .map(|l| {
+            let cols: Vec<&str> = l.split_whitespace().collect();
+            let n = cols.len();
+            let count = |i: usize| cols[n - i].parse::<u32>().unwrap();
+            (count(7), count(5), count(3), cols[n - 1].parse().unwrap())
+        })
+        .collect()
+}
+
+#[test]
+fn test_pokenum_multiway_counts_boards() {
+    // Each board counts once per player: a win only for the sole best hand.
+    let out = stdout(&[
+        "-h", "As", "Ad", "-", "Kh", "Kd", "-", "7c", "7d", "--", "2c", "3c", "4c",
+    ]);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(
+        lines[0],
+        "Holdem Hi: 903 enumerated boards containing 2c 3c 4c"
+    );
+    assert_eq!(
+        lines[2..],
+        [
+            "Ad As     475  52.60       424  46.95         4   0.44     0.528",
+            "Kh Kd      39   4.32       860  95.24         4   0.44     0.045",
+            "7d 7c     385  42.64       514  56.92         4   0.44     0.428",
+        ]
+    );
+
+    let out = stdout(&["-h", "AsAd", "-", "KhKd", "-", "7c7d"]);
+    assert_eq!(
+        out.lines().nth(2).unwrap(),
+        "Ad As  885306  64.59    482274  35.18      3174   0.23     0.647"
+    );
+    for (win, lose, tie, _) in player_rows(&out) {
+        assert_eq!(win + lose + tie, 1370754);
+    }
+
+    let out = stdout(&[
+        "-mc", "20000", "-h", "AsAd", "-", "KhKd", "-", "7c7d", "--", "2c3c4c",
+    ]);
+    let rows = player_rows(&out);
+    for &(win, lose, tie, _) in &rows {
+        assert_eq!(win + lose + tie, 20000);
+    }
+    let ev: f64 = rows.iter().map(|r| r.3).sum();
+    assert!((ev - 1.0).abs() < 0.002, "EVs sum to {}", ev);
+    assert!((rows[0].0 as f64 / 20000.0 - 0.526).abs() < 0.02);
+}
+
+#[test]
+fn test_pokenum_hilo_sampling_and_ordering() {
+    let out = stdout(&[
+        "-mc", "2000", "-o8", "As2s3d4d", "-", "KsKd5h6h", "--", "7c8cQh",
+    ]);
+    assert!(out.starts_with("Omaha Hi/Low 8-or-better: 2000 sampled boards containing"));
+    assert!(out.lines().nth(1).unwrap().contains("scoop     HIwin"));
+
+    let out = stdout(&["-O", "-h", "AsAd", "-", "KhKd", "-", "7c7d", "--", "2c3c4c"]);
+    let hist: Vec<&str> = out
+        .lines()
+        .skip_while(|l| *l != "Histogram of relative hand ranks:")
+        .skip(2)
+        .collect();
+    let total: u32 = hist
+        .iter()
+        .map(|l| l.split_whitespace().last().unwrap().parse::<u32>().unwrap())
+        .sum();
+    assert_eq!(total, 903);
+
+    let out = stdout(&[
+        "-t", "-O", "-o8", "As2s3d4d", "-", "KsKd5h6h", "--", "7c8cQh",
+    ]);
+    let lines: Vec<&str> = out.lines().collect();
+    assert!(lines[0].starts_with("EV 2: "));
+    assert!(lines[1].starts_with("ORD 3 2:"));
+    assert!(lines[1].contains(" 2  1  1 NQ"));
+}
+
+#[test]
+fn test_pokenum_usage_errors() {
+    for args in [
+        &[][..],
+        &["-h", "As", "Ad", "-", "As", "Kd"][..],
+        &["-x", "AsAd", "-", "KhKd"][..],
+        &["-o", "AsAd", "-", "KhKd"][..],
+        &["-mc", "0", "-h", "AsAd", "-", "KhKd"][..],
+    ] {
+        let out = pokenum(args);
+        assert_eq!(out.status.code(), Some(1), "{:?}", args);
+        assert!(String::from_utf8_lossy(&out.stderr).contains("usage: pokenum"));
+    }
+}
@@ -88,3 +88,24 @@ proptest! {
         }
     }
 }
+
+proptest! {
+    // eval_n_detailed must rank hands exactly like eval_n
+    #[test]
+    fn test_detailed_eval_matches_eval_n_ordering(
+        cards1 in proptest::collection::vec(0u8..52, 5..=7),
+        cards2 in proptest::collection::vec(0u8..52, 5..=7)
+    ) {
+        let m1 = mask_from_indices(&cards1);
+        let m2 = mask_from_indices(&cards2);
+        if m1.num_cards() >= 5 && m2.num_cards() >= 5 {
+            let fast1 = Eval::eval_n(&m1, m1.num_cards());
+            let fast2 = Eval::eval_n(&m2, m2.num_cards());
+            let slow1 = Eval::eval_n_detailed(&m1, m1.num_cards());
+            let slow2 = Eval::eval_n_detailed(&m2, m2.num_cards());
+
+            prop_assert_eq!(fast1.hand_type(), slow1.hand_type());
+            prop_assert_eq!(fast1.value.cmp(&fast2.value), slow1.value.cmp(&slow2.value));
+        }
+    }
+}
@@ -14,14 +14,69 @@ def test_string_to_mask():
 def test_eval_n():
     hand = "As Ks Qs Js Ts"
     val = poker_eval_rs.eval_n(hand)
-    assert "Straight Flush" in val
+    assert val.hand_type == "StFlush"
+    assert val.ranks == ["A"]
+    assert str(val) == "StFlush ( A)"
+
+def test_hand_val_comparison():
+    quads = poker_eval_rs.eval_n("Ah Ad Ac As 2d")
+    boat = poker_eval_rs.eval_n("Kh Kd Kc As Ad")
+    assert quads > boat
+    assert boat.ranks == ["K", "A"]
+    assert quads == poker_eval_rs.eval_n("Ah Ad Ac As 2c")
+    assert max([boat, quads]) is quads
+    with pytest.raises(TypeError):
+        quads < "Quads"
+
+def test_low_hand_val():
+    wheel = poker_eval_rs.eval_low_func("Ac 2d 3h 4s 5c")
+    eight = poker_eval_rs.eval_low_func("8c 7d 6h 4s 2c")
+    assert wheel > eight
+    assert wheel.ranks == ["5", "4", "3", "2", "A"]
+    assert wheel.hand_type == "NoPair"
 
 def test_batch_eval():
     hands = ["As Ks Qs Js Ts", "2h 3h 4h 5h 7h"]
     results = poker_eval_rs.eval_n_batch(hands)
     assert len(results) == 2
-    assert "Straight Flush" in results[0]
-    assert "Flush" in results[1]
+    assert results[0].hand_type == "StFlush"
+    assert results[1].hand_type == "Flush"
+    assert results[0] > results[1]
+    with pytest.raises(ValueError):
+        poker_eval_rs.eval_n_batch(["As Ks Qs Js Ts", "2h 3h"])
+
+def test_numpy_batch_eval():
+    np = pytest.importorskip("numpy")
+    # Card index = suit * 13 + rank: As Ks Qs Js Ts and 2h 3h 4h 5h 7h
+    cards = np.array([[51, 50, 49, 48, 47], [0, 1, 2, 3, 5]], dtype=np.uint8)
+    masks = poker_eval_rs.cards_to_masks(cards)
+    assert masks.dtype == np.uint64
+    vals = poker_eval_rs.eval_n_masks(masks)
+    assert vals.dtype == np.uint32
+    assert vals[0] == int(poker_eval_rs.eval_n("As Ks Qs Js Ts"))
+    assert vals[1] == int(poker_eval_rs.eval_n("2h 3h 4h 5h 7h"))
+    lows = poker_eval_rs.eval_low_masks(masks)
+    assert lows[1] == int(poker_eval_rs.eval_low_func("2h 3h 4h 5h 7h"))
+    # Raw low values are lower-is-better: 7-5 low beats king-high.
+    assert lows[1] < lows[0]
+    with pytest.raises(ValueError):
+        poker_eval_rs.cards_to_masks(np.array([[0, 0]], dtype=np.uint8))
+    # An empty mask is not a hand; the error names its row.
+    with pytest.raises(ValueError, match="Hand 1 has 0 cards"):
+        poker_eval_rs.eval_n_masks(np.array([masks[0], 0], dtype=np.uint64))
+
+def test_numpy_omaha_batch_eval():
+    np = pytest.importorskip("numpy")
+    to_mask = lambda s: int(poker_eval_rs.string_to_mask(s), 2)
+    holes = np.array([to_mask("As Ks Qd Jd"), to_mask("Ac 2c 3d 4h")], dtype=np.uint64)
+    boards = np.array([to_mask("Ts 9s 2h 3c 4d"), to_mask("5s 6s 8h Kc Qc")], dtype=np.uint64)
+    his = poker_eval_rs.eval_omaha_hi_masks(holes, boards)
+    assert his[0] == int(poker_eval_rs.eval_omaha_hi("As Ks Qd Jd", "Ts 9s 2h 3c 4d"))
+    hi, lo = poker_eval_rs.eval_omaha_hi_lo_masks(holes, boards)
+    assert (hi == his).all()
+    assert lo[1] == int(poker_eval_rs.eval_omaha_hi_lo("Ac 2c 3d 4h", "5s 6s 8h Kc Qc")[1])
+    with pytest.raises(ValueError):
+        poker_eval_rs.eval_omaha_hi_masks(holes[:1], boards)
 
 def test_omaha_hi():
     hole = "As Ks Qd Jd"
@@ -38,8 +93,8 @@ def test_omaha_hi():
     # Pairs? No.
     # High card Ace.
     val = poker_eval_rs.eval_omaha_hi(hole, board)
-    print(val)
-    assert "High Card" in val or "Pair" in val # Example check
+    assert val.hand_type == "NoPair"
+    assert val.ranks == ["A", "K", "T", "9", "4"]
 
 def test_omaha_hi_lo():
     hole = "As 2s 3d 4d"
@@ -49,8 +104,12 @@ def test_omaha_hi_lo():
     # Hole: A, 2. Board: 5, 6, 7. -> A-2-5-6-7. Valid 8-low.
     # Result is tuple (hi, lo)
     hi, lo = poker_eval_rs.eval_omaha_hi_lo(hole, board)
-    assert hi != "No valid hand"
-    assert lo != "No valid low"
+    assert hi.hand_type == "Flush"
+    assert lo.hand_type == "NoPair"
+    _, a2_low = poker_eval_rs.eval_omaha_hi_lo("As 2s Kd Kh", board)
+    assert a2_low.ranks == ["7", "6", "5", "2", "A"]
+    _, no_low = poker_eval_rs.eval_omaha_hi_lo("Ks Kd Qd Qh", board)
+    assert no_low is None
 
 def test_duplicates():
     with pytest.raises(ValueError):
@@ -59,25 +118,114 @@ def test_duplicates():
 def test_equity_holdem():
     hands = ["As Ks", "2h 2d"]
     res = poker_eval_rs.calculate_equity(hands, game="holdem", monte_carlo=True, iterations=1000)
-    assert "players" in res
-    assert len(res["players"]) == 2
+    assert len(res.players) == 2
+    assert res.mode == "monte_carlo"
+    assert res.samples == 1000
+    assert res[0].hand == "As Ks"
+    assert res.to_dict()["players"][1]["hand"] == "2h 2d"
 
 def test_equity_omaha_hi_lo():
     hands = ["As 2s 3d 4d", "Ks Kd 5h 6h"]
     res = poker_eval_rs.calculate_equity(hands, game="omaha8", monte_carlo=True, iterations=1000)
-    assert "players" in res
-    p1 = res["players"][0]
-    # Check for scoop/lo keys
-    assert "scoop" in p1
-    assert "win_lo" in p1
+    p1 = res.players[0]
+    assert p1.scoop is not None
+    assert p1.win_lo is not None
+    assert "win_lo" in p1.to_dict()
+
+def test_equity_records():
+    res = poker_eval_rs.calculate_equity(["AsAd", "KsKd"], board="2c7d9h")
+    records = res.to_records()
+    assert [r["player"] for r in records] == [0, 1]
+    # Every record has the same keys, so they load straight into a DataFrame.
+    assert records[0].keys() == records[1].keys()
+    assert records[0]["scoop"] is None
+    assert abs(records[0]["win"] - 91.62) < 0.01
+
+def test_equity_progress_and_cancel():
+    calls = []
+    res = poker_eval_rs.calculate_equity(
+        ["AsAd", "KsKd"], board="2c7d9h", progress=lambda done, total: calls.append((done, total))
+    )
+    assert calls[-1] == (990, 990)
+    assert abs(res[0].win - 91.62) < 0.01
+
+    token = poker_eval_rs.CancelToken()
+    with pytest.raises(poker_eval_rs.CalculationCancelled):
+        poker_eval_rs.calculate_equity(
+            ["AsAd", "KsKd"], progress=lambda done, total: token.cancel(), cancel=token
+        )
+    assert token.cancelled
+
+def test_equity_async():
+    import asyncio
+
+    async def run():
+        res = await poker_eval_rs.calculate_equity_async(["AsAd", "KsKd"], board="2c7d9h")
+        future = poker_eval_rs.calculate_equity_async(["AsAd", "KsKd"])
+        future.cancel()
+        with pytest.raises(asyncio.CancelledError):
+            await future
+        return res
+
+    res = asyncio.run(run())
+    assert res.samples == 990
 
 def test_equity_probability_sum():
     """Validate that win + tie + lose probabilities sum to ~100% for each player."""
     hands = ["As Ks", "2h 2d"]
     res = poker_eval_rs.calculate_equity(hands, game="holdem", monte_carlo=True, iterations=10000)
-    assert "players" in res
-    for i, p in enumerate(res["players"]):
-        total = p["win"] + p["tie"] + p["lose"]
+    for i, p in enumerate(res.players):
+        total = p.win + p.tie + p.lose
         assert abs(total - 100.0) < 1.0, (
             f"Player {i}: probabilities sum to {total}, expected ~100"
         )
+
+def test_outs_vs_opponent():
+    res = poker_eval_rs.calculate_outs_vs("As Js", "Ks 7s 2d", "Kd Qc")
+    # 9 spades + 3 aces win outright against top pair
+    assert len(res["clean_outs"]) == 12
+    assert res["discounted_outs"] == 12.0
+    assert "Jh" in res["tainted_outs"]
+
+def test_outs_skip_dead_cards():
+    assert len(poker_eval_rs.calculate_outs("2s 3s", "As Ks 9d")["Flush"]) == 9
+    res = poker_eval_rs.calculate_outs("2s 3s", "As Ks 9d", dead="Qs Js")
+    assert len(res["Flush"]) == 7
+
+def test_game_outs_omaha8():
+    res = poker_eval_rs.calculate_game_outs("Ah 2h Ks Kd", "7c 5d Qs", game="omaha8")
+    assert len(res["lo"]) == 16
+    assert res["counterfeits"] == []
+    assert "Trips" in res["hi"]
+
+def test_equity_vs_random():
+    res = poker_eval_rs.calculate_equity_vs_random("AhKc", 1, board="Qd Js Tc 4h 2d")
+    assert res["exact"]
+    assert res["samples"] == 990
+    assert res["ties"] == 9
+    res = poker_eval_rs.calculate_equity_vs_random("As Ad", opponents=4, iterations=5000)
+    assert not res["exact"]
+    assert 0.50 < res["equity"] < 0.62
+
+def test_range_and_vs_random_progress_and_cancel():
+    calls = []
+    res = poker_eval_rs.calculate_equity_vs_random(
+        "AhKc", board="Qd Js Tc", progress=lambda done, total: calls.append((done, total))
+    )
+    assert res["exact"]
+    assert calls[-1] == (res["samples"], res["samples"])
+
+    calls = []
+    aa, kk = poker_eval_rs.HandRange("AA"), poker_eval_rs.HandRange("KK")
+    res = poker_eval_rs.calculate_range_equity(
+        aa, kk, iterations=120000, progress=lambda done, total: calls.append((done, total))
+    )
+    assert calls == [(50000, 120000), (100000, 120000), (120000, 120000)]
+    assert 0.78 < res["equity"] < 0.86
+
+    token = poker_eval_rs.CancelToken()
+    token.cancel()
+    with pytest.raises(poker_eval_rs.CalculationCancelled):
+        poker_eval_rs.calculate_range_equity(aa, kk, cancel=token)
+    with pytest.raises(poker_eval_rs.CalculationCancelled):
+        poker_eval_rs.calculate_equity_vs_random("As Ad", opponents=2, cancel=token)
@@ -43,11 +43,12 @@ class TestPokerEval(unittest.TestCase):
     def test_eval_n(self):
         # Royal Flush
         res = poker_eval_rs.eval_n("As Ks Qs Js Ts")
-        self.assertEqual(res, "StFlush")
+        self.assertEqual(res.hand_type, "StFlush")
         
         # Pairs
         res = poker_eval_rs.eval_n("As Ad 2d 3c 4s")
-        self.assertEqual(res, "Pair")
+        self.assertEqual(res.hand_type, "OnePair")
+        self.assertEqual(res.ranks, ["A", "4", "3", "2"])
 
     def test_board_texture(self):
         # Rainbow
@@ -85,22 +86,22 @@ class TestPokerEval(unittest.TestCase):
         range1 = poker_eval_rs.HandRange("AA")
         range2 = poker_eval_rs.HandRange("KK")
         res = poker_eval_rs.calculate_range_equity(range1, range2, "", 1000)
-        self.assertTrue(res["equity"] > 80.0) # AA is approx 82% vs KK
+        self.assertTrue(res["equity"] > 0.80) # AA is approx 82% vs KK
 
     def test_calculate_equity(self):
         # AA vs KK
         res = poker_eval_rs.calculate_equity(["AsAc", "KsKc"], "", "", "holdem", True, 10000)
-        players = res["players"]
+        players = res.players
         self.assertEqual(len(players), 2)
         
         # Check that probabilities sum to 100%
         for p in players:
-            total_prob = p["win"] + p["tie"] + p["lose"]
+            total_prob = p.win + p.tie + p.lose
             self.assertTrue(abs(total_prob - 100.0) < 0.01, f"Probabilities should sum to 100, got {total_prob}")
             
         # AA should be favorite (approx 82%)
-        aa_stats = next(p for p in players if "AsAc" in p["hand"])
-        self.assertTrue(aa_stats["win"] > 80.0)
+        aa_stats = next(p for p in players if "AsAc" in p.hand)
+        self.assertTrue(aa_stats.win > 80.0)
 
 if __name__ == '__main__':
     unittest.main()
@@ -1,7 +1,25 @@
+use poker_eval_rs::deck::StdDeckCardMask;
 use poker_eval_rs::range::HandRange;
 use proptest::prelude::*;
 use std::str::FromStr;
 
+const RANKS: &[u8] = b"23456789TJQKA";
+
+/// Hands and weight bits, sorted, for order-insensitive comparison.
+fn sorted_hands(range: &HandRange) -> Vec<(u64, u64)> {
+    let mut hands: Vec<(u64, u64)> = range
+        .hands()
+        .iter()
+        .map(|(h, w)| (h.as_raw(), w.to_bits()))
+        .collect();
+    hands.sort_unstable();
+    hands
+}
+
+fn weight_strategy() -> impl Strategy<Value = f64> {
+    prop_oneof![Just(1.0), Just(0.5), Just(0.25), 0.0..=1.0f64]
+}
+
 #[test]
 fn test_parser_edge_cases() {
     // Empty string
@@ -105,4 +123,39 @@ proptest! {
              prop_assert_eq!(range.len(), 4);
         }
     }
+
+    #[test]
+    fn prop_notation_round_trips(
+        classes in proptest::collection::vec((0usize..13, 0usize..13, 0usize..3, weight_strategy()), 0..40),
+        combos in proptest::collection::vec((0usize..52, 0usize..52, weight_strategy()), 0..10),
+    ) {
+        let mut range = HandRange::new();
+        for (r1, r2, shape, w) in classes {
+            let (a, b) = (RANKS[r1] as char, RANKS[r2] as char);
+            let spec = match shape {
+                _ if r1 == r2 => format!("{}{}", a, b),
+                0 => format!("{}{}", a, b),
+                1 => format!("{}{}s", a, b),
+                _ => format!("{}{}o", a, b),
+            };
+            let mut class = HandRange::from_str(&spec).unwrap();
+            for (_, weight) in &mut class.hands {
+                *weight = w;
+            }
+            range.merge(class);
+        }
+        for (c1, c2, w) in combos {
+            if c1 != c2 {
+                let mut mask = StdDeckCardMask::new();
+                mask.set(c1);
+                mask.set(c2);
+                range.push_weighted(mask, w);
+            }
+        }
+
+        let notation = range.to_notation();
+        let parsed = HandRange::from_str(&notation);
+        prop_assert!(parsed.is_ok(), "Failed to parse {}", notation);
+        prop_assert_eq!(sorted_hands(&parsed.unwrap()), sorted_hands(&range), "{}", notation);
+    }
 }
@@ -184,3 +184,112 @@ fn regression_stud8_qualifier() {
 
     assert_ne!(lo_val_q.value, LOW_HAND_VAL_NOTHING, "8-low should qualify");
 }
+
+#[test]
+fn regression_sampled_hand_types_match_enumeration() {
+    use poker_eval_rs::enumdefs::{EnumResult, Game};
+    use poker_eval_rs::enumerate::{enum_exhaustive, enum_sample};
+
+    let mask = |s: &str| StdDeck::string_to_mask(s).unwrap().0;
+    let pockets = [mask("AsKs"), mask("QdQc")];
+    let board = mask("2c7d9h");
+    let dead = StdDeckCardMask::new();
+
+    let mut exact = EnumResult::new(Game::Holdem);
+    enum_exhaustive(Game::Holdem, &pockets, board, dead, 2, 3, false, &mut exact).unwrap();
+    let mut sampled = EnumResult::new(Game::Holdem);
+    sampled.seed = Some(5);
+    enum_sample(
+        Game::Holdem,
+        &pockets,
+        board,
+        dead,
+        2,
+        3,
+        200_000,
+        false,
+        &mut sampled,
+    )
+    .unwrap();
+
+    // Every sample is a fresh turn and river, so the hand types sampled for each
+    // player follow the enumerated ones.
+    let freq = |r: &EnumResult, n: u32| n as f64 / r.nsamples as f64;
+    for player in 0..2 {
+        for t in 0..exact.nhandtype[player].len() {
+            let want = freq(&exact, exact.nhandtype[player][t]);
+            let got = freq(&sampled, sampled.nhandtype[player][t]);
+            assert!(
+                (want - got).abs() < 0.01,
+                "player {} hand type {}: {} sampled, {} enumerated",
+                player,
+                t,
+                got,
+                want
+            );
+        }
+    }
+    let win = |r: &EnumResult| freq(r, r.nwinhi[0]);
+    assert!((win(&exact) - win(&sampled)).abs() < 0.01);
+}
+
+#[test]
+fn regression_sampled_hilo_without_low() {
+    use poker_eval_rs::enumdefs::{EnumResult, Game};
+    use poker_eval_rs::enumerate::enum_sample;
+
+    // Four cards above eight on the board: nobody can make a low, so no low is
+    // won or tied and the EVs add up to one pot per deal.
+    let mask = |s: &str| StdDeck::string_to_mask(s).unwrap().0;
+    for (game, hands) in [
+        (Game::Holdem8, ["As2d", "QhQc"]),
+        (Game::Omaha8, ["As2d3cKh", "4s5dQcQh"]),
+    ] {
+        let pockets = [mask(hands[0]), mask(hands[1])];
+        let mut result = EnumResult::new(game);
+        result.seed = Some(9);
+        enum_sample(
+            game,
+            &pockets,
+            mask("KcQdJhTs"),
+            StdDeckCardMask::new(),
+            2,
+            4,
+            5_000,
+            false,
+            &mut result,
+        )
+        .unwrap();
+        for i in 0..2 {
+            assert_eq!(result.nwinlo[i] + result.ntielo[i], 0, "{:?}", game);
+        }
+        let ev: f64 = result.ev[..2].iter().sum();
+        assert!((ev - result.nsamples as f64).abs() < 1e-6, "{:?}", game);
+    }
+}
+
+#[test]
+fn regression_hilo_scoop_needs_both_halves_outright() {
+    use poker_eval_rs::enumdefs::{EnumResult, Game};
+    use poker_eval_rs::enumerate::enum_exhaustive;
+
+    // 6h9c has the best low and beats 8d7s high, but 4hAd takes the high: only
+    // half the pot, not a scoop.
+    let mask = |s: &str| StdDeck::string_to_mask(s).unwrap().0;
+    let pockets = [mask("4hAd"), mask("6h9c"), mask("8d7s")];
+    let mut result = EnumResult::new(Game::Holdem8);
+    enum_exhaustive(
+        Game::Holdem8,
+        &pockets,
+        mask("Ah2c4c5c2s"),
+        StdDeckCardMask::new(),
+        3,
+        5,
+        false,
+        &mut result,
+    )
+    .unwrap();
+    assert_eq!(result.nwinlo[1], 2);
+    assert_eq!(result.nlosehi[1], 1);
+    assert_eq!(result.nscoop[..3], [0, 0, 0]);
+}
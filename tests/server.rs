@@ -0,0 +1,207 @@
+//! The HTTP/JSON server, exercised over a localhost socket.
+#![cfg(feature = "server")]
+
+use poker_eval_rs::server::{Server, ServerConfig};
+use poker_eval_rs::service::Limits;
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+
+fn start(config: ServerConfig) -> SocketAddr {
+    let server = Server::bind("127.0.0.1:0", config).expect("bind to localhost");
+    let addr = server.local_addr().unwrap();
+    std::thread::spawn(move || server.run());
+    addr
+}
+
+/// Sends one request and returns the status code and the JSON body.
+fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        method,
+        path,
+        body.len(),
+        body
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response[9..12].parse().unwrap();
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+fn post(addr: SocketAddr, path: &str, body: &str) -> (u16, Value) {
+    request(addr, "POST", path, body)
+}
+
+#[test]
+fn test_endpoints() {
+    let addr = start(ServerConfig::default());
+
+    assert_eq!(
+        request(addr, "GET", "/health", ""),
+        (200, serde_json::json!({ "status": "ok" }))
+    );
+
+    let (status, eval) = post(addr, "/eval", r#"{"hand": "AsKs", "board": "QsJsTs"}"#);
+    assert_eq!(status, 200);
+    assert_eq!(eval["hi"]["hand_type"], "StFlush");
+
+    let (status, equity) = post(
+        addr,
+        "/equity",
+        r#"{"game": "holdem", "players": ["AsAd", "KsKd"], "board": "2c7d9h"}"#,
+    );
+    assert_eq!(status, 200);
+    assert_eq!(equity["samples"], 990);
+    assert!(equity["players"][0]["ev"].as_f64().unwrap() > 0.9);
+
+    let (status, range) = post(
+        addr,
+        "/range_equity",
+        r#"{"hero": "AA", "villain": "KK", "iterations": 20000}"#,
+    );
+    assert_eq!(status, 200);
+    assert_eq!(range["samples"], 20000);
+
+    let (status, outs) = post(
+        addr,
+        "/outs",
+        r#"{"pocket": "AsJs", "board": "Ks7s2d", "vs": "KdQc"}"#,
+    );
+    assert_eq!(status, 200);
+    assert!(outs["vs"]["clean_outs"].as_array().unwrap().len() >= 9);
+
+    let (status, texture) = post(addr, "/texture", r#"{"board": "AsKsQs"}"#);
+    assert_eq!(status, 200);
+    assert_eq!(texture["is_monotone"], true);
+}
+
+#[test]
+fn test_errors_map_to_status_codes() {
+    let addr = start(ServerConfig {
+        max_body_bytes: 256,
+        limits: Limits {
+            max_iterations: 1000,
+            timeout: None,
+        },
+        ..ServerConfig::default()
+    });
+
+    let status = |method: &str, path: &str, body: &str| request(addr, method, path, body).0;
+    assert_eq!(status("POST", "/nope", "{}"), 404);
+    assert_eq!(status("GET", "/equity", ""), 405);
+    assert_eq!(status("POST", "/eval", "not json"), 400);
+    assert_eq!(status("POST", "/eval", r#"{"hand": "AsAs"}"#), 400);
+    assert_eq!(status("POST", "/texture", r#"{"board": "As"}"#), 422);
+    assert_eq!(status("POST", "/eval", &" ".repeat(300)), 413);
+
+    let (code, body) = post(
+        addr,
+        "/equity",
+        r#"{"game": "holdem", "players": ["AsAd", "KsKd"], "mode": "monte_carlo", "iterations": 5000}"#,
+    );
+    assert_eq!(code, 400);
+    assert!(body["error"].as_str().unwrap().contains("limit"));
+}
+
+#[test]
+fn test_timeout() {
+    let addr = start(ServerConfig {
+        limits: Limits {
+            timeout: Some(Duration::ZERO),
+            ..Limits::default()
+        },
+        ..ServerConfig::default()
+    });
+    let (status, body) = post(
+        addr,
+        "/equity",
+        r#"{"game": "holdem", "players": ["AsAd", "KsKd"]}"#,
+    );
+    assert_eq!(status, 503);
+    assert!(body["error"].as_str().unwrap().starts_with("Timed out"));
+}
+
+#[test]
+fn test_concurrent_requests() {
+    let addr = start(ServerConfig::default());
+    let clients: Vec<_> = (0..8)
+        .map(|i| {
+            std::thread::spawn(move || {
+                let body = format!(
+                    r#"{{"game": "holdem", "players": ["AsAd", "KsKd"], "mode": "monte_carlo", "iterations": 20000, "seed": {}}}"#,
+                    i
+                );
+                post(addr, "/equity", &body)
+            })
+        })
+        .collect();
+    for client in clients {
+        let (status, report) = client.join().unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(report["samples"], 20000);
+    }
+}
+
+/// Opens a request whose body is held back, and waits until a worker starts reading it.
+fn hold_worker(addr: SocketAddr) -> TcpStream {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let body = r#"{"board": "AsKsQs"}"#;
+    write!(
+        stream,
+        "POST /texture HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\
+         Expect: 100-continue\r\nConnection: close\r\n\r\n",
+        body.len()
+    )
+    .unwrap();
+    let mut interim = Vec::new();
+    let mut byte = [0u8];
+    while !interim.ends_with(b"\r\n\r\n") {
+        stream.read_exact(&mut byte).unwrap();
+        interim.push(byte[0]);
+    }
+    assert!(interim.starts_with(b"HTTP/1.1 100 Continue"));
+    stream
+}
+
+fn release(mut stream: TcpStream) -> u16 {
+    stream.write_all(br#"{"board": "AsKsQs"}"#).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response[9..12].parse().unwrap()
+}
+
+#[test]
+fn test_requests_beyond_the_pool_get_503() {
+    let addr = start(ServerConfig {
+        workers: 2,
+        backlog: 0,
+        ..ServerConfig::default()
+    });
+    let held = [hold_worker(addr), hold_worker(addr)];
+
+    let clients: Vec<_> = (0..4)
+        .map(|_| std::thread::spawn(move || request(addr, "GET", "/health", "")))
+        .collect();
+    for client in clients {
+        let (status, body) = client.join().unwrap();
+        assert_eq!(status, 503);
+        assert!(body["error"].as_str().unwrap().contains("busy"));
+    }
+
+    for stream in held {
+        assert_eq!(release(stream), 200);
+    }
+    // The workers go back to waiting for requests once their reply is out.
+    let served = (0..100).any(|_| {
+        std::thread::sleep(Duration::from_millis(10));
+        request(addr, "GET", "/health", "").0 == 200
+    });
+    assert!(served);
+}
@@ -1,4 +1,6 @@
-use poker_eval_rs::deck::StdDeck;
+use poker_eval_rs::deck::{StdDeck, StdDeckCardMask};
+use poker_eval_rs::enumdefs::{EnumResult, Game};
+use poker_eval_rs::enumerate::{enum_exhaustive, enum_sample};
 use poker_eval_rs::evaluators::{HandEvaluator, ShortDeckEvaluator};
 use poker_eval_rs::rules::HandType;
 
@@ -45,12 +47,49 @@ fn test_short_deck_straight_low() {
 
 #[test]
 fn test_short_deck_no_low_cards() {
-    // Ensuring that 2, 3, 4, 5 are not generated in random enumeration is hard here without mocking.
-    // But we can check that if we PASS them, maybe they are ignored or handled?
-    // Actually ShortDeck usually strictly uses a subset of standard deck.
-    // Use the library's deck generation to verify 2-5 are absent.
-
-    // We can't easily access the deck generation logic from here as it's internal to Enumerate usually.
-    // But we can check `enumerate::get_deck(Game::ShortDeck)`.
-    // Wait, `get_deck` might be internal.
+    // AhKh vs QsQc on 6h7h9sTd: of the 28 rivers left in a 36-card deck, five hearts,
+    // three aces and three kings win, three eights tie and the other 14 lose.
+    let mask = |s: &str| StdDeck::string_to_mask(s).unwrap().0;
+    let pockets = [mask("AhKh"), mask("QsQc")];
+    let board = mask("6h7h9sTd");
+    let dead = StdDeckCardMask::new();
+
+    let mut result = EnumResult::default();
+    enum_exhaustive(
+        Game::ShortDeck,
+        &pockets,
+        board,
+        dead,
+        2,
+        4,
+        false,
+        &mut result,
+    )
+    .unwrap();
+    assert_eq!(result.nsamples, 28);
+    assert_eq!(
+        (result.nwinhi[0], result.ntiehi[0], result.nlosehi[0]),
+        (11, 3, 14)
+    );
+
+    // Sampled rivers come from the same 28 cards: hearts among them, and no deuce
+    // to five that would leave the aces' win rate off 11 in 28.
+    let mut result = EnumResult::new(Game::ShortDeck);
+    result.seed = Some(11);
+    enum_sample(
+        Game::ShortDeck,
+        &pockets,
+        board,
+        dead,
+        2,
+        4,
+        20_000,
+        false,
+        &mut result,
+    )
+    .unwrap();
+    assert_eq!(result.nsamples, 20_000);
+    assert!(result.nhandtype[0][HandType::Flush as usize] > 0);
+    let win = result.nwinhi[0] as f64 / 20_000.0;
+    assert!((win - 11.0 / 28.0).abs() < 0.05, "win rate {}", win);
 }